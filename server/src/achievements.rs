@@ -0,0 +1,103 @@
+use spacetimedb::{Identity, ReducerContext, Table, Timestamp};
+use log;
+
+
+// --- Achievement Keys ---
+// Call sites pass these to `grant_achievement`; definitions are seeded from `ACHIEVEMENT_DEFINITIONS`.
+pub(crate) const ACH_FIRST_TREE_CHOPPED: &str = "first_tree_chopped";
+pub(crate) const ACH_FIRST_KILL: &str = "first_kill";
+pub(crate) const ACH_SURVIVE_10_DAYS: &str = "survive_10_days";
+pub(crate) const SURVIVE_DAYS_ACHIEVEMENT_THRESHOLD: u32 = 10;
+
+// (key, name, description, reward item name, reward quantity)
+const ACHIEVEMENT_DEFINITIONS: &[(&str, &str, &str, Option<&str>, u32)] = &[
+    (ACH_FIRST_TREE_CHOPPED, "Lumberjack", "Chop down your first tree.", Some("Wood"), 10),
+    (ACH_FIRST_KILL, "First Blood", "Defeat another player.", None, 0),
+    (ACH_SURVIVE_10_DAYS, "Survivor", "Survive 10 days in a single life.", Some("Mushroom"), 5),
+];
+
+// --- Tables ---
+
+#[spacetimedb::table(name = achievement_definition, public)]
+#[derive(Clone)]
+pub struct AchievementDefinition {
+    #[primary_key]
+    pub key: String,
+    pub name: String,
+    pub description: String,
+    pub reward_item_name: Option<String>, // Item granted on unlock, if any
+    pub reward_quantity: u32,
+}
+
+#[spacetimedb::table(name = player_achievement, public)]
+#[derive(Clone)]
+pub struct PlayerAchievement {
+    #[primary_key]
+    pub id: String, // "<identity hex>:<achievement key>", so the already-unlocked check is a single lookup
+    pub player_identity: Identity,
+    pub achievement_key: String,
+    pub unlocked_at: Timestamp,
+}
+
+// --- Seeding (called from init_module) ---
+pub(crate) fn seed_achievements(ctx: &ReducerContext) -> Result<(), String> {
+    let definitions = ctx.db.achievement_definition();
+    let mut seeded_count = 0;
+    for (key, name, description, reward_item_name, reward_quantity) in ACHIEVEMENT_DEFINITIONS {
+        if definitions.key().find(key.to_string()).is_some() {
+            continue;
+        }
+        definitions.insert(AchievementDefinition {
+            key: key.to_string(),
+            name: name.to_string(),
+            description: description.to_string(),
+            reward_item_name: reward_item_name.map(|item_name| item_name.to_string()),
+            reward_quantity: *reward_quantity,
+        });
+        seeded_count += 1;
+    }
+    if seeded_count > 0 {
+        log::info!("Seeded {} achievement definitions.", seeded_count);
+    }
+    Ok(())
+}
+
+// --- Grant Helper ---
+
+/// Unlocks `key` for the player if they don't have it yet, and hands out its reward.
+/// Cheap enough for hot reducers: one primary-key lookup when already unlocked.
+pub(crate) fn grant_achievement(ctx: &ReducerContext, player_id: Identity, key: &str) {
+    let player_achievements = ctx.db.player_achievement();
+    let unlock_id = format!("{}:{}", player_id.to_hex(), key);
+    if player_achievements.id().find(unlock_id.clone()).is_some() {
+        return;
+    }
+
+    let Some(definition) = ctx.db.achievement_definition().key().find(key.to_string()) else {
+        log::warn!("[Achievements] Unknown achievement '{}'.", key);
+        return;
+    };
+
+    player_achievements.insert(PlayerAchievement {
+        id: unlock_id,
+        player_identity: player_id,
+        achievement_key: key.to_string(),
+        unlocked_at: ctx.timestamp,
+    });
+    log::info!("[Achievements] Player {:?} unlocked '{}'.", player_id, definition.name);
+
+    // Rewards are a bonus; failing to grant one (e.g. full inventory) keeps the unlock
+    if let (Some(item_name), quantity) = (definition.reward_item_name, definition.reward_quantity) {
+        if quantity == 0 {
+            return;
+        }
+        match crate::items::get_item_def_by_name(ctx, &item_name) {
+            Some(item_def) => {
+                if let Err(e) = crate::items::grant_or_drop(ctx, player_id, item_def.id, quantity) {
+                    log::warn!("[Achievements] Could not grant reward {}x {} to {:?}: {}", quantity, item_name, player_id, e);
+                }
+            }
+            None => log::error!("[Achievements] Reward item '{}' for '{}' not found.", item_name, key),
+        }
+    }
+}
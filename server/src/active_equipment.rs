@@ -1,6 +1,5 @@
-use spacetimedb::{ Identity, ReducerContext, Table, Timestamp };
+use spacetimedb::{ Identity, ReducerContext, SpacetimeType, Table, Timestamp };
 use log;
-use std::time::Duration;
 
 // Import specific constants directly from their modules
 use crate::tree::{TREE_COLLISION_Y_OFFSET, PLAYER_TREE_COLLISION_DISTANCE_SQUARED};
@@ -19,17 +18,23 @@ use crate::active_equipment as ActiveEquipmentTableTrait;
 // use crate::items::ItemDefinition; // Remove - Not used directly here
 // use crate::{Player, PLAYER_RADIUS}; // Remove - Not used directly here
 use crate::PLAYER_RADIUS; // Add back the import for PLAYER_RADIUS
+use crate::utils::{get_distance_squared, is_within_arc};
 use std::f32::consts::PI;
 use crate::items::{InventoryItem, ItemDefinition, ItemCategory, EquipmentSlot};
 use crate::Player; // Corrected import path
+use crate::combat_log::CombatTargetType;
+use crate::errors::GameError;
 
 // --- Constants ---
 pub(crate) const RESPAWN_TIME_MS: u64 = 5000; // 5 seconds respawn time
-const PVP_DAMAGE_MULTIPLIER: f32 = 6.0;
+const WEAPON_DRAW_TIME_MS: u64 = 400; // Delay after drawing (or equipping) before the item can be swung
+const HEAVY_ATTACK_STAMINA_COST: f32 = 25.0; // Light attacks stay free; heavy ones cost this much stamina
+const HEAVY_ATTACK_DAMAGE_MULTIPLIER: f32 = 2.0; // Damage scale of a heavy attack with enough stamina
+const MIN_FALLOFF_DAMAGE_FRACTION: f32 = 0.5; // Damage kept at max reach by weapons with `damage_falloff`
 pub(crate) const RESOURCE_RESPAWN_DURATION_SECS: u64 = 300; // 5 minutes respawn time for trees/stones
-
-const PLAYER_INTERACT_DISTANCE: f32 = 80.0;
-const PLAYER_INTERACT_DISTANCE_SQUARED: f32 = PLAYER_INTERACT_DISTANCE * PLAYER_INTERACT_DISTANCE;
+const DEFAULT_ATTACK_RANGE: f32 = PLAYER_RADIUS * 4.0; // Reach for items without their own `attack_range`
+const DEFAULT_ATTACK_ARC_DEGREES: f32 = 90.0; // Swing width for items without their own `attack_arc_degrees`
+const MAX_ATTACK_ARC_DEGREES: f32 = 360.0;
 
 #[spacetimedb::table(name = active_equipment, public)]
 #[derive(Clone, Default, Debug)]
@@ -39,6 +44,8 @@ pub struct ActiveEquipment {
     pub equipped_item_def_id: Option<u64>, // ID from ItemDefinition table
     pub equipped_item_instance_id: Option<u64>, // Instance ID from InventoryItem
     pub swing_start_time_ms: u64, // Timestamp (ms) when the current swing started, 0 if not swinging
+    pub weapon_drawn: bool, // False while the main hand item is sheathed (still equipped, but holstered)
+    pub draw_ready_at_ms: u64, // Timestamp (ms) when the current draw finishes and swings are allowed
     // Fields for worn armor
     pub head_item_instance_id: Option<u64>,
     pub chest_item_instance_id: Option<u64>,
@@ -88,6 +95,9 @@ pub fn equip_item(ctx: &ReducerContext, item_instance_id: u64) -> Result<(), Str
     equipment.equipped_item_def_id = Some(item_def.id);
     equipment.equipped_item_instance_id = Some(item_instance_id);
     equipment.swing_start_time_ms = 0; // Reset swing state when equipping
+    // Equipping pulls the item out, which takes the same moment as drawing it
+    equipment.weapon_drawn = true;
+    equipment.draw_ready_at_ms = (ctx.timestamp.to_micros_since_unix_epoch() / 1000) as u64 + WEAPON_DRAW_TIME_MS;
 
     active_equipments.player_identity().update(equipment); // Update the existing row
     log::info!("Player {:?} equipped item: {} (Instance ID: {}) to main hand.", sender_id, item_def.name, item_instance_id);
@@ -112,6 +122,7 @@ pub fn unequip_item(ctx: &ReducerContext) -> Result<(), String> {
              equipment.equipped_item_def_id = None;
              equipment.equipped_item_instance_id = None;
              equipment.swing_start_time_ms = 0;
+             equipment.weapon_drawn = false;
              active_equipments.player_identity().update(equipment);
         }
     } else {
@@ -121,9 +132,75 @@ pub fn unequip_item(ctx: &ReducerContext) -> Result<(), String> {
     Ok(())
 }
 
+// Reducer to select a hotbar slot (None clears the selection). The server keeps the main hand in
+// sync with the selection: the slot's item is equipped, and an empty slot unequips the main hand.
+#[spacetimedb::reducer]
+pub fn select_hotbar_slot(ctx: &ReducerContext, slot: Option<u8>) -> Result<(), String> {
+    let sender_id = ctx.sender;
+    if let Some(slot_index) = slot {
+        if slot_index >= 6 { // Assuming 6 hotbar slots (0-5)
+            return Err(GameError::InvalidSlot(format!("Hotbar slot {} does not exist", slot_index)).into());
+        }
+    }
+
+    let players = ctx.db.player();
+    let mut player = players.identity().find(sender_id)
+        .ok_or_else(|| GameError::NotFound("Player".to_string()))?;
+    player.selected_hotbar_slot = slot;
+    players.identity().update(player);
+
+    let selected_item = slot.and_then(|slot_index| {
+        ctx.db.inventory_item().player_identity().filter(&sender_id)
+            .find(|item| item.hotbar_slot == Some(slot_index))
+    });
+    match selected_item {
+        // equip_item clears the main hand itself for items that can't be held
+        Some(item) => equip_item(ctx, item.instance_id),
+        None => unequip_item(ctx),
+    }
+}
+
+// Reducer to sheathe or draw the main hand item without unequipping it
+#[spacetimedb::reducer]
+pub fn toggle_weapon_drawn(ctx: &ReducerContext) -> Result<(), String> {
+    let sender_id = ctx.sender;
+    let active_equipments = ctx.db.active_equipment();
+    let mut equipment = active_equipments.player_identity().find(sender_id)
+        .ok_or_else(|| "No active equipment record found.".to_string())?;
+
+    if equipment.equipped_item_instance_id.is_none() {
+        return Err("No item equipped to draw or sheathe.".to_string());
+    }
+
+    let now_ms = (ctx.timestamp.to_micros_since_unix_epoch() / 1000) as u64;
+    if equipment.weapon_drawn {
+        equipment.weapon_drawn = false;
+        equipment.swing_start_time_ms = 0; // Sheathing cancels any swing in progress
+        log::info!("Player {:?} sheathed their main hand item.", sender_id);
+    } else {
+        equipment.weapon_drawn = true;
+        equipment.draw_ready_at_ms = now_ms + WEAPON_DRAW_TIME_MS;
+        log::info!("Player {:?} drew their main hand item.", sender_id);
+    }
+    active_equipments.player_identity().update(equipment);
+    Ok(())
+}
+
 // Reducer to trigger the 'use' action (swing) of the equipped item
 #[spacetimedb::reducer]
 pub fn use_equipped_item(ctx: &ReducerContext) -> Result<(), String> {
+    perform_equipped_item_use(ctx, false)
+}
+
+// Reducer for a heavy swing: spends stamina for extra damage if it hits something.
+// With less than `HEAVY_ATTACK_STAMINA_COST` stamina left, the bonus shrinks proportionally.
+#[spacetimedb::reducer]
+pub fn use_equipped_item_heavy(ctx: &ReducerContext) -> Result<(), String> {
+    perform_equipped_item_use(ctx, true)
+}
+
+// Shared swing logic for light and heavy attacks. Heavy attacks that land cost stamina for extra damage.
+fn perform_equipped_item_use(ctx: &ReducerContext, heavy: bool) -> Result<(), String> {
     let sender_id = ctx.sender;
     let now_ts = ctx.timestamp;
     let now_micros = now_ts.to_micros_since_unix_epoch();
@@ -140,6 +217,9 @@ pub fn use_equipped_item(ctx: &ReducerContext) -> Result<(), String> {
     // --- Get Player and Equipment Info ---
     let player = players.identity().find(sender_id)
         .ok_or_else(|| "Player not found".to_string())?;
+    if player.is_dead {
+        return Err("Cannot attack while dead.".to_string());
+    }
     let mut current_equipment = active_equipments.player_identity().find(sender_id)
         .ok_or_else(|| "No active equipment record found.".to_string())?;
 
@@ -148,6 +228,14 @@ pub fn use_equipped_item(ctx: &ReducerContext) -> Result<(), String> {
     let item_def = item_defs.id().find(item_def_id)
         .ok_or_else(|| "Equipped item definition not found".to_string())?;
 
+    // --- Check Drawn State ---
+    if !current_equipment.weapon_drawn {
+        return Err("Equipped item is sheathed.".to_string());
+    }
+    if now_ms < current_equipment.draw_ready_at_ms {
+        return Err("Still drawing equipped item.".to_string());
+    }
+
     // --- Update Swing Time ---
     // TODO: Add cooldown check?
     current_equipment.swing_start_time_ms = now_ms;
@@ -156,15 +244,23 @@ pub fn use_equipped_item(ctx: &ReducerContext) -> Result<(), String> {
              sender_id, item_def.name, item_def_id);
 
     // --- Get Item Damage ---
-    let item_damage = match item_def.damage {
+    // Crafted quality of the equipped instance scales its base damage
+    let quality_multiplier = current_equipment.equipped_item_instance_id
+        .and_then(|instance_id| inventory_items.instance_id().find(instance_id))
+        .map_or(1.0, |item| crate::item_quality::quality_multiplier(item.quality));
+    let base_damage = match item_def.damage {
         Some(dmg) if dmg > 0 => dmg,
         _ => return Ok(()), // Item has no damage, nothing more to do
     };
+    // Heavy swings scale with the stamina available now; it is only spent if the swing hits (see below)
+    let heavy_stamina = if heavy { player.stamina.clamp(0.0, HEAVY_ATTACK_STAMINA_COST) } else { 0.0 };
+    let damage_multiplier = if heavy { heavy_attack_damage_multiplier(heavy_stamina) } else { 1.0 };
+    let item_damage = ((base_damage as f32 * damage_multiplier * quality_multiplier).round() as u32).max(1);
 
     // --- Attack Logic ---
-    let attack_range = PLAYER_RADIUS * 4.0; // Increased range further
-    let attack_angle_degrees = 90.0; // Widen attack arc to 90 degrees
-    let attack_angle_rad = attack_angle_degrees * PI / 180.0;
+    // Reach and swing width come from the item (e.g. daggers short and narrow, hammers short and wide)
+    let attack_range = attack_range_of(&item_def);
+    let attack_angle_rad = attack_arc_degrees_of(&item_def) * PI / 180.0;
     let half_attack_angle_rad = attack_angle_rad / 2.0;
 
     // Calculate player's forward vector based on direction
@@ -182,49 +278,28 @@ pub fn use_equipped_item(ctx: &ReducerContext) -> Result<(), String> {
 
     // Find closest Tree target
     for tree in trees.iter() {
-        let dx = tree.pos_x - player.position_x;
         // Target the tree's defined collision Y coordinate
         let target_y = tree.pos_y - TREE_COLLISION_Y_OFFSET;
-        let dy = target_y - player.position_y; 
-        let dist_sq = dx * dx + dy * dy;
-
-        if dist_sq < (attack_range * attack_range) && dist_sq > 0.0 {
-            let distance = dist_sq.sqrt();
-            let target_vec_x = dx / distance;
-            let target_vec_y = dy / distance;
-
-            // Calculate angle between player forward and target vector
-            let dot_product: f32 = forward_x * target_vec_x + forward_y * target_vec_y;
-            let angle_rad = dot_product.acos(); // Angle in radians
-
-            if angle_rad <= half_attack_angle_rad {
-                // Target is within range and angle
-                if closest_tree_target.is_none() || dist_sq < closest_tree_target.unwrap().1 {
-                    closest_tree_target = Some((tree.id, dist_sq));
-                }
-            }
+        let dx = tree.pos_x - player.position_x;
+        let dy = target_y - player.position_y;
+        let dist_sq = get_distance_squared(tree.pos_x, target_y, player.position_x, player.position_y);
+
+        if is_in_swing(dx, dy, (forward_x, forward_y), attack_range, half_attack_angle_rad)
+            && is_closer_target((tree.id, dist_sq), closest_tree_target) {
+            closest_tree_target = Some((tree.id, dist_sq));
         }
     }
 
     // Find closest Stone target
     for stone in stones.iter() {
-        let dx = stone.pos_x - player.position_x;
         let target_y = stone.pos_y - STONE_COLLISION_Y_OFFSET;
+        let dx = stone.pos_x - player.position_x;
         let dy = target_y - player.position_y;
-        let dist_sq = dx * dx + dy * dy;
-
-        if dist_sq < (attack_range * attack_range) && dist_sq > 0.0 {
-            let distance = dist_sq.sqrt();
-            let target_vec_x = dx / distance;
-            let target_vec_y = dy / distance;
-            let dot_product: f32 = forward_x * target_vec_x + forward_y * target_vec_y;
-            let angle_rad = dot_product.acos();
-
-            if angle_rad <= half_attack_angle_rad {
-                if closest_stone_target.is_none() || dist_sq < closest_stone_target.unwrap().1 {
-                    closest_stone_target = Some((stone.id, dist_sq));
-                }
-            }
+        let dist_sq = get_distance_squared(stone.pos_x, target_y, player.position_x, player.position_y);
+
+        if is_in_swing(dx, dy, (forward_x, forward_y), attack_range, half_attack_angle_rad)
+            && is_closer_target((stone.id, dist_sq), closest_stone_target) {
+            closest_stone_target = Some((stone.id, dist_sq));
         }
     }
 
@@ -235,26 +310,18 @@ pub fn use_equipped_item(ctx: &ReducerContext) -> Result<(), String> {
 
         let dx = other_player.position_x - player.position_x;
         let dy = other_player.position_y - player.position_y;
-        let dist_sq = dx * dx + dy * dy;
-
-        if dist_sq < (attack_range * attack_range) && dist_sq > 0.0 {
-            let distance = dist_sq.sqrt();
-            let target_vec_x = dx / distance;
-            let target_vec_y = dy / distance;
-            let dot_product: f32 = forward_x * target_vec_x + forward_y * target_vec_y;
-            let angle_rad = dot_product.acos();
-
-            if angle_rad <= half_attack_angle_rad {
-                if closest_player_target.is_none() || dist_sq < closest_player_target.unwrap().1 {
-                    closest_player_target = Some((other_player.identity, dist_sq));
-                }
-            }
+        let dist_sq = get_distance_squared(other_player.position_x, other_player.position_y, player.position_x, player.position_y);
+
+        if is_in_swing(dx, dy, (forward_x, forward_y), attack_range, half_attack_angle_rad)
+            && is_closer_target((other_player.identity, dist_sq), closest_player_target) {
+            closest_player_target = Some((other_player.identity, dist_sq));
         }
     }
 
     // --- Apply Damage based on Tool Type and Target Priority ---
     let tool_name = item_def.name.as_str();
     let mut hit_something = false;
+    let mut hit_player = false; // PvP hits combat-tag the attacker too
 
     if tool_name == "Stone Pickaxe" {
         // Pickaxe: Prioritize Stones > Players
@@ -263,15 +330,17 @@ pub fn use_equipped_item(ctx: &ReducerContext) -> Result<(), String> {
             let mut stone = stones.id().find(stone_id).ok_or("Target stone disappeared?")?;
             let old_health = stone.health;
             stone.health = stone.health.saturating_sub(item_damage);
+            stone.stage = crate::utils::resource_stage_for_health(stone.health, crate::stone::STONE_INITIAL_HEALTH);
             stone.last_hit_time = Some(now_ts); // Set last hit time for shake effect
             log::info!("Player {:?} hit Stone {} with {} for {} damage. Health: {} -> {}",
                     sender_id, stone_id, item_def.name, item_damage, old_health, stone.health);
+            crate::combat_log::record_combat_hit(ctx, sender_id, CombatTargetType::Stone, None, Some(stone_id), item_def.id, item_damage as f32, stone.health == 0);
 
             // --- Grant Stone Item --- 
-            let stone_def_opt = item_defs.iter().find(|def| def.name == "Stone");
+            let stone_def_opt = crate::items::get_item_def_by_name(ctx, "Stone");
             if let Some(stone_def) = stone_def_opt {
-                let stone_to_grant = item_damage as u32; 
-                match crate::items::add_item_to_player_inventory(ctx, sender_id, stone_def.id, stone_to_grant) {
+                let stone_to_grant = crate::utils::take_resource_yield(&mut stone.remaining_yield, stone.health, crate::stone::STONE_INITIAL_HEALTH, crate::stone::STONE_TOTAL_YIELD);
+                match crate::items::grant_or_drop(ctx, sender_id, stone_def.id, stone_to_grant) {
                     Ok(_) => log::debug!("Granted {} Stone to player {:?} via helper.", stone_to_grant, sender_id),
                     Err(e) => log::error!("Failed to grant Stone to player {:?}: {}", sender_id, e),
                 }
@@ -281,38 +350,15 @@ pub fn use_equipped_item(ctx: &ReducerContext) -> Result<(), String> {
             // --- End Grant Stone Item ---
 
             if stone.health == 0 {
-                log::info!("Stone {} depleted by Player {:?}. Scheduling respawn.", stone_id, sender_id);
-                let respawn_time = now_ts + Duration::from_secs(RESOURCE_RESPAWN_DURATION_SECS);
-                stone.respawn_at = Some(respawn_time);
-                stones.id().update(stone); // Update with health 0 and respawn time
-                // stones.id().delete(stone_id); // Removed delete
-            } else {
-                stones.id().update(stone);
+                on_stone_depleted(ctx, sender_id, &mut stone);
             }
+            stones.id().update(stone);
             hit_something = true;
 
-        } else if let Some((target_player_id, _)) = closest_player_target {
+        } else if let Some((target_player_id, target_dist_sq)) = closest_player_target {
             // --- Damage Player ---
-            let mut target_player = players.identity().find(target_player_id)
-                .ok_or("Target player disappeared?")?;
-            let old_health = target_player.health;
-            // Apply PvP multiplier
-            let actual_damage = (item_damage as f32 * PVP_DAMAGE_MULTIPLIER).max(0.0);
-            target_player.health = (target_player.health - actual_damage).max(0.0);
-            target_player.last_hit_time = Some(now_ts); // <-- Set last hit time
-            log::info!("Player {:?} hit Player {:?} with {} for {:.1} ({} base * {}x) damage. Health: {:.1} -> {:.1}",
-                     sender_id, target_player_id, item_def.name, actual_damage, item_damage, PVP_DAMAGE_MULTIPLIER, old_health, target_player.health);
-
-            // Check for death
-            if target_player.health <= 0.0 && !target_player.is_dead {
-                target_player.is_dead = true;
-                let respawn_micros = now_micros.saturating_add((RESPAWN_TIME_MS * 1000) as i64);
-                target_player.respawn_at = Timestamp::from_micros_since_unix_epoch(respawn_micros);
-                log::info!("Player {:?} killed Player {:?}. Respawn at {:?}", sender_id, target_player_id, target_player.respawn_at);
-                // TODO: Drop items? Clear equipment?
-            }
-
-            players.identity().update(target_player);
+            apply_pvp_hit(ctx, sender_id, target_player_id, item_damage as f32, &item_def, target_dist_sq, attack_range)?;
+            hit_player = true;
             hit_something = true;
         }
 
@@ -323,15 +369,17 @@ pub fn use_equipped_item(ctx: &ReducerContext) -> Result<(), String> {
             let mut tree = trees.id().find(tree_id).ok_or("Target tree disappeared?")?;
             let old_health = tree.health;
             tree.health = tree.health.saturating_sub(item_damage);
+            tree.stage = crate::utils::resource_stage_for_health(tree.health, crate::tree::TREE_INITIAL_HEALTH);
             tree.last_hit_time = Some(now_ts);
             log::info!("Player {:?} hit Tree {} with {} for {} damage. Health: {} -> {}",
                      sender_id, tree_id, item_def.name, item_damage, old_health, tree.health);
+            crate::combat_log::record_combat_hit(ctx, sender_id, CombatTargetType::Tree, None, Some(tree_id), item_def.id, item_damage as f32, tree.health == 0);
 
             // --- Grant Wood Item ---
-            let wood_def_opt = item_defs.iter().find(|def| def.name == "Wood");
+            let wood_def_opt = crate::items::get_item_def_by_name(ctx, "Wood");
             if let Some(wood_def) = wood_def_opt {
-                let wood_to_grant = item_damage as u32; 
-                match crate::items::add_item_to_player_inventory(ctx, sender_id, wood_def.id, wood_to_grant) {
+                let wood_to_grant = crate::utils::take_resource_yield(&mut tree.remaining_yield, tree.health, crate::tree::TREE_INITIAL_HEALTH, crate::tree::TREE_TOTAL_YIELD);
+                match crate::items::grant_or_drop(ctx, sender_id, wood_def.id, wood_to_grant) {
                     Ok(_) => log::debug!("Granted {} Wood to player {:?} via helper.", wood_to_grant, sender_id),
                     Err(e) => log::error!("Failed to grant Wood to player {:?}: {}", sender_id, e),
                 }
@@ -341,43 +389,21 @@ pub fn use_equipped_item(ctx: &ReducerContext) -> Result<(), String> {
             // --- End Grant Wood Item ---
             
             if tree.health == 0 {
-                log::info!("Tree {} destroyed by Player {:?}. Scheduling respawn.", tree_id, sender_id);
-                let respawn_time = now_ts + Duration::from_secs(RESOURCE_RESPAWN_DURATION_SECS);
-                tree.respawn_at = Some(respawn_time);
-                trees.id().update(tree); // Update with health 0 and respawn time
-                // trees.id().delete(tree_id); // REMOVED delete
-            } else {
-                trees.id().update(tree);
+                on_tree_felled(ctx, sender_id, &mut tree, (forward_x, forward_y));
             }
+            trees.id().update(tree);
             hit_something = true;
 
-        } else if let Some((target_player_id, _)) = closest_player_target {
+        } else if let Some((target_player_id, target_dist_sq)) = closest_player_target {
             // --- Damage Player ---
-            let mut target_player = players.identity().find(target_player_id)
-                .ok_or("Target player disappeared?")?;
-            let old_health = target_player.health;
-            // Apply PvP multiplier
-            let actual_damage = (item_damage as f32 * PVP_DAMAGE_MULTIPLIER).max(0.0);
-            target_player.health = (target_player.health - actual_damage).max(0.0);
-            target_player.last_hit_time = Some(now_ts); // <-- Set last hit time
-            log::info!("Player {:?} hit Player {:?} with {} for {:.1} ({} base * {}x) damage. Health: {:.1} -> {:.1}",
-                     sender_id, target_player_id, item_def.name, actual_damage, item_damage, PVP_DAMAGE_MULTIPLIER, old_health, target_player.health);
-
-            // Check for death
-            if target_player.health <= 0.0 && !target_player.is_dead {
-                target_player.is_dead = true;
-                let respawn_micros = now_micros.saturating_add((RESPAWN_TIME_MS * 1000) as i64);
-                target_player.respawn_at = Timestamp::from_micros_since_unix_epoch(respawn_micros);
-                log::info!("Player {:?} killed Player {:?}. Respawn at {:?}", sender_id, target_player_id, target_player.respawn_at);
-                // TODO: Drop items? Clear equipment?
-            }
-
-            players.identity().update(target_player);
+            apply_pvp_hit(ctx, sender_id, target_player_id, item_damage as f32, &item_def, target_dist_sq, attack_range)?;
+            hit_player = true;
             hit_something = true;
         }
 
     } else if tool_name == "Rock" {
         // Rock: Prioritize closest Stone, Tree, OR Player
+        // (strict `<` below means exact ties across types resolve tree > stone > player)
         let mut closest_dist_sq = f32::MAX;
         let mut closest_target_type = None; // Option<"tree" | "stone" | "player">
 
@@ -407,14 +433,17 @@ pub fn use_equipped_item(ctx: &ReducerContext) -> Result<(), String> {
                     let mut tree = trees.id().find(tree_id).ok_or("Target tree disappeared?")?;
                     let old_health = tree.health;
                     tree.health = tree.health.saturating_sub(1); // Rock damage = 1
+                    tree.stage = crate::utils::resource_stage_for_health(tree.health, crate::tree::TREE_INITIAL_HEALTH);
                     tree.last_hit_time = Some(now_ts);
                     log::info!("Player {:?} hit Tree {} with {} for {} damage. Health: {} -> {}",
                             sender_id, tree_id, item_def.name, 1, old_health, tree.health);
+                    crate::combat_log::record_combat_hit(ctx, sender_id, CombatTargetType::Tree, None, Some(tree_id), item_def.id, 1.0, tree.health == 0);
 
-                    // Grant 1 Wood - USE REFACTORED HELPER
-                    if let Some(wood_def) = item_defs.iter().find(|def| def.name == "Wood") {
-                        match crate::items::add_item_to_player_inventory(ctx, sender_id, wood_def.id, 1) {
-                            Ok(_) => log::debug!("Granted 1 Wood to player {:?} via helper.", sender_id),
+                    // Grant Wood for the health lost - USE REFACTORED HELPER
+                    let wood_to_grant = crate::utils::take_resource_yield(&mut tree.remaining_yield, tree.health, crate::tree::TREE_INITIAL_HEALTH, crate::tree::TREE_TOTAL_YIELD);
+                    if let Some(wood_def) = crate::items::get_item_def_by_name(ctx, "Wood") {
+                        match crate::items::grant_or_drop(ctx, sender_id, wood_def.id, wood_to_grant) {
+                            Ok(_) => log::debug!("Granted {} Wood to player {:?} via helper.", wood_to_grant, sender_id),
                             Err(e) => log::error!("Failed to grant Wood to player {:?}: {}", sender_id, e),
                         }
                     } else { 
@@ -422,14 +451,9 @@ pub fn use_equipped_item(ctx: &ReducerContext) -> Result<(), String> {
                     }
 
                     if tree.health == 0 {
-                        log::info!("Tree {} destroyed by Player {:?}. Scheduling respawn.", tree_id, sender_id);
-                        let respawn_time = now_ts + Duration::from_secs(RESOURCE_RESPAWN_DURATION_SECS);
-                        tree.respawn_at = Some(respawn_time);
-                        trees.id().update(tree); // Update with health 0 and respawn time
-                        // trees.id().delete(tree_id); // REMOVED delete
-                    } else {
-                        trees.id().update(tree);
+                        on_tree_felled(ctx, sender_id, &mut tree, (forward_x, forward_y));
                     }
+                    trees.id().update(tree);
                     hit_something = true;
                 }
             },
@@ -439,14 +463,17 @@ pub fn use_equipped_item(ctx: &ReducerContext) -> Result<(), String> {
                     let mut stone = stones.id().find(stone_id).ok_or("Target stone disappeared?")?;
                     let old_health = stone.health;
                     stone.health = stone.health.saturating_sub(1); // Rock damage = 1
+                    stone.stage = crate::utils::resource_stage_for_health(stone.health, crate::stone::STONE_INITIAL_HEALTH);
                     stone.last_hit_time = Some(now_ts);
                     log::info!("Player {:?} hit Stone {} with {} for {} damage. Health: {} -> {}",
                             sender_id, stone_id, item_def.name, 1, old_health, stone.health);
+                    crate::combat_log::record_combat_hit(ctx, sender_id, CombatTargetType::Stone, None, Some(stone_id), item_def.id, 1.0, stone.health == 0);
 
-                    // Grant 1 Stone - USE REFACTORED HELPER
-                    if let Some(stone_def) = item_defs.iter().find(|def| def.name == "Stone") {
-                       match crate::items::add_item_to_player_inventory(ctx, sender_id, stone_def.id, 1) {
-                           Ok(_) => log::debug!("Granted 1 Stone to player {:?} via helper.", sender_id),
+                    // Grant Stone for the health lost - USE REFACTORED HELPER
+                    let stone_to_grant = crate::utils::take_resource_yield(&mut stone.remaining_yield, stone.health, crate::stone::STONE_INITIAL_HEALTH, crate::stone::STONE_TOTAL_YIELD);
+                    if let Some(stone_def) = crate::items::get_item_def_by_name(ctx, "Stone") {
+                       match crate::items::grant_or_drop(ctx, sender_id, stone_def.id, stone_to_grant) {
+                           Ok(_) => log::debug!("Granted {} Stone to player {:?} via helper.", stone_to_grant, sender_id),
                            Err(e) => log::error!("Failed to grant Stone to player {:?}: {}", sender_id, e),
                        }
                     } else { 
@@ -454,39 +481,17 @@ pub fn use_equipped_item(ctx: &ReducerContext) -> Result<(), String> {
                     }
 
                     if stone.health == 0 {
-                        log::info!("Stone {} depleted by Player {:?}. Scheduling respawn.", stone_id, sender_id);
-                        let respawn_time = now_ts + Duration::from_secs(RESOURCE_RESPAWN_DURATION_SECS);
-                        stone.respawn_at = Some(respawn_time);
-                        stones.id().update(stone); // Update with health 0 and respawn time
-                        // stones.id().delete(stone_id);
-                    } else {
-                        stones.id().update(stone);
+                        on_stone_depleted(ctx, sender_id, &mut stone);
                     }
+                    stones.id().update(stone);
                     hit_something = true;
                 }
             },
             Some("player") => {
-                if let Some((player_id, _)) = closest_player_target { // Retrieve ID again
+                if let Some((player_id, target_dist_sq)) = closest_player_target { // Retrieve ID again
                     // --- Damage Player (Rock Damage = 1 * Multiplier) ---
-                    let mut target_player = players.identity().find(player_id)
-                        .ok_or("Target player disappeared?")?;
-                    let old_health = target_player.health;
-                    // Rock base damage is 1
-                    let actual_damage = (1.0 * PVP_DAMAGE_MULTIPLIER).max(0.0);
-                    target_player.health = (target_player.health - actual_damage).max(0.0);
-                    target_player.last_hit_time = Some(now_ts);
-                    log::info!("Player {:?} hit Player {:?} with {} for {:.1} (1 base * {}x) damage. Health: {:.1} -> {:.1}",
-                            sender_id, player_id, item_def.name, actual_damage, PVP_DAMAGE_MULTIPLIER, old_health, target_player.health);
-
-                    // Check for death
-                    if target_player.health <= 0.0 && !target_player.is_dead {
-                        target_player.is_dead = true;
-                        let respawn_micros = now_micros.saturating_add((RESPAWN_TIME_MS * 1000) as i64);
-                        target_player.respawn_at = Timestamp::from_micros_since_unix_epoch(respawn_micros);
-                        log::info!("Player {:?} killed Player {:?}. Respawn at {:?}", sender_id, player_id, target_player.respawn_at);
-                    }
-
-                    players.identity().update(target_player);
+                    apply_pvp_hit(ctx, sender_id, player_id, 1.0, &item_def, target_dist_sq, attack_range)?;
+                    hit_player = true;
                     hit_something = true;
                 }
             },
@@ -524,18 +529,15 @@ pub fn use_equipped_item(ctx: &ReducerContext) -> Result<(), String> {
                     let mut tree = trees.id().find(tree_id).ok_or("Target tree disappeared?")?;
                     let old_health = tree.health;
                     tree.health = tree.health.saturating_sub(item_damage);
+                    tree.stage = crate::utils::resource_stage_for_health(tree.health, crate::tree::TREE_INITIAL_HEALTH);
                     tree.last_hit_time = Some(now_ts);
                     log::info!("Player {:?} hit Tree {} with {} for {} damage. Health: {} -> {}",
                             sender_id, tree_id, item_def.name, item_damage, old_health, tree.health);
+                    crate::combat_log::record_combat_hit(ctx, sender_id, CombatTargetType::Tree, None, Some(tree_id), item_def.id, item_damage as f32, tree.health == 0);
                     if tree.health == 0 {
-                        log::info!("Tree {} destroyed by Player {:?}. Scheduling respawn.", tree_id, sender_id);
-                        let respawn_time = now_ts + Duration::from_secs(RESOURCE_RESPAWN_DURATION_SECS);
-                        tree.respawn_at = Some(respawn_time);
-                        trees.id().update(tree); // Update with health 0 and respawn time
-                        // trees.id().delete(tree_id); // REMOVED delete
-                    } else {
-                        trees.id().update(tree);
+                        on_tree_felled(ctx, sender_id, &mut tree, (forward_x, forward_y));
                     }
+                    trees.id().update(tree);
                     hit_something = true;
                 }
             },
@@ -544,43 +546,22 @@ pub fn use_equipped_item(ctx: &ReducerContext) -> Result<(), String> {
                     let mut stone = stones.id().find(stone_id).ok_or("Target stone disappeared?")?;
                     let old_health = stone.health;
                     stone.health = stone.health.saturating_sub(item_damage);
+                    stone.stage = crate::utils::resource_stage_for_health(stone.health, crate::stone::STONE_INITIAL_HEALTH);
                     stone.last_hit_time = Some(now_ts); // Set last hit time for shake effect
                     log::info!("Player {:?} hit Stone {} with {} for {} damage. Health: {} -> {}",
                             sender_id, stone_id, item_def.name, item_damage, old_health, stone.health);
+                    crate::combat_log::record_combat_hit(ctx, sender_id, CombatTargetType::Stone, None, Some(stone_id), item_def.id, item_damage as f32, stone.health == 0);
                     if stone.health == 0 {
-                        log::info!("Stone {} depleted by Player {:?}. Scheduling respawn.", stone_id, sender_id);
-                        let respawn_time = now_ts + Duration::from_secs(RESOURCE_RESPAWN_DURATION_SECS);
-                        stone.respawn_at = Some(respawn_time);
-                        stones.id().update(stone); // Update with health 0 and respawn time
-                        // stones.id().delete(stone_id);
-                    } else {
-                        stones.id().update(stone);
+                        on_stone_depleted(ctx, sender_id, &mut stone);
                     }
+                    stones.id().update(stone);
                     hit_something = true;
                 }
             },
             Some("player") => {
-                if let Some((player_id, _)) = closest_player_target { // Retrieve ID again
-                    let mut target_player = players.identity().find(player_id)
-                        .ok_or("Target player disappeared?")?;
-                    let old_health = target_player.health;
-                    // Apply PvP multiplier
-                    let actual_damage = (item_damage as f32 * PVP_DAMAGE_MULTIPLIER).max(0.0);
-                    target_player.health = (target_player.health - actual_damage).max(0.0);
-                    target_player.last_hit_time = Some(now_ts); // <-- Set last hit time
-                    log::info!("Player {:?} hit Player {:?} with {} for {:.1} ({} base * {}x) damage. Health: {:.1} -> {:.1}",
-                            sender_id, player_id, item_def.name, actual_damage, item_damage, PVP_DAMAGE_MULTIPLIER, old_health, target_player.health);
-
-                    // Check for death
-                    if target_player.health <= 0.0 && !target_player.is_dead {
-                        target_player.is_dead = true;
-                        let respawn_micros = now_micros.saturating_add((RESPAWN_TIME_MS * 1000) as i64);
-                        target_player.respawn_at = Timestamp::from_micros_since_unix_epoch(respawn_micros);
-                        log::info!("Player {:?} killed Player {:?}. Respawn at {:?}", sender_id, player_id, target_player.respawn_at);
-                        // TODO: Drop items? Clear equipment?
-                    }
-
-                    players.identity().update(target_player);
+                if let Some((player_id, target_dist_sq)) = closest_player_target { // Retrieve ID again
+                    apply_pvp_hit(ctx, sender_id, player_id, item_damage as f32, &item_def, target_dist_sq, attack_range)?;
+                    hit_player = true;
                     hit_something = true;
                 }
             },
@@ -591,11 +572,138 @@ pub fn use_equipped_item(ctx: &ReducerContext) -> Result<(), String> {
 
     if !hit_something {
         log::debug!("Player {:?} swung {} but hit nothing.", sender_id, item_def.name);
+        if crate::combat_log::LOG_COMBAT_MISSES {
+            crate::combat_log::record_combat_hit(ctx, sender_id, CombatTargetType::Miss, None, None, item_def.id, 0.0, false);
+        }
+    } else if heavy {
+        spend_heavy_attack_stamina(ctx, sender_id, heavy_stamina);
     }
 
+    // Tag the attacker as well, so they can't combat-log either
+    if hit_player {
+        if let Some(mut attacker) = players.identity().find(sender_id) {
+            attacker.combat_until = Some(crate::combat_log::combat_tag_until(now_ts));
+            players.identity().update(attacker);
+        }
+    }
+
+    Ok(())
+}
+
+/// Applies one weapon hit from `attacker_id` to another player `target_dist_sq` away: scales `base_damage`
+/// by the world's PvP multiplier, the weapon's falloff and the target's armor set bonus, tags the target
+/// for combat, and handles the kill.
+fn apply_pvp_hit(ctx: &ReducerContext, attacker_id: Identity, target_id: Identity, base_damage: f32, weapon: &ItemDefinition, target_dist_sq: f32, attack_range: f32) -> Result<(), String> {
+    let players = ctx.db.player();
+    let now_ts = ctx.timestamp;
+    let pvp_damage_multiplier = crate::world_config::get_pvp_damage_multiplier(ctx);
+
+    let mut target_player = players.identity().find(target_id)
+        .ok_or("Target player disappeared?")?;
+    let old_health = target_player.health;
+    // Apply PvP multiplier, falloff and the target's armor set bonus
+    let actual_damage = apply_damage_falloff(base_damage * pvp_damage_multiplier, target_dist_sq, attack_range, weapon.damage_falloff).max(0.0)
+        * crate::armor_sets::damage_taken_multiplier(ctx, target_id);
+    target_player.health = (target_player.health - actual_damage).max(0.0);
+    target_player.last_hit_time = Some(now_ts);
+    target_player.combat_until = Some(crate::combat_log::combat_tag_until(now_ts));
+    log::info!("Player {:?} hit Player {:?} with {} for {:.1} ({} base * {}x) damage. Health: {:.1} -> {:.1}",
+             attacker_id, target_id, weapon.name, actual_damage, base_damage, pvp_damage_multiplier, old_health, target_player.health);
+    crate::combat_log::record_combat_hit(ctx, attacker_id, CombatTargetType::Player, Some(target_id), None, weapon.id, actual_damage, target_player.health <= 0.0 && !target_player.is_dead);
+
+    // Check for death
+    if target_player.health <= 0.0 && !target_player.is_dead {
+        target_player.is_dead = true;
+        let respawn_micros = now_ts.to_micros_since_unix_epoch().saturating_add((RESPAWN_TIME_MS * 1000) as i64);
+        target_player.respawn_at = Timestamp::from_micros_since_unix_epoch(respawn_micros);
+        log::info!("Player {:?} killed Player {:?}. Respawn at {:?}", attacker_id, target_id, target_player.respawn_at);
+        crate::achievements::grant_achievement(ctx, attacker_id, crate::achievements::ACH_FIRST_KILL);
+        crate::player_stats::award_pvp_kill(ctx, attacker_id, target_id);
+        if let Err(e) = crate::items::drop_inventory_on_death(ctx, &target_player) {
+            log::error!("Failed to drop inventory of killed player {:?}: {}", target_id, e);
+        }
+    }
+
+    players.identity().update(target_player);
     Ok(())
 }
 
+/// A tree the sender just brought to 0 health: schedules its respawn, leaves a stump, may fall
+/// along the sender's facing onto players beside it, and grants the first-tree achievement.
+/// The caller saves the tree.
+fn on_tree_felled(ctx: &ReducerContext, sender_id: Identity, tree: &mut crate::tree::Tree, forward: (f32, f32)) {
+    log::info!("Tree {} destroyed by Player {:?}. Scheduling respawn.", tree.id, sender_id);
+    crate::achievements::grant_achievement(ctx, sender_id, crate::achievements::ACH_FIRST_TREE_CHOPPED);
+    tree.respawn_at = crate::environment::depleted_respawn_at(ctx, ctx.timestamp); // None in renewable mode: regrows instead
+    crate::resource_remnant::spawn_remnant(ctx, crate::resource_remnant::RemnantKind::Stump, tree.id, tree.pos_x, tree.pos_y);
+    crate::hazards::try_fell_tree(ctx, sender_id, tree.id, tree.pos_x, tree.pos_y - TREE_COLLISION_Y_OFFSET, forward);
+}
+
+/// A stone the sender just brought to 0 health: schedules its respawn and leaves rubble.
+/// The caller saves the stone.
+fn on_stone_depleted(ctx: &ReducerContext, sender_id: Identity, stone: &mut crate::stone::Stone) {
+    log::info!("Stone {} depleted by Player {:?}. Scheduling respawn.", stone.id, sender_id);
+    stone.respawn_at = crate::environment::depleted_respawn_at(ctx, ctx.timestamp); // None in renewable mode: regrows instead
+    crate::resource_remnant::spawn_remnant(ctx, crate::resource_remnant::RemnantKind::Rubble, stone.id, stone.pos_x, stone.pos_y);
+}
+
+/// Melee reach of an item; unset, non-finite or non-positive values use `DEFAULT_ATTACK_RANGE`.
+fn attack_range_of(item_def: &ItemDefinition) -> f32 {
+    item_def.attack_range.filter(|r| r.is_finite() && *r > 0.0).unwrap_or(DEFAULT_ATTACK_RANGE)
+}
+
+/// Swing width of an item in degrees, capped at a full circle; invalid values use the default.
+fn attack_arc_degrees_of(item_def: &ItemDefinition) -> f32 {
+    item_def.attack_arc_degrees
+        .filter(|a| a.is_finite() && *a > 0.0)
+        .map_or(DEFAULT_ATTACK_ARC_DEGREES, |a| a.min(MAX_ATTACK_ARC_DEGREES))
+}
+
+// A target at offset (dx, dy) is hit if it is strictly inside `attack_range` and within the swing arc.
+fn is_in_swing(dx: f32, dy: f32, forward: (f32, f32), attack_range: f32, half_angle_rad: f32) -> bool {
+    let dist_sq = dx * dx + dy * dy;
+    dist_sq > 0.0 && dist_sq < attack_range * attack_range && is_within_arc(dx, dy, forward.0, forward.1, half_angle_rad)
+}
+
+// Linear melee falloff for weapons that opt in: full damage point-blank,
+// MIN_FALLOFF_DAMAGE_FRACTION of it at the edge of `attack_range`.
+fn apply_damage_falloff(damage: f32, dist_sq: f32, attack_range: f32, falloff: bool) -> f32 {
+    if !falloff || attack_range <= 0.0 {
+        return damage;
+    }
+    let range_fraction = (dist_sq.sqrt() / attack_range).clamp(0.0, 1.0);
+    damage * (1.0 - (1.0 - MIN_FALLOFF_DAMAGE_FRACTION) * range_fraction)
+}
+
+// Charges a heavy attack that hit something for the stamina its damage bonus was based on.
+fn spend_heavy_attack_stamina(ctx: &ReducerContext, player_id: Identity, stamina_spent: f32) {
+    let players = ctx.db.player();
+    let Some(mut player) = players.identity().find(player_id) else {
+        return;
+    };
+    player.stamina = (player.stamina - stamina_spent).max(0.0);
+    players.identity().update(player);
+    log::debug!("Player {:?} heavy attack: spent {:.1} stamina for {:.2}x damage.",
+             player_id, stamina_spent, heavy_attack_damage_multiplier(stamina_spent));
+}
+
+/// Damage scale for a heavy attack that spent `stamina_spent` (clamped to the full cost).
+fn heavy_attack_damage_multiplier(stamina_spent: f32) -> f32 {
+    let stamina_fraction = (stamina_spent / HEAVY_ATTACK_STAMINA_COST).clamp(0.0, 1.0);
+    1.0 + (HEAVY_ATTACK_DAMAGE_MULTIPLIER - 1.0) * stamina_fraction
+}
+
+/// Returns true if `candidate` (id, distance_sq) should replace the current closest target.
+/// Equal distances are broken by the lower id, so selection doesn't depend on table iteration order.
+fn is_closer_target<K: Ord + Copy>(candidate: (K, f32), current: Option<(K, f32)>) -> bool {
+    match current {
+        None => true,
+        Some((current_id, current_dist_sq)) => {
+            candidate.1 < current_dist_sq || (candidate.1 == current_dist_sq && candidate.0 < current_id)
+        }
+    }
+}
+
 // Helper to find or create ActiveEquipment row
 fn get_or_create_active_equipment(ctx: &ReducerContext, player_id: Identity) -> Result<ActiveEquipment, String> {
     let table = ctx.db.active_equipment();
@@ -608,6 +716,8 @@ fn get_or_create_active_equipment(ctx: &ReducerContext, player_id: Identity) ->
             equipped_item_def_id: None, // Initialize hand slot
             equipped_item_instance_id: None,
             swing_start_time_ms: 0,
+            weapon_drawn: false,
+            draw_ready_at_ms: 0,
             // Initialize all armor slots to None
             head_item_instance_id: None,
             chest_item_instance_id: None,
@@ -698,3 +808,240 @@ pub fn equip_armor(ctx: &ReducerContext, item_instance_id: u64) -> Result<(), St
              
     Ok(())
 }
+
+// --- Item Comparison ---
+
+/// Stat deltas of a candidate item versus whatever is equipped in the slot it would occupy
+/// (candidate minus equipped). Every delta is `None` when that slot is currently empty.
+#[derive(SpacetimeType, Clone, Debug, PartialEq)]
+pub struct ItemStatDeltas {
+    pub damage: Option<f32>,             // Base damage after crafted quality
+    pub attack_range: Option<f32>,       // Melee reach in px
+    pub attack_arc_degrees: Option<f32>, // Swing width
+    pub protection: Option<f32>,         // Armor only: quality scale on worn protection (see armor_sets.rs)
+    pub changes_armor_set: Option<bool>, // Armor only: true if swapping changes which set the slot counts toward
+}
+
+// --- Item Comparison Table ---
+// One row per viewer with the comparison for the item they last asked about (tooltip on hover).
+// Clients subscribe to `SELECT * FROM item_comparison WHERE viewer = <self>`; `compare_item` overwrites it.
+#[spacetimedb::table(name = item_comparison, public)]
+#[derive(Clone)]
+pub struct ItemComparison {
+    #[primary_key]
+    pub viewer: Identity,
+    pub candidate_item_instance_id: u64,
+    pub candidate_item_def_id: u64,
+    pub equipped_item_instance_id: Option<u64>,
+    pub equipped_item_def_id: Option<u64>,
+    pub deltas: ItemStatDeltas,
+    pub compared_at: Timestamp,
+}
+
+/// Pure comparison between a candidate and the equipped item (definition and crafted quality).
+/// No table access.
+pub(crate) fn compare_item_definitions(candidate: &ItemDefinition, candidate_quality: Option<u8>, equipped: Option<(&ItemDefinition, Option<u8>)>) -> ItemStatDeltas {
+    let damage_of = |def: &ItemDefinition, quality: Option<u8>| def.damage.unwrap_or(0) as f32 * crate::item_quality::quality_multiplier(quality);
+    let range_of = |def: &ItemDefinition| def.attack_range.filter(|r| r.is_finite() && *r > 0.0).unwrap_or(DEFAULT_ATTACK_RANGE);
+    let arc_of = |def: &ItemDefinition| def.attack_arc_degrees
+        .filter(|a| a.is_finite() && *a > 0.0)
+        .map_or(DEFAULT_ATTACK_ARC_DEGREES, |a| a.min(MAX_ATTACK_ARC_DEGREES));
+    let is_armor = candidate.category == ItemCategory::Armor;
+
+    let Some((equipped_def, equipped_quality)) = equipped else {
+        return ItemStatDeltas { damage: None, attack_range: None, attack_arc_degrees: None, protection: None, changes_armor_set: None };
+    };
+    ItemStatDeltas {
+        damage: Some(damage_of(candidate, candidate_quality) - damage_of(equipped_def, equipped_quality)),
+        attack_range: Some(range_of(candidate) - range_of(equipped_def)),
+        attack_arc_degrees: Some(arc_of(candidate) - arc_of(equipped_def)),
+        protection: is_armor.then(|| crate::item_quality::quality_multiplier(candidate_quality) - crate::item_quality::quality_multiplier(equipped_quality)),
+        changes_armor_set: is_armor.then(|| candidate.armor_set != equipped_def.armor_set),
+    }
+}
+
+/// Returns the equipped item instance ID in the slot the given definition would occupy.
+/// Tools use the main hand, armor uses its body slot.
+fn get_equipped_instance_for_definition(equipment: &ActiveEquipment, item_def: &ItemDefinition) -> Result<Option<u64>, String> {
+    if item_def.category == ItemCategory::Armor {
+        let slot = item_def.equipment_slot.as_ref()
+            .ok_or_else(|| format!("Armor '{}' does not have a defined equipment slot.", item_def.name))?;
+        Ok(match slot {
+            EquipmentSlot::Head => equipment.head_item_instance_id,
+            EquipmentSlot::Chest => equipment.chest_item_instance_id,
+            EquipmentSlot::Legs => equipment.legs_item_instance_id,
+            EquipmentSlot::Feet => equipment.feet_item_instance_id,
+            EquipmentSlot::Hands => equipment.hands_item_instance_id,
+            EquipmentSlot::Back => equipment.back_item_instance_id,
+        })
+    } else if item_def.is_equippable {
+        Ok(equipment.equipped_item_instance_id)
+    } else {
+        Err(format!("Item '{}' cannot be equipped.", item_def.name))
+    }
+}
+
+/// Compares an item the caller owns against what they have equipped in the slot it would occupy
+/// and overwrites their `item_comparison` row with the result.
+#[spacetimedb::reducer]
+pub fn compare_item(ctx: &ReducerContext, item_instance_id: u64) -> Result<(), String> {
+    let sender_id = ctx.sender;
+    let inventory_items = ctx.db.inventory_item();
+    let item_defs = ctx.db.item_definition();
+
+    // 1. Find the candidate item and its definition
+    let candidate_item = inventory_items.instance_id().find(item_instance_id)
+        .ok_or_else(|| GameError::NotFound(format!("Item instance {}", item_instance_id)))?;
+    if candidate_item.player_identity != sender_id {
+        return Err(GameError::NotOwner.into());
+    }
+    let candidate_def = item_defs.id().find(candidate_item.item_def_id)
+        .ok_or_else(|| GameError::NotFound(format!("Item definition {}", candidate_item.item_def_id)))?;
+
+    // 2. Find what is equipped in the relevant slot (no row means nothing equipped)
+    let equipped_item = match ctx.db.active_equipment().player_identity().find(sender_id) {
+        Some(equipment) => get_equipped_instance_for_definition(&equipment, &candidate_def)?
+            .and_then(|instance_id| inventory_items.instance_id().find(instance_id)),
+        None => None,
+    };
+    let equipped_def = equipped_item.as_ref().and_then(|item| item_defs.id().find(item.item_def_id));
+    let equipped = match (&equipped_item, &equipped_def) {
+        (Some(item), Some(def)) => Some((def, item.quality)),
+        _ => None,
+    };
+
+    // 3. Compute deltas and overwrite the caller's comparison row
+    let comparison = ItemComparison {
+        viewer: sender_id,
+        candidate_item_instance_id: item_instance_id,
+        candidate_item_def_id: candidate_def.id,
+        equipped_item_instance_id: equipped.and(equipped_item.as_ref().map(|item| item.instance_id)),
+        equipped_item_def_id: equipped.map(|(def, _)| def.id),
+        deltas: compare_item_definitions(&candidate_def, candidate_item.quality, equipped),
+        compared_at: ctx.timestamp,
+    };
+    let comparisons = ctx.db.item_comparison();
+    if comparisons.viewer().find(sender_id).is_some() {
+        comparisons.viewer().update(comparison);
+    } else {
+        comparisons.insert(comparison);
+    }
+    Ok(())
+}
+
+/// Drops the viewer's comparison row (on disconnect).
+pub(crate) fn clear_item_comparison(ctx: &ReducerContext, viewer_id: Identity) {
+    ctx.db.item_comparison().viewer().delete(viewer_id);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::items::test_item_def;
+    use crate::item_quality::{QUALITY_FINE, QUALITY_STANDARD};
+
+    fn weapon(id: u64, damage: u32, attack_range: Option<f32>) -> ItemDefinition {
+        ItemDefinition { id, damage: Some(damage), attack_range, is_equippable: true, ..test_item_def("Weapon", ItemCategory::Tool) }
+    }
+
+    fn armor(id: u64, armor_set: Option<&str>) -> ItemDefinition {
+        ItemDefinition {
+            id,
+            is_equippable: true,
+            equipment_slot: Some(EquipmentSlot::Chest),
+            armor_set: armor_set.map(str::to_string),
+            ..test_item_def("Shirt", ItemCategory::Armor)
+        }
+    }
+
+    fn assert_close(actual: Option<f32>, expected: f32) {
+        let actual = actual.expect("expected a delta");
+        assert!((actual - expected).abs() < 1e-4, "expected {}, got {}", expected, actual);
+    }
+
+    #[test]
+    fn empty_slot_gives_no_deltas() {
+        let deltas = compare_item_definitions(&weapon(1, 20, None), None, None);
+        assert_eq!(deltas, ItemStatDeltas { damage: None, attack_range: None, attack_arc_degrees: None, protection: None, changes_armor_set: None });
+    }
+
+    #[test]
+    fn weapons_compare_damage_after_quality_and_reach() {
+        let candidate = weapon(1, 20, Some(80.0));
+        let equipped = weapon(2, 15, None);
+        let deltas = compare_item_definitions(&candidate, Some(QUALITY_FINE), Some((&equipped, None)));
+        assert_close(deltas.damage, 20.0 * 1.1 - 15.0);
+        assert_close(deltas.attack_range, 80.0 - DEFAULT_ATTACK_RANGE);
+        assert_close(deltas.attack_arc_degrees, 0.0);
+        assert_eq!(deltas.protection, None);
+        assert_eq!(deltas.changes_armor_set, None);
+    }
+
+    #[test]
+    fn armor_compares_protection_and_set() {
+        let candidate = armor(1, Some("Cloth"));
+        let equipped = armor(2, None);
+        let deltas = compare_item_definitions(&candidate, Some(QUALITY_FINE), Some((&equipped, Some(QUALITY_STANDARD))));
+        assert_close(deltas.protection, 0.1);
+        assert_eq!(deltas.changes_armor_set, Some(true));
+
+        let same_set = compare_item_definitions(&candidate, None, Some((&armor(3, Some("Cloth")), None)));
+        assert_close(same_set.protection, 0.0);
+        assert_eq!(same_set.changes_armor_set, Some(false));
+    }
+
+    // Runs the closest-target scan the way perform_equipped_item_use does
+    fn closest(targets: &[(u64, f32)]) -> Option<(u64, f32)> {
+        targets.iter().fold(None, |current, &candidate| if is_closer_target(candidate, current) { Some(candidate) } else { current })
+    }
+
+    #[test]
+    fn equidistant_targets_resolve_to_the_lowest_id() {
+        assert_eq!(closest(&[(7, 100.0), (3, 100.0)]), Some((3, 100.0)));
+        assert_eq!(closest(&[(3, 100.0), (7, 100.0)]), Some((3, 100.0)));
+        assert_eq!(closest(&[(3, 100.0), (7, 99.0)]), Some((7, 99.0)));
+    }
+
+    #[test]
+    fn falloff_scales_damage_down_to_the_minimum_at_max_reach() {
+        assert_eq!(apply_damage_falloff(40.0, 0.0, 100.0, true), 40.0);
+        assert_eq!(apply_damage_falloff(40.0, 50.0 * 50.0, 100.0, true), 30.0);
+        assert_eq!(apply_damage_falloff(40.0, 100.0 * 100.0, 100.0, true), 40.0 * MIN_FALLOFF_DAMAGE_FRACTION);
+        // Targets past the edge (hitbox overlap) don't drop below the minimum
+        assert_eq!(apply_damage_falloff(40.0, 150.0 * 150.0, 100.0, true), 40.0 * MIN_FALLOFF_DAMAGE_FRACTION);
+    }
+
+    #[test]
+    fn weapons_without_falloff_keep_full_damage() {
+        assert_eq!(apply_damage_falloff(40.0, 100.0 * 100.0, 100.0, false), 40.0);
+        assert_eq!(apply_damage_falloff(40.0, 100.0, 0.0, true), 40.0);
+    }
+
+    #[test]
+    fn heavy_attack_bonus_scales_with_stamina_spent() {
+        assert_eq!(heavy_attack_damage_multiplier(0.0), 1.0);
+        assert_eq!(heavy_attack_damage_multiplier(HEAVY_ATTACK_STAMINA_COST / 2.0), 1.5);
+        assert_eq!(heavy_attack_damage_multiplier(HEAVY_ATTACK_STAMINA_COST), HEAVY_ATTACK_DAMAGE_MULTIPLIER);
+        assert_eq!(heavy_attack_damage_multiplier(HEAVY_ATTACK_STAMINA_COST * 4.0), HEAVY_ATTACK_DAMAGE_MULTIPLIER);
+        assert_eq!(heavy_attack_damage_multiplier(-5.0), 1.0);
+    }
+
+    #[test]
+    fn long_reach_weapons_hit_targets_short_ones_cannot() {
+        let spear = ItemDefinition { attack_range: Some(150.0), attack_arc_degrees: Some(30.0), ..weapon(1, 10, None) };
+        let dagger = ItemDefinition { attack_range: Some(72.0), attack_arc_degrees: Some(60.0), ..weapon(2, 10, None) };
+        let half_arc = |def: &ItemDefinition| attack_arc_degrees_of(def) * PI / 180.0 / 2.0;
+        let swing_hits = |def: &ItemDefinition, dx: f32, dy: f32| is_in_swing(dx, dy, (0.0, 1.0), attack_range_of(def), half_arc(def));
+
+        // Straight ahead at 120px: only the spear reaches
+        assert!(swing_hits(&spear, 0.0, 120.0));
+        assert!(!swing_hits(&dagger, 0.0, 120.0));
+        // Close but 25 degrees off-axis: inside the dagger's arc, outside the spear's
+        let (sin, cos) = 25.0_f32.to_radians().sin_cos();
+        assert!(swing_hits(&dagger, 50.0 * sin, 50.0 * cos));
+        assert!(!swing_hits(&spear, 50.0 * sin, 50.0 * cos));
+
+        let unset = weapon(3, 10, Some(-1.0));
+        assert_eq!((attack_range_of(&unset), attack_arc_degrees_of(&unset)), (DEFAULT_ATTACK_RANGE, DEFAULT_ATTACK_ARC_DEGREES));
+    }
+}
@@ -0,0 +1,110 @@
+use spacetimedb::{Identity, ReducerContext};
+
+use crate::active_equipment::active_equipment as ActiveEquipmentTableTrait;
+use crate::items::{inventory_item as InventoryItemTableTrait, item_definition as ItemDefinitionTableTrait};
+
+// --- Armor Set Bonuses ---
+// Wearing a piece tagged with the same `armor_set` in every set slot grants that set's bonus.
+// The back slot (backpacks) is not part of any set. Mixed or incomplete sets grant nothing.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct ArmorSetBonus {
+    pub set_name: &'static str,
+    pub damage_reduction: f32,         // Fraction of incoming damage removed (0.05 = 5%)
+    pub stamina_drain_multiplier: f32, // Applied to sprint stamina drain (0.85 = 15% less)
+}
+
+// To add a set: tag its pieces with `armor_set: Some("<Name>")` in items_database.rs and list it here.
+const ARMOR_SET_BONUSES: &[ArmorSetBonus] = &[
+    ArmorSetBonus { set_name: "Cloth", damage_reduction: 0.05, stamina_drain_multiplier: 0.85 },
+];
+
+/// Returns the set bonus `player_id` currently has, if every set slot holds a piece of the same set.
+pub(crate) fn get_active_set_bonus(ctx: &ReducerContext, player_id: Identity) -> Option<ArmorSetBonus> {
+    let equipment = ctx.db.active_equipment().player_identity().find(player_id)?;
+    let set_slot_items = [
+        equipment.head_item_instance_id,
+        equipment.chest_item_instance_id,
+        equipment.legs_item_instance_id,
+        equipment.feet_item_instance_id,
+        equipment.hands_item_instance_id,
+    ];
+
+    let inventory_items = ctx.db.inventory_item();
+    let item_defs = ctx.db.item_definition();
+    let slot_sets: Vec<Option<String>> = set_slot_items.into_iter()
+        .map(|instance_id| {
+            let item = inventory_items.instance_id().find(instance_id?)?;
+            item_defs.id().find(item.item_def_id)?.armor_set
+        })
+        .collect();
+    set_bonus_for_slots(&slot_sets)
+}
+
+/// The bonus for the armor sets tagged in each set slot (None = empty slot or untagged piece).
+/// Only a full set of one name counts.
+fn set_bonus_for_slots(slot_sets: &[Option<String>]) -> Option<ArmorSetBonus> {
+    let worn_set = slot_sets.first()?.as_deref()?;
+    if slot_sets.iter().any(|set| set.as_deref() != Some(worn_set)) {
+        return None;
+    }
+    ARMOR_SET_BONUSES.iter().find(|bonus| bonus.set_name == worn_set).copied()
+}
+
+/// Multiplier for damage dealt to `player_id` (1.0 without a set bonus).
+/// The set's damage reduction scales with the average crafted quality of the worn pieces.
+pub(crate) fn damage_taken_multiplier(ctx: &ReducerContext, player_id: Identity) -> f32 {
+    get_active_set_bonus(ctx, player_id)
+        .map_or(1.0, |bonus| {
+            let reduction = bonus.damage_reduction * worn_set_quality_multiplier(ctx, player_id);
+            1.0 - reduction.clamp(0.0, 1.0)
+        })
+}
+
+// Average `quality_multiplier` over the occupied set slots (1.0 if none are occupied).
+fn worn_set_quality_multiplier(ctx: &ReducerContext, player_id: Identity) -> f32 {
+    let Some(equipment) = ctx.db.active_equipment().player_identity().find(player_id) else { return 1.0; };
+    let inventory_items = ctx.db.inventory_item();
+    let multipliers: Vec<f32> = [
+        equipment.head_item_instance_id,
+        equipment.chest_item_instance_id,
+        equipment.legs_item_instance_id,
+        equipment.feet_item_instance_id,
+        equipment.hands_item_instance_id,
+    ].into_iter()
+        .flatten()
+        .filter_map(|instance_id| inventory_items.instance_id().find(instance_id))
+        .map(|item| crate::item_quality::quality_multiplier(item.quality))
+        .collect();
+    if multipliers.is_empty() { 1.0 } else { multipliers.iter().sum::<f32>() / multipliers.len() as f32 }
+}
+
+/// Multiplier for `player_id`'s sprint stamina drain (1.0 without a set bonus).
+pub(crate) fn stamina_drain_multiplier(ctx: &ReducerContext, player_id: Identity) -> f32 {
+    get_active_set_bonus(ctx, player_id)
+        .map_or(1.0, |bonus| bonus.stamina_drain_multiplier.max(0.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn slots(sets: [Option<&str>; 5]) -> Vec<Option<String>> {
+        sets.iter().map(|set| set.map(str::to_string)).collect()
+    }
+
+    #[test]
+    fn full_set_grants_its_bonus() {
+        let bonus = set_bonus_for_slots(&slots([Some("Cloth"); 5])).expect("full cloth set");
+        assert_eq!(bonus.set_name, "Cloth");
+        assert_eq!(bonus.damage_reduction, 0.05);
+    }
+
+    #[test]
+    fn mixed_or_incomplete_sets_grant_nothing() {
+        assert_eq!(set_bonus_for_slots(&slots([Some("Cloth"), Some("Cloth"), Some("Leather"), Some("Cloth"), Some("Cloth")])), None);
+        assert_eq!(set_bonus_for_slots(&slots([Some("Cloth"), Some("Cloth"), Some("Cloth"), Some("Cloth"), None])), None);
+        assert_eq!(set_bonus_for_slots(&slots([None; 5])), None);
+        // A full set nobody defined a bonus for
+        assert_eq!(set_bonus_for_slots(&slots([Some("Bone"); 5])), None);
+    }
+}
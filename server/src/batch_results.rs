@@ -0,0 +1,45 @@
+use spacetimedb::{Identity, ReducerContext, SpacetimeType, Table, Timestamp};
+use log;
+
+// Why a batch operation stopped before (or after) handling everything it found
+#[derive(Clone, Debug, PartialEq, Eq, SpacetimeType)]
+pub enum BatchStopReason {
+    Completed,     // Everything was handled
+    InventoryFull, // Ran out of room; the rest was left where it was
+}
+
+// --- Batch Operation Result Table ---
+// Reducers can only report success or an error string, so batch reducers that can partially
+// succeed (`take_all_from_container`, `pickup_nearby_items`) write their outcome here instead.
+// One row per player, overwritten by their latest batch operation, so the client can show
+// "picked up 5 of 8 items (inventory full)".
+#[spacetimedb::table(name = batch_operation_result, public)]
+#[derive(Clone)]
+pub struct BatchOperationResult {
+    #[primary_key]
+    pub player_identity: Identity,
+    pub operation: String, // Name of the reducer that produced it
+    pub processed: u32,    // Stacks/piles fully handled
+    pub total: u32,        // Stacks/piles the operation found
+    pub stop_reason: BatchStopReason,
+    pub finished_at: Timestamp,
+}
+
+/// Records the outcome of a batch operation for the player, replacing their previous one.
+pub(crate) fn record_batch_result(ctx: &ReducerContext, player_id: Identity, operation: &str, processed: u32, total: u32, stop_reason: BatchStopReason) {
+    log::debug!("[BatchResult] {:?} {}: {}/{} ({:?}).", player_id, operation, processed, total, stop_reason);
+    let results = ctx.db.batch_operation_result();
+    let result = BatchOperationResult {
+        player_identity: player_id,
+        operation: operation.to_string(),
+        processed,
+        total,
+        stop_reason,
+        finished_at: ctx.timestamp,
+    };
+    if results.player_identity().find(player_id).is_some() {
+        results.player_identity().update(result);
+    } else {
+        results.insert(result);
+    }
+}
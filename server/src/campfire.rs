@@ -2,30 +2,94 @@ use spacetimedb::{Identity, Timestamp, ReducerContext, Table};
 use log;
 use std::time::Duration;
 use spacetimedb::spacetimedb_lib::ScheduleAt;
-use std::cmp::min; // Import min for merging logic
 
 // Import table traits AND concrete types
 use crate::player as PlayerTableTrait;
 use crate::items::{inventory_item as InventoryItemTableTrait, item_definition as ItemDefinitionTableTrait, InventoryItem, ItemDefinition};
 // Import helper functions
-use crate::items::add_item_to_player_inventory;
+use crate::errors::GameError;
+use crate::utils::within_range;
+use crate::interaction::CAMPFIRE_INTERACTION_DISTANCE_SQUARED;
+use crate::inventory_management::ItemContainer;
+use crate::containers::ContainerType;
+use crate::Player;
 
 // --- Constants ---
 pub(crate) const CAMPFIRE_COLLISION_RADIUS: f32 = 18.0; // Smaller than player radius
 pub(crate) const CAMPFIRE_COLLISION_Y_OFFSET: f32 = 10.0; // Y offset for collision checking (relative to fire's center)
 pub(crate) const PLAYER_CAMPFIRE_COLLISION_DISTANCE_SQUARED: f32 = (super::PLAYER_RADIUS + CAMPFIRE_COLLISION_RADIUS) * (super::PLAYER_RADIUS + CAMPFIRE_COLLISION_RADIUS);
-pub(crate) const CAMPFIRE_CAMPFIRE_COLLISION_DISTANCE_SQUARED: f32 = (CAMPFIRE_COLLISION_RADIUS * 2.0) * (CAMPFIRE_COLLISION_RADIUS * 2.0); // Prevent placing campfires too close
-
-// Interaction Constants
-pub(crate) const PLAYER_CAMPFIRE_INTERACTION_DISTANCE: f32 = 64.0;
-pub(crate) const PLAYER_CAMPFIRE_INTERACTION_DISTANCE_SQUARED: f32 = PLAYER_CAMPFIRE_INTERACTION_DISTANCE * PLAYER_CAMPFIRE_INTERACTION_DISTANCE;
 
 pub(crate) const WARMTH_RADIUS: f32 = 150.0; // How far the warmth effect reaches
 pub(crate) const WARMTH_RADIUS_SQUARED: f32 = WARMTH_RADIUS * WARMTH_RADIUS;
-pub(crate) const WARMTH_PER_SECOND: f32 = 5.0; // How much warmth is gained per second near a fire
-pub(crate) const FUEL_CONSUME_INTERVAL_SECS: u64 = 5; // Consume 1 wood every 5 seconds
+pub(crate) const WARMTH_PER_SECOND: f32 = 5.0; // Default Camp Fire `warmth_output`, used if its definition has none
+const MAX_COMBINED_WARMTH_FACTOR: f32 = 1.5; // Several sources of one type give at most 1.5x the strongest one's output
+const EXTRA_FIRE_WARMTH_FACTOR: f32 = 0.5; // Each additional fire counts half as much as the previous one
+pub(crate) const FUEL_CONSUME_INTERVAL_SECS: u64 = 5; // Wood's `fuel_burn_duration_secs`, and the fallback for fuel without one
 pub const NUM_FUEL_SLOTS: usize = 5; // Made public
 const FUEL_CHECK_INTERVAL_SECS: u64 = 1; // Check every second
+const STATE_CHECK_INTERVAL_SECS: u64 = 60; // Self-heal pass over every campfire's burning state
+const RESIDUAL_HEAT_SECS: i64 = 30; // An extinguished fire's warmth fades to nothing over this long
+const CAMPFIRE_TOGGLE_COOLDOWN_MS: i64 = 1000; // Minimum time between light/extinguish toggles
+pub(crate) const MAX_CAMPFIRES_PER_PLAYER: usize = 10; // Placed campfires one player may have standing at once
+pub(crate) const BURNT_FOOD_NAME: &str = "Burnt Food"; // What a stack turns into when cooking fails (see skills.rs)
+
+/// Warmth per second a heat source with `warmth_output` gives at the given squared distance.
+/// Full `warmth_output` at the source, falling off linearly to zero at `WARMTH_RADIUS`.
+pub(crate) fn warmth_per_second_at_distance_sq(distance_sq: f32, warmth_output: f32) -> f32 {
+    if distance_sq >= WARMTH_RADIUS_SQUARED {
+        return 0.0;
+    }
+    let falloff = 1.0 - (distance_sq.sqrt() / WARMTH_RADIUS);
+    warmth_output.max(0.0) * falloff.clamp(0.0, 1.0)
+}
+
+/// Combines the warmth from every source of one type in range into one per-second gain.
+/// Rule: sort contributions strongest first, weight them 1, 1/2, 1/4, ... and sum,
+/// then clamp to `MAX_COMBINED_WARMTH_FACTOR` x `warmth_output`. A second fire still helps a bit,
+/// but a "campfire carpet" can't stack warmth without limit. Different source types add up.
+pub(crate) fn combine_fire_warmth(mut contributions: Vec<f32>, warmth_output: f32) -> f32 {
+    contributions.sort_by(|a, b| b.partial_cmp(a).unwrap_or(std::cmp::Ordering::Equal));
+    let mut weight = 1.0;
+    let mut total = 0.0;
+    for contribution in contributions {
+        total += contribution.max(0.0) * weight;
+        weight *= EXTRA_FIRE_WARMTH_FACTOR;
+    }
+    total.min(warmth_output.max(0.0) * MAX_COMBINED_WARMTH_FACTOR)
+}
+
+/// Warmth/s a lit campfire gives up close, from the Camp Fire item definition.
+pub(crate) fn campfire_warmth_output(ctx: &ReducerContext) -> f32 {
+    campfire_item_def(ctx)
+        .and_then(|def| def.warmth_output)
+        .unwrap_or(WARMTH_PER_SECOND)
+}
+
+/// How long one unit of `fuel_def` burns, from its `fuel_burn_duration_secs`.
+fn fuel_unit_burn_duration(fuel_def: &ItemDefinition) -> Duration {
+    Duration::from_secs(fuel_def.fuel_burn_duration_secs.map_or(FUEL_CONSUME_INTERVAL_SECS, |secs| secs.max(1)))
+}
+
+/// Burn time of the unit the campfire will consume next (the first fuel slot holding fuel).
+/// Falls back to `FUEL_CONSUME_INTERVAL_SECS` if no slot holds any.
+fn next_fuel_burn_duration(ctx: &ReducerContext, campfire: &Campfire) -> Duration {
+    let inventory_items = ctx.db.inventory_item();
+    let item_defs = ctx.db.item_definition();
+    for slot_index in 0..NUM_FUEL_SLOTS as u8 {
+        let Some(instance_id) = campfire.get_slot_instance_id(slot_index) else { continue; };
+        let Some(item) = inventory_items.instance_id().find(instance_id) else { continue; };
+        let Some(def) = item_defs.id().find(item.item_def_id) else { continue; };
+        if is_campfire_fuel(&def) && item.quantity > 0 {
+            return fuel_unit_burn_duration(&def);
+        }
+    }
+    Duration::from_secs(FUEL_CONSUME_INTERVAL_SECS)
+}
+
+fn campfire_item_def(ctx: &ReducerContext) -> Option<ItemDefinition> {
+    ctx.db.item_definition().iter()
+        .find(|def| def.placeable_kind == Some(crate::items::PlaceableKind::Campfire))
+}
 
 #[spacetimedb::table(name = campfire, public)]
 #[derive(Clone)]
@@ -50,6 +114,31 @@ pub struct Campfire {
     pub fuel_instance_id_4: Option<u64>,
     pub fuel_def_id_4: Option<u64>,
     pub next_fuel_consume_at: Option<Timestamp>, // Timestamp for next fuel consumption check
+    pub last_toggled_at: Option<Timestamp>, // Last manual light/extinguish, for the toggle cooldown
+    pub extinguished_at: Option<Timestamp>, // When it last went out; gives residual warmth until cooled. None while lit (read by offline_summary.rs)
+    pub cooking_instance_id: Option<u64>, // Raw food stack currently cooking (the first cookable stack in the slots)
+    pub cook_progress_secs: u64, // Seconds of burning that stack has had. Pauses while unlit, resets when the stack changes
+    pub no_fuel_consumption: bool, // Admin "always on" fire (e.g. spawn safe zone): burns without fuel and can't go out
+}
+
+impl Campfire {
+    /// Puts the fire out and starts its cool-down (see `campfire_heat_fraction`).
+    pub(crate) fn extinguish(&mut self, now: Timestamp) {
+        self.is_burning = false;
+        self.next_fuel_consume_at = None;
+        self.extinguished_at = Some(now);
+    }
+}
+
+/// Share of its full warmth a campfire gives off: 1.0 while lit, then fading linearly
+/// to 0.0 over `RESIDUAL_HEAT_SECS` after it goes out.
+pub(crate) fn campfire_heat_fraction(campfire: &Campfire, now: Timestamp) -> f32 {
+    if campfire.is_burning {
+        return 1.0;
+    }
+    let Some(extinguished_at) = campfire.extinguished_at else { return 0.0; };
+    let cooled_micros = now.to_micros_since_unix_epoch().saturating_sub(extinguished_at.to_micros_since_unix_epoch());
+    (1.0 - cooled_micros as f32 / (RESIDUAL_HEAT_SECS * 1_000_000) as f32).clamp(0.0, 1.0)
 }
 
 // --- Schedule Table for Fuel Check --- 
@@ -62,375 +151,320 @@ pub struct CampfireFuelCheckSchedule {
     pub scheduled_at: ScheduleAt,
 }
 
-// --- Reducers ---
+// --- Schedule Table for the State Self-Heal Pass ---
+#[spacetimedb::table(name = campfire_state_check_schedule, scheduled(validate_all_campfires))]
+#[derive(Clone)]
+pub struct CampfireStateCheckSchedule {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub scheduled_at: ScheduleAt,
+}
 
-/// Reducer called by the client when the player attempts to interact (e.g., press 'E')
-/// Currently, this only validates proximity. The client will handle opening the UI.
-#[spacetimedb::reducer]
-pub fn interact_with_campfire(ctx: &ReducerContext, campfire_id: u32) -> Result<(), String> {
-    let sender_id = ctx.sender;
-    let players = ctx.db.player();
-    let campfires = ctx.db.campfire();
+// --- Trait Implementation ---
 
-    // 1. Find Player
-    let player = players.identity().find(sender_id)
-        .ok_or_else(|| "Player not found".to_string())?;
+impl ItemContainer for Campfire {
+    fn num_slots(&self) -> usize {
+        NUM_FUEL_SLOTS
+    }
 
-    // 2. Find Campfire
-    let campfire = campfires.id().find(campfire_id)
-        .ok_or_else(|| format!("Campfire {} not found", campfire_id))?;
+    fn get_slot_instance_id(&self, slot_index: u8) -> Option<u64> {
+        match slot_index {
+            0 => self.fuel_instance_id_0,
+            1 => self.fuel_instance_id_1,
+            2 => self.fuel_instance_id_2,
+            3 => self.fuel_instance_id_3,
+            4 => self.fuel_instance_id_4,
+            _ => None,
+        }
+    }
 
-    // 3. Check Distance
-    let dx = player.position_x - campfire.pos_x;
-    let dy = player.position_y - campfire.pos_y;
-    let dist_sq = dx * dx + dy * dy;
+    fn get_slot_def_id(&self, slot_index: u8) -> Option<u64> {
+        match slot_index {
+            0 => self.fuel_def_id_0,
+            1 => self.fuel_def_id_1,
+            2 => self.fuel_def_id_2,
+            3 => self.fuel_def_id_3,
+            4 => self.fuel_def_id_4,
+            _ => None,
+        }
+    }
 
-    if dist_sq > PLAYER_CAMPFIRE_INTERACTION_DISTANCE_SQUARED {
-        return Err("Too far away to interact with the campfire".to_string());
+    fn set_slot(&mut self, slot_index: u8, instance_id: Option<u64>, def_id: Option<u64>) {
+        match slot_index {
+            0 => { self.fuel_instance_id_0 = instance_id; self.fuel_def_id_0 = def_id; }
+            1 => { self.fuel_instance_id_1 = instance_id; self.fuel_def_id_1 = def_id; }
+            2 => { self.fuel_instance_id_2 = instance_id; self.fuel_def_id_2 = def_id; }
+            3 => { self.fuel_instance_id_3 = instance_id; self.fuel_def_id_3 = def_id; }
+            4 => { self.fuel_instance_id_4 = instance_id; self.fuel_def_id_4 = def_id; }
+            _ => {} // Ignore invalid index
+        }
     }
 
-    log::debug!("Player {:?} interaction check OK for campfire {}", sender_id, campfire_id);
-    // Interaction is valid, client can proceed to open UI
-    Ok(())
+    // Slots take things that burn, or raw food to cook over the fire
+    fn accepts(&self, _slot_index: u8, item_def: &ItemDefinition) -> bool {
+        is_campfire_fuel(item_def) || is_cookable(item_def)
+    }
 }
 
-/// Adds an item from the player's inventory as fuel to a specific campfire slot.
-#[spacetimedb::reducer]
-pub fn add_fuel_to_campfire(ctx: &ReducerContext, campfire_id: u32, target_slot_index: u8, item_instance_id: u64) -> Result<(), String> {
-    let sender_id = ctx.sender;
-    let players = ctx.db.player();
-    let mut campfires = ctx.db.campfire();
-    let mut inventory_items = ctx.db.inventory_item();
-    let item_defs = ctx.db.item_definition();
+/// Anything that turns into something else after a set cooking time can be cooked.
+pub(crate) fn is_cookable(item_def: &ItemDefinition) -> bool {
+    item_def.cookable_into.is_some() && item_def.cook_time_secs.is_some()
+}
 
-    // 1. Validate slot index
-    if target_slot_index >= NUM_FUEL_SLOTS as u8 {
-        return Err(format!("Invalid target fuel slot index: {}", target_slot_index));
-    }
-    let slot_idx = target_slot_index as usize; // Use usize for indexing later
+/// Anything with a burn duration is fuel.
+pub(crate) fn is_campfire_fuel(item_def: &ItemDefinition) -> bool {
+    item_def.fuel_burn_duration_secs.is_some()
+}
 
-    // 2. Find Player & Campfire
-    let player = players.identity().find(sender_id).ok_or("Player not found")?;
-    let mut campfire = campfires.id().find(campfire_id).ok_or(format!("Campfire {} not found", campfire_id))?;
+// --- Reducers ---
 
-    // 3. Check Distance
-    let dx = player.position_x - campfire.pos_x;
-    let dy = player.position_y - campfire.pos_y;
-    if (dx * dx + dy * dy) > PLAYER_CAMPFIRE_INTERACTION_DISTANCE_SQUARED { return Err("Too far away".to_string()); }
-
-    // 4. Find the dragged item (item_to_add) & its definition
-    let mut item_to_add = inventory_items.instance_id().find(item_instance_id).ok_or("Item instance not found")?;
-    if item_to_add.player_identity != sender_id { return Err("Item does not belong to player".to_string()); }
-    let definition_to_add = item_defs.id().find(item_to_add.item_def_id).ok_or("Item definition not found")?;
-
-    // --- Determine Original Location --- 
-    let original_location_was_equipment = item_to_add.inventory_slot.is_none() && item_to_add.hotbar_slot.is_none();
-    if original_location_was_equipment {
-        log::debug!("[AddFuel] Item {} potentially coming from equipment slot.", item_instance_id);
-    }
+/// Validates that the sender can use the campfire (exists, sender alive and in range).
+pub(crate) fn validate_campfire_interaction(ctx: &ReducerContext, campfire_id: u32) -> Result<(Player, Campfire), String> {
+    let campfire = ctx.db.campfire().id().find(campfire_id)
+        .ok_or_else(|| GameError::NotFound(format!("Campfire {}", campfire_id)))?;
+    let player = ensure_player_near_campfire(ctx, &campfire)?;
+    Ok((player, campfire))
+}
 
-    // 5. Check the target campfire fuel slot
-    let target_instance_id_opt = match target_slot_index {
-        0 => campfire.fuel_instance_id_0,
-        1 => campfire.fuel_instance_id_1,
-        2 => campfire.fuel_instance_id_2,
-        3 => campfire.fuel_instance_id_3,
-        4 => campfire.fuel_instance_id_4,
-        _ => None,
-    };
+/// True if the campfire is lit and uses up fuel; always-on fires burn for free.
+fn burns_fuel(campfire: &Campfire) -> bool {
+    campfire.is_burning && !campfire.no_fuel_consumption
+}
 
-    if let Some(target_instance_id) = target_instance_id_opt {
-        // --- Target Slot is Occupied: Try to Merge --- 
-        let mut target_item = inventory_items.instance_id().find(target_instance_id)
-                                .ok_or_else(|| format!("Target item instance {} in slot {} not found!", target_instance_id, target_slot_index))?;
-
-        // Call the merge helper
-        match crate::items::calculate_merge_result(&item_to_add, &target_item, &definition_to_add) {
-            Ok((qty_transfer, source_new_qty, target_new_qty, delete_source)) => {
-                log::info!("[AddFuel Merge] Merging {} from item {} onto item {}. New Qty: {}, Target Qty: {}", 
-                         qty_transfer, item_instance_id, target_instance_id, source_new_qty, target_new_qty);
-
-                // Update target item quantity
-                target_item.quantity = target_new_qty;
-                inventory_items.instance_id().update(target_item);
-
-                // Update or delete source item
-                if delete_source {
-                    inventory_items.instance_id().delete(item_instance_id);
-                    log::info!("[AddFuel Merge] Source item {} deleted.", item_instance_id);
-                } else {
-                    item_to_add.quantity = source_new_qty;
-                    inventory_items.instance_id().update(item_to_add);
-                }
-                // Campfire state doesn't change in a merge (item ref remains the same)
-            },
-            Err(e) => {
-                // Merge failed (e.g., different types, target full) - reject the drop for now
-                log::warn!("[AddFuel Merge] Cannot merge item {} onto slot {}: {}", item_instance_id, target_slot_index, e);
-                return Err(format!("Cannot merge into slot {}: {}", target_slot_index, e));
-                // FUTURE: Could implement swapping logic here as a fallback
+/// Units left in a fuel stack of `quantity` after a fuel check at `now`: one unit burns only if
+/// the fire uses fuel and its next burn is due.
+fn fuel_left_after_tick(campfire: &Campfire, now: Timestamp, quantity: u32) -> u32 {
+    let due = campfire.next_fuel_consume_at.is_some_and(|at| now >= at);
+    if burns_fuel(campfire) && due { quantity.saturating_sub(1) } else { quantity }
+}
+
+/// Puts out a burning campfire whose fuel slots no longer hold anything burnable.
+/// Call after any change to the fuel slots, before saving the campfire.
+pub(crate) fn refresh_campfire_burning_state(ctx: &ReducerContext, campfire: &mut Campfire) {
+    if burns_fuel(campfire) && !check_if_campfire_has_fuel(ctx, campfire) {
+        campfire.extinguish(ctx.timestamp);
+        log::info!("Campfire {} extinguished: no fuel left in its slots.", campfire.id);
+    }
+}
+
+/// Repairs a campfire whose slots or burning flags have drifted from its actual fuel.
+/// Clears slots pointing at deleted items, puts out a fire with nothing to burn, schedules
+/// fuel use for a lit fire that has none scheduled, and drops a stale schedule from an unlit one.
+/// An unlit fire with fuel is left unlit (players put fires out on purpose).
+/// Returns true if anything was corrected; the caller saves the campfire.
+pub(crate) fn reconcile_campfire_state(ctx: &ReducerContext, campfire: &mut Campfire) -> bool {
+    let inventory_items = ctx.db.inventory_item();
+    let mut corrected = false;
+
+    for slot_index in 0..NUM_FUEL_SLOTS as u8 {
+        let Some(instance_id) = campfire.get_slot_instance_id(slot_index) else { continue; };
+        match inventory_items.instance_id().find(instance_id) {
+            None => {
+                log::warn!("[CampfireState] Campfire {} slot {} pointed at missing item {}; clearing slot.", campfire.id, slot_index, instance_id);
+                campfire.set_slot(slot_index, None, None);
+                corrected = true;
+            }
+            Some(item) if campfire.get_slot_def_id(slot_index) != Some(item.item_def_id) => {
+                log::warn!("[CampfireState] Campfire {} slot {} had def {:?} for item {} (def {}); fixing.",
+                         campfire.id, slot_index, campfire.get_slot_def_id(slot_index), instance_id, item.item_def_id);
+                campfire.set_slot(slot_index, Some(instance_id), Some(item.item_def_id));
+                corrected = true;
             }
+            Some(_) => {}
         }
+    }
 
-        // Clear equipment slot AFTER successful merge
-        if original_location_was_equipment {
-            crate::items::clear_specific_item_from_equipment_slots(ctx, sender_id, item_instance_id);
+    let has_fuel = !campfire.no_fuel_consumption && check_if_campfire_has_fuel(ctx, campfire);
+    match burn_state_fix(campfire, has_fuel) {
+        Some(BurnStateFix::Relight) => {
+            log::warn!("[CampfireState] Always-on campfire {} was unlit or had fuel use scheduled; relighting.", campfire.id);
+            campfire.is_burning = true;
+            campfire.extinguished_at = None;
+            campfire.next_fuel_consume_at = None;
         }
-
-    } else {
-        // --- Target Slot is Empty: Place Item --- 
-        // 6. Update item (remove from inv/hotbar)
-        item_to_add.inventory_slot = None;
-        item_to_add.hotbar_slot = None;
-        inventory_items.instance_id().update(item_to_add.clone()); // Clone needed as item_to_add borrowed for log
-        log::info!("Moved item {} ({}) from player {:?} inv/hotbar to campfire {}", item_instance_id, definition_to_add.name, sender_id, campfire_id);
-
-        // 7. Update campfire state in the specific slot (use match)
-        match target_slot_index {
-            0 => {
-                log::info!("[AddFuel Debug] Matching slot 0");
-                campfire.fuel_instance_id_0 = Some(item_instance_id); campfire.fuel_def_id_0 = Some(definition_to_add.id); 
-            },
-            1 => { campfire.fuel_instance_id_1 = Some(item_instance_id); campfire.fuel_def_id_1 = Some(definition_to_add.id); },
-            2 => { campfire.fuel_instance_id_2 = Some(item_instance_id); campfire.fuel_def_id_2 = Some(definition_to_add.id); },
-            3 => { campfire.fuel_instance_id_3 = Some(item_instance_id); campfire.fuel_def_id_3 = Some(definition_to_add.id); },
-            4 => { campfire.fuel_instance_id_4 = Some(item_instance_id); campfire.fuel_def_id_4 = Some(definition_to_add.id); },
-            _ => {}, // Should not happen
+        Some(BurnStateFix::Extinguish) => {
+            log::warn!("[CampfireState] Campfire {} was burning with no fuel; extinguishing.", campfire.id);
+            campfire.extinguish(ctx.timestamp);
+        }
+        Some(BurnStateFix::ScheduleFuel) => {
+            log::warn!("[CampfireState] Campfire {} was burning with no fuel use scheduled; scheduling.", campfire.id);
+            campfire.next_fuel_consume_at = Some(ctx.timestamp + next_fuel_burn_duration(ctx, campfire));
         }
-        
-        // Re-check if fire should extinguish if it was burning without valid fuel
-        let can_light_now = check_if_campfire_has_fuel(ctx, &campfire);
-        if !can_light_now && campfire.is_burning {
-            campfire.is_burning = false;
+        Some(BurnStateFix::ClearSchedule) => {
+            log::warn!("[CampfireState] Campfire {} was unlit but still had fuel use scheduled; clearing.", campfire.id);
             campfire.next_fuel_consume_at = None;
-            log::warn!("Campfire {} extinguished as newly added fuel is not valid wood.", campfire_id);
         }
+        None => return corrected,
+    }
+    true
+}
 
-        campfires.id().update(campfire); // Update the campfire
-        log::info!("Added item instance {} (Def {}) as fuel to campfire {} slot {}.", item_instance_id, definition_to_add.id, campfire_id, target_slot_index);
+/// A correction `reconcile_campfire_state` makes to a campfire's burning flags.
+#[derive(Debug, PartialEq)]
+enum BurnStateFix {
+    Relight,
+    Extinguish,
+    ScheduleFuel,
+    ClearSchedule,
+}
 
-        // Clear equipment slot AFTER successful placement
-        if original_location_was_equipment {
-            crate::items::clear_specific_item_from_equipment_slots(ctx, sender_id, item_instance_id);
-        }
+/// What, if anything, is wrong with a campfire's burning flags given whether it holds burnable fuel.
+/// Always-on fires must be lit with no fuel use scheduled; an unlit fire with fuel is fine.
+fn burn_state_fix(campfire: &Campfire, has_fuel: bool) -> Option<BurnStateFix> {
+    let scheduled = campfire.next_fuel_consume_at.is_some();
+    if campfire.no_fuel_consumption {
+        return (!campfire.is_burning || scheduled).then_some(BurnStateFix::Relight);
+    }
+    match (campfire.is_burning, has_fuel, scheduled) {
+        (true, false, _) => Some(BurnStateFix::Extinguish),
+        (true, true, false) => Some(BurnStateFix::ScheduleFuel),
+        (false, _, true) => Some(BurnStateFix::ClearSchedule),
+        _ => None,
     }
+}
 
+/// Admin/maintenance: re-checks one campfire's burning state against its fuel and fixes it.
+#[spacetimedb::reducer]
+pub fn validate_campfire_state(ctx: &ReducerContext, campfire_id: u32) -> Result<(), String> {
+    crate::world_config::ensure_admin(ctx)?;
+    let mut campfire = ctx.db.campfire().id().find(campfire_id)
+        .ok_or_else(|| GameError::NotFound(format!("Campfire {}", campfire_id)))?;
+    if reconcile_campfire_state(ctx, &mut campfire) {
+        ctx.db.campfire().id().update(campfire);
+        log::info!("[CampfireState] Admin {:?} corrected campfire {}.", ctx.sender, campfire_id);
+    } else {
+        log::info!("[CampfireState] Campfire {} is consistent.", campfire_id);
+    }
     Ok(())
 }
 
-/// Removes the fuel item from a specific campfire slot and returns it to the player,
-/// attempting to merge with existing stacks first.
+/// Admin: marks a campfire as "always on" (burns without using fuel and can't be put out),
+/// e.g. for safe-zone warmth near spawn. Turning it off hands the fire back to the normal
+/// fuel rules: it keeps burning on whatever fuel it holds, or goes out if it has none.
 #[spacetimedb::reducer]
-pub fn auto_remove_fuel_from_campfire(ctx: &ReducerContext, campfire_id: u32, source_slot_index: u8) -> Result<(), String> {
-    let sender_id = ctx.sender;
-    let players = ctx.db.player();
-    let mut campfires = ctx.db.campfire();
-    let mut inventory_items = ctx.db.inventory_item();
-    let item_defs = ctx.db.item_definition();
+pub fn set_campfire_no_fuel_consumption(ctx: &ReducerContext, campfire_id: u32, enabled: bool) -> Result<(), String> {
+    crate::world_config::ensure_admin(ctx)?;
+    let mut campfire = ctx.db.campfire().id().find(campfire_id)
+        .ok_or_else(|| GameError::NotFound(format!("Campfire {}", campfire_id)))?;
 
-    log::info!(
-        "[AutoRemoveFuel] Player {:?} removing fuel from campfire {} slot {}",
-        sender_id, campfire_id, source_slot_index
-    );
-
-    // 1. Validate slot index
-    if source_slot_index >= NUM_FUEL_SLOTS as u8 {
-        return Err(format!("Invalid source fuel slot index: {}", source_slot_index));
+    campfire.no_fuel_consumption = enabled;
+    if enabled {
+        campfire.is_burning = true;
+        campfire.extinguished_at = None;
+        campfire.next_fuel_consume_at = None;
+    } else {
+        reconcile_campfire_state(ctx, &mut campfire); // Schedules fuel use, or puts it out if there's no fuel
     }
+    ctx.db.campfire().id().update(campfire);
+    log::info!("[CampfireState] Admin {:?} set campfire {} always-on: {}.", ctx.sender, campfire_id, enabled);
+    Ok(())
+}
 
-    // 2. Find Player & Campfire
-    let player = players.identity().find(sender_id).ok_or("Player not found")?;
-    let mut campfire = campfires.id().find(campfire_id).ok_or(format!("Campfire {} not found", campfire_id))?;
+/// Scheduled self-heal pass over every campfire (see `reconcile_campfire_state`).
+#[spacetimedb::reducer]
+pub fn validate_all_campfires(ctx: &ReducerContext, _schedule: CampfireStateCheckSchedule) -> Result<(), String> {
+    let campfires = ctx.db.campfire();
+    let mut corrected_count = 0;
+    for mut campfire in campfires.iter().collect::<Vec<_>>() {
+        if reconcile_campfire_state(ctx, &mut campfire) {
+            campfires.id().update(campfire);
+            corrected_count += 1;
+        }
+    }
+    if corrected_count > 0 {
+        log::info!("[CampfireState] Corrected {} campfires.", corrected_count);
+    }
+    Ok(())
+}
 
-    // 3. Check Distance
-    let dx = player.position_x - campfire.pos_x;
-    let dy = player.position_y - campfire.pos_y;
-    if (dx * dx + dy * dy) > PLAYER_CAMPFIRE_INTERACTION_DISTANCE_SQUARED {
-        return Err("Too far away".to_string());
+/// Rejects the call unless the sender is alive and within interaction range of `campfire`.
+/// The fuel-slot reducers take slot indices from the client, so they must not trust it to be nearby.
+fn ensure_player_near_campfire(ctx: &ReducerContext, campfire: &Campfire) -> Result<Player, String> {
+    let player = ctx.db.player().identity().find(ctx.sender)
+        .ok_or_else(|| GameError::NotFound("Player".to_string()))?;
+    if player.is_dead {
+        return Err("Cannot use a campfire while dead".to_string());
     }
+    if !within_range(player.position_x, player.position_y, campfire.pos_x, campfire.pos_y, CAMPFIRE_INTERACTION_DISTANCE_SQUARED) {
+        return Err(GameError::TooFarAway.into());
+    }
+    Ok(player)
+}
 
-    // 4. Check if there is a fuel item in the specified slot (use match)
-    let (fuel_instance_id, fuel_def_id) = match source_slot_index {
-        0 => (campfire.fuel_instance_id_0, campfire.fuel_def_id_0),
-        1 => (campfire.fuel_instance_id_1, campfire.fuel_def_id_1),
-        2 => (campfire.fuel_instance_id_2, campfire.fuel_def_id_2),
-        3 => (campfire.fuel_instance_id_3, campfire.fuel_def_id_3),
-        4 => (campfire.fuel_instance_id_4, campfire.fuel_def_id_4),
-        _ => (None, None),
-    };
-    let fuel_instance_id = fuel_instance_id
-        .ok_or_else(|| format!("No fuel item in campfire slot {} to remove", source_slot_index))?;
-    let fuel_def_id = fuel_def_id
-        .ok_or_else(|| format!("Missing def ID for fuel item in slot {}", source_slot_index))?;
-
-    // 5. Find Inventory Item (mutable) and Definition
-    let mut item_to_return = inventory_items.instance_id().find(fuel_instance_id)
-        .ok_or_else(|| format!("Could not find fuel item instance {}", fuel_instance_id))?;
-    let definition = item_defs.id().find(fuel_def_id)
-        .ok_or("Fuel item definition not found")?;
-
-    // 6. Attempt to merge into player's inventory/hotbar
-    let mut item_fully_merged = false;
-    if definition.is_stackable {
-        // Prioritize merging into hotbar
-        let hotbar_items: Vec<InventoryItem> = inventory_items.iter()
-            .filter(|i| i.player_identity == sender_id && i.item_def_id == fuel_def_id && i.hotbar_slot.is_some())
-            .collect(); // Collect to avoid borrowing issues
-        
-        for mut target_item in hotbar_items {
-             match crate::items::calculate_merge_result(&item_to_return, &target_item, &definition) {
-                Ok((qty_transfer, source_new_qty, target_new_qty, delete_source)) => {
-                    if qty_transfer > 0 {
-                        log::info!(
-                            "[AutoRemoveFuel Merge] Merging {} from campfire item {} onto hotbar item {}.",
-                            qty_transfer, fuel_instance_id, target_item.instance_id
-                        );
-                        target_item.quantity = target_new_qty;
-                        inventory_items.instance_id().update(target_item);
-                        item_to_return.quantity = source_new_qty;
-                        if delete_source {
-                            item_fully_merged = true;
-                            break;
-                        }
-                    }
-                }
-                 Err(_) => {} // Ignore errors (e.g., target full)
-             }
-        }
+/// Reducer called by the client when the player attempts to interact (e.g., press 'E')
+/// Currently, this only validates proximity. The client will handle opening the UI.
+#[spacetimedb::reducer]
+pub fn interact_with_campfire(ctx: &ReducerContext, campfire_id: u32) -> Result<(), String> {
+    let sender_id = ctx.sender;
+    let players = ctx.db.player();
+    let campfires = ctx.db.campfire();
 
-        // If not fully merged, try merging into main inventory
-        if !item_fully_merged {
-            let inventory_items_main: Vec<InventoryItem> = inventory_items.iter()
-                .filter(|i| i.player_identity == sender_id && i.item_def_id == fuel_def_id && i.inventory_slot.is_some())
-                .collect();
+    // 1. Find Player
+    let player = players.identity().find(sender_id)
+        .ok_or_else(|| "Player not found".to_string())?;
 
-            for mut target_item in inventory_items_main {
-                 match crate::items::calculate_merge_result(&item_to_return, &target_item, &definition) {
-                    Ok((qty_transfer, source_new_qty, target_new_qty, delete_source)) => {
-                        if qty_transfer > 0 {
-                             log::info!(
-                                "[AutoRemoveFuel Merge] Merging {} from campfire item {} onto inventory item {}.",
-                                qty_transfer, fuel_instance_id, target_item.instance_id
-                            );
-                            target_item.quantity = target_new_qty;
-                            inventory_items.instance_id().update(target_item);
-                            item_to_return.quantity = source_new_qty;
-                            if delete_source {
-                                item_fully_merged = true;
-                                break;
-                            }
-                        }
-                    }
-                    Err(_) => {} // Ignore errors
-                 }
-            }
-        }
-    }
+    // 2. Find Campfire
+    let campfire = campfires.id().find(campfire_id)
+        .ok_or_else(|| format!("Campfire {} not found", campfire_id))?;
 
-    // 7. If item fully merged, delete the original and clear campfire slot
-    if item_fully_merged {
-        log::info!(
-            "[AutoRemoveFuel] Item {} fully merged into player inventory. Deleting original.",
-            fuel_instance_id
-        );
-        inventory_items.instance_id().delete(fuel_instance_id);
-    } else {
-        // 8. Item not fully merged, find empty slot (Hotbar first, then Inventory)
-        log::info!(
-            "[AutoRemoveFuel] Item {} (qty {}) not fully merged. Finding empty slot...",
-            fuel_instance_id, item_to_return.quantity
-        );
-        let occupied_hotbar_slots: std::collections::HashSet<u8> = inventory_items.iter()
-            .filter(|i| i.player_identity == sender_id && i.hotbar_slot.is_some())
-            .map(|i| i.hotbar_slot.unwrap())
-            .collect();
-        let empty_hotbar_slot = (0..6).find(|slot| !occupied_hotbar_slots.contains(slot));
-
-        if let Some(slot_index) = empty_hotbar_slot {
-            // Place in empty hotbar slot
-            log::info!(
-                "[AutoRemoveFuel] Placing remaining item {} into hotbar slot {}",
-                fuel_instance_id, slot_index
-            );
-            item_to_return.player_identity = sender_id; // Ensure ownership
-            item_to_return.inventory_slot = None;
-            item_to_return.hotbar_slot = Some(slot_index);
-            inventory_items.instance_id().update(item_to_return);
-        } else {
-            // Hotbar full, try main inventory
-            let occupied_inventory_slots: std::collections::HashSet<u16> = inventory_items.iter()
-                .filter(|i| i.player_identity == sender_id && i.inventory_slot.is_some())
-                .map(|i| i.inventory_slot.unwrap())
-                .collect();
-            let empty_inventory_slot = (0..24).find(|slot| !occupied_inventory_slots.contains(slot));
-
-            if let Some(slot_index) = empty_inventory_slot {
-                 // Place in empty inventory slot
-                log::info!(
-                    "[AutoRemoveFuel] Placing remaining item {} into inventory slot {}",
-                    fuel_instance_id, slot_index
-                );
-                item_to_return.player_identity = sender_id; // Ensure ownership
-                item_to_return.inventory_slot = Some(slot_index);
-                item_to_return.hotbar_slot = None;
-                inventory_items.instance_id().update(item_to_return);
-            } else {
-                // Inventory full, cannot place item
-                 log::error!(
-                    "[AutoRemoveFuel] Player {:?} inventory full, cannot return item {} from campfire.",
-                    sender_id, fuel_instance_id
-                );
-                return Err("Inventory is full".to_string());
-            }
-        }
+    // 3. Check Distance
+    if !within_range(player.position_x, player.position_y, campfire.pos_x, campfire.pos_y, CAMPFIRE_INTERACTION_DISTANCE_SQUARED) {
+        return Err("Too far away to interact with the campfire".to_string());
     }
 
-    // 9. Update campfire state: clear the specific source slot (use match)
-    match source_slot_index {
-        0 => {
-            campfire.fuel_instance_id_0 = None;
-            campfire.fuel_def_id_0 = None;
-        }
-        1 => {
-            campfire.fuel_instance_id_1 = None;
-            campfire.fuel_def_id_1 = None;
-        }
-        2 => {
-            campfire.fuel_instance_id_2 = None;
-            campfire.fuel_def_id_2 = None;
-        }
-        3 => {
-            campfire.fuel_instance_id_3 = None;
-            campfire.fuel_def_id_3 = None;
-        }
-        4 => {
-            campfire.fuel_instance_id_4 = None;
-            campfire.fuel_def_id_4 = None;
-        }
-        _ => {} // Should not happen
+    log::debug!("Player {:?} interaction check OK for campfire {}", sender_id, campfire_id);
+    // Interaction is valid, client can proceed to open UI
+    Ok(())
+}
+
+/// Picks a placed campfire back up as a Camp Fire item (owner only).
+/// The fuel slots must be empty first, so nothing inside is destroyed.
+#[spacetimedb::reducer]
+pub fn pickup_campfire(ctx: &ReducerContext, campfire_id: u32) -> Result<(), String> {
+    let sender_id = ctx.sender;
+    log::info!("[PickupCampfire] Player {:?} attempting pickup of campfire {}", sender_id, campfire_id);
+
+    // 1. Validate Interaction & Ownership
+    let campfire = ctx.db.campfire().id().find(campfire_id)
+        .ok_or_else(|| GameError::NotFound(format!("Campfire {}", campfire_id)))?;
+    ensure_player_near_campfire(ctx, &campfire)?;
+    if campfire.placed_by != sender_id {
+        return Err(GameError::NotOwner.into());
     }
 
-    // Check if fire should extinguish
-    let still_has_fuel = check_if_campfire_has_fuel(ctx, &campfire);
-    if !still_has_fuel && campfire.is_burning {
-        campfire.is_burning = false;
-        campfire.next_fuel_consume_at = None;
-        log::info!(
-            "Campfire {} extinguished as last valid fuel was removed.",
-            campfire_id
-        );
+    // 2. Refuse rather than destroy whatever is still in the slots
+    if !crate::inventory_management::is_container_empty(&campfire) {
+        return Err("Cannot pick up a campfire that still holds items. Empty its slots first.".to_string());
     }
 
-    campfires.id().update(campfire); // Update the campfire
-    log::info!(
-        "Removed/merged fuel from campfire {} slot {}.",
-        campfire_id, source_slot_index
-    );
+    // 3. Give the item back first, so a full inventory leaves the campfire in place
+    let campfire_def = campfire_item_def(ctx)
+        .ok_or_else(|| GameError::NotFound("Camp Fire item definition".to_string()))?;
+    crate::items::add_item_to_player_inventory(ctx, sender_id, campfire_def.id, 1)
+        .map_err(|e| format!("Failed to pick up campfire: {}", e))?;
 
+    // 4. Remove it. With the row gone the fire is out: no fuel schedule or residual warmth survives it
+    ctx.db.campfire().id().delete(campfire_id);
+    log::info!("[PickupCampfire] Player {:?} picked up campfire {} (was burning: {}).", sender_id, campfire_id, campfire.is_burning);
     Ok(())
 }
 
-// Helper function to check if any fuel slot contains valid fuel (Wood with quantity > 0)
+/// Adds an item from the player's inventory as fuel to a specific campfire slot.
+/// Kept for existing clients; same as `move_inventory_to_container` with `ContainerType::Campfire`.
+#[spacetimedb::reducer]
+pub fn add_fuel_to_campfire(ctx: &ReducerContext, campfire_id: u32, target_slot_index: u8, item_instance_id: u64) -> Result<(), String> {
+    crate::containers::move_inventory_to_container(ctx, ContainerType::Campfire, campfire_id, target_slot_index, item_instance_id)
+}
+
+/// Removes the fuel item from a specific campfire slot and returns it to the player,
+/// attempting to merge with existing stacks first. Same as `quick_move_from_container`.
+#[spacetimedb::reducer]
+pub fn auto_remove_fuel_from_campfire(ctx: &ReducerContext, campfire_id: u32, source_slot_index: u8) -> Result<(), String> {
+    crate::containers::quick_move_from_container(ctx, ContainerType::Campfire, campfire_id, source_slot_index)
+}
+
+// Helper function to check if any fuel slot contains valid fuel (any fuel item with quantity > 0)
 // Change signature to take ReducerContext
 pub(crate) fn check_if_campfire_has_fuel(ctx: &ReducerContext, campfire: &Campfire) -> bool {
     // Get table handles from context
@@ -448,7 +482,7 @@ pub(crate) fn check_if_campfire_has_fuel(ctx: &ReducerContext, campfire: &Campfi
         if let Some(instance_id) = instance_id_opt {
             if let Some(item) = inventory.instance_id().find(instance_id) {
                 if let Some(def) = item_defs.id().find(item.item_def_id) {
-                    if def.name == "Wood" && item.quantity > 0 {
+                    if is_campfire_fuel(&def) && item.quantity > 0 {
                         return true; // Found valid fuel
                     }
                 }
@@ -459,7 +493,7 @@ pub(crate) fn check_if_campfire_has_fuel(ctx: &ReducerContext, campfire: &Campfi
 }
 
 /// Toggles the burning state of the campfire (lights or extinguishes it).
-/// Relies on checking if *any* fuel slot has Wood with quantity > 0.
+/// Relies on checking if *any* fuel slot has fuel with quantity > 0.
 #[spacetimedb::reducer]
 pub fn toggle_campfire_burning(ctx: &ReducerContext, campfire_id: u32) -> Result<(), String> {
     let sender_id = ctx.sender;
@@ -475,15 +509,24 @@ pub fn toggle_campfire_burning(ctx: &ReducerContext, campfire_id: u32) -> Result
     let mut campfire = campfires.id().find(campfire_id).ok_or(format!("Campfire {} not found", campfire_id))?;
 
     // 3. Check Distance
-    let dx = player.position_x - campfire.pos_x;
-    let dy = player.position_y - campfire.pos_y;
-    if (dx * dx + dy * dy) > PLAYER_CAMPFIRE_INTERACTION_DISTANCE_SQUARED { return Err("Too far away".to_string()); }
+    if !within_range(player.position_x, player.position_y, campfire.pos_x, campfire.pos_y, CAMPFIRE_INTERACTION_DISTANCE_SQUARED) { return Err(GameError::TooFarAway.into()); }
 
-    // 4. Determine Action: Light or Extinguish?
+    // 4. Toggle cooldown (prevents spam and fuel reschedule churn)
+    if let Some(last_toggled_at) = campfire.last_toggled_at {
+        let elapsed_ms = (ctx.timestamp.to_micros_since_unix_epoch() - last_toggled_at.to_micros_since_unix_epoch()) / 1000;
+        if elapsed_ms < CAMPFIRE_TOGGLE_COOLDOWN_MS {
+            return Err("Please wait a moment before toggling the campfire again.".to_string());
+        }
+    }
+    campfire.last_toggled_at = Some(ctx.timestamp);
+
+    // 5. Determine Action: Light or Extinguish?
         if campfire.is_burning {
         // --- Action: Extinguish ---
-            campfire.is_burning = false;
-        campfire.next_fuel_consume_at = None;
+            if campfire.no_fuel_consumption {
+                return Err("This campfire is kept burning and can't be put out.".to_string());
+            }
+            campfire.extinguish(ctx.timestamp);
             campfires.id().update(campfire);
         log::info!("Campfire {} extinguished by player {:?}.", campfire_id, sender_id);
         Ok(())
@@ -492,12 +535,13 @@ pub fn toggle_campfire_burning(ctx: &ReducerContext, campfire_id: u32) -> Result
         // Check if any slot has valid fuel (pass ctx)
         let has_valid_fuel = check_if_campfire_has_fuel(ctx, &campfire);
         if !has_valid_fuel {
-            return Err("Cannot light campfire, requires Wood with quantity > 0 in at least one fuel slot".to_string());
+            return Err(GameError::NotEnoughMaterials.into()); // No fuel with quantity > 0 in any fuel slot
         }
 
         // Checks passed, light the fire!
         campfire.is_burning = true;
-        campfire.next_fuel_consume_at = Some(ctx.timestamp + Duration::from_secs(FUEL_CONSUME_INTERVAL_SECS));
+        campfire.extinguished_at = None;
+        campfire.next_fuel_consume_at = Some(ctx.timestamp + next_fuel_burn_duration(ctx, &campfire));
         let next_check_time_for_log = campfire.next_fuel_consume_at;
         campfires.id().update(campfire);
         log::info!("Campfire {} lit by player {:?}. Next fuel check at {:?}.", campfire_id, sender_id, next_check_time_for_log);
@@ -529,7 +573,7 @@ pub fn check_campfire_fuel_consumption(ctx: &ReducerContext, _schedule: Campfire
         if let Some(campfire_ref) = campfires.id().find(campfire_id) {
             let mut campfire = campfire_ref.clone(); 
             let mut campfire_changed = false;
-            if campfire.is_burning {
+            if burns_fuel(&campfire) { // Always-on fires skip consumption but still cook
                 if let Some(consume_time) = campfire.next_fuel_consume_at {
                     log::trace!("Campfire {}: Checking consumption. Now: {:?}, ConsumeAt: {:?}", campfire_id, now, consume_time);
                     if now >= consume_time {
@@ -547,7 +591,7 @@ pub fn check_campfire_fuel_consumption(ctx: &ReducerContext, _schedule: Campfire
                              if let Some(instance_id) = instance_id_opt {
                                 if let Some(item) = inventory_items.instance_id().find(*instance_id) {
                                     if let Some(def) = item_defs.id().find(item.item_def_id) {
-                                        if def.name == "Wood" && item.quantity > 0 {
+                                        if is_campfire_fuel(&def) && item.quantity > 0 {
                                             slot_to_consume_from = Some(slot_idx);
                                             log::debug!("Campfire {}: Found valid fuel in slot {}", campfire_id, slot_idx);
                                             break;
@@ -560,7 +604,7 @@ pub fn check_campfire_fuel_consumption(ctx: &ReducerContext, _schedule: Campfire
                         if let Some(slot_idx) = slot_to_consume_from {
                             let instance_id = instance_ids[slot_idx].unwrap(); 
                             if let Some(mut fuel_item) = inventory_items.instance_id().find(instance_id) {
-                                fuel_item.quantity -= 1;
+                                fuel_item.quantity = fuel_left_after_tick(&campfire, now, fuel_item.quantity);
                                 remaining = fuel_item.quantity;
                                 inventory_items.instance_id().update(fuel_item); 
                                 log::info!("Campfire {}: Consumed 1 fuel from slot {}. Remaining: {}", campfire_id, slot_idx, remaining);
@@ -581,8 +625,7 @@ pub fn check_campfire_fuel_consumption(ctx: &ReducerContext, _schedule: Campfire
                                     let still_has_fuel_after_empty = check_if_campfire_has_fuel(ctx, &campfire);
                                      log::info!("Campfire {}: Immediate extinguish check result: {}", campfire_id, still_has_fuel_after_empty);
                                     if !still_has_fuel_after_empty {
-                                        campfire.is_burning = false;
-                                        campfire.next_fuel_consume_at = None;
+                                        campfire.extinguish(ctx.timestamp);
                                         log::info!("Campfire {}: Extinguishing immediately as last fuel in slot {} was consumed.", campfire_id, slot_idx);
                                     }
                                 }
@@ -600,29 +643,25 @@ pub fn check_campfire_fuel_consumption(ctx: &ReducerContext, _schedule: Campfire
                             }
                         } else {
                             log::warn!("Campfire {}: Was burning but no valid fuel found. Extinguishing.", campfire_id);
-                            campfire.is_burning = false;
-                            campfire.next_fuel_consume_at = None;
+                            campfire.extinguish(ctx.timestamp);
                             campfire_changed = true;
                         }
 
                         if campfire.is_burning {
-                            log::debug!("Campfire {}: Still burning, checking if reschedule needed (remaining: {}).", campfire_id, remaining);
-                            if remaining > 0 { 
-                                let still_has_fuel = check_if_campfire_has_fuel(ctx, &campfire);
-                                log::debug!("Campfire {}: check_if_campfire_has_fuel result: {}", campfire_id, still_has_fuel);
-                                if still_has_fuel {
-                                    let new_consume_time = now + Duration::from_secs(FUEL_CONSUME_INTERVAL_SECS);
-                                    campfire.next_fuel_consume_at = Some(new_consume_time);
-                                    log::info!("Campfire {}: Rescheduled fuel check to {:?}", campfire_id, new_consume_time);
-                                    campfire_changed = true;
-                                } else {
-                                    campfire.is_burning = false;
-                                    campfire.next_fuel_consume_at = None;
-                                    log::warn!("Campfire {}: No remaining fuel after check. Extinguishing.", campfire_id);
-                                    campfire_changed = true;
-                                }
+                            log::debug!("Campfire {}: Still burning, rescheduling (remaining in slot: {}).", campfire_id, remaining);
+                            // Always reschedule, even if this slot just ran out: the next unit may
+                            // come from another slot holding a different fuel with its own burn time
+                            let still_has_fuel = check_if_campfire_has_fuel(ctx, &campfire);
+                            log::debug!("Campfire {}: check_if_campfire_has_fuel result: {}", campfire_id, still_has_fuel);
+                            if still_has_fuel {
+                                let new_consume_time = now + next_fuel_burn_duration(ctx, &campfire);
+                                campfire.next_fuel_consume_at = Some(new_consume_time);
+                                log::info!("Campfire {}: Rescheduled fuel check to {:?}", campfire_id, new_consume_time);
+                                campfire_changed = true;
                             } else {
-                                log::debug!("Campfire {}: Not rescheduling as remaining fuel is 0.", campfire_id);
+                                campfire.extinguish(ctx.timestamp);
+                                log::warn!("Campfire {}: No remaining fuel after check. Extinguishing.", campfire_id);
+                                campfire_changed = true;
                             }
                         } else {
                              log::debug!("Campfire {}: Not rescheduling as fire is not burning.", campfire_id);
@@ -635,17 +674,21 @@ pub fn check_campfire_fuel_consumption(ctx: &ReducerContext, _schedule: Campfire
                      let still_has_fuel = check_if_campfire_has_fuel(ctx, &campfire);
                       log::debug!("Campfire {}: Burning but no consume time set. Has fuel? {}", campfire_id, still_has_fuel);
                      if still_has_fuel {
-                         campfire.next_fuel_consume_at = Some(now + Duration::from_secs(FUEL_CONSUME_INTERVAL_SECS));
+                         campfire.next_fuel_consume_at = Some(now + next_fuel_burn_duration(ctx, &campfire));
                          campfire_changed = true;
                          log::info!("Campfire {}: Scheduling initial fuel consumption check to {:?}.", campfire_id, campfire.next_fuel_consume_at);
                      } else {
-                         campfire.is_burning = false;
-                         campfire.next_fuel_consume_at = None;
+                         campfire.extinguish(ctx.timestamp);
                          campfire_changed = true;
                          log::warn!("Campfire {}: Extinguishing immediately, no valid fuel found upon check.", campfire_id);
                      }
                 }
             }
+
+            // Cook whatever raw food is in the slots while the fire burns
+            if campfire.is_burning && advance_cooking(ctx, &mut campfire, FUEL_CHECK_INTERVAL_SECS) {
+                campfire_changed = true;
+            }
             
             if campfire_changed {
                 campfires_to_update.push(campfire);
@@ -667,87 +710,90 @@ pub fn check_campfire_fuel_consumption(ctx: &ReducerContext, _schedule: Campfire
     Ok(())
 }
 
-// --- NEW: Split Stack Into Campfire Reducer ---
+// --- Cooking ---
 
-#[spacetimedb::reducer]
-pub fn split_stack_into_campfire(
-    ctx: &ReducerContext,
-    source_item_instance_id: u64,
-    quantity_to_split: u32,
-    target_campfire_id: u32,
-    target_slot_index: u8,
-) -> Result<(), String> {
-    let sender_id = ctx.sender;
-    let mut inventory_items = ctx.db.inventory_item();
-    let mut campfires = ctx.db.campfire();
+/// Adds `elapsed_secs` of cooking to the first cookable stack in the campfire's slots. Once it has
+/// cooked for its item's `cook_time_secs`, the whole stack turns into its `cookable_into` item, or
+/// burns, with a chance that falls with the cooking skill of the player who put it on the fire.
+/// Returns true if the campfire row changed.
+fn advance_cooking(ctx: &ReducerContext, campfire: &mut Campfire, elapsed_secs: u64) -> bool {
+    let inventory_items = ctx.db.inventory_item();
     let item_defs = ctx.db.item_definition();
 
-    log::info!("[SplitIntoCampfire] Player {:?} splitting {} from item {} into campfire {} slot {}",
-             sender_id, quantity_to_split, source_item_instance_id, target_campfire_id, target_slot_index);
-
-    // 1. Validate target slot index
-    if target_slot_index >= NUM_FUEL_SLOTS as u8 {
-        return Err(format!("Invalid target fuel slot index: {}", target_slot_index));
-    }
-
-    // 2. Find source item and check ownership/location
-    let mut source_item = inventory_items.instance_id().find(source_item_instance_id)
-        .ok_or("Source item instance not found")?;
-    if source_item.player_identity != sender_id { return Err("Source item not owned by caller".to_string()); }
-    if source_item.inventory_slot.is_none() && source_item.hotbar_slot.is_none() {
-        return Err("Source item must be in inventory or hotbar to split into campfire".to_string());
+    // 1. Find the first cookable stack
+    let mut cooking: Option<(u8, InventoryItem, ItemDefinition)> = None;
+    for slot_index in 0..NUM_FUEL_SLOTS as u8 {
+        let Some(instance_id) = campfire.get_slot_instance_id(slot_index) else { continue; };
+        let Some(item) = inventory_items.instance_id().find(instance_id) else { continue; };
+        let Some(def) = item_defs.id().find(item.item_def_id) else { continue; };
+        if is_cookable(&def) && item.quantity > 0 {
+            cooking = Some((slot_index, item, def));
+            break;
+        }
     }
-
-    // 3. Find target campfire
-    let mut campfire = campfires.id().find(target_campfire_id)
-        .ok_or(format!("Target campfire {} not found", target_campfire_id))?;
-
-    // 4. Check if target slot is empty
-    let is_slot_occupied = match target_slot_index {
-        0 => campfire.fuel_instance_id_0.is_some(),
-        1 => campfire.fuel_instance_id_1.is_some(),
-        2 => campfire.fuel_instance_id_2.is_some(),
-        3 => campfire.fuel_instance_id_3.is_some(),
-        4 => campfire.fuel_instance_id_4.is_some(),
-        _ => return Err("Invalid slot index logic".to_string()),
+    let Some((slot_index, mut item, raw_def)) = cooking else {
+        // Nothing to cook; drop any stale tracker
+        let changed = campfire.cooking_instance_id.is_some();
+        campfire.cooking_instance_id = None;
+        campfire.cook_progress_secs = 0;
+        return changed;
     };
-    if is_slot_occupied {
-        return Err(format!("Target campfire fuel slot {} is already occupied.", target_slot_index));
-    }
 
-    // 5. Call the core split logic helper (passes mutable borrow of source_item)
-    let new_item_instance_id = crate::items::split_stack_helper(ctx, &mut source_item, quantity_to_split)?;
-
-    // 6. Get definition ID for the new item (same as source)
-    let definition_id = source_item.item_def_id;
-
-    // 7. Update the target campfire slot with the NEW item instance ID
-    match target_slot_index {
-        0 => { campfire.fuel_instance_id_0 = Some(new_item_instance_id); campfire.fuel_def_id_0 = Some(definition_id); },
-        1 => { campfire.fuel_instance_id_1 = Some(new_item_instance_id); campfire.fuel_def_id_1 = Some(definition_id); },
-        2 => { campfire.fuel_instance_id_2 = Some(new_item_instance_id); campfire.fuel_def_id_2 = Some(definition_id); },
-        3 => { campfire.fuel_instance_id_3 = Some(new_item_instance_id); campfire.fuel_def_id_3 = Some(definition_id); },
-        4 => { campfire.fuel_instance_id_4 = Some(new_item_instance_id); campfire.fuel_def_id_4 = Some(definition_id); },
-        _ => {}, // Should not happen
+    // 2. Advance progress, restarting if a different stack is now first in line
+    if campfire.cooking_instance_id != Some(item.instance_id) {
+        campfire.cooking_instance_id = Some(item.instance_id);
+        campfire.cook_progress_secs = 0;
+    }
+    campfire.cook_progress_secs = campfire.cook_progress_secs.saturating_add(elapsed_secs);
+    if campfire.cook_progress_secs < raw_def.cook_time_secs.unwrap_or(0) {
+        return true;
     }
 
-    // Re-check fuel state and update campfire
-    let can_light_now = check_if_campfire_has_fuel(ctx, &campfire);
-    if !can_light_now && campfire.is_burning {
-        campfire.is_burning = false;
-        campfire.next_fuel_consume_at = None;
-        log::warn!("Campfire {} extinguished as newly added fuel is not valid wood.", target_campfire_id);
+    // 3. Done: the stack's owner is the cook; their skill decides whether it burns
+    let cook_id = item.player_identity;
+    let cooking_level = crate::skills::get_cooking_level(ctx, cook_id);
+    let burnt = crate::utils::GameRng::from_ctx(ctx).chance(crate::skills::cooking_burn_chance(cooking_level));
+
+    // 4. Swap the stack to the cooked (or burnt) item in place
+    let cooked_name = if burnt { BURNT_FOOD_NAME.to_string() } else { raw_def.cookable_into.clone().unwrap_or_default() };
+    let Some(cooked_def) = crate::items::get_item_def_by_name(ctx, &cooked_name) else {
+        log::error!("[Cooking] Campfire {}: '{}' cooks into unknown item '{}'.", campfire.id, raw_def.name, cooked_name);
+        campfire.cooking_instance_id = None;
+        campfire.cook_progress_secs = 0;
+        return true;
+    };
+    item.item_def_id = cooked_def.id;
+    item.spoils_at = crate::spoilage::initial_spoils_at(ctx, &cooked_def); // Freshly cooked
+    // Better cooks tend to turn out better food, which restores more when eaten
+    item.quality = if burnt { None } else { Some(crate::item_quality::roll_crafted_quality(ctx, cooking_level, 0)) };
+    let quantity = item.quantity;
+    let instance_id = item.instance_id;
+    inventory_items.instance_id().update(item);
+    campfire.set_slot(slot_index, Some(instance_id), Some(cooked_def.id));
+    campfire.cooking_instance_id = None;
+    campfire.cook_progress_secs = 0;
+    if !burnt {
+        crate::skills::grant_cooking_xp(ctx, cook_id, quantity);
     }
-    campfires.id().update(campfire);
+    log::info!("[Cooking] Campfire {}: {} {} in slot {} cooked into {} (cook {:?}, level {}).",
+             campfire.id, quantity, raw_def.name, slot_index, cooked_def.name, cook_id, cooking_level);
+    true
+}
 
-    log::info!("[SplitIntoCampfire] Split successful. New item {} placed in campfire {} slot {}.", 
-             new_item_instance_id, target_campfire_id, target_slot_index);
+// --- Fuel Slot Reducers ---
+// Kept for existing clients; each forwards to the matching reducer in `containers`.
 
-    Ok(())
+#[spacetimedb::reducer]
+pub fn split_stack_into_campfire(
+    ctx: &ReducerContext,
+    source_item_instance_id: u64,
+    quantity_to_split: u32,
+    target_campfire_id: u32,
+    target_slot_index: u8,
+) -> Result<(), String> {
+    crate::containers::split_inventory_into_container(ctx, ContainerType::Campfire, target_campfire_id, target_slot_index, source_item_instance_id, quantity_to_split)
 }
 
-// --- NEW: Move/Merge/Swap Within Campfire Reducer ---
-
 #[spacetimedb::reducer]
 pub fn move_fuel_within_campfire(
     ctx: &ReducerContext,
@@ -755,139 +801,9 @@ pub fn move_fuel_within_campfire(
     source_slot_index: u8,
     target_slot_index: u8,
 ) -> Result<(), String> {
-    let sender_id = ctx.sender;
-    let mut campfires = ctx.db.campfire();
-    let mut inventory_items = ctx.db.inventory_item();
-    let item_defs = ctx.db.item_definition();
-
-    log::info!("[MoveWithinCampfire] Player {:?} moving fuel from slot {} to slot {} in campfire {}",
-             sender_id, source_slot_index, target_slot_index, campfire_id);
-
-    // 1. Validate slot indices
-    if source_slot_index >= NUM_FUEL_SLOTS as u8 || target_slot_index >= NUM_FUEL_SLOTS as u8 || source_slot_index == target_slot_index {
-        return Err("Invalid source or target slot index".to_string());
-    }
-    let source_idx = source_slot_index as usize;
-    let target_idx = target_slot_index as usize;
-
-    // 2. Find Campfire
-    let mut campfire = campfires.id().find(campfire_id)
-        .ok_or(format!("Target campfire {} not found", campfire_id))?;
-
-    // 3. Get source item ID and definition ID
-    let (source_instance_id, source_def_id) = match source_slot_index {
-        0 => (campfire.fuel_instance_id_0, campfire.fuel_def_id_0),
-        1 => (campfire.fuel_instance_id_1, campfire.fuel_def_id_1),
-        2 => (campfire.fuel_instance_id_2, campfire.fuel_def_id_2),
-        3 => (campfire.fuel_instance_id_3, campfire.fuel_def_id_3),
-        4 => (campfire.fuel_instance_id_4, campfire.fuel_def_id_4),
-        _ => (None, None),
-    };
-    let source_instance_id = source_instance_id.ok_or(format!("Source slot {} is empty", source_slot_index))?;
-    let source_def_id = source_def_id.ok_or("Source definition ID missing")?;
-
-    // 4. Get target item ID and definition ID (if occupied)
-     let (target_instance_id_opt, target_def_id_opt) = match target_slot_index {
-        0 => (campfire.fuel_instance_id_0, campfire.fuel_def_id_0),
-        1 => (campfire.fuel_instance_id_1, campfire.fuel_def_id_1),
-        2 => (campfire.fuel_instance_id_2, campfire.fuel_def_id_2),
-        3 => (campfire.fuel_instance_id_3, campfire.fuel_def_id_3),
-        4 => (campfire.fuel_instance_id_4, campfire.fuel_def_id_4),
-        _ => (None, None),
-    };
-
-    // --- Logic Branching --- 
-    if let Some(target_instance_id) = target_instance_id_opt {
-        // == Target is Occupied: Attempt Merge then Swap ==
-        let mut source_item = inventory_items.instance_id().find(source_instance_id).ok_or("Source item not found")?;
-        let mut target_item = inventory_items.instance_id().find(target_instance_id).ok_or("Target item not found")?;
-        let item_def = item_defs.id().find(source_def_id).ok_or("Item definition not found")?; // Assume source/target defs match if merge possible
-
-        match crate::items::calculate_merge_result(&source_item, &target_item, &item_def) {
-            Ok((_, source_new_qty, target_new_qty, delete_source)) => {
-                // -- Merge Possible --
-                log::info!("[MoveWithinCampfire] Merging slot {} onto slot {}", source_slot_index, target_slot_index);
-                target_item.quantity = target_new_qty;
-                inventory_items.instance_id().update(target_item);
-                if delete_source {
-                    inventory_items.instance_id().delete(source_instance_id);
-                } else {
-                    source_item.quantity = source_new_qty;
-                    inventory_items.instance_id().update(source_item);
-                }
-                // Clear source slot in campfire
-                 match source_slot_index {
-                    0 => { campfire.fuel_instance_id_0 = None; campfire.fuel_def_id_0 = None; },
-                    1 => { campfire.fuel_instance_id_1 = None; campfire.fuel_def_id_1 = None; },
-                    2 => { campfire.fuel_instance_id_2 = None; campfire.fuel_def_id_2 = None; },
-                    3 => { campfire.fuel_instance_id_3 = None; campfire.fuel_def_id_3 = None; },
-                    4 => { campfire.fuel_instance_id_4 = None; campfire.fuel_def_id_4 = None; },
-                    _ => {}
-                }
-            },
-            Err(_) => {
-                // -- Merge Failed: Perform Swap --
-                log::info!("[MoveWithinCampfire] Cannot merge, swapping slot {} and {}", source_slot_index, target_slot_index);
-                // Just swap the references in the campfire struct
-                 match (source_slot_index, target_slot_index) {
-                    // This requires temporary variables to avoid overwriting
-                    // A bit verbose, but necessary without direct array indexing
-                    _ => { // Generic swap logic (handle all pairs)
-                        let temp_instance_id = target_instance_id_opt;
-                        let temp_def_id = target_def_id_opt;
-                        
-                        // Set target slot from source
-                         match target_slot_index {
-                            0 => { campfire.fuel_instance_id_0 = Some(source_instance_id); campfire.fuel_def_id_0 = Some(source_def_id); },
-                            1 => { campfire.fuel_instance_id_1 = Some(source_instance_id); campfire.fuel_def_id_1 = Some(source_def_id); },
-                            2 => { campfire.fuel_instance_id_2 = Some(source_instance_id); campfire.fuel_def_id_2 = Some(source_def_id); },
-                            3 => { campfire.fuel_instance_id_3 = Some(source_instance_id); campfire.fuel_def_id_3 = Some(source_def_id); },
-                            4 => { campfire.fuel_instance_id_4 = Some(source_instance_id); campfire.fuel_def_id_4 = Some(source_def_id); },
-                            _ => {}
-                        }
-                        // Set source slot from target (using temps)
-                         match source_slot_index {
-                            0 => { campfire.fuel_instance_id_0 = temp_instance_id; campfire.fuel_def_id_0 = temp_def_id; },
-                            1 => { campfire.fuel_instance_id_1 = temp_instance_id; campfire.fuel_def_id_1 = temp_def_id; },
-                            2 => { campfire.fuel_instance_id_2 = temp_instance_id; campfire.fuel_def_id_2 = temp_def_id; },
-                            3 => { campfire.fuel_instance_id_3 = temp_instance_id; campfire.fuel_def_id_3 = temp_def_id; },
-                            4 => { campfire.fuel_instance_id_4 = temp_instance_id; campfire.fuel_def_id_4 = temp_def_id; },
-                            _ => {}
-                        }
-                    }
-                }
-            }
-        }
-    } else {
-        // == Target is Empty: Move Item ==
-        log::info!("[MoveWithinCampfire] Moving from slot {} to empty slot {}", source_slot_index, target_slot_index);
-        // Clear source slot
-        match source_slot_index {
-            0 => { campfire.fuel_instance_id_0 = None; campfire.fuel_def_id_0 = None; },
-            1 => { campfire.fuel_instance_id_1 = None; campfire.fuel_def_id_1 = None; },
-            2 => { campfire.fuel_instance_id_2 = None; campfire.fuel_def_id_2 = None; },
-            3 => { campfire.fuel_instance_id_3 = None; campfire.fuel_def_id_3 = None; },
-            4 => { campfire.fuel_instance_id_4 = None; campfire.fuel_def_id_4 = None; },
-            _ => {}
-        }
-        // Set target slot
-        match target_slot_index {
-            0 => { campfire.fuel_instance_id_0 = Some(source_instance_id); campfire.fuel_def_id_0 = Some(source_def_id); },
-            1 => { campfire.fuel_instance_id_1 = Some(source_instance_id); campfire.fuel_def_id_1 = Some(source_def_id); },
-            2 => { campfire.fuel_instance_id_2 = Some(source_instance_id); campfire.fuel_def_id_2 = Some(source_def_id); },
-            3 => { campfire.fuel_instance_id_3 = Some(source_instance_id); campfire.fuel_def_id_3 = Some(source_def_id); },
-            4 => { campfire.fuel_instance_id_4 = Some(source_instance_id); campfire.fuel_def_id_4 = Some(source_def_id); },
-            _ => {}
-        }
-    }
-
-    // Update the campfire state
-    campfires.id().update(campfire);
-
-    Ok(())
+    crate::containers::move_within_container(ctx, ContainerType::Campfire, campfire_id, source_slot_index, target_slot_index)
 }
 
-// --- NEW: Split Stack Within Campfire Reducer ---
 #[spacetimedb::reducer]
 pub fn split_stack_within_campfire(
     ctx: &ReducerContext,
@@ -896,259 +812,18 @@ pub fn split_stack_within_campfire(
     quantity_to_split: u32,
     target_slot_index: u8,
 ) -> Result<(), String> {
-    let sender_id = ctx.sender;
-    let mut inventory_items = ctx.db.inventory_item();
-    let mut campfires = ctx.db.campfire();
-
-    log::info!("[SplitWithinCampfire] Player {:?} splitting {} from slot {} to slot {} in campfire {}",
-             sender_id, quantity_to_split, source_slot_index, target_slot_index, campfire_id);
-
-    // 1. Validate slot indices
-    if source_slot_index >= NUM_FUEL_SLOTS as u8 || target_slot_index >= NUM_FUEL_SLOTS as u8 || source_slot_index == target_slot_index {
-        return Err("Invalid source or target slot index for split".to_string());
-    }
-
-    // 2. Find campfire
-    let mut campfire = campfires.id().find(campfire_id)
-        .ok_or(format!("Target campfire {} not found", campfire_id))?;
-
-    // 3. Get source item ID
-    let source_instance_id = match source_slot_index {
-        0 => campfire.fuel_instance_id_0,
-        1 => campfire.fuel_instance_id_1,
-        2 => campfire.fuel_instance_id_2,
-        3 => campfire.fuel_instance_id_3,
-        4 => campfire.fuel_instance_id_4,
-        _ => None,
-    }.ok_or(format!("No item found in source campfire slot {}", source_slot_index))?;
-
-    // 4. Get source item (mutable)
-    let mut source_item = inventory_items.instance_id().find(source_instance_id)
-        .ok_or("Source item instance not found in inventory table")?;
-
-    // 5. Check if target slot is empty
-    let is_target_occupied = match target_slot_index {
-        0 => campfire.fuel_instance_id_0.is_some(),
-        1 => campfire.fuel_instance_id_1.is_some(),
-        2 => campfire.fuel_instance_id_2.is_some(),
-        3 => campfire.fuel_instance_id_3.is_some(),
-        4 => campfire.fuel_instance_id_4.is_some(),
-        _ => true, // Treat invalid index as occupied
-    };
-    if is_target_occupied {
-        return Err(format!("Target campfire fuel slot {} is already occupied.", target_slot_index));
-    }
-    
-    // 6. Validate split quantity (using info from mutable source_item)
-    let item_def = ctx.db.item_definition().id().find(source_item.item_def_id).ok_or("Item def not found")?;
-     if !item_def.is_stackable { return Err("Source item is not stackable".to_string()); }
-     if quantity_to_split == 0 || quantity_to_split >= source_item.quantity {
-        return Err(format!("Invalid split quantity {} (must be > 0 and < {})", quantity_to_split, source_item.quantity));
-    }
-
-    // 7. Perform Split using helper
-    let new_item_instance_id = crate::items::split_stack_helper(ctx, &mut source_item, quantity_to_split)?;
-    let new_item_def_id = source_item.item_def_id;
-
-    // 8. Update target campfire slot
-    match target_slot_index {
-        0 => { campfire.fuel_instance_id_0 = Some(new_item_instance_id); campfire.fuel_def_id_0 = Some(new_item_def_id); },
-        1 => { campfire.fuel_instance_id_1 = Some(new_item_instance_id); campfire.fuel_def_id_1 = Some(new_item_def_id); },
-        2 => { campfire.fuel_instance_id_2 = Some(new_item_instance_id); campfire.fuel_def_id_2 = Some(new_item_def_id); },
-        3 => { campfire.fuel_instance_id_3 = Some(new_item_instance_id); campfire.fuel_def_id_3 = Some(new_item_def_id); },
-        4 => { campfire.fuel_instance_id_4 = Some(new_item_instance_id); campfire.fuel_def_id_4 = Some(new_item_def_id); },
-        _ => {}, // Should not happen
-    }
-    campfires.id().update(campfire);
-
-     log::info!("[SplitWithinCampfire] Split successful. New item {} placed in slot {}.", 
-             new_item_instance_id, target_slot_index);
-
-    Ok(())
+    crate::containers::split_within_container(ctx, ContainerType::Campfire, campfire_id, source_slot_index, target_slot_index, quantity_to_split)
 }
 
-// --- NEW Reducer: Moves an item to the first available/mergeable campfire slot ---
 #[spacetimedb::reducer]
 pub fn quick_move_to_campfire(
     ctx: &ReducerContext,
     campfire_id: u32,
     item_instance_id: u64,
 ) -> Result<(), String> {
-    let sender_id = ctx.sender;
-    let mut inventory_items = ctx.db.inventory_item();
-    let mut campfires = ctx.db.campfire();
-    let item_defs = ctx.db.item_definition();
-
-    log::info!(
-        "[QuickMoveToCampfire] Player {:?} trying to quick-move item {} to campfire {}",
-        sender_id, item_instance_id, campfire_id
-    );
-
-    // 1. Find Campfire & Item
-    let mut campfire = campfires
-        .id()
-        .find(campfire_id)
-        .ok_or(format!("Target campfire {} not found", campfire_id))?;
-    let mut item_to_add = inventory_items
-        .instance_id()
-        .find(item_instance_id)
-        .ok_or("Source item instance not found")?;
-
-    // 2. Validations
-    if item_to_add.player_identity != sender_id {
-        return Err("Item not owned by caller".to_string());
-    }
-    if item_to_add.inventory_slot.is_none() && item_to_add.hotbar_slot.is_none() {
-        return Err("Item must be in inventory or hotbar".to_string());
-    }
-    let definition = item_defs
-        .id()
-        .find(item_to_add.item_def_id)
-        .ok_or("Item definition not found")?;
-
-    // Check stackability for merge logic
-    let is_stackable = definition.is_stackable;
-    let item_def_id_to_add = definition.id;
-
-    // 3. Attempt to Merge onto existing matching stacks in campfire
-    let fuel_instance_ids = [
-        campfire.fuel_instance_id_0,
-        campfire.fuel_instance_id_1,
-        campfire.fuel_instance_id_2,
-        campfire.fuel_instance_id_3,
-        campfire.fuel_instance_id_4,
-    ];
-
-    let mut source_item_depleted = false;
-    for target_instance_id_opt in fuel_instance_ids {
-        if let Some(target_instance_id) = target_instance_id_opt {
-            if let Some(mut target_item) = inventory_items.instance_id().find(target_instance_id) {
-                // Check if the target item has the same definition ID AND item is stackable
-                if target_item.item_def_id == item_def_id_to_add && is_stackable {
-                    // Attempt merge
-                    match crate::items::calculate_merge_result(&item_to_add, &target_item, &definition) {
-                        Ok((qty_transfer, source_new_qty, target_new_qty, delete_source)) => {
-                            if qty_transfer > 0 {
-                                log::info!(
-                                    "[QuickMoveToCampfire Merge] Merging {} from item {} onto campfire item {}.",
-                                    qty_transfer, item_instance_id, target_instance_id
-                                );
-                                // Update target item quantity
-                                target_item.quantity = target_new_qty;
-                                inventory_items.instance_id().update(target_item);
-
-                                // Update source item quantity
-                                item_to_add.quantity = source_new_qty;
-
-                                if delete_source {
-                                    log::info!(
-                                        "[QuickMoveToCampfire Merge] Source item {} depleted, deleting.",
-                                        item_instance_id
-                                    );
-                                    inventory_items.instance_id().delete(item_instance_id);
-                                    source_item_depleted = true;
-                                    break; // Source is gone, stop trying to merge
-                                } else {
-                                    // Update source item in DB (still has quantity left)
-                                    inventory_items.instance_id().update(item_to_add.clone());
-                                }
-                            }
-                        }
-                        Err(e) => {
-                             log::debug!("[QuickMoveToCampfire Merge] Cannot merge onto {}: {}", target_instance_id, e);
-                             // Continue to next slot if merge failed (e.g., target full)
-                        }
-                    }
-                }
-            }
-        }
-        // If source depleted, exit the loop
-        if source_item_depleted {
-            break;
-        }
-    }
-
-    // 4. If source item still exists, find first empty slot and place it
-    if !source_item_depleted {
-        log::info!(
-            "[QuickMoveToCampfire] Source item {} still has {} quantity after merge attempts. Finding empty slot...",
-            item_instance_id, item_to_add.quantity
-        );
-        let mut empty_slot_found: Option<u8> = None;
-        if campfire.fuel_instance_id_0.is_none() {
-            empty_slot_found = Some(0);
-        } else if campfire.fuel_instance_id_1.is_none() {
-            empty_slot_found = Some(1);
-        } else if campfire.fuel_instance_id_2.is_none() {
-            empty_slot_found = Some(2);
-        } else if campfire.fuel_instance_id_3.is_none() {
-            empty_slot_found = Some(3);
-        } else if campfire.fuel_instance_id_4.is_none() {
-            empty_slot_found = Some(4);
-        }
-
-        if let Some(slot_index) = empty_slot_found {
-            log::info!(
-                "[QuickMoveToCampfire] Placing remaining item {} into empty slot {}",
-                item_instance_id, slot_index
-            );
-            // Update item (remove from inv/hotbar)
-            item_to_add.inventory_slot = None;
-            item_to_add.hotbar_slot = None;
-            inventory_items.instance_id().update(item_to_add);
-
-            // Update campfire slot
-            match slot_index {
-                0 => {
-                    campfire.fuel_instance_id_0 = Some(item_instance_id);
-                    campfire.fuel_def_id_0 = Some(item_def_id_to_add); // Use correct def ID
-                }
-                1 => {
-                    campfire.fuel_instance_id_1 = Some(item_instance_id);
-                    campfire.fuel_def_id_1 = Some(item_def_id_to_add); // Use correct def ID
-                }
-                2 => {
-                    campfire.fuel_instance_id_2 = Some(item_instance_id);
-                    campfire.fuel_def_id_2 = Some(item_def_id_to_add); // Use correct def ID
-                }
-                3 => {
-                    campfire.fuel_instance_id_3 = Some(item_instance_id);
-                    campfire.fuel_def_id_3 = Some(item_def_id_to_add); // Use correct def ID
-                }
-                4 => {
-                    campfire.fuel_instance_id_4 = Some(item_instance_id);
-                    campfire.fuel_def_id_4 = Some(item_def_id_to_add); // Use correct def ID
-                }
-                _ => {} // Should not happen
-            }
-            campfires.id().update(campfire);
-        } else {
-            log::warn!(
-                "[QuickMoveToCampfire] Campfire {} fuel slots full, cannot place remaining item {}.",
-                campfire_id, item_instance_id
-            );
-            // If NO merge happened AND no empty slot, return error.
-            // Check if quantity changed. If it did, some merge happened, so it's a partial success (Ok).
-            // If quantity is unchanged AND no empty slot, then it failed.
-            let original_quantity_opt = inventory_items.instance_id().find(item_instance_id).map(|i| i.quantity); // Re-fetch original quantity
-            if let Some(original_quantity) = original_quantity_opt {
-                 let current_quantity = inventory_items.instance_id().find(item_instance_id).map(|i| i.quantity).unwrap_or(0); // Get current quantity
-                 if current_quantity == original_quantity { // No merge happened
-                    return Err("Campfire fuel slots are full (no space to merge or place)".to_string());
-                 } // else: merge happened, so Ok(()) is fine below
-            } else {
-                 // Item was somehow deleted mid-process? Should be rare.
-                 log::error!("[QuickMoveToCampfire] Source item {} disappeared during processing!", item_instance_id);
-                 return Err("Internal error during quick move".to_string());
-            }
-        }
-    }
-
-    Ok(())
+    crate::containers::quick_move_to_container(ctx, ContainerType::Campfire, campfire_id, item_instance_id)
 }
 
-// --- Re-Add: Move Fuel Item to Player Slot Reducer --- 
-
 #[spacetimedb::reducer]
 pub fn move_fuel_item_to_player_slot(
     ctx: &ReducerContext,
@@ -1157,73 +832,66 @@ pub fn move_fuel_item_to_player_slot(
     target_slot_type: String,
     target_slot_index: u32, // u32 to match client flexibility
 ) -> Result<(), String> {
-    let sender_id = ctx.sender;
-    let mut campfires = ctx.db.campfire();
-    // Inventory items table needed for move functions
-    let inventory_items = ctx.db.inventory_item(); 
-
-    log::info!(
-        "[MoveFuelToPlayer] Player {:?} moving fuel from campfire {} slot {} to {} slot {}",
-        sender_id, campfire_id, source_slot_index, target_slot_type, target_slot_index
-    );
-
-    // 1. Validate source slot index
-    if source_slot_index >= NUM_FUEL_SLOTS as u8 {
-        return Err(format!("Invalid source fuel slot index: {}", source_slot_index));
-    }
+    crate::containers::move_container_to_inventory(ctx, ContainerType::Campfire, campfire_id, source_slot_index, target_slot_type, target_slot_index)
+}
 
-    // 2. Find Campfire
-    let mut campfire = campfires.id().find(campfire_id)
-        .ok_or(format!("Campfire {} not found", campfire_id))?;
-
-    // 3. Get the instance ID from the source slot
-    let fuel_instance_id = match source_slot_index {
-        0 => campfire.fuel_instance_id_0,
-        1 => campfire.fuel_instance_id_1,
-        2 => campfire.fuel_instance_id_2,
-        3 => campfire.fuel_instance_id_3,
-        4 => campfire.fuel_instance_id_4,
-        _ => None,
-    }.ok_or(format!("No fuel item in campfire slot {} to move", source_slot_index))?;
-
-    // 4. Call the appropriate move function from items.rs
-    let move_result = match target_slot_type.as_str() {
-        "inventory" => {
-            if target_slot_index >= 24 { return Err("Invalid inventory target index".to_string()); }
-            crate::items::move_item_to_inventory(ctx, fuel_instance_id, target_slot_index as u16)
-        },
-        "hotbar" => {
-            if target_slot_index >= 6 { return Err("Invalid hotbar target index".to_string()); }
-            crate::items::move_item_to_hotbar(ctx, fuel_instance_id, target_slot_index as u8)
-        },
-        _ => Err(format!("Invalid target slot type '{}'", target_slot_type)),
+// --- Spawn Helper ---
+
+/// Creates a burning campfire entity with 50 Wood in fuel slot 0.
+/// Called by the generic placement flow after validation and item consumption.
+pub(crate) fn spawn_campfire(ctx: &ReducerContext, placer_id: Identity, world_x: f32, world_y: f32) -> Result<(), String> {
+    // 1. Create Initial Fuel Item (Wood)
+    let wood_def = crate::items::get_item_def_by_name(ctx, "Wood")
+        .ok_or_else(|| "Wood item definition not found for initial fuel".to_string())?;
+
+    let initial_fuel_item = InventoryItem {
+        instance_id: 0, // Auto-inc
+        player_identity: placer_id, // Belongs to the placer initially (needed? maybe not)
+        item_def_id: wood_def.id,
+        quantity: 50, // Start with 50 wood
+        hotbar_slot: None, // Not in hotbar
+        inventory_slot: None, // Not in inventory (it's "in" the campfire slot 0)
+        charges: None,
+        durability: None,
+        spoils_at: None,
+        quality: None,
     };
+    // Insert the fuel item and get its generated instance ID
+    let inserted_fuel_item = crate::items::insert_new_inventory_item(ctx, initial_fuel_item);
+    let fuel_instance_id = inserted_fuel_item.instance_id;
+    log::info!("[SpawnCampfire] Created initial fuel item (Wood, instance {}) for campfire.", fuel_instance_id);
+
+    // 2. Initialize Campfire with Fuel and Burning
+    let first_consumption_time = ctx.timestamp + fuel_unit_burn_duration(&wood_def);
+    let new_campfire = Campfire {
+        id: 0, // Auto-incremented
+        pos_x: world_x,
+        pos_y: world_y,
+        placed_by: placer_id,
+        placed_at: ctx.timestamp,
+        is_burning: true, // Start burning
+        fuel_instance_id_0: Some(fuel_instance_id), // Add the wood
+        fuel_def_id_0: Some(wood_def.id),
+        fuel_instance_id_1: None,
+        fuel_def_id_1: None,
+        fuel_instance_id_2: None,
+        fuel_def_id_2: None,
+        fuel_instance_id_3: None,
+        fuel_def_id_3: None,
+        fuel_instance_id_4: None,
+        fuel_def_id_4: None,
+        next_fuel_consume_at: Some(first_consumption_time), // Schedule consumption
+        last_toggled_at: None,
+        extinguished_at: None,
+        cooking_instance_id: None,
+        cook_progress_secs: 0,
+        no_fuel_consumption: false,
+    };
+    ctx.db.campfire().try_insert(new_campfire)?;
 
-    // 5. If move was successful, clear the source slot in the campfire
-    if move_result.is_ok() {
-        log::info!(
-            "[MoveFuelToPlayer] Move successful. Clearing campfire {} slot {}.",
-            campfire_id, source_slot_index
-        );
-        match source_slot_index {
-            0 => { campfire.fuel_instance_id_0 = None; campfire.fuel_def_id_0 = None; },
-            1 => { campfire.fuel_instance_id_1 = None; campfire.fuel_def_id_1 = None; },
-            2 => { campfire.fuel_instance_id_2 = None; campfire.fuel_def_id_2 = None; },
-            3 => { campfire.fuel_instance_id_3 = None; campfire.fuel_def_id_3 = None; },
-            4 => { campfire.fuel_instance_id_4 = None; campfire.fuel_def_id_4 = None; },
-            _ => {} // Should not happen
-        }
-        // Update campfire state AFTER clearing the slot
-        campfires.id().update(campfire);
-    } else {
-        // Log error if move failed, but return the original error from move_result
-        log::error!(
-            "[MoveFuelToPlayer] Failed to move fuel item {} to player slot: {:?}. Campfire slot {} unchanged.",
-            fuel_instance_id, move_result.as_ref().err(), source_slot_index
-        );
-    }
-
-    move_result // Return the actual result of the move operation
+    log::info!("[SpawnCampfire] Player {:?} placed a campfire at ({:.1}, {:.1}) with initial fuel (Item {} in slot 0).",
+             placer_id, world_x, world_y, fuel_instance_id);
+    Ok(())
 }
 
 // --- Init Helper --- 
@@ -1253,4 +921,128 @@ pub(crate) fn init_campfire_fuel_schedule(ctx: &ReducerContext) -> Result<(), St
     }
     */
     Ok(())
-}
\ No newline at end of file
+}
+
+pub(crate) fn init_campfire_state_check_schedule(ctx: &ReducerContext) -> Result<(), String> {
+    let schedule_table = ctx.db.campfire_state_check_schedule();
+    if schedule_table.iter().count() == 0 {
+        log::info!("Starting campfire state check schedule (every {}s).", STATE_CHECK_INTERVAL_SECS);
+        let interval = Duration::from_secs(STATE_CHECK_INTERVAL_SECS);
+        schedule_table.insert(CampfireStateCheckSchedule {
+            id: 0, // Auto-incremented
+            scheduled_at: ScheduleAt::Interval(interval.into()),
+        });
+    } else {
+        log::debug!("Campfire state check schedule already exists.");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::items::{test_item_def, ItemCategory};
+
+    #[test]
+    fn warmth_is_stronger_close_to_the_fire() {
+        let close = warmth_per_second_at_distance_sq(10.0 * 10.0, WARMTH_PER_SECOND);
+        let far = warmth_per_second_at_distance_sq((WARMTH_RADIUS - 10.0).powi(2), WARMTH_PER_SECOND);
+        assert!(close > far && far > 0.0);
+        assert_eq!(warmth_per_second_at_distance_sq(0.0, WARMTH_PER_SECOND), WARMTH_PER_SECOND);
+        assert_eq!(warmth_per_second_at_distance_sq(WARMTH_RADIUS_SQUARED, WARMTH_PER_SECOND), 0.0);
+    }
+
+    #[test]
+    fn three_overlapping_fires_have_diminishing_returns() {
+        let one_fire = combine_fire_warmth(vec![4.0], WARMTH_PER_SECOND);
+        let three_fires = combine_fire_warmth(vec![2.0, 4.0, 3.0], WARMTH_PER_SECOND);
+        assert_eq!(one_fire, 4.0);
+        // Strongest first at full weight, then 1/2 and 1/4: 4 + 1.5 + 0.5, capped at 1.5x output
+        assert_eq!(three_fires, (4.0_f32 + 1.5 + 0.5).min(WARMTH_PER_SECOND * MAX_COMBINED_WARMTH_FACTOR));
+        assert!(three_fires < 4.0 + 3.0 + 2.0);
+
+        let carpet = combine_fire_warmth(vec![WARMTH_PER_SECOND; 3], WARMTH_PER_SECOND);
+        assert_eq!(carpet, WARMTH_PER_SECOND * MAX_COMBINED_WARMTH_FACTOR);
+    }
+
+    #[test]
+    fn fuel_and_heat_come_from_the_item_definition() {
+        let fuel = |secs: Option<u64>| ItemDefinition { fuel_burn_duration_secs: secs, ..test_item_def("Fuel", ItemCategory::Material) };
+        assert_eq!(fuel_unit_burn_duration(&fuel(Some(20))), Duration::from_secs(20));
+        assert_eq!(fuel_unit_burn_duration(&fuel(Some(0))), Duration::from_secs(1));
+        assert_eq!(fuel_unit_burn_duration(&fuel(None)), Duration::from_secs(FUEL_CONSUME_INTERVAL_SECS));
+        assert!(is_campfire_fuel(&fuel(Some(20))) && !is_campfire_fuel(&fuel(None)));
+
+        // A hotter source warms proportionally more at the same distance
+        let distance_sq = (WARMTH_RADIUS / 2.0).powi(2);
+        let hotter = warmth_per_second_at_distance_sq(distance_sq, WARMTH_PER_SECOND * 2.0);
+        assert_eq!(hotter, 2.0 * warmth_per_second_at_distance_sq(distance_sq, WARMTH_PER_SECOND));
+    }
+
+    fn test_campfire(is_burning: bool, next_fuel_consume_at: Option<Timestamp>) -> Campfire {
+        Campfire {
+            id: 1, pos_x: 0.0, pos_y: 0.0, placed_by: Identity::default(),
+            placed_at: Timestamp::from_micros_since_unix_epoch(0), is_burning,
+            fuel_instance_id_0: None, fuel_def_id_0: None, fuel_instance_id_1: None, fuel_def_id_1: None,
+            fuel_instance_id_2: None, fuel_def_id_2: None, fuel_instance_id_3: None, fuel_def_id_3: None,
+            fuel_instance_id_4: None, fuel_def_id_4: None,
+            next_fuel_consume_at, last_toggled_at: None, extinguished_at: None,
+            cooking_instance_id: None, cook_progress_secs: 0, no_fuel_consumption: false,
+        }
+    }
+
+    #[test]
+    fn inconsistent_campfires_are_corrected() {
+        let soon = Some(Timestamp::from_micros_since_unix_epoch(1_000_000));
+        // Stuck lit with no fuel, lit with nothing scheduled, unlit with a stale schedule
+        assert_eq!(burn_state_fix(&test_campfire(true, soon), false), Some(BurnStateFix::Extinguish));
+        assert_eq!(burn_state_fix(&test_campfire(true, None), true), Some(BurnStateFix::ScheduleFuel));
+        assert_eq!(burn_state_fix(&test_campfire(false, soon), true), Some(BurnStateFix::ClearSchedule));
+        // Put out on purpose with fuel left: not relit
+        assert_eq!(burn_state_fix(&test_campfire(false, None), true), None);
+        assert_eq!(burn_state_fix(&test_campfire(true, soon), true), None);
+
+        let always_on = Campfire { no_fuel_consumption: true, ..test_campfire(false, None) };
+        assert_eq!(burn_state_fix(&always_on, false), Some(BurnStateFix::Relight));
+        assert_eq!(burn_state_fix(&Campfire { is_burning: true, ..always_on }, false), None);
+    }
+
+    #[test]
+    fn always_on_campfires_burn_without_fuel() {
+        let lit = test_campfire(true, None);
+        let always_on = Campfire { no_fuel_consumption: true, ..test_campfire(true, None) };
+        assert!(burns_fuel(&lit));
+        assert!(!burns_fuel(&test_campfire(false, None)));
+        assert!(!burns_fuel(&always_on));
+        // Empty slots never put an always-on fire out or get fuel use scheduled
+        assert_eq!(burn_state_fix(&always_on, false), None);
+        assert_eq!(burn_state_fix(&lit, false), Some(BurnStateFix::Extinguish));
+
+        // A due burn tick only uses fuel on a normal lit fire, never an out or always-on one
+        let due = Some(Timestamp::from_micros_since_unix_epoch(1_000_000));
+        let now = Timestamp::from_micros_since_unix_epoch(2_000_000);
+        assert_eq!(fuel_left_after_tick(&test_campfire(true, due), now, 5), 4);
+        assert_eq!(fuel_left_after_tick(&test_campfire(false, due), now, 5), 5);
+        assert_eq!(fuel_left_after_tick(&Campfire { next_fuel_consume_at: due, ..always_on }, now, 5), 5);
+    }
+
+    #[test]
+    fn player_between_three_packed_fires_stays_under_the_cap() {
+        // Three fires packed as tightly as placement allows around the player
+        let spacing = CAMPFIRE_COLLISION_RADIUS * 2.0;
+        let fires = [(0.0_f32, 0.0_f32), (spacing, 0.0), (spacing / 2.0, spacing * 0.87)];
+        let (player_x, player_y) = (spacing / 2.0, spacing * 0.29);
+        for output in [WARMTH_PER_SECOND, WARMTH_PER_SECOND * 2.0] {
+            let contributions: Vec<f32> = fires.iter()
+                .map(|&(x, y)| warmth_per_second_at_distance_sq((x - player_x).powi(2) + (y - player_y).powi(2), output))
+                .collect();
+            assert!(contributions.iter().all(|warmth| *warmth > output * 0.5)); // Each one alone is strong
+            let combined = combine_fire_warmth(contributions.clone(), output);
+            assert!(combined <= output * MAX_COMBINED_WARMTH_FACTOR);
+            assert!(combined > contributions.iter().copied().fold(0.0, f32::max)); // Extra fires still help a little
+            // A cooling fire adds less than a lit one in the same spot
+            let cooling = combine_fire_warmth(vec![contributions[0], contributions[1], contributions[2] * 0.25], output);
+            assert!(cooling <= combined);
+        }
+    }
+}
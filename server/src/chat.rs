@@ -0,0 +1,114 @@
+use spacetimedb::{Identity, ReducerContext, Table, Timestamp};
+use spacetimedb::spacetimedb_lib::ScheduleAt;
+use std::time::Duration;
+use log;
+
+use crate::player as PlayerTableTrait;
+use crate::errors::GameError;
+
+// --- Constants ---
+const MAX_CHAT_MESSAGE_LEN: usize = 256; // Characters, after trimming
+const CHAT_RETENTION_SECS: i64 = 600; // Messages older than this are pruned
+const CHAT_CLEANUP_INTERVAL_SECS: u64 = 60;
+
+// --- Chat Message Table ---
+// Public: clients subscribe to it and render a scrolling chat log. Kept bounded by `prune_chat_messages`.
+#[spacetimedb::table(name = chat_message, public)]
+#[derive(Clone)]
+pub struct ChatMessage {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub sender_identity: Identity,
+    pub sender_username: String, // Copied at send time so old lines keep their name
+    pub text: String,
+    pub sent_at: Timestamp,
+}
+
+// --- Schedule Table ---
+#[spacetimedb::table(name = chat_cleanup_schedule, scheduled(prune_chat_messages))]
+#[derive(Clone)]
+pub struct ChatCleanupSchedule {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub scheduled_at: ScheduleAt,
+}
+
+// --- Helpers ---
+
+/// Drops control characters (newlines, escapes), trims, and truncates to the max length.
+fn sanitize_chat_text(text: &str) -> String {
+    let cleaned: String = text.chars().filter(|c| !c.is_control()).collect();
+    cleaned.trim().chars().take(MAX_CHAT_MESSAGE_LEN).collect()
+}
+
+// --- Reducers ---
+
+/// Posts a message to the global chat.
+#[spacetimedb::reducer]
+pub fn send_chat_message(ctx: &ReducerContext, text: String) -> Result<(), String> {
+    let sender_id = ctx.sender;
+    let player = ctx.db.player().identity().find(sender_id)
+        .ok_or_else(|| GameError::NotFound("Player".to_string()))?;
+
+    let text = sanitize_chat_text(&text);
+    if text.is_empty() {
+        return Err(GameError::InvalidInput("message must not be empty".to_string()).into());
+    }
+
+    let message = ctx.db.chat_message().insert(ChatMessage {
+        id: 0, // Auto-incremented
+        sender_identity: sender_id,
+        sender_username: player.username,
+        text,
+        sent_at: ctx.timestamp,
+    });
+    log::debug!("[Chat] {} ({:?}) sent message {}.", message.sender_username, sender_id, message.id);
+    Ok(())
+}
+
+/// Scheduled reducer that deletes chat messages past the retention window.
+#[spacetimedb::reducer]
+pub fn prune_chat_messages(ctx: &ReducerContext, _schedule: ChatCleanupSchedule) -> Result<(), String> {
+    let cutoff_micros = ctx.timestamp.to_micros_since_unix_epoch().saturating_sub(CHAT_RETENTION_SECS * 1_000_000);
+    let messages = ctx.db.chat_message();
+    let expired: Vec<u64> = messages.iter()
+        .filter(|message| message.sent_at.to_micros_since_unix_epoch() < cutoff_micros)
+        .map(|message| message.id)
+        .collect();
+    for message_id in &expired {
+        messages.id().delete(*message_id);
+    }
+    if !expired.is_empty() {
+        log::debug!("[Chat] Pruned {} old messages.", expired.len());
+    }
+    Ok(())
+}
+
+// --- Init Helper (Called from lib.rs) ---
+pub(crate) fn init_chat_cleanup_schedule(ctx: &ReducerContext) -> Result<(), String> {
+    let schedule_table = ctx.db.chat_cleanup_schedule();
+    if schedule_table.iter().count() == 0 {
+        log::info!("Starting chat cleanup schedule (every {}s).", CHAT_CLEANUP_INTERVAL_SECS);
+        let interval = Duration::from_secs(CHAT_CLEANUP_INTERVAL_SECS);
+        schedule_table.insert(ChatCleanupSchedule {
+            id: 0, // Auto-incremented
+            scheduled_at: ScheduleAt::Interval(interval.into()),
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chat_text_is_stripped_trimmed_and_capped() {
+        assert_eq!(sanitize_chat_text("  hello\n world\u{1b}[31m \t"), "hello world[31m");
+        assert_eq!(sanitize_chat_text(" \r\n\t "), "");
+        let long = "é".repeat(MAX_CHAT_MESSAGE_LEN + 10);
+        assert_eq!(sanitize_chat_text(&long).chars().count(), MAX_CHAT_MESSAGE_LEN); // Counts characters, not bytes
+    }
+}
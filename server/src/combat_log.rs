@@ -0,0 +1,192 @@
+/*
+ * server/src/combat_log.rs
+ *
+ * Purpose: Combat tagging, so players can't escape a PvP fight by disconnecting.
+ *
+ * How it works:
+ *   - `use_equipped_item` calls `combat_tag_until` on every PvP hit and stores the result in
+ *     `Player.combat_until` for BOTH the attacker and the victim.
+ *   - On disconnect, `identity_disconnected` checks `is_in_combat`. If the player is tagged, their
+ *     Player row (the "body") is left in the world, still hittable, and a one-shot
+ *     `combat_logout_schedule` row is queued for `combat_until`. When it fires,
+ *     `finish_combat_logout` runs the normal disconnect cleanup.
+ *   - If the player reconnects before then, `cancel_pending_combat_logout` drops the queued
+ *     cleanup and they resume control of the same body.
+ *
+ * It also keeps a short, public history of recent hits (`combat_log_entry`) for kill feeds and
+ * PvP disputes. `use_equipped_item` records each hit via `record_combat_hit`, and a scheduled
+ * cleanup prunes entries older than `COMBAT_LOG_RETENTION_SECS`.
+ */
+
+use spacetimedb::{Identity, ReducerContext, SpacetimeType, Table, Timestamp};
+use spacetimedb::spacetimedb_lib::ScheduleAt;
+use std::time::Duration;
+use log;
+
+use crate::Player;
+use crate::player as PlayerTableTrait;
+
+// --- Constants ---
+const COMBAT_TAG_DURATION_SECS: i64 = 30; // How long a PvP hit keeps both sides tagged
+const COMBAT_LOG_RETENTION_SECS: i64 = 600; // Hit history kept for 10 minutes
+const COMBAT_LOG_CLEANUP_INTERVAL_SECS: u64 = 60;
+pub(crate) const LOG_COMBAT_MISSES: bool = false; // Swings that hit nothing are noisy; enable for debugging
+
+// What a logged swing connected with
+#[derive(Clone, Debug, PartialEq, Eq, SpacetimeType)]
+pub enum CombatTargetType {
+    Player,
+    Tree,
+    Stone,
+    Miss,
+}
+
+// --- Hit History Table ---
+#[spacetimedb::table(name = combat_log_entry, public)]
+#[derive(Clone)]
+pub struct CombatLogEntry {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub attacker: Identity,
+    pub target_type: CombatTargetType,
+    pub target_player: Option<Identity>, // Set for Player hits
+    pub target_entity_id: Option<u64>,   // Tree/Stone id for resource hits
+    pub weapon_def_id: u64,
+    pub damage: f32,
+    pub killed: bool, // The hit killed the player or depleted the resource
+    pub timestamp: Timestamp,
+}
+
+#[spacetimedb::table(name = combat_log_cleanup_schedule, scheduled(prune_combat_log))]
+#[derive(Clone)]
+pub struct CombatLogCleanupSchedule {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub scheduled_at: ScheduleAt,
+}
+
+// --- Schedule Table ---
+// One row per combat-tagged player who disconnected; fires once at the end of their tag.
+#[spacetimedb::table(name = combat_logout_schedule, scheduled(finish_combat_logout))]
+#[derive(Clone)]
+pub struct CombatLogoutSchedule {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub player_identity: Identity,
+    pub scheduled_at: ScheduleAt,
+}
+
+// --- Helpers ---
+
+/// Returns the timestamp a PvP hit at `now` keeps a player combat-tagged until.
+pub(crate) fn combat_tag_until(now: Timestamp) -> Timestamp {
+    let until_micros = now.to_micros_since_unix_epoch().saturating_add(COMBAT_TAG_DURATION_SECS * 1_000_000);
+    Timestamp::from_micros_since_unix_epoch(until_micros)
+}
+
+/// Returns true if the player is still inside their combat-log window.
+pub(crate) fn is_in_combat(player: &Player, now: Timestamp) -> bool {
+    player.combat_until.is_some_and(|until| now < until)
+}
+
+/// Leaves the player's body in the world and queues the real cleanup for when their tag expires.
+pub(crate) fn schedule_combat_logout(ctx: &ReducerContext, player: &Player) {
+    let until = player.combat_until.unwrap_or(ctx.timestamp);
+    ctx.db.combat_logout_schedule().insert(CombatLogoutSchedule {
+        id: 0, // Auto-incremented
+        player_identity: player.identity,
+        scheduled_at: ScheduleAt::Time(until),
+    });
+    log::info!("[CombatLog] Player {} ({:?}) disconnected while in combat. Body stays until {:?}.",
+             player.username, player.identity, until);
+}
+
+/// Called on connect: if the player combat-logged earlier and is still lingering, keep their body.
+pub(crate) fn cancel_pending_combat_logout(ctx: &ReducerContext, player_id: Identity) {
+    let schedules = ctx.db.combat_logout_schedule();
+    let pending: Vec<u64> = schedules.iter()
+        .filter(|s| s.player_identity == player_id)
+        .map(|s| s.id)
+        .collect();
+    for schedule_id in &pending {
+        schedules.id().delete(*schedule_id);
+    }
+    if !pending.is_empty() {
+        log::info!("[CombatLog] Player {:?} reconnected before their combat tag expired. Cancelled pending logout.", player_id);
+    }
+}
+
+// --- Scheduled Reducer ---
+
+/// Runs the normal disconnect cleanup once a combat-logged player's tag has expired.
+#[spacetimedb::reducer]
+pub fn finish_combat_logout(ctx: &ReducerContext, schedule: CombatLogoutSchedule) -> Result<(), String> {
+    if ctx.db.player().identity().find(schedule.player_identity).is_none() {
+        log::debug!("[CombatLog] Player {:?} already gone; nothing to clean up.", schedule.player_identity);
+        return Ok(());
+    }
+    log::info!("[CombatLog] Combat tag expired for offline player {:?}. Removing body.", schedule.player_identity);
+    crate::cleanup_disconnected_player(ctx, schedule.player_identity);
+    Ok(())
+}
+
+// --- Hit History ---
+
+/// Appends a hit to the combat log. `target_player` / `target_entity_id` depend on `target_type`.
+pub(crate) fn record_combat_hit(
+    ctx: &ReducerContext,
+    attacker: Identity,
+    target_type: CombatTargetType,
+    target_player: Option<Identity>,
+    target_entity_id: Option<u64>,
+    weapon_def_id: u64,
+    damage: f32,
+    killed: bool,
+) {
+    ctx.db.combat_log_entry().insert(CombatLogEntry {
+        id: 0, // Auto-incremented
+        attacker,
+        target_type,
+        target_player,
+        target_entity_id,
+        weapon_def_id,
+        damage,
+        killed,
+        timestamp: ctx.timestamp,
+    });
+}
+
+/// Deletes hit history older than the retention window.
+#[spacetimedb::reducer]
+pub fn prune_combat_log(ctx: &ReducerContext, _schedule: CombatLogCleanupSchedule) -> Result<(), String> {
+    let cutoff_micros = ctx.timestamp.to_micros_since_unix_epoch().saturating_sub(COMBAT_LOG_RETENTION_SECS * 1_000_000);
+    let entries = ctx.db.combat_log_entry();
+    let expired: Vec<u64> = entries.iter()
+        .filter(|entry| entry.timestamp.to_micros_since_unix_epoch() < cutoff_micros)
+        .map(|entry| entry.id)
+        .collect();
+    for entry_id in &expired {
+        entries.id().delete(*entry_id);
+    }
+    if !expired.is_empty() {
+        log::debug!("[CombatLog] Pruned {} old hit entries.", expired.len());
+    }
+    Ok(())
+}
+
+// --- Init Helper (Called from lib.rs) ---
+pub(crate) fn init_combat_log_cleanup_schedule(ctx: &ReducerContext) -> Result<(), String> {
+    let schedule_table = ctx.db.combat_log_cleanup_schedule();
+    if schedule_table.iter().count() == 0 {
+        log::info!("Starting combat log cleanup schedule (every {}s).", COMBAT_LOG_CLEANUP_INTERVAL_SECS);
+        let interval = Duration::from_secs(COMBAT_LOG_CLEANUP_INTERVAL_SECS);
+        schedule_table.insert(CombatLogCleanupSchedule {
+            id: 0, // Auto-incremented
+            scheduled_at: ScheduleAt::Interval(interval.into()),
+        });
+    }
+    Ok(())
+}
@@ -0,0 +1,310 @@
+use spacetimedb::{Identity, ReducerContext, Table};
+use spacetimedb::spacetimedb_lib::ScheduleAt;
+use std::time::Duration;
+use log;
+
+use crate::items::{InventoryItem, ItemDefinition, PlaceableKind};
+use crate::items::inventory_item as InventoryItemTableTrait;
+use crate::player as PlayerTableTrait;
+use crate::inventory_management::ItemContainer;
+use crate::errors::GameError;
+use crate::containers::ContainerType;
+use crate::utils::within_range;
+use crate::interaction::COMPOSTER_INTERACTION_DISTANCE_SQUARED;
+use crate::Player;
+
+// --- Composter Constants ---
+pub(crate) const NUM_COMPOSTER_SLOTS: usize = 5;
+const NUM_COMPOSTER_INPUT_SLOTS: u8 = 4; // Slots 0-3 take compostables
+const COMPOSTER_OUTPUT_SLOT: u8 = 4;     // Slot 4 only receives Fertilizer
+const COMPOSTER_TICK_INTERVAL_SECS: u64 = 10;
+const COMPOST_TIME_SECS: u32 = 60; // Time to turn one compostable into one Fertilizer
+pub(crate) const FERTILIZER_NAME: &str = "Fertilizer";
+const COMPOSTABLE_ITEM_NAMES: &[&str] = &["Rotten Food", "Mushroom"];
+
+// --- Composter Table ---
+// Placeable container that slowly turns compostable items into Fertilizer.
+#[spacetimedb::table(name = composter, public)]
+#[derive(Clone)]
+pub struct Composter {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u32,
+    pub pos_x: f32,
+    pub pos_y: f32,
+    pub placed_by: Identity,
+    pub compost_progress_secs: u32, // Progress towards the next Fertilizer
+    // --- Input Slots (0-3) ---
+    pub slot_instance_id_0: Option<u64>,
+    pub slot_def_id_0: Option<u64>,
+    pub slot_instance_id_1: Option<u64>,
+    pub slot_def_id_1: Option<u64>,
+    pub slot_instance_id_2: Option<u64>,
+    pub slot_def_id_2: Option<u64>,
+    pub slot_instance_id_3: Option<u64>,
+    pub slot_def_id_3: Option<u64>,
+    // --- Output Slot (4) ---
+    pub slot_instance_id_4: Option<u64>,
+    pub slot_def_id_4: Option<u64>,
+}
+
+// --- Schedule Table ---
+#[spacetimedb::table(name = composter_schedule, scheduled(process_composters))]
+#[derive(Clone)]
+pub struct ComposterSchedule {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub scheduled_at: ScheduleAt,
+}
+
+// --- Trait Implementation ---
+
+impl ItemContainer for Composter {
+    fn num_slots(&self) -> usize {
+        NUM_COMPOSTER_SLOTS
+    }
+
+    fn get_slot_instance_id(&self, slot_index: u8) -> Option<u64> {
+        match slot_index {
+            0 => self.slot_instance_id_0,
+            1 => self.slot_instance_id_1,
+            2 => self.slot_instance_id_2,
+            3 => self.slot_instance_id_3,
+            4 => self.slot_instance_id_4,
+            _ => None,
+        }
+    }
+
+    fn get_slot_def_id(&self, slot_index: u8) -> Option<u64> {
+        match slot_index {
+            0 => self.slot_def_id_0,
+            1 => self.slot_def_id_1,
+            2 => self.slot_def_id_2,
+            3 => self.slot_def_id_3,
+            4 => self.slot_def_id_4,
+            _ => None,
+        }
+    }
+
+    fn set_slot(&mut self, slot_index: u8, instance_id: Option<u64>, def_id: Option<u64>) {
+        match slot_index {
+            0 => { self.slot_instance_id_0 = instance_id; self.slot_def_id_0 = def_id; }
+            1 => { self.slot_instance_id_1 = instance_id; self.slot_def_id_1 = def_id; }
+            2 => { self.slot_instance_id_2 = instance_id; self.slot_def_id_2 = def_id; }
+            3 => { self.slot_instance_id_3 = instance_id; self.slot_def_id_3 = def_id; }
+            4 => { self.slot_instance_id_4 = instance_id; self.slot_def_id_4 = def_id; }
+            _ => {} // Ignore invalid index
+        }
+    }
+
+    // Input slots take compostables only; the output slot is filled by the composter itself.
+    fn accepts(&self, slot_index: u8, item_def: &ItemDefinition) -> bool {
+        slot_index < NUM_COMPOSTER_INPUT_SLOTS && is_compostable(item_def)
+    }
+}
+
+// --- Helpers ---
+
+pub(crate) fn is_compostable(item_def: &ItemDefinition) -> bool {
+    COMPOSTABLE_ITEM_NAMES.contains(&item_def.name.as_str())
+}
+
+/// Validates that the player exists and is close enough to the composter.
+pub(crate) fn validate_composter_interaction(ctx: &ReducerContext, composter_id: u32) -> Result<(Player, Composter), String> {
+    let player = ctx.db.player().identity().find(ctx.sender)
+        .ok_or_else(|| GameError::NotFound("Player".to_string()))?;
+    let composter = ctx.db.composter().id().find(composter_id)
+        .ok_or_else(|| GameError::NotFound(format!("Composter {}", composter_id)))?;
+
+    if !within_range(player.position_x, player.position_y, composter.pos_x, composter.pos_y, COMPOSTER_INTERACTION_DISTANCE_SQUARED) {
+        return Err(GameError::TooFarAway.into());
+    }
+    Ok((player, composter))
+}
+
+/// Removes the given item from whichever composter slot holds it, if any.
+pub(crate) fn clear_item_from_composters(ctx: &ReducerContext, item_instance_id: u64) -> bool {
+    let composters = ctx.db.composter();
+    for mut composter in composters.iter() {
+        for slot_index in 0..NUM_COMPOSTER_SLOTS as u8 {
+            if composter.get_slot_instance_id(slot_index) == Some(item_instance_id) {
+                log::debug!("[Composter] Clearing item {} from composter {} slot {}.", item_instance_id, composter.id, slot_index);
+                composter.set_slot(slot_index, None, None);
+                composters.id().update(composter);
+                return true;
+            }
+        }
+    }
+    false
+}
+
+// --- Placement (registered in placement.rs) ---
+
+/// Creates an empty Composter entity.
+pub(crate) fn spawn_composter(ctx: &ReducerContext, placer_id: Identity, world_x: f32, world_y: f32) -> Result<(), String> {
+    let new_composter = Composter {
+        id: 0, // Auto-incremented
+        pos_x: world_x,
+        pos_y: world_y,
+        placed_by: placer_id,
+        compost_progress_secs: 0,
+        slot_instance_id_0: None,
+        slot_def_id_0: None,
+        slot_instance_id_1: None,
+        slot_def_id_1: None,
+        slot_instance_id_2: None,
+        slot_def_id_2: None,
+        slot_instance_id_3: None,
+        slot_def_id_3: None,
+        slot_instance_id_4: None,
+        slot_def_id_4: None,
+    };
+    ctx.db.composter().insert(new_composter);
+    log::info!("[SpawnComposter] Player {:?} placed a Composter at ({:.1}, {:.1}).", placer_id, world_x, world_y);
+    Ok(())
+}
+
+/// Places a Composter item from the player's inventory/hotbar.
+#[spacetimedb::reducer]
+pub fn place_composter(ctx: &ReducerContext, item_instance_id: u64, world_x: f32, world_y: f32) -> Result<(), String> {
+    crate::placement::place_item_of_kind(ctx, item_instance_id, world_x, world_y, 0.0, Some(PlaceableKind::Composter))
+}
+
+// --- Container Reducers ---
+// Kept for existing clients; each forwards to the matching reducer in `containers`.
+
+/// Moves an item from the player's inventory/hotbar into a composter input slot.
+#[spacetimedb::reducer]
+pub fn move_item_to_composter(ctx: &ReducerContext, composter_id: u32, target_slot_index: u8, item_instance_id: u64) -> Result<(), String> {
+    crate::containers::move_inventory_to_container(ctx, ContainerType::Composter, composter_id, target_slot_index, item_instance_id)
+}
+
+/// Moves an item (usually Fertilizer from the output slot) from a composter into the player's inventory.
+#[spacetimedb::reducer]
+pub fn move_item_from_composter(
+    ctx: &ReducerContext,
+    composter_id: u32,
+    source_slot_index: u8,
+    target_slot_type: String,
+    target_slot_index: u32,
+) -> Result<(), String> {
+    crate::containers::move_container_to_inventory(ctx, ContainerType::Composter, composter_id, source_slot_index, target_slot_type, target_slot_index)
+}
+
+/// Quickly moves a compostable from the player into the first fitting input slot.
+#[spacetimedb::reducer]
+pub fn quick_move_to_composter(ctx: &ReducerContext, composter_id: u32, item_instance_id: u64) -> Result<(), String> {
+    crate::containers::quick_move_to_container(ctx, ContainerType::Composter, composter_id, item_instance_id)
+}
+
+/// Quickly moves an item from a composter slot into the player's inventory.
+#[spacetimedb::reducer]
+pub fn quick_move_from_composter(ctx: &ReducerContext, composter_id: u32, source_slot_index: u8) -> Result<(), String> {
+    crate::containers::quick_move_from_container(ctx, ContainerType::Composter, composter_id, source_slot_index)
+}
+
+// --- Scheduled Composting ---
+
+/// Advances every composter that has input. Each completed cycle consumes one
+/// compostable and adds one Fertilizer to the output slot.
+#[spacetimedb::reducer]
+pub fn process_composters(ctx: &ReducerContext, _schedule: ComposterSchedule) -> Result<(), String> {
+    let fertilizer_def = match crate::items::get_item_def_by_name(ctx, FERTILIZER_NAME) {
+        Some(def) => def,
+        None => {
+            log::warn!("[Composter] '{}' item definition missing; skipping composting.", FERTILIZER_NAME);
+            return Ok(());
+        }
+    };
+
+    for mut composter in ctx.db.composter().iter() {
+        // 1. Find the first input slot with something in it
+        let Some(input_slot) = (0..NUM_COMPOSTER_INPUT_SLOTS).find(|&i| composter.get_slot_instance_id(i).is_some()) else {
+            if composter.compost_progress_secs != 0 {
+                composter.compost_progress_secs = 0;
+                ctx.db.composter().id().update(composter);
+            }
+            continue;
+        };
+
+        // 2. Advance progress (capped, so a blocked composter finishes as soon as it has room)
+        composter.compost_progress_secs = (composter.compost_progress_secs + COMPOSTER_TICK_INTERVAL_SECS as u32).min(COMPOST_TIME_SECS);
+        if composter.compost_progress_secs < COMPOST_TIME_SECS {
+            ctx.db.composter().id().update(composter);
+            continue;
+        }
+
+        // 3. Make room in the output slot, or wait if it's full
+        if !add_fertilizer_to_output(ctx, &mut composter, &fertilizer_def) {
+            log::debug!("[Composter] Composter {} output slot is full; waiting.", composter.id);
+            ctx.db.composter().id().update(composter);
+            continue;
+        }
+
+        // 4. Consume one compostable from the input slot
+        if let Some(input_instance_id) = composter.get_slot_instance_id(input_slot) {
+            match ctx.db.inventory_item().instance_id().find(input_instance_id) {
+                Some(mut input_item) if input_item.quantity > 1 => {
+                    input_item.quantity -= 1;
+                    ctx.db.inventory_item().instance_id().update(input_item);
+                }
+                Some(_) => {
+                    ctx.db.inventory_item().instance_id().delete(input_instance_id);
+                    composter.set_slot(input_slot, None, None);
+                }
+                None => composter.set_slot(input_slot, None, None), // Stale slot, just clear it
+            }
+        }
+
+        composter.compost_progress_secs = 0;
+        log::info!("[Composter] Composter {} produced 1 {}.", composter.id, FERTILIZER_NAME);
+        ctx.db.composter().id().update(composter);
+    }
+
+    Ok(())
+}
+
+// Adds one Fertilizer to the output slot. Returns false if the slot is full or holds something else.
+fn add_fertilizer_to_output(ctx: &ReducerContext, composter: &mut Composter, fertilizer_def: &ItemDefinition) -> bool {
+    let inventory = ctx.db.inventory_item();
+    if let Some(output_instance_id) = composter.get_slot_instance_id(COMPOSTER_OUTPUT_SLOT) {
+        return match inventory.instance_id().find(output_instance_id) {
+            Some(mut output_item) if output_item.item_def_id == fertilizer_def.id && output_item.quantity < crate::items::effective_stack_size(ctx, fertilizer_def) => {
+                output_item.quantity += 1;
+                inventory.instance_id().update(output_item);
+                true
+            }
+            _ => false,
+        };
+    }
+
+    let new_item = crate::items::insert_new_inventory_item(ctx, InventoryItem {
+        instance_id: 0, // Auto-inc
+        player_identity: composter.placed_by,
+        item_def_id: fertilizer_def.id,
+        quantity: 1,
+        hotbar_slot: None,
+        inventory_slot: None,
+        charges: None,
+        durability: None,
+        spoils_at: None,
+        quality: None,
+    });
+    composter.set_slot(COMPOSTER_OUTPUT_SLOT, Some(new_item.instance_id), Some(fertilizer_def.id));
+    true
+}
+
+// --- Init Helper (Called from lib.rs) ---
+pub(crate) fn init_composter_schedule(ctx: &ReducerContext) -> Result<(), String> {
+    let schedule_table = ctx.db.composter_schedule();
+    if schedule_table.iter().count() == 0 {
+        log::info!("Starting composter schedule (every {}s).", COMPOSTER_TICK_INTERVAL_SECS);
+        let interval = Duration::from_secs(COMPOSTER_TICK_INTERVAL_SECS);
+        schedule_table.insert(ComposterSchedule {
+            id: 0, // Auto-incremented
+            scheduled_at: ScheduleAt::Interval(interval.into()),
+        });
+    }
+    Ok(())
+}
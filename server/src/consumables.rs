@@ -12,6 +12,11 @@ use crate::items::ItemCategory; // Import the enum itself
 const MUSHROOM_HEALTH_GAIN: f32 = 5.0;
 const MUSHROOM_HUNGER_GAIN: f32 = 10.0;
 const MUSHROOM_THIRST_GAIN: f32 = 5.0;
+const COOKED_MUSHROOM_HEALTH_GAIN: f32 = 10.0;
+const COOKED_MUSHROOM_HUNGER_GAIN: f32 = 20.0;
+const ROTTEN_FOOD_HUNGER_GAIN: f32 = 5.0;
+const BURNT_FOOD_HUNGER_GAIN: f32 = 3.0;
+const ROTTEN_FOOD_SICKNESS_DAMAGE: f32 = 5.0; // Eating rotten food makes you mildly sick
 const MAX_STAT_VALUE: f32 = 100.0; // Max value for health, hunger, thirst
 
 #[spacetimedb::reducer]
@@ -53,20 +58,76 @@ pub fn consume_item(ctx: &ReducerContext, item_instance_id: u64) -> Result<(), S
         let old_hunger = player.hunger;
         let old_thirst = player.thirst;
 
-        player.health = (player.health + MUSHROOM_HEALTH_GAIN).min(MAX_STAT_VALUE);
-        player.hunger = (player.hunger + MUSHROOM_HUNGER_GAIN).min(MAX_STAT_VALUE);
-        player.thirst = (player.thirst + MUSHROOM_THIRST_GAIN).min(MAX_STAT_VALUE);
+        // Older food restores less (see spoilage.rs)
+        let freshness = crate::spoilage::freshness(ctx, &item_to_consume, &item_def);
+        let effect_scale = crate::spoilage::freshness_effect_multiplier(freshness);
+
+        player.health = (player.health + MUSHROOM_HEALTH_GAIN * effect_scale).min(MAX_STAT_VALUE);
+        player.hunger = (player.hunger + MUSHROOM_HUNGER_GAIN * effect_scale).min(MAX_STAT_VALUE);
+        player.thirst = (player.thirst + MUSHROOM_THIRST_GAIN * effect_scale).min(MAX_STAT_VALUE);
         
         stat_changed = true; // Assume stats changed if it's a mushroom
 
         log::info!(
-            "[ConsumeItem] Player {:?} consumed {}. Stats: H {:.1}->{:.1}, Hu {:.1}->{:.1}, T {:.1}->{:.1}",
-            sender_id, item_def.name, 
+            "[ConsumeItem] Player {:?} consumed {} (freshness {:.2}). Stats: H {:.1}->{:.1}, Hu {:.1}->{:.1}, T {:.1}->{:.1}",
+            sender_id, item_def.name, freshness,
             old_health, player.health, 
             old_hunger, player.hunger, 
             old_thirst, player.thirst
         );
 
+    } else if item_def.name == "Cooked Mushroom" {
+        let old_health = player.health;
+        let old_hunger = player.hunger;
+
+        // Fresher and better-cooked food restores more (see spoilage.rs, item_quality.rs)
+        let freshness = crate::spoilage::freshness(ctx, &item_to_consume, &item_def);
+        let effect_scale = crate::spoilage::freshness_effect_multiplier(freshness)
+            * crate::item_quality::quality_multiplier(item_to_consume.quality);
+
+        // Cooking drives the water out: more food value, no thirst relief
+        player.health = (player.health + COOKED_MUSHROOM_HEALTH_GAIN * effect_scale).min(MAX_STAT_VALUE);
+        player.hunger = (player.hunger + COOKED_MUSHROOM_HUNGER_GAIN * effect_scale).min(MAX_STAT_VALUE);
+
+        stat_changed = true;
+
+        log::info!(
+            "[ConsumeItem] Player {:?} consumed {} (freshness {:.2}). Stats: H {:.1}->{:.1}, Hu {:.1}->{:.1}",
+            sender_id, item_def.name, freshness,
+            old_health, player.health,
+            old_hunger, player.hunger
+        );
+
+    } else if item_def.name == crate::spoilage::ROTTEN_FOOD_NAME {
+        let old_health = player.health;
+        let old_hunger = player.hunger;
+
+        player.hunger = (player.hunger + ROTTEN_FOOD_HUNGER_GAIN).min(MAX_STAT_VALUE);
+        // Never kill the player outright through food poisoning
+        player.health = (player.health - ROTTEN_FOOD_SICKNESS_DAMAGE).max(1.0);
+
+        stat_changed = true;
+
+        log::info!(
+            "[ConsumeItem] Player {:?} ate {} and feels sick. Stats: H {:.1}->{:.1}, Hu {:.1}->{:.1}",
+            sender_id, item_def.name,
+            old_health, player.health,
+            old_hunger, player.hunger
+        );
+
+    } else if item_def.name == crate::campfire::BURNT_FOOD_NAME {
+        let old_hunger = player.hunger;
+
+        player.hunger = (player.hunger + BURNT_FOOD_HUNGER_GAIN).min(MAX_STAT_VALUE);
+
+        stat_changed = true;
+
+        log::info!(
+            "[ConsumeItem] Player {:?} ate {}. Stats: Hu {:.1}->{:.1}",
+            sender_id, item_def.name,
+            old_hunger, player.hunger
+        );
+
     } else {
         log::warn!("[ConsumeItem] Consumed item '{}' has no defined effect.", item_def.name);
         // Return Ok even if no effect, item is still consumed
@@ -0,0 +1,199 @@
+use spacetimedb::{ReducerContext, SpacetimeType};
+use log;
+
+use crate::campfire::campfire as CampfireTableTrait;
+use crate::wooden_storage_box::wooden_storage_box as WoodenStorageBoxTableTrait;
+use crate::composter::composter as ComposterTableTrait;
+use crate::items::inventory_item as InventoryItemTableTrait;
+use crate::errors::GameError;
+use crate::inventory_management;
+use crate::batch_results::{record_batch_result, BatchStopReason};
+
+// --- Container Types ---
+// World entities that hold items in slots. Each one implements `ItemContainer` and has a
+// `validate_*_interaction` helper; per-item rules live in its `ItemContainer::accepts`.
+// Adding a container means adding a variant here and an arm to `with_container!`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, SpacetimeType)]
+pub enum ContainerType {
+    Campfire,
+    WoodenStorageBox,
+    Composter,
+}
+
+/// Validates the sender can use the container, binds it mutably to `$container`, runs `$action`
+/// (a `Result<(), String>` expression) and saves the container if it succeeded.
+/// Campfires also re-check whether they still have fuel, so moving the last Wood out puts them out.
+macro_rules! with_container {
+    ($ctx:expr, $container_type:expr, $container_id:expr, |$container:ident| $action:expr) => {{
+        match $container_type {
+            ContainerType::Campfire => {
+                let (_player, mut $container) = crate::campfire::validate_campfire_interaction($ctx, $container_id)?;
+                $action?;
+                crate::campfire::refresh_campfire_burning_state($ctx, &mut $container);
+                $ctx.db.campfire().id().update($container);
+            }
+            ContainerType::WoodenStorageBox => {
+                let (_player, mut $container) = crate::wooden_storage_box::validate_box_interaction($ctx, $container_id)?;
+                $action?;
+                $ctx.db.wooden_storage_box().id().update($container);
+            }
+            ContainerType::Composter => {
+                let (_player, mut $container) = crate::composter::validate_composter_interaction($ctx, $container_id)?;
+                $action?;
+                $ctx.db.composter().id().update($container);
+            }
+        }
+        Ok(())
+    }};
+}
+
+// --- Generic Container Reducers ---
+// Every container UI action goes through these. The older per-container reducers
+// (`add_fuel_to_campfire`, `move_item_to_box`, ...) are thin wrappers kept for existing clients.
+
+/// Moves an item from the player's inventory/hotbar into a specific container slot (merge, swap or place).
+#[spacetimedb::reducer]
+pub fn move_inventory_to_container(
+    ctx: &ReducerContext,
+    container_type: ContainerType,
+    container_id: u32,
+    container_slot: u8,
+    item_instance_id: u64,
+) -> Result<(), String> {
+    log::debug!("[Containers] Player {:?} moving item {} to {:?} {} slot {}.",
+              ctx.sender, item_instance_id, container_type, container_id, container_slot);
+    with_container!(ctx, container_type, container_id, |container| {
+        inventory_management::handle_move_to_container_slot(ctx, &mut container, container_slot, item_instance_id)
+    })
+}
+
+/// Moves an item from a container slot into a specific inventory ("inventory") or hotbar ("hotbar") slot.
+#[spacetimedb::reducer]
+pub fn move_container_to_inventory(
+    ctx: &ReducerContext,
+    container_type: ContainerType,
+    container_id: u32,
+    container_slot: u8,
+    target_slot_type: String,
+    target_slot_index: u32,
+) -> Result<(), String> {
+    log::debug!("[Containers] Player {:?} moving {:?} {} slot {} to {} slot {}.",
+              ctx.sender, container_type, container_id, container_slot, target_slot_type, target_slot_index);
+    with_container!(ctx, container_type, container_id, |container| {
+        inventory_management::handle_move_from_container_slot(ctx, &mut container, container_slot, target_slot_type, target_slot_index)
+    })
+}
+
+/// Moves, merges or swaps an item between two slots of the same container.
+#[spacetimedb::reducer]
+pub fn move_within_container(
+    ctx: &ReducerContext,
+    container_type: ContainerType,
+    container_id: u32,
+    source_slot: u8,
+    target_slot: u8,
+) -> Result<(), String> {
+    with_container!(ctx, container_type, container_id, |container| {
+        inventory_management::handle_move_within_container(ctx, &mut container, source_slot, target_slot)
+    })
+}
+
+/// Splits part of an inventory/hotbar stack into a container slot (empty, or merging onto the same item).
+#[spacetimedb::reducer]
+pub fn split_inventory_into_container(
+    ctx: &ReducerContext,
+    container_type: ContainerType,
+    container_id: u32,
+    container_slot: u8,
+    source_item_instance_id: u64,
+    quantity_to_split: u32,
+) -> Result<(), String> {
+    let mut source_item = ctx.db.inventory_item().instance_id().find(source_item_instance_id)
+        .ok_or_else(|| GameError::NotFound(format!("Item {}", source_item_instance_id)))?;
+    if source_item.player_identity != ctx.sender {
+        return Err(GameError::NotOwner.into());
+    }
+    if source_item.inventory_slot.is_none() && source_item.hotbar_slot.is_none() {
+        return Err(GameError::InvalidSlot("source item must be in inventory or hotbar to split".to_string()).into());
+    }
+    with_container!(ctx, container_type, container_id, |container| {
+        inventory_management::handle_split_into_container(ctx, &mut container, container_slot, &mut source_item, quantity_to_split)
+    })
+}
+
+/// Splits part of a container stack into a specific inventory or hotbar slot.
+#[spacetimedb::reducer]
+pub fn split_container_to_inventory(
+    ctx: &ReducerContext,
+    container_type: ContainerType,
+    container_id: u32,
+    container_slot: u8,
+    quantity_to_split: u32,
+    target_slot_type: String,
+    target_slot_index: u32,
+) -> Result<(), String> {
+    with_container!(ctx, container_type, container_id, |container| {
+        inventory_management::handle_split_from_container(ctx, &mut container, container_slot, quantity_to_split, target_slot_type, target_slot_index)
+    })
+}
+
+/// Splits part of a container stack into another slot of the same container.
+#[spacetimedb::reducer]
+pub fn split_within_container(
+    ctx: &ReducerContext,
+    container_type: ContainerType,
+    container_id: u32,
+    source_slot: u8,
+    target_slot: u8,
+    quantity_to_split: u32,
+) -> Result<(), String> {
+    with_container!(ctx, container_type, container_id, |container| {
+        inventory_management::handle_split_within_container(ctx, &mut container, source_slot, target_slot, quantity_to_split)
+    })
+}
+
+/// Moves an inventory/hotbar item into the first container slot that accepts it (merging first).
+#[spacetimedb::reducer]
+pub fn quick_move_to_container(
+    ctx: &ReducerContext,
+    container_type: ContainerType,
+    container_id: u32,
+    item_instance_id: u64,
+) -> Result<(), String> {
+    with_container!(ctx, container_type, container_id, |container| {
+        inventory_management::handle_quick_move_to_container(ctx, &mut container, item_instance_id)
+    })
+}
+
+/// Moves everything in a container into the player's inventory. Stops short when the inventory
+/// fills up; the outcome ("took 5 of 8 stacks, inventory full") goes to `batch_operation_result`.
+#[spacetimedb::reducer]
+pub fn take_all_from_container(
+    ctx: &ReducerContext,
+    container_type: ContainerType,
+    container_id: u32,
+) -> Result<(), String> {
+    let mut outcome = (0, 0);
+    let result: Result<(), String> = with_container!(ctx, container_type, container_id, |container| {
+        inventory_management::handle_take_all_from_container(ctx, &mut container).map(|counts| outcome = counts)
+    });
+    result?;
+
+    let (moved, found) = outcome;
+    let stop_reason = if moved < found { BatchStopReason::InventoryFull } else { BatchStopReason::Completed };
+    record_batch_result(ctx, ctx.sender, "take_all_from_container", moved, found, stop_reason);
+    Ok(())
+}
+
+/// Moves a container slot's item into the player's inventory wherever it fits (merging first).
+#[spacetimedb::reducer]
+pub fn quick_move_from_container(
+    ctx: &ReducerContext,
+    container_type: ContainerType,
+    container_id: u32,
+    container_slot: u8,
+) -> Result<(), String> {
+    with_container!(ctx, container_type, container_id, |container| {
+        inventory_management::handle_quick_move_from_container(ctx, &mut container, container_slot)
+    })
+}
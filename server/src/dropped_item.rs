@@ -10,10 +10,17 @@ use std::time::Duration;
 use crate::items::inventory_item as InventoryItemTableTrait;
 use crate::items::item_definition as ItemDefinitionTableTrait; // Import ItemDefinition trait
 use crate::player as PlayerTableTrait; // Import Player trait
-use crate::items::{add_item_to_player_inventory, InventoryItem, ItemDefinition};
+use crate::items::{add_item_to_player_inventory, InventoryItem, ItemCategory, ItemDefinition, ItemInstanceState};
 // Corrected imports for Player and PLAYER_RADIUS from crate root
 use crate::{Player, PLAYER_RADIUS}; 
 use crate::utils::get_distance_squared; // Assuming a utility function for distance
+use crate::interaction::DROPPED_ITEM_PICKUP_DISTANCE_SQUARED;
+use crate::errors::GameError;
+use crate::batch_results::{record_batch_result, BatchStopReason};
+use crate::tree::tree as TreeTableTrait;
+use crate::stone::stone as StoneTableTrait;
+use crate::campfire::campfire as CampfireTableTrait;
+use crate::wooden_storage_box::wooden_storage_box as WoodenStorageBoxTableTrait;
 
 // Define the table for items dropped in the world
 #[spacetimedb::table(name = dropped_item, public)]
@@ -27,6 +34,24 @@ pub struct DroppedItem {
     pub pos_x: f32,            // World X position
     pub pos_y: f32,            // World Y position
     pub created_at: Timestamp, // When the item was dropped (for potential cleanup)
+    pub owner: Option<Identity>, // Player the item is reserved for during the lock window
+    pub locked_until: Option<Timestamp>, // Until then only `owner` may pick it up. None = unlocked
+    // --- Per-instance state of the dropped stack, restored on pickup (see `InventoryItem`) ---
+    pub charges: Option<u32>,
+    pub durability: Option<u32>,
+    pub spoils_at: Option<Timestamp>,
+    pub quality: Option<u8>,
+}
+
+impl DroppedItem {
+    pub(crate) fn instance_state(&self) -> ItemInstanceState {
+        ItemInstanceState { charges: self.charges, durability: self.durability, spoils_at: self.spoils_at, quality: self.quality }
+    }
+
+    /// True while the pile is still reserved for an owner other than `player_id`.
+    pub(crate) fn is_locked_against(&self, player_id: Identity, now: Timestamp) -> bool {
+        matches!((self.owner, self.locked_until), (Some(owner), Some(locked_until)) if owner != player_id && now < locked_until)
+    }
 }
 
 // --- Schedule Table --- 
@@ -41,9 +66,10 @@ pub struct DroppedItemDespawnSchedule {
 }
 
 // Constants
-const PICKUP_RADIUS: f32 = 64.0; // How close the player needs to be to pick up (adjust as needed)
-const PICKUP_RADIUS_SQUARED: f32 = PICKUP_RADIUS * PICKUP_RADIUS;
 pub(crate) const DROP_OFFSET: f32 = 40.0; // How far in front of the player to drop the item
+pub(crate) const MAX_TOSS_DISTANCE: f32 = 120.0; // How far an aimed toss can throw an item
+const TOSS_STEP_BACK: f32 = 10.0; // Pull-back per step when the aimed spot is blocked
+const DROP_SCATTER_JITTER: f32 = 12.0; // Max offset for extra piles when a drop is split into full stacks
 // Ensure constant is i64
 const DROPPED_ITEM_DESPAWN_DURATION_SECS: i64 = 1800; // 30 minutes
 const DESPAWN_CHECK_INTERVAL_SECS: u64 = 60; // Check every 1 minute
@@ -55,36 +81,24 @@ const DESPAWN_CHECK_INTERVAL_SECS: u64 = 60; // Check every 1 minute
 pub fn pickup_dropped_item(ctx: &ReducerContext, dropped_item_id: u64) -> Result<(), String> {
     let sender_id = ctx.sender;
     let dropped_items_table = ctx.db.dropped_item();
-    let players_table = ctx.db.player();
     let item_defs_table = ctx.db.item_definition(); // Needed for logging
 
     log::info!("[PickupDropped] Player {:?} attempting to pick up dropped item ID {}", sender_id, dropped_item_id);
 
-    // 1. Find the Player
-    let player = players_table.identity().find(sender_id)
-        .ok_or_else(|| "Player not found.".to_string())?;
-
-    // 2. Find the DroppedItem
-    let dropped_item = dropped_items_table.id().find(dropped_item_id)
-        .ok_or_else(|| format!("Dropped item with ID {} not found.", dropped_item_id))?;
-
-    // 3. Check Proximity
-    let distance_sq = get_distance_squared(player.position_x, player.position_y, dropped_item.pos_x, dropped_item.pos_y);
-
-    if distance_sq > PICKUP_RADIUS_SQUARED {
-         log::warn!("[PickupDropped] Player {:?} too far from item {} (DistSq: {:.1} > {:.1})",
-                   sender_id, dropped_item_id, distance_sq, PICKUP_RADIUS_SQUARED);
-        return Err("Too far away to pick up the item.".to_string());
-    }
+    // 1-4. Player alive, item exists, in range and not locked to someone else
+    let dropped_item = validate_pickup(ctx, dropped_item_id)?;
 
-    // 4. Attempt to add item to player inventory (using existing helper from items.rs)
+    // 5. Attempt to add item to player inventory (using existing helper from items.rs)
     log::info!("[PickupDropped] Player {:?} is close enough. Attempting to add item def {} (qty {}) to inventory.",
              sender_id, dropped_item.item_def_id, dropped_item.quantity);
 
-    // Call the helper function from the items module
-    match crate::items::add_item_to_player_inventory(ctx, sender_id, dropped_item.item_def_id, dropped_item.quantity) {
+    // Call the helper function from the items module, keeping the pile's per-instance state.
+    // A partial fit counts as a failure; the reducer error rolls back what was added.
+    let add_result = crate::items::add_as_much_as_fits_with_state(ctx, sender_id, dropped_item.item_def_id, dropped_item.quantity, &dropped_item.instance_state())
+        .and_then(|leftover| if leftover > 0 { Err(GameError::InventoryFull.into()) } else { Ok(()) });
+    match add_result {
         Ok(_) => {
-            // 5. If successful, delete the dropped item entity
+            // 6. If successful, delete the dropped item entity
             dropped_items_table.id().delete(dropped_item_id);
             let item_name = item_defs_table.id().find(dropped_item.item_def_id)
                                .map(|def| def.name.clone())
@@ -102,6 +116,173 @@ pub fn pickup_dropped_item(ctx: &ReducerContext, dropped_item_id: u64) -> Result
     }
 }
 
+/// Picks up a dropped item into a chosen inventory or hotbar slot instead of the first free one.
+/// The slot must be empty or hold a stack the item can merge onto; if the stack can't take the
+/// whole pile, what fits is picked up and the rest stays on the ground.
+#[spacetimedb::reducer]
+pub fn pickup_to_slot(ctx: &ReducerContext, dropped_item_id: u64, slot_type: String, slot_index: u32) -> Result<(), String> {
+    let sender_id = ctx.sender;
+    let dropped_items_table = ctx.db.dropped_item();
+    let inventory_items = ctx.db.inventory_item();
+
+    // 1. Shared pickup checks
+    let mut dropped_item = validate_pickup(ctx, dropped_item_id)?;
+    let item_def = ctx.db.item_definition().id().find(dropped_item.item_def_id)
+        .ok_or_else(|| GameError::NotFound(format!("Item definition {}", dropped_item.item_def_id)))?;
+
+    // 2. Validate the target slot
+    let target_is_inventory = match slot_type.as_str() {
+        "inventory" => true,
+        "hotbar" => false,
+        _ => return Err(GameError::InvalidSlot(format!("slot type '{}' (must be 'inventory' or 'hotbar')", slot_type)).into()),
+    };
+    if (target_is_inventory && slot_index >= 24) || (!target_is_inventory && slot_index >= 6) {
+        return Err(GameError::InvalidSlot(format!("{} slot {}", slot_type, slot_index)).into());
+    }
+
+    // 3. Work out how much the slot can take (the pile keeps its per-instance state, so it
+    //    only merges onto a stack in exactly the same state)
+    let state = dropped_item.instance_state();
+    let occupant = inventory_items.player_identity().filter(&sender_id).find(|i| {
+        if target_is_inventory { i.inventory_slot == Some(slot_index as u16) } else { i.hotbar_slot == Some(slot_index as u8) }
+    });
+    let quantity_to_pick_up = match &occupant {
+        None => dropped_item.quantity,
+        Some(target) => {
+            let mergeable = item_def.is_stackable
+                && target.item_def_id == dropped_item.item_def_id
+                && state.matches(target);
+            if !mergeable {
+                return Err(format!("Target {} slot {} is already occupied.", slot_type, slot_index));
+            }
+            let space_available = crate::items::effective_stack_size(ctx, &item_def).saturating_sub(target.quantity);
+            if space_available == 0 {
+                return Err(format!("The stack in {} slot {} is full.", slot_type, slot_index));
+            }
+            dropped_item.quantity.min(space_available)
+        }
+    };
+
+    // 4. Create the stack unslotted, then let the move helpers place or merge it
+    let new_item = crate::items::insert_new_inventory_item(ctx, InventoryItem {
+        instance_id: 0, // Auto-incremented
+        player_identity: sender_id,
+        item_def_id: dropped_item.item_def_id,
+        quantity: quantity_to_pick_up,
+        hotbar_slot: None,
+        inventory_slot: None,
+        charges: state.charges,
+        durability: state.durability,
+        spoils_at: state.spoils_at,
+        quality: state.quality,
+    });
+    if target_is_inventory {
+        crate::items::move_item_to_inventory(ctx, new_item.instance_id, slot_index as u16)?;
+    } else {
+        crate::items::move_item_to_hotbar(ctx, new_item.instance_id, slot_index as u8)?;
+    }
+
+    // 5. Remove what was picked up from the world
+    if quantity_to_pick_up == dropped_item.quantity {
+        dropped_items_table.id().delete(dropped_item_id);
+    } else {
+        dropped_item.quantity -= quantity_to_pick_up;
+        dropped_items_table.id().update(dropped_item);
+    }
+    log::info!("[PickupToSlot] Player {:?} picked up {} of '{}' (dropped item {}) into {} slot {}.",
+             sender_id, quantity_to_pick_up, item_def.name, dropped_item_id, slot_type, slot_index);
+    Ok(())
+}
+
+/// Picks up every dropped item in pickup range, skipping item types on the caller's ignore list
+/// (see `pickup_preferences.rs`) and items still locked to someone else. Whatever doesn't fit
+/// in the inventory stays on the ground; the outcome goes to `batch_operation_result`.
+#[spacetimedb::reducer]
+pub fn pickup_nearby_items(ctx: &ReducerContext) -> Result<(), String> {
+    let sender_id = ctx.sender;
+    let dropped_items_table = ctx.db.dropped_item();
+
+    // 1. Find the player
+    let player = ctx.db.player().identity().find(sender_id)
+        .ok_or_else(|| "Player not found.".to_string())?;
+    if player.is_dead {
+        return Err("Cannot pick up items while dead.".to_string());
+    }
+
+    // 2. Collect candidates: in range, not reserved for another player, not ignored
+    let ignored_def_ids = crate::pickup_preferences::ignored_item_def_ids(ctx, sender_id);
+    let candidates: Vec<DroppedItem> = dropped_items_table.iter()
+        .filter(|item| is_bulk_pickup_candidate(item, sender_id, player.position_x, player.position_y, ctx.timestamp, &ignored_def_ids))
+        .collect();
+
+    // 3. Take as much of each pile as fits
+    let total_piles = candidates.len() as u32;
+    let mut picked_up_piles = 0;
+    for mut dropped_item in candidates {
+        let leftover = crate::items::add_as_much_as_fits_with_state(ctx, sender_id, dropped_item.item_def_id, dropped_item.quantity, &dropped_item.instance_state())?;
+        if leftover == 0 {
+            dropped_items_table.id().delete(dropped_item.id);
+            picked_up_piles += 1;
+        } else if leftover < dropped_item.quantity {
+            dropped_item.quantity = leftover;
+            dropped_items_table.id().update(dropped_item);
+        }
+        // A pile that didn't fit doesn't end the loop: later piles may still stack onto existing stacks
+    }
+
+    log::info!("[PickupNearby] Player {:?} picked up {} of {} dropped item piles.", sender_id, picked_up_piles, total_piles);
+    let stop_reason = if picked_up_piles < total_piles { BatchStopReason::InventoryFull } else { BatchStopReason::Completed };
+    record_batch_result(ctx, sender_id, "pickup_nearby_items", picked_up_piles, total_piles, stop_reason);
+    Ok(())
+}
+
+/// Checks that the caller is alive, near `dropped_item_id` and allowed to take it (owner lock).
+fn validate_pickup(ctx: &ReducerContext, dropped_item_id: u64) -> Result<DroppedItem, String> {
+    let sender_id = ctx.sender;
+    let dropped_items_table = ctx.db.dropped_item();
+    let players_table = ctx.db.player();
+
+    // 1. Find the Player
+    let player = players_table.identity().find(sender_id)
+        .ok_or_else(|| "Player not found.".to_string())?;
+    if player.is_dead {
+        return Err("Cannot pick up items while dead.".to_string());
+    }
+
+    // 2. Find the DroppedItem
+    let dropped_item = dropped_items_table.id().find(dropped_item_id)
+        .ok_or_else(|| format!("Dropped item with ID {} not found.", dropped_item_id))?;
+
+    // 3. Check Proximity
+    if !in_pickup_range(player.position_x, player.position_y, dropped_item.pos_x, dropped_item.pos_y) {
+         let distance_sq = get_distance_squared(player.position_x, player.position_y, dropped_item.pos_x, dropped_item.pos_y);
+         log::warn!("[PickupDropped] Player {:?} too far from item {} (DistSq: {:.1} > {:.1})",
+                   sender_id, dropped_item_id, distance_sq, DROPPED_ITEM_PICKUP_DISTANCE_SQUARED);
+        return Err(GameError::TooFarAway.into());
+    }
+
+    // 4. Respect the owner lock, if it is still active
+    if dropped_item.is_locked_against(sender_id, ctx.timestamp) {
+        log::warn!("[PickupDropped] Player {:?} tried to pick up item {} locked to {:?}.", sender_id, dropped_item_id, dropped_item.owner);
+        return Err("This item belongs to someone else for now.".to_string());
+    }
+
+    Ok(dropped_item)
+}
+
+/// True if bulk pickup by `player_id` at (player_x, player_y) takes this pile: in reach,
+/// not reserved for someone else, and not an item type the player has ignored.
+fn is_bulk_pickup_candidate(item: &DroppedItem, player_id: Identity, player_x: f32, player_y: f32, now: Timestamp, ignored_def_ids: &[u64]) -> bool {
+    in_pickup_range(player_x, player_y, item.pos_x, item.pos_y)
+        && !item.is_locked_against(player_id, now)
+        && !ignored_def_ids.contains(&item.item_def_id)
+}
+
+/// True if a player at (player_x, player_y) can reach a pile at (item_x, item_y). The edge counts as in reach.
+fn in_pickup_range(player_x: f32, player_y: f32, item_x: f32, item_y: f32) -> bool {
+    crate::utils::within_range(player_x, player_y, item_x, item_y, DROPPED_ITEM_PICKUP_DISTANCE_SQUARED)
+}
+
 // --- Scheduled Despawn Reducer ---
 
 /// Scheduled reducer that runs periodically to remove expired dropped items.
@@ -143,20 +324,46 @@ pub fn despawn_expired_items(ctx: &ReducerContext, _schedule: DroppedItemDespawn
         log::info!("[DespawnCheck] Despawned {} items.", despawn_count);
     }
 
+    // Catch up if the cap was exceeded some other way (e.g. lowered by an admin)
+    enforce_dropped_item_cap(ctx);
+
     Ok(())
 }
 
 // --- Helper Functions (Internal to this module) ---
 
-/// Creates a DroppedItem entity in the world.
+/// Creates a DroppedItem entity in the world, carrying the dropped stack's per-instance `state`.
 /// Assumes validation (like position checks) might happen before calling this.
 pub(crate) fn create_dropped_item_entity(
     ctx: &ReducerContext,
     item_def_id: u64,
     quantity: u32,
+    state: &ItemInstanceState,
     pos_x: f32,
     pos_y: f32,
 ) -> Result<(), String> { // Changed return type to Result<(), String> as we don't need the entity back
+    create_owner_locked_dropped_item_entity(ctx, item_def_id, quantity, state, pos_x, pos_y, None, 0)
+}
+
+/// Like `create_dropped_item_entity`, but reserves the item for `owner` for `lock_secs` seconds.
+/// With no owner or a zero duration the item is unlocked.
+pub(crate) fn create_owner_locked_dropped_item_entity(
+    ctx: &ReducerContext,
+    item_def_id: u64,
+    quantity: u32,
+    state: &ItemInstanceState,
+    pos_x: f32,
+    pos_y: f32,
+    owner: Option<Identity>,
+    lock_secs: u32,
+) -> Result<(), String> {
+    let (owner, locked_until) = match owner {
+        Some(owner_id) if lock_secs > 0 => {
+            let until_micros = ctx.timestamp.to_micros_since_unix_epoch().saturating_add(lock_secs as i64 * 1_000_000);
+            (Some(owner_id), Some(Timestamp::from_micros_since_unix_epoch(until_micros)))
+        }
+        _ => (None, None),
+    };
      let new_dropped_item = DroppedItem {
         id: 0, // Auto-incremented
         item_def_id,
@@ -164,12 +371,19 @@ pub(crate) fn create_dropped_item_entity(
         pos_x,
         pos_y,
         created_at: ctx.timestamp,
+        owner,
+        locked_until,
+        charges: state.charges,
+        durability: state.durability,
+        spoils_at: state.spoils_at,
+        quality: state.quality,
     };
 
     match ctx.db.dropped_item().try_insert(new_dropped_item) {
         Ok(_) => {
             log::info!("[CreateDroppedItem] Created dropped item entity (DefID: {}, Qty: {}) at ({:.1}, {:.1})",
                      item_def_id, quantity, pos_x, pos_y);
+            enforce_dropped_item_cap(ctx);
             Ok(())
         },
         Err(e) => {
@@ -179,6 +393,87 @@ pub(crate) fn create_dropped_item_entity(
     }
 }
 
+/// Evicts dropped items until at most `max_dropped_items` (see WorldConfig) remain.
+/// Unlocked drops go before owner-locked ones, common categories before rare ones, then oldest first.
+pub(crate) fn enforce_dropped_item_cap(ctx: &ReducerContext) {
+    let dropped_items_table = ctx.db.dropped_item();
+    let max_items = crate::world_config::get_max_dropped_items(ctx) as usize;
+    let total = dropped_items_table.iter().count();
+    if total <= max_items {
+        return;
+    }
+
+    let item_defs = ctx.db.item_definition();
+    let mut candidates: Vec<(bool, u8, Timestamp, DroppedItem)> = dropped_items_table.iter()
+        .map(|item| {
+            let is_locked = item.locked_until.is_some_and(|until| until > ctx.timestamp);
+            let tier = item_defs.id().find(item.item_def_id)
+                .map_or(0, |def| eviction_value_tier(&def.category));
+            (is_locked, tier, item.created_at, item)
+        })
+        .collect();
+    candidates.sort_by_key(|(is_locked, tier, created_at, _)| (*is_locked, *tier, *created_at));
+
+    let evict_count = total - max_items;
+    for (_, tier, _, item) in candidates.into_iter().take(evict_count) {
+        log::info!("[DropCap] Evicting dropped item {} (DefID: {}, Qty: {}, tier {}) at ({:.1}, {:.1}); cap {} exceeded.",
+                 item.id, item.item_def_id, item.quantity, tier, item.pos_x, item.pos_y, max_items);
+        dropped_items_table.id().delete(item.id);
+    }
+    log::warn!("[DropCap] {} dropped items exceeded the cap of {}; evicted {}.", total, max_items, evict_count);
+}
+
+/// Rough value ranking used for eviction (lower goes first). Items have no rarity field,
+/// so gathered materials count as common and crafted gear as rare.
+fn eviction_value_tier(category: &ItemCategory) -> u8 {
+    match category {
+        ItemCategory::Material => 0,
+        ItemCategory::Consumable => 1,
+        ItemCategory::Placeable => 2,
+        ItemCategory::Tool | ItemCategory::Armor => 3,
+    }
+}
+
+/// Drops `quantity` (all in the same `state`) as piles of at most one stack each (see `items::effective_stack_size`), so
+/// picking a pile back up never yields an over-cap stack. The first pile lands exactly at the
+/// given spot and the rest are scattered around it. Non-stackable items are dropped one per pile.
+pub(crate) fn create_stack_sized_dropped_item_entities(
+    ctx: &ReducerContext,
+    item_def: &ItemDefinition,
+    quantity: u32,
+    state: &ItemInstanceState,
+    pos_x: f32,
+    pos_y: f32,
+    owner: Option<Identity>,
+    lock_secs: u32,
+) -> Result<(), String> {
+    let pile_size = if item_def.is_stackable { crate::items::effective_stack_size(ctx, item_def).max(1) } else { 1 };
+    let mut rng = crate::utils::GameRng::from_ctx(ctx);
+    for (pile_index, pile_quantity) in pile_quantities(quantity, pile_size).into_iter().enumerate() {
+        let (pile_x, pile_y) = if pile_index == 0 {
+            (pos_x, pos_y)
+        } else {
+            (
+                (pos_x + rng.gen_range(-DROP_SCATTER_JITTER..=DROP_SCATTER_JITTER)).clamp(PLAYER_RADIUS, crate::WORLD_WIDTH_PX - PLAYER_RADIUS),
+                (pos_y + rng.gen_range(-DROP_SCATTER_JITTER..=DROP_SCATTER_JITTER)).clamp(PLAYER_RADIUS, crate::WORLD_HEIGHT_PX - PLAYER_RADIUS),
+            )
+        };
+        create_owner_locked_dropped_item_entity(ctx, item_def.id, pile_quantity, state, pile_x, pile_y, owner, lock_secs)?;
+    }
+    Ok(())
+}
+
+/// Splits `quantity` into piles of at most `pile_size` (at least 1); the last pile takes the remainder.
+pub(crate) fn pile_quantities(quantity: u32, pile_size: u32) -> Vec<u32> {
+    let pile_size = pile_size.max(1);
+    let remainder = quantity % pile_size;
+    let mut piles = vec![pile_size; (quantity / pile_size) as usize];
+    if remainder > 0 {
+        piles.push(remainder);
+    }
+    piles
+}
+
 /// Calculates a position slightly in front of the player based on their direction.
 pub(crate) fn calculate_drop_position(player: &Player) -> (f32, f32) {
     let mut drop_x = player.position_x;
@@ -194,12 +489,45 @@ pub(crate) fn calculate_drop_position(player: &Player) -> (f32, f32) {
 
     // Basic boundary clamping (could add collision checks later if needed)
     // Using player radius as a buffer from the edge
-    drop_x = drop_x.max(PLAYER_RADIUS).min(crate::WORLD_WIDTH_PX - PLAYER_RADIUS);
-    drop_y = drop_y.max(PLAYER_RADIUS).min(crate::WORLD_HEIGHT_PX - PLAYER_RADIUS);
+    drop_x = drop_x.clamp(PLAYER_RADIUS, crate::WORLD_WIDTH_PX - PLAYER_RADIUS);
+    drop_y = drop_y.clamp(PLAYER_RADIUS, crate::WORLD_HEIGHT_PX - PLAYER_RADIUS);
 
     (drop_x, drop_y)
 }
 
+/// Landing spot for an item tossed along `aim` (a direction; it doesn't need to be normalized).
+/// Goes as far as `MAX_TOSS_DISTANCE`, stepping back toward the player while the spot is inside
+/// a tree, stone, campfire or box. Falls back to `calculate_drop_position` with no usable aim
+/// or if every step is blocked.
+pub(crate) fn calculate_aimed_drop_position(ctx: &ReducerContext, player: &Player, aim: Option<(f32, f32)>) -> (f32, f32) {
+    let Some((aim_x, aim_y)) = aim else { return calculate_drop_position(player); };
+    let length = (aim_x * aim_x + aim_y * aim_y).sqrt();
+    if !length.is_finite() || length < f32::EPSILON {
+        return calculate_drop_position(player);
+    }
+    let (dir_x, dir_y) = (aim_x / length, aim_y / length);
+
+    let mut distance = MAX_TOSS_DISTANCE;
+    while distance >= DROP_OFFSET {
+        let drop_x = (player.position_x + dir_x * distance).clamp(PLAYER_RADIUS, crate::WORLD_WIDTH_PX - PLAYER_RADIUS);
+        let drop_y = (player.position_y + dir_y * distance).clamp(PLAYER_RADIUS, crate::WORLD_HEIGHT_PX - PLAYER_RADIUS);
+        if !is_drop_spot_blocked(ctx, drop_x, drop_y) {
+            return (drop_x, drop_y);
+        }
+        distance -= TOSS_STEP_BACK;
+    }
+    calculate_drop_position(player)
+}
+
+// True if (x, y) lies inside the collision circle of a tree, stone, campfire or storage box.
+fn is_drop_spot_blocked(ctx: &ReducerContext, x: f32, y: f32) -> bool {
+    let inside = |pos_x: f32, pos_y: f32, y_offset: f32, radius: f32| get_distance_squared(x, y, pos_x, pos_y - y_offset) < radius * radius;
+    ctx.db.tree().iter().any(|tree| tree.health > 0 && inside(tree.pos_x, tree.pos_y, crate::tree::TREE_COLLISION_Y_OFFSET, crate::tree::TREE_TRUNK_RADIUS))
+        || ctx.db.stone().iter().any(|stone| stone.health > 0 && inside(stone.pos_x, stone.pos_y, crate::stone::STONE_COLLISION_Y_OFFSET, crate::stone::STONE_RADIUS))
+        || ctx.db.campfire().iter().any(|fire| inside(fire.pos_x, fire.pos_y, crate::campfire::CAMPFIRE_COLLISION_Y_OFFSET, crate::campfire::CAMPFIRE_COLLISION_RADIUS))
+        || ctx.db.wooden_storage_box().iter().any(|storage_box| inside(storage_box.pos_x, storage_box.pos_y, crate::wooden_storage_box::BOX_COLLISION_Y_OFFSET, crate::wooden_storage_box::BOX_COLLISION_RADIUS))
+}
+
 // --- Init Helper (Called from lib.rs) ---
 pub(crate) fn init_dropped_item_schedule(ctx: &ReducerContext) -> Result<(), String> {
     let schedule_table = ctx.db.dropped_item_despawn_schedule();
@@ -216,3 +544,55 @@ pub(crate) fn init_dropped_item_schedule(ctx: &ReducerContext) -> Result<(), Str
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interaction::DEFAULT_INTERACTION_DISTANCE;
+
+    #[test]
+    fn pickup_reach_ends_at_the_interaction_distance() {
+        let reach = DEFAULT_INTERACTION_DISTANCE;
+        assert!(in_pickup_range(100.0, 100.0, 100.0, 100.0));
+        assert!(in_pickup_range(100.0, 100.0, 100.0 + reach, 100.0));
+        assert!(!in_pickup_range(100.0, 100.0, 100.0 + reach + 0.5, 100.0));
+        assert!(!in_pickup_range(100.0, 100.0, 100.0 + reach, 100.0 + reach));
+    }
+
+    #[test]
+    fn eviction_takes_common_categories_before_gear() {
+        assert!(eviction_value_tier(&ItemCategory::Material) < eviction_value_tier(&ItemCategory::Consumable));
+        assert!(eviction_value_tier(&ItemCategory::Consumable) < eviction_value_tier(&ItemCategory::Placeable));
+        assert!(eviction_value_tier(&ItemCategory::Placeable) < eviction_value_tier(&ItemCategory::Tool));
+        assert_eq!(eviction_value_tier(&ItemCategory::Tool), eviction_value_tier(&ItemCategory::Armor));
+
+        // Same key enforce_dropped_item_cap sorts by: (locked, tier, created_at)
+        let mut keys = [
+            (true, eviction_value_tier(&ItemCategory::Material), 1),
+            (false, eviction_value_tier(&ItemCategory::Tool), 2),
+            (false, eviction_value_tier(&ItemCategory::Material), 3),
+            (false, eviction_value_tier(&ItemCategory::Material), 0),
+        ];
+        keys.sort();
+        assert_eq!(keys.map(|(_, _, created)| created), [0, 3, 2, 1]);
+    }
+
+    #[test]
+    fn bulk_pickup_leaves_ignored_items_on_the_ground() {
+        let (player, other) = (Identity::default(), Identity::from_byte_array([1; 32]));
+        let now = Timestamp::from_micros_since_unix_epoch(1_000_000);
+        let pile = |item_def_id| DroppedItem {
+            id: item_def_id, item_def_id, quantity: 5, pos_x: 110.0, pos_y: 100.0, created_at: now,
+            owner: None, locked_until: None, charges: None, durability: None, spoils_at: None, quality: None,
+        };
+        let ignored = [2];
+        let takes = |item: &DroppedItem| is_bulk_pickup_candidate(item, player, 100.0, 100.0, now, &ignored);
+
+        assert!(takes(&pile(1)));
+        assert!(!takes(&pile(2))); // Ignored type stays put
+        assert!(!takes(&DroppedItem { pos_x: 1000.0, ..pile(1) }));
+        let locked_to_other = DroppedItem { owner: Some(other), locked_until: Some(Timestamp::from_micros_since_unix_epoch(2_000_000)), ..pile(1) };
+        assert!(!takes(&locked_to_other));
+        assert!(takes(&DroppedItem { owner: Some(player), ..locked_to_other.clone() }));
+    }
+}
@@ -1,7 +1,7 @@
 use spacetimedb::{Identity, ReducerContext, Table, Timestamp};
 use log;
 use std::time::Duration;
-use rand::Rng;
+use crate::utils::GameRng;
 use crate::Player;
 use crate::PLAYER_RADIUS;
 use crate::WORLD_WIDTH_PX;
@@ -65,9 +65,9 @@ pub fn spawn_enemies(ctx: &ReducerContext) -> Result<(), String> {
     }
 
     // Spawn enemies around each player
+    let mut rng = GameRng::from_ctx(ctx);
     for player in alive_players {
-        let mut rng = rand::thread_rng();
-        
+
         // Random angle and distance for spawn position
         let angle = rng.gen_range(0.0..2.0 * std::f32::consts::PI);
         let distance = rng.gen_range(ENEMY_SPAWN_RADIUS * 0.8..ENEMY_SPAWN_RADIUS);
@@ -5,11 +5,23 @@
  *          excluding player-specific state.
  *
  * Responsibilities:
- *   - `seed_environment`: Populates the world with initial resources (trees, stones, mushrooms)
+ *   - `seed_environment`: Populates the world with initial resources (trees, stones, mushrooms, water)
  *                         on server startup if the environment is empty. Uses helpers from `utils.rs`.
+ *                         Tree/stone/mushroom counts follow the `WorldConfig` densities.
+ *   - `reseed_resources`: Admin reducer that clears and re-seeds trees, stones and mushrooms.
+ *   - `set_world_seed`: Admin reducer that changes `WorldState.world_seed` before anyone has
+ *                       registered and regenerates the map from it. All seeding draws from
+ *                       `GameRng::from_seed(world_seed)`, so the same seed yields the same layout.
  *   - `check_resource_respawns`: Checks periodically if any depleted resources (trees, stones,
  *                                mushrooms with `respawn_at` set) are ready to respawn.
  *                                Uses a macro from `utils.rs` for conciseness.
+ *   - Trees and stones have two gathering modes (`WorldConfig.renewable_resources`):
+ *       - Destructive (default): a node hit to 0 health gets `respawn_at` and sits depleted
+ *         until the timer runs out, then resets to full health and full yield at once.
+ *       - Renewable: a node hit to 0 gets no `respawn_at`. Instead, `regrow_renewable_resources`
+ *         heals every damaged node back, starting `RENEWABLE_REGROW_DELAY_SECS` after its last hit,
+ *         at a pace that takes `RESOURCE_RESPAWN_DURATION_SECS` from empty to full. `remaining_yield`
+ *         grows back with health, so wood/stone is only paid for health that actually regrew.
  *
  * Note: Resource definitions (structs, constants) are in their respective modules (e.g., `tree.rs`).
  */
@@ -27,25 +39,45 @@ use crate::mushroom;
 use crate::tree::tree as TreeTableTrait;
 use crate::stone::stone as StoneTableTrait;
 use crate::mushroom::mushroom as MushroomTableTrait;
+use crate::water::water_source as WaterSourceTableTrait;
+use crate::world_state::world_state as WorldStateTableTrait;
+use crate::player as PlayerTableTrait;
 
 // Import utils helpers and macro
 use crate::utils::{calculate_tile_bounds, attempt_single_spawn};
 use crate::check_and_respawn_resource; // Import the macro
 
 use noise::{NoiseFn, Perlin, Fbm};
-use rand::{Rng, SeedableRng};
-use rand::rngs::StdRng;
+use rand::Rng;
+use crate::utils::GameRng;
 use std::collections::HashSet;
 use log;
 
 // --- Environment Seeding ---
 
+const WATER_SEED_SALT: u64 = 0x5741_5445_5253; // Keeps the water layout independent of how many rolls land seeding takes
+
+/// The seed every environment roll derives from. Falls back to a one-off random seed if
+/// `WorldState` hasn't been seeded yet (the layout just won't be reproducible then).
+fn world_seed(ctx: &ReducerContext) -> u64 {
+    ctx.db.world_state().iter().next()
+        .map(|state| state.world_seed)
+        .unwrap_or_else(|| ctx.rng().gen())
+}
+
 #[spacetimedb::reducer]
 pub fn seed_environment(ctx: &ReducerContext) -> Result<(), String> {
     let trees = ctx.db.tree();
     let stones = ctx.db.stone();
     let mushrooms = ctx.db.mushroom();
 
+    // Water sources are seeded on their own so worlds created before they existed still get some.
+    if ctx.db.water_source().iter().count() == 0 {
+        let mut water_rng = GameRng::from_seed(world_seed(ctx) ^ WATER_SEED_SALT);
+        let spawned_water_count = crate::water::seed_water_sources(ctx, &mut water_rng);
+        log::info!("Seeded {} water sources (target: {}).", spawned_water_count, crate::water::WATER_SOURCE_COUNT);
+    }
+
     if trees.iter().count() > 0 || stones.iter().count() > 0 || mushrooms.iter().count() > 0 {
         log::info!(
             "Environment already seeded (Trees: {}, Stones: {}, Mushrooms: {}). Skipping.",
@@ -56,18 +88,16 @@ pub fn seed_environment(ctx: &ReducerContext) -> Result<(), String> {
 
     log::info!("Seeding environment (trees, stones, mushrooms)..." );
 
-    let fbm = Fbm::<Perlin>::new(ctx.rng().gen());
-    let mut rng = StdRng::from_rng(ctx.rng()).map_err(|e| format!("Failed to seed RNG: {}", e))?;
+    let mut rng = GameRng::from_seed(world_seed(ctx));
+    let fbm = Fbm::<Perlin>::new(rng.gen());
 
     let total_tiles = crate::WORLD_WIDTH_TILES * crate::WORLD_HEIGHT_TILES;
+    let (tree_density, stone_density, mushroom_density) = crate::world_config::get_resource_densities(ctx);
 
     // Calculate targets and limits
-    let target_tree_count = (total_tiles as f32 * crate::tree::TREE_DENSITY_PERCENT) as u32;
-    let max_tree_attempts = target_tree_count * crate::tree::MAX_TREE_SEEDING_ATTEMPTS_FACTOR;
-    let target_stone_count = (total_tiles as f32 * crate::stone::STONE_DENSITY_PERCENT) as u32;
-    let max_stone_attempts = target_stone_count * crate::tree::MAX_TREE_SEEDING_ATTEMPTS_FACTOR; 
-    let target_mushroom_count = (total_tiles as f32 * crate::mushroom::MUSHROOM_DENSITY_PERCENT) as u32;
-    let max_mushroom_attempts = target_mushroom_count * crate::tree::MAX_TREE_SEEDING_ATTEMPTS_FACTOR; 
+    let (target_tree_count, max_tree_attempts) = seeding_targets(total_tiles, tree_density);
+    let (target_stone_count, max_stone_attempts) = seeding_targets(total_tiles, stone_density);
+    let (target_mushroom_count, max_mushroom_attempts) = seeding_targets(total_tiles, mushroom_density);
 
     log::info!("Target Trees: {}, Max Attempts: {}", target_tree_count, max_tree_attempts);
     log::info!("Target Stones: {}, Max Attempts: {}", target_stone_count, max_stone_attempts);
@@ -112,9 +142,12 @@ pub fn seed_environment(ctx: &ReducerContext) -> Result<(), String> {
                 pos_x,
                 pos_y,
                 health: crate::tree::TREE_INITIAL_HEALTH,
+                stage: crate::utils::ResourceStage::Full,
+                remaining_yield: crate::tree::TREE_TOTAL_YIELD,
                 tree_type: crate::tree::TreeType::Oak,
                 last_hit_time: None,
                 respawn_at: None,
+                last_regrow_at: None,
             },
             trees,
         ) {
@@ -150,8 +183,11 @@ pub fn seed_environment(ctx: &ReducerContext) -> Result<(), String> {
                 pos_x,
                 pos_y,
                 health: crate::stone::STONE_INITIAL_HEALTH,
+                stage: crate::utils::ResourceStage::Full,
+                remaining_yield: crate::stone::STONE_TOTAL_YIELD,
                 last_hit_time: None,
                 respawn_at: None,
+                last_regrow_at: None,
             },
             stones,
         ) {
@@ -205,11 +241,141 @@ pub fn seed_environment(ctx: &ReducerContext) -> Result<(), String> {
     Ok(())
 }
 
+/// (target count, max attempts) for seeding one resource at `density` (fraction of tiles) over `total_tiles`.
+fn seeding_targets(total_tiles: u32, density: f32) -> (u32, u32) {
+    let target_count = (total_tiles as f32 * density) as u32;
+    (target_count, target_count.saturating_mul(crate::tree::MAX_TREE_SEEDING_ATTEMPTS_FACTOR))
+}
+
+/// Admin: clears all trees, stones and mushrooms and seeds them again with the current
+/// `WorldConfig` densities. Water sources are left alone.
+#[spacetimedb::reducer]
+pub fn reseed_resources(ctx: &ReducerContext) -> Result<(), String> {
+    crate::world_config::ensure_admin(ctx)?;
+
+    clear_resource_nodes(ctx);
+    seed_environment(ctx)
+}
+
+/// Admin: sets the world seed and regenerates trees, stones, mushrooms and water from it.
+/// Only allowed before any player has registered, so nobody's base ends up inside a new tree.
+#[spacetimedb::reducer]
+pub fn set_world_seed(ctx: &ReducerContext, seed: u64) -> Result<(), String> {
+    crate::world_config::ensure_admin(ctx)?;
+    if ctx.db.player().iter().next().is_some() {
+        return Err("The world seed can only be changed before any player registers".to_string());
+    }
+
+    crate::world_state::seed_world_state(ctx)?;
+    let mut state = ctx.db.world_state().iter().next()
+        .ok_or_else(|| "WorldState not found".to_string())?;
+    let old_seed = state.world_seed;
+    state.world_seed = seed;
+    ctx.db.world_state().id().update(state);
+    log::info!("[WorldSeed] Admin {:?} changed world seed {} -> {}. Regenerating environment.", ctx.sender, old_seed, seed);
+
+    clear_resource_nodes(ctx);
+    let water_ids: Vec<u32> = ctx.db.water_source().iter().map(|w| w.id).collect();
+    for id in &water_ids { ctx.db.water_source().id().delete(*id); }
+    seed_environment(ctx)
+}
+
+/// Deletes every tree, stone and mushroom so `seed_environment` will run again.
+fn clear_resource_nodes(ctx: &ReducerContext) {
+    let tree_ids: Vec<u64> = ctx.db.tree().iter().map(|t| t.id).collect();
+    let stone_ids: Vec<u64> = ctx.db.stone().iter().map(|s| s.id).collect();
+    let mushroom_ids: Vec<u64> = ctx.db.mushroom().iter().map(|m| m.id).collect();
+    for id in &tree_ids { ctx.db.tree().id().delete(*id); }
+    for id in &stone_ids { ctx.db.stone().id().delete(*id); }
+    for id in &mushroom_ids { ctx.db.mushroom().id().delete(*id); }
+    log::info!("[Reseed] {:?} cleared {} trees, {} stones, {} mushrooms.",
+             ctx.sender, tree_ids.len(), stone_ids.len(), mushroom_ids.len());
+}
+
+// --- Renewable Resources ---
+
+const RENEWABLE_REGROW_DELAY_SECS: i64 = 30; // Nodes only start healing once nobody has hit them for this long
+
+/// When a node hit to 0 health should respawn. None in renewable mode, where it regrows instead.
+pub(crate) fn depleted_respawn_at(ctx: &ReducerContext, now: Timestamp) -> Option<Timestamp> {
+    if crate::world_config::resources_are_renewable(ctx) {
+        None
+    } else {
+        Some(now + std::time::Duration::from_secs(crate::active_equipment::RESOURCE_RESPAWN_DURATION_SECS))
+    }
+}
+
+/// Health a node has earned back since it was last hit or last regrew, and the new regrow anchor.
+/// Full regrowth from 0 takes `RESOURCE_RESPAWN_DURATION_SECS`, matching the destructive respawn timer.
+fn regrow_step(now: Timestamp, last_hit_time: Option<Timestamp>, last_regrow_at: Option<Timestamp>, max_health: u32) -> Option<(u32, Timestamp)> {
+    let now_micros = now.to_micros_since_unix_epoch();
+    let last_hit_micros = last_hit_time.map_or(0, |t| t.to_micros_since_unix_epoch());
+    let regrow_start_micros = last_hit_micros.saturating_add(RENEWABLE_REGROW_DELAY_SECS * 1_000_000);
+    if now_micros < regrow_start_micros || max_health == 0 {
+        return None;
+    }
+    // Measure from the later of "delay after last hit" and the previous regrow step
+    let anchor_micros = last_regrow_at
+        .map_or(regrow_start_micros, |t| t.to_micros_since_unix_epoch().max(regrow_start_micros));
+    let micros_per_point = (crate::active_equipment::RESOURCE_RESPAWN_DURATION_SECS as i64 * 1_000_000 / max_health as i64).max(1);
+    let points = (now_micros.saturating_sub(anchor_micros) / micros_per_point) as u32;
+    if points == 0 {
+        return None;
+    }
+    // Advance the anchor by whole points only, so partial progress carries over to the next pass
+    let new_anchor = Timestamp::from_micros_since_unix_epoch(anchor_micros + points as i64 * micros_per_point);
+    Some((points, new_anchor))
+}
+
+/// Renewable mode: heals damaged trees and stones, growing their remaining yield back alongside.
+fn regrow_renewable_resources(ctx: &ReducerContext) {
+    let now = ctx.timestamp;
+
+    let trees = ctx.db.tree();
+    let damaged_trees: Vec<crate::tree::Tree> = trees.iter()
+        .filter(|t| t.health < crate::tree::TREE_INITIAL_HEALTH && t.respawn_at.is_none())
+        .collect();
+    for mut tree in damaged_trees {
+        let Some((points, anchor)) = regrow_step(now, tree.last_hit_time, tree.last_regrow_at, crate::tree::TREE_INITIAL_HEALTH) else { continue; };
+        tree.health = (tree.health + points).min(crate::tree::TREE_INITIAL_HEALTH);
+        tree.stage = crate::utils::resource_stage_for_health(tree.health, crate::tree::TREE_INITIAL_HEALTH);
+        tree.remaining_yield = tree.remaining_yield.max(regrown_yield(tree.health, crate::tree::TREE_INITIAL_HEALTH, crate::tree::TREE_TOTAL_YIELD));
+        tree.last_regrow_at = Some(anchor);
+        log::debug!("Tree {} regrew to {} health (yield {}).", tree.id, tree.health, tree.remaining_yield);
+        trees.id().update(tree);
+    }
+
+    let stones = ctx.db.stone();
+    let damaged_stones: Vec<crate::stone::Stone> = stones.iter()
+        .filter(|s| s.health < crate::stone::STONE_INITIAL_HEALTH && s.respawn_at.is_none())
+        .collect();
+    for mut stone in damaged_stones {
+        let Some((points, anchor)) = regrow_step(now, stone.last_hit_time, stone.last_regrow_at, crate::stone::STONE_INITIAL_HEALTH) else { continue; };
+        stone.health = (stone.health + points).min(crate::stone::STONE_INITIAL_HEALTH);
+        stone.stage = crate::utils::resource_stage_for_health(stone.health, crate::stone::STONE_INITIAL_HEALTH);
+        stone.remaining_yield = stone.remaining_yield.max(regrown_yield(stone.health, crate::stone::STONE_INITIAL_HEALTH, crate::stone::STONE_TOTAL_YIELD));
+        stone.last_regrow_at = Some(anchor);
+        log::debug!("Stone {} regrew to {} health (yield {}).", stone.id, stone.health, stone.remaining_yield);
+        stones.id().update(stone);
+    }
+}
+
+/// Yield a node at `health` should still hold, i.e. what `take_resource_yield` will pay out
+/// for hitting it back down to 0.
+fn regrown_yield(health: u32, max_health: u32, total_yield: u32) -> u32 {
+    (total_yield as u64 * health.min(max_health) as u64 / max_health.max(1) as u64) as u32
+}
+
 // --- Resource Respawn Reducer --- Refactored using Macro ---
 
 #[spacetimedb::reducer]
 pub fn check_resource_respawns(ctx: &ReducerContext) -> Result<(), String> {
-    
+    // Renewable mode heals damaged nodes in place. Nodes depleted before the mode was switched
+    // on still carry `respawn_at` and come back through the normal respawn below.
+    if crate::world_config::resources_are_renewable(ctx) {
+        regrow_renewable_resources(ctx);
+    }
+
     // Respawn Stones
     check_and_respawn_resource!(
         ctx,
@@ -219,8 +385,11 @@ pub fn check_resource_respawns(ctx: &ReducerContext) -> Result<(), String> {
         |s: &crate::stone::Stone| s.health == 0, // Filter: only check stones with 0 health
         |s: &mut crate::stone::Stone| { // Update logic
             s.health = crate::stone::STONE_INITIAL_HEALTH;
+            s.stage = crate::utils::ResourceStage::Full;
+            s.remaining_yield = crate::stone::STONE_TOTAL_YIELD;
             s.respawn_at = None;
             s.last_hit_time = None;
+            s.last_regrow_at = None;
         }
     );
 
@@ -233,8 +402,11 @@ pub fn check_resource_respawns(ctx: &ReducerContext) -> Result<(), String> {
         |t: &crate::tree::Tree| t.health == 0,
         |t: &mut crate::tree::Tree| {
             t.health = crate::tree::TREE_INITIAL_HEALTH;
+            t.stage = crate::utils::ResourceStage::Full;
+            t.remaining_yield = crate::tree::TREE_TOTAL_YIELD;
             t.respawn_at = None;
             t.last_hit_time = None;
+            t.last_regrow_at = None;
         }
     );
 
@@ -252,3 +424,22 @@ pub fn check_resource_respawns(ctx: &ReducerContext) -> Result<(), String> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn doubling_density_doubles_seeding_targets() {
+        let total_tiles = crate::WORLD_WIDTH_TILES * crate::WORLD_HEIGHT_TILES;
+        for density in [crate::tree::TREE_DENSITY_PERCENT, crate::stone::STONE_DENSITY_PERCENT, crate::mushroom::MUSHROOM_DENSITY_PERCENT] {
+            let (single, single_attempts) = seeding_targets(total_tiles, density);
+            let (double, double_attempts) = seeding_targets(total_tiles, density * 2.0);
+            assert!(single > 0);
+            assert!(double.abs_diff(single * 2) <= 1, "{} vs 2 x {}", double, single);
+            assert_eq!(double_attempts, double * crate::tree::MAX_TREE_SEEDING_ATTEMPTS_FACTOR);
+            assert_eq!(single_attempts, single * crate::tree::MAX_TREE_SEEDING_ATTEMPTS_FACTOR);
+        }
+        assert_eq!(seeding_targets(total_tiles, 0.0), (0, 0));
+    }
+}
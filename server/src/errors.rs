@@ -0,0 +1,88 @@
+/*
+ * server/src/errors.rs
+ *
+ * Purpose: Shared error codes for reducers.
+ *
+ * Reducers still return `Result<(), String>`, but errors built from `GameError`
+ * are formatted as "CODE: message" so clients can switch on the code prefix
+ * (and localize) while the human-readable text is preserved for logs.
+ */
+
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GameError {
+    TooFarAway,
+    InventoryFull,
+    NotOwner,
+    NotEnoughMaterials,
+    NotFound(String),      // What was not found (e.g. "Item instance 12")
+    InvalidSlot(String),   // Description of the bad slot
+    InvalidPlacement(String), // Why the placement was rejected
+    InvalidInput(String),  // Which client-supplied argument was malformed
+}
+
+impl GameError {
+    /// Stable, machine-readable code for the client.
+    pub fn code(&self) -> &'static str {
+        match self {
+            GameError::TooFarAway => "TOO_FAR_AWAY",
+            GameError::InventoryFull => "INVENTORY_FULL",
+            GameError::NotOwner => "NOT_OWNER",
+            GameError::NotEnoughMaterials => "NOT_ENOUGH_MATERIALS",
+            GameError::NotFound(_) => "NOT_FOUND",
+            GameError::InvalidSlot(_) => "INVALID_SLOT",
+            GameError::InvalidPlacement(_) => "INVALID_PLACEMENT",
+            GameError::InvalidInput(_) => "INVALID_INPUT",
+        }
+    }
+
+    /// Human-readable message (unchanged from the old ad hoc strings where possible).
+    pub fn message(&self) -> String {
+        match self {
+            GameError::TooFarAway => "Too far away".to_string(),
+            GameError::InventoryFull => "Inventory is full".to_string(),
+            GameError::NotOwner => "Item does not belong to player".to_string(),
+            GameError::NotEnoughMaterials => "Not enough materials".to_string(),
+            GameError::NotFound(what) => format!("{} not found", what),
+            GameError::InvalidSlot(detail) => format!("Invalid slot: {}", detail),
+            GameError::InvalidPlacement(reason) => format!("Invalid placement: {}", reason),
+            GameError::InvalidInput(detail) => format!("Invalid input: {}", detail),
+        }
+    }
+}
+
+impl fmt::Display for GameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.code(), self.message())
+    }
+}
+
+/// Rejects NaN/infinite client-supplied floats before they reach position or collision math.
+/// Call at the top of any reducer taking raw `f32` input: `ensure_finite(&[("x", x), ("y", y)])?;`
+pub fn ensure_finite(values: &[(&str, f32)]) -> Result<(), String> {
+    match values.iter().find(|(_, value)| !value.is_finite()) {
+        Some((name, value)) => Err(GameError::InvalidInput(format!("{} must be a finite number (got {})", name, value)).into()),
+        None => Ok(()),
+    }
+}
+
+// Lets reducers returning Result<(), String> use `GameError::X.into()` or `?` via map_err
+impl From<GameError> for String {
+    fn from(error: GameError) -> Self {
+        error.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ensure_finite_names_the_first_bad_value() {
+        assert_eq!(ensure_finite(&[("x", 1.0), ("y", -2.5)]), Ok(()));
+        let error = ensure_finite(&[("x", 1.0), ("y", f32::NAN), ("z", f32::INFINITY)]).unwrap_err();
+        assert!(error.contains("y must be a finite number"), "{}", error);
+        assert!(ensure_finite(&[("x", f32::NEG_INFINITY)]).is_err());
+    }
+}
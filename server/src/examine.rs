@@ -0,0 +1,162 @@
+use spacetimedb::{Identity, ReducerContext, SpacetimeType, Table, Timestamp};
+use log;
+
+use crate::player as PlayerTableTrait;
+use crate::tree::tree as TreeTableTrait;
+use crate::stone::stone as StoneTableTrait;
+use crate::campfire::campfire as CampfireTableTrait;
+use crate::wooden_storage_box::wooden_storage_box as WoodenStorageBoxTableTrait;
+use crate::active_equipment::active_equipment as ActiveEquipmentTableTrait;
+use crate::items::{inventory_item as InventoryItemTableTrait, item_definition as ItemDefinitionTableTrait};
+use crate::presence::online_player as OnlinePlayerTableTrait;
+use crate::inventory_management::ItemContainer;
+use crate::errors::GameError;
+use crate::world_config::ensure_admin;
+
+// Which kind of entity `examine_entity` should look up
+#[derive(Clone, Debug, PartialEq, SpacetimeType)]
+pub enum ExaminableEntity {
+    Tree,
+    Stone,
+    Campfire,
+    WoodenStorageBox,
+    Player,
+}
+
+// --- Entity Examination Table ---
+// Private: only the module owner (admin tooling) can read it. One row per `examine_entity` call,
+// holding a human-readable dump of the entity's server-side state at that moment.
+#[spacetimedb::table(name = entity_examination)]
+#[derive(Clone)]
+pub struct EntityExamination {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub examined_by: Identity,
+    pub entity_type: ExaminableEntity,
+    pub entity_id: String,
+    pub summary: String,
+    pub examined_at: Timestamp,
+}
+
+/// Admin/support: records the full server-side state of one entity in `entity_examination`
+/// (and the log). `entity_id` is the numeric id, or the identity hex for players.
+#[spacetimedb::reducer]
+pub fn examine_entity(ctx: &ReducerContext, entity_type: ExaminableEntity, entity_id: String) -> Result<(), String> {
+    ensure_admin(ctx)?;
+
+    let summary = match entity_type {
+        ExaminableEntity::Tree => {
+            let id = parse_numeric_id(&entity_id)?;
+            let tree = ctx.db.tree().id().find(id)
+                .ok_or_else(|| GameError::NotFound(format!("Tree {}", id)))?;
+            format!(
+                "Tree {} at ({:.1}, {:.1}) | health {}/{} stage {:?} | yield left {} | last hit {:?} | respawn at {:?} | last regrow {:?}",
+                tree.id, tree.pos_x, tree.pos_y, tree.health, crate::tree::TREE_INITIAL_HEALTH, tree.stage,
+                tree.remaining_yield, tree.last_hit_time, tree.respawn_at, tree.last_regrow_at,
+            )
+        }
+        ExaminableEntity::Stone => {
+            let id = parse_numeric_id(&entity_id)?;
+            let stone = ctx.db.stone().id().find(id)
+                .ok_or_else(|| GameError::NotFound(format!("Stone {}", id)))?;
+            format!(
+                "Stone {} at ({:.1}, {:.1}) | health {}/{} stage {:?} | yield left {} | last hit {:?} | respawn at {:?} | last regrow {:?}",
+                stone.id, stone.pos_x, stone.pos_y, stone.health, crate::stone::STONE_INITIAL_HEALTH, stone.stage,
+                stone.remaining_yield, stone.last_hit_time, stone.respawn_at, stone.last_regrow_at,
+            )
+        }
+        ExaminableEntity::Campfire => {
+            let id = parse_numeric_id(&entity_id)? as u32;
+            let campfire = ctx.db.campfire().id().find(id)
+                .ok_or_else(|| GameError::NotFound(format!("Campfire {}", id)))?;
+            format!(
+                "Campfire {} at ({:.1}, {:.1}) | placed by {:?} | burning {} (has fuel {}) | next fuel use {:?} | extinguished at {:?} | last toggled {:?} | slots: {}",
+                campfire.id, campfire.pos_x, campfire.pos_y, campfire.placed_by, campfire.is_burning,
+                crate::campfire::check_if_campfire_has_fuel(ctx, &campfire), campfire.next_fuel_consume_at,
+                campfire.extinguished_at, campfire.last_toggled_at, describe_slots(ctx, &campfire),
+            )
+        }
+        ExaminableEntity::WoodenStorageBox => {
+            let id = parse_numeric_id(&entity_id)? as u32;
+            let storage_box = ctx.db.wooden_storage_box().id().find(id)
+                .ok_or_else(|| GameError::NotFound(format!("Storage box {}", id)))?;
+            format!(
+                "Storage box {} {:?} at ({:.1}, {:.1}) | placed by {:?} | slots: {}",
+                storage_box.id, storage_box.label, storage_box.pos_x, storage_box.pos_y, storage_box.placed_by, describe_slots(ctx, &storage_box),
+            )
+        }
+        ExaminableEntity::Player => {
+            let identity = Identity::from_hex(entity_id.trim())
+                .map_err(|_| GameError::InvalidInput(format!("'{}' is not an identity hex string", entity_id)))?;
+            describe_player(ctx, identity)?
+        }
+    };
+
+    log::info!("[Examine] Admin {:?} examined {:?} {}: {}", ctx.sender, entity_type, entity_id, summary);
+    ctx.db.entity_examination().insert(EntityExamination {
+        id: 0, // Auto-incremented
+        examined_by: ctx.sender,
+        entity_type,
+        entity_id,
+        summary,
+        examined_at: ctx.timestamp,
+    });
+    Ok(())
+}
+
+fn parse_numeric_id(entity_id: &str) -> Result<u64, String> {
+    entity_id.trim().parse::<u64>()
+        .map_err(|_| GameError::InvalidInput(format!("'{}' is not a numeric entity id", entity_id)).into())
+}
+
+/// One entry per occupied slot. Flags ghost items: slots pointing at deleted instances
+/// or whose cached def id disagrees with the item actually stored.
+fn describe_slots<C: ItemContainer>(ctx: &ReducerContext, container: &C) -> String {
+    let inventory_items = ctx.db.inventory_item();
+    let item_defs = ctx.db.item_definition();
+    let mut entries: Vec<String> = Vec::new();
+    for slot_index in 0..container.num_slots() as u8 {
+        let Some(instance_id) = container.get_slot_instance_id(slot_index) else { continue; };
+        let slot_def_id = container.get_slot_def_id(slot_index);
+        let entry = match inventory_items.instance_id().find(instance_id) {
+            None => format!("[{}] GHOST: item {} missing (slot def {:?})", slot_index, instance_id, slot_def_id),
+            Some(item) => {
+                let name = item_defs.id().find(item.item_def_id).map_or_else(|| "?".to_string(), |def| def.name);
+                let mismatch = if slot_def_id != Some(item.item_def_id) { format!(" MISMATCH: slot def {:?}", slot_def_id) } else { String::new() };
+                let quality = item.quality.map_or_else(String::new, |q| format!(" [{}]", crate::item_quality::quality_name(Some(q))));
+                format!("[{}] {}{} x{} (item {}, owner {:?}){}", slot_index, name, quality, item.quantity, instance_id, item.player_identity, mismatch)
+            }
+        };
+        entries.push(entry);
+    }
+    if entries.is_empty() { "empty".to_string() } else { entries.join(", ") }
+}
+
+fn describe_player(ctx: &ReducerContext, identity: Identity) -> Result<String, String> {
+    let player = ctx.db.player().identity().find(identity)
+        .ok_or_else(|| GameError::NotFound("Player".to_string()))?;
+    let online = ctx.db.online_player().identity().find(identity).map(|p| p.is_online);
+    let equipment = ctx.db.active_equipment().player_identity().find(identity);
+
+    let mut hotbar_items = 0;
+    let mut inventory_items = 0;
+    let mut unslotted_items = 0; // Equipped, in a container, or orphaned
+    for item in ctx.db.inventory_item().player_identity().filter(&identity) {
+        match (item.hotbar_slot, item.inventory_slot) {
+            (Some(_), _) => hotbar_items += 1,
+            (None, Some(_)) => inventory_items += 1,
+            (None, None) => unslotted_items += 1,
+        }
+    }
+
+    Ok(format!(
+        "Player '{}' {:?} | online {:?} | at ({:.1}, {:.1}) facing {} | hp {:.1} stamina {:.1} thirst {:.1} hunger {:.1} warmth {:.1} | \
+         dead {} respawn at {:?} | sprinting {} | combat until {:?} | last update {:?} | selected hotbar {:?} | \
+         items: {} hotbar, {} inventory, {} unslotted | equipment {:?}",
+        player.username, identity, online, player.position_x, player.position_y, player.direction,
+        player.health, player.stamina, player.thirst, player.hunger, player.warmth,
+        player.is_dead, player.respawn_at, player.is_sprinting, player.combat_until, player.last_update,
+        player.selected_hotbar_slot, hotbar_items, inventory_items, unslotted_items, equipment,
+    ))
+}
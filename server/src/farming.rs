@@ -0,0 +1,398 @@
+use spacetimedb::{Identity, ReducerContext, SpacetimeType, Table, Timestamp};
+use spacetimedb::spacetimedb_lib::ScheduleAt;
+use std::time::Duration;
+use log;
+
+// Import table traits needed for ctx.db access
+use crate::player as PlayerTableTrait;
+use crate::items::{inventory_item as InventoryItemTableTrait, item_definition as ItemDefinitionTableTrait};
+use crate::stone::stone as StoneTableTrait;
+use crate::tree::tree as TreeTableTrait;
+use crate::water::water_source as WaterSourceTableTrait;
+use crate::world_state::world_state as WorldStateTableTrait;
+
+use crate::composter::FERTILIZER_NAME;
+use crate::errors::{GameError, ensure_finite};
+use crate::utils::get_distance_squared;
+use crate::interaction::{CROP_INTERACTION_DISTANCE_SQUARED, PLANTING_MAX_DISTANCE_SQUARED};
+use crate::world_state::TimeOfDay;
+use crate::{WORLD_WIDTH_PX, WORLD_HEIGHT_PX};
+
+// --- Farming Constants ---
+const MIN_CROP_SPACING_SQUARED: f32 = 32.0 * 32.0;     // Crops can be planted in tight rows
+const MIN_CROP_OBSTACLE_DISTANCE_SQUARED: f32 = 48.0 * 48.0; // Keep clear of rock and tree roots
+const CROP_GROWTH_TICK_SECS: u64 = 10;
+const CROP_GROWTH_TIME_SECS: f32 = 300.0;      // Base time from planting to mature
+const CROP_SPROUT_PROGRESS: f32 = 0.5;         // Seedling becomes Growing at half progress
+const FERTILIZED_GROWTH_MULTIPLIER: f32 = 2.0;
+const NIGHT_GROWTH_MULTIPLIER: f32 = 0.5;      // Crops grow slower in the dark
+const WET_GROWTH_MULTIPLIER: f32 = 1.5;       // Near water or recently watered
+const DRY_GROWTH_MULTIPLIER: f32 = 0.5;
+const CROP_WATERED_DURATION_SECS: i64 = 120;   // How long one watering keeps a crop wet
+const CROP_WITHER_AFTER_DRY_SECS: i64 = 600;   // Unwatered crops away from water die after this long
+const CROP_PRODUCE_YIELD: u32 = 3;
+const CROP_SEED_YIELD: u32 = 2;
+
+// (seed item, produce item) pairs. Harvesting a crop returns produce plus more of its seed.
+const SEED_CROPS: &[(&str, &str)] = &[
+    ("Mushroom Spores", "Mushroom"),
+];
+
+#[derive(Clone, Debug, PartialEq, Eq, SpacetimeType)]
+pub enum CropStage {
+    Seedling,
+    Growing,
+    Mature,
+    Withered, // Died of neglect; harvesting just clears it
+}
+
+// --- Crop Table ---
+#[spacetimedb::table(name = crop, public)]
+#[derive(Clone)]
+pub struct Crop {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub pos_x: f32,
+    pub pos_y: f32,
+    pub planted_by: Identity,
+    pub planted_at: Timestamp,
+    pub seed_def_id: u64,
+    pub produce_def_id: u64,
+    pub stage: CropStage,
+    pub growth_progress: f32, // 0.0 to 1.0; Mature at 1.0
+    pub fertilized: bool,
+    pub last_watered_at: Option<Timestamp>, // None = never watered
+    pub stage_changed_at: Option<Timestamp>, // When it last moved to a new stage (read by offline_summary.rs)
+}
+
+// --- Schedule Table ---
+#[spacetimedb::table(name = crop_growth_schedule, scheduled(grow_crops))]
+#[derive(Clone)]
+pub struct CropGrowthSchedule {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub scheduled_at: ScheduleAt,
+}
+
+// --- Reducers ---
+
+/// Plants one seed from the given stack at a world position.
+#[spacetimedb::reducer]
+pub fn plant_seed(ctx: &ReducerContext, item_instance_id: u64, world_x: f32, world_y: f32) -> Result<(), String> {
+    ensure_finite(&[("world_x", world_x), ("world_y", world_y)])?;
+    let sender_id = ctx.sender;
+    let inventory = ctx.db.inventory_item();
+    let item_defs = ctx.db.item_definition();
+
+    // 1. Find the seed stack and validate ownership
+    let mut seed_item = inventory.instance_id().find(item_instance_id)
+        .ok_or_else(|| GameError::NotFound(format!("Item instance {}", item_instance_id)))?;
+    if seed_item.player_identity != sender_id {
+        return Err(GameError::NotOwner.into());
+    }
+    if seed_item.inventory_slot.is_none() && seed_item.hotbar_slot.is_none() {
+        return Err(GameError::InvalidPlacement("Seeds must be in your inventory or hotbar".to_string()).into());
+    }
+
+    // 2. Resolve which crop this seed grows
+    let seed_def = item_defs.id().find(seed_item.item_def_id)
+        .ok_or_else(|| GameError::NotFound(format!("Item definition {}", seed_item.item_def_id)))?;
+    let produce_name = SEED_CROPS.iter()
+        .find(|(seed_name, _)| *seed_name == seed_def.name)
+        .map(|(_, produce_name)| *produce_name)
+        .ok_or_else(|| GameError::InvalidPlacement(format!("'{}' cannot be planted", seed_def.name)))?;
+    let produce_def = crate::items::get_item_def_by_name(ctx, produce_name)
+        .ok_or_else(|| GameError::NotFound(format!("Item definition '{}'", produce_name)))?;
+
+    // 3. Validate the ground
+    validate_planting_location(ctx, world_x, world_y)?;
+
+    // 4. Consume one seed
+    if seed_item.quantity > 1 {
+        seed_item.quantity -= 1;
+        inventory.instance_id().update(seed_item);
+    } else {
+        inventory.instance_id().delete(item_instance_id);
+    }
+
+    // 5. Create the crop
+    let crop = ctx.db.crop().insert(Crop {
+        id: 0, // Auto-incremented
+        pos_x: world_x,
+        pos_y: world_y,
+        planted_by: sender_id,
+        planted_at: ctx.timestamp,
+        seed_def_id: seed_def.id,
+        produce_def_id: produce_def.id,
+        stage: CropStage::Seedling,
+        growth_progress: 0.0,
+        fertilized: false,
+        last_watered_at: None,
+        stage_changed_at: None,
+    });
+    log::info!("[Farming] Player {:?} planted {} (crop {}) at ({:.1}, {:.1}).", sender_id, seed_def.name, crop.id, world_x, world_y);
+    Ok(())
+}
+
+/// Uses one Fertilizer on a crop, doubling its growth speed until harvest.
+#[spacetimedb::reducer]
+pub fn fertilize_crop(ctx: &ReducerContext, crop_id: u64, item_instance_id: u64) -> Result<(), String> {
+    let sender_id = ctx.sender;
+    let inventory = ctx.db.inventory_item();
+    let mut crop = validate_crop_interaction(ctx, crop_id)?;
+
+    if crop.stage == CropStage::Withered {
+        return Err("This crop has withered.".to_string());
+    }
+    if crop.fertilized {
+        return Err("This crop is already fertilized.".to_string());
+    }
+    if crop.stage == CropStage::Mature {
+        return Err("This crop is already fully grown.".to_string());
+    }
+
+    let mut fertilizer = inventory.instance_id().find(item_instance_id)
+        .ok_or_else(|| GameError::NotFound(format!("Item instance {}", item_instance_id)))?;
+    if fertilizer.player_identity != sender_id {
+        return Err(GameError::NotOwner.into());
+    }
+    let is_fertilizer = ctx.db.item_definition().id().find(fertilizer.item_def_id)
+        .is_some_and(|def| def.name == FERTILIZER_NAME);
+    if !is_fertilizer {
+        return Err(format!("Only {} can be used on crops.", FERTILIZER_NAME));
+    }
+
+    if fertilizer.quantity > 1 {
+        fertilizer.quantity -= 1;
+        inventory.instance_id().update(fertilizer);
+    } else {
+        inventory.instance_id().delete(item_instance_id);
+    }
+
+    crop.fertilized = true;
+    ctx.db.crop().id().update(crop);
+    log::info!("[Farming] Player {:?} fertilized crop {}.", sender_id, crop_id);
+    Ok(())
+}
+
+/// Waters a crop using one charge from a carried water container (e.g. a Water Bottle).
+#[spacetimedb::reducer]
+pub fn water_crop(ctx: &ReducerContext, crop_id: u64, item_instance_id: u64) -> Result<(), String> {
+    let sender_id = ctx.sender;
+    let inventory = ctx.db.inventory_item();
+    let mut crop = validate_crop_interaction(ctx, crop_id)?;
+
+    if crop.stage == CropStage::Withered {
+        return Err("This crop has withered; watering won't save it.".to_string());
+    }
+    if crop.stage == CropStage::Mature {
+        return Err("This crop is already fully grown.".to_string());
+    }
+
+    let mut container = inventory.instance_id().find(item_instance_id)
+        .ok_or_else(|| GameError::NotFound(format!("Item instance {}", item_instance_id)))?;
+    let item_def = ctx.db.item_definition().id().find(container.item_def_id)
+        .ok_or_else(|| GameError::NotFound(format!("Item definition {}", container.item_def_id)))?;
+    crate::water::carried_water_capacity(sender_id, &container, &item_def)?;
+    let charges = container.charges.unwrap_or(0);
+    if charges == 0 {
+        return Err(format!("{} is empty.", item_def.name));
+    }
+
+    container.charges = Some(charges - 1);
+    inventory.instance_id().update(container);
+    crop.last_watered_at = Some(ctx.timestamp);
+    ctx.db.crop().id().update(crop);
+    log::info!("[Farming] Player {:?} watered crop {} ({} sips left).", sender_id, crop_id, charges - 1);
+    Ok(())
+}
+
+/// Harvests a mature crop, granting produce and seeds, and removes it.
+/// Withered crops are simply cleared.
+#[spacetimedb::reducer]
+pub fn harvest_crop(ctx: &ReducerContext, crop_id: u64) -> Result<(), String> {
+    let sender_id = ctx.sender;
+    let crop = validate_crop_interaction(ctx, crop_id)?;
+
+    if crop.stage == CropStage::Withered {
+        ctx.db.crop().id().delete(crop_id);
+        log::info!("[Farming] Player {:?} cleared withered crop {}.", sender_id, crop_id);
+        return Ok(());
+    }
+
+    if crop.stage != CropStage::Mature {
+        return Err(format!("Crop is not ready to harvest yet ({:.0}% grown).", crop.growth_progress * 100.0));
+    }
+
+    // Give produce first; if the inventory is full the crop stays in the ground
+    crate::items::add_item_to_player_inventory(ctx, sender_id, crop.produce_def_id, CROP_PRODUCE_YIELD)?;
+    // Seeds spill onto the ground rather than being lost
+    if let Err(e) = crate::items::grant_or_drop(ctx, sender_id, crop.seed_def_id, CROP_SEED_YIELD) {
+        log::warn!("[Farming] Player {:?} got produce from crop {} but seeds could not be granted: {}", sender_id, crop_id, e);
+    }
+
+    ctx.db.crop().id().delete(crop_id);
+    log::info!("[Farming] Player {:?} harvested crop {}.", sender_id, crop_id);
+    Ok(())
+}
+
+// --- Scheduled Growth ---
+
+/// Advances every growing crop. Growth is slower at night, faster when fertilized,
+/// and depends on water: wet crops grow faster, dry ones slower, and neglected ones wither.
+#[spacetimedb::reducer]
+pub fn grow_crops(ctx: &ReducerContext, _schedule: CropGrowthSchedule) -> Result<(), String> {
+    let time_multiplier = match ctx.db.world_state().iter().next().map(|state| state.time_of_day) {
+        Some(TimeOfDay::Night) | Some(TimeOfDay::Midnight) => NIGHT_GROWTH_MULTIPLIER,
+        _ => 1.0,
+    };
+    let base_step = CROP_GROWTH_TICK_SECS as f32 / CROP_GROWTH_TIME_SECS;
+
+    for mut crop in ctx.db.crop().iter() {
+        if crop.stage == CropStage::Mature || crop.stage == CropStage::Withered {
+            continue;
+        }
+
+        // Water: standing water nearby counts as always watered
+        let near_water = crate::water::is_near_water(ctx, crop.pos_x, crop.pos_y);
+        let last_wet_at = crop.last_watered_at.unwrap_or(crop.planted_at);
+        let dry_secs = (ctx.timestamp.to_micros_since_unix_epoch() - last_wet_at.to_micros_since_unix_epoch()) / 1_000_000;
+        let water_multiplier = match crop_water_multiplier(near_water, crop.last_watered_at.is_some(), dry_secs) {
+            Some(multiplier) => multiplier,
+            None => {
+                crop.stage = CropStage::Withered;
+                crop.stage_changed_at = Some(ctx.timestamp);
+                log::info!("[Farming] Crop {} withered after {}s without water.", crop.id, dry_secs);
+                ctx.db.crop().id().update(crop);
+                continue;
+            }
+        };
+
+        let fertilizer_multiplier = if crop.fertilized { FERTILIZED_GROWTH_MULTIPLIER } else { 1.0 };
+        crop.growth_progress = (crop.growth_progress + base_step * time_multiplier * fertilizer_multiplier * water_multiplier).min(1.0);
+        let new_stage = crop_stage_for_progress(crop.growth_progress);
+        if new_stage != crop.stage {
+            crop.stage = new_stage;
+            crop.stage_changed_at = Some(ctx.timestamp);
+        }
+        if crop.stage == CropStage::Mature {
+            log::debug!("[Farming] Crop {} is ready to harvest.", crop.id);
+        }
+        ctx.db.crop().id().update(crop);
+    }
+    Ok(())
+}
+
+// --- Helpers ---
+
+/// Growth multiplier from a crop's water supply, or None if it has been dry long enough to wither.
+/// `dry_secs` counts from the last watering, or from planting if it was never watered.
+fn crop_water_multiplier(near_water: bool, ever_watered: bool, dry_secs: i64) -> Option<f32> {
+    if near_water {
+        return Some(WET_GROWTH_MULTIPLIER);
+    }
+    if dry_secs >= CROP_WITHER_AFTER_DRY_SECS {
+        return None;
+    }
+    if ever_watered && dry_secs < CROP_WATERED_DURATION_SECS {
+        Some(WET_GROWTH_MULTIPLIER)
+    } else {
+        Some(DRY_GROWTH_MULTIPLIER)
+    }
+}
+
+fn crop_stage_for_progress(progress: f32) -> CropStage {
+    if progress >= 1.0 {
+        CropStage::Mature
+    } else if progress >= CROP_SPROUT_PROGRESS {
+        CropStage::Growing
+    } else {
+        CropStage::Seedling
+    }
+}
+
+/// Checks range, world bounds, and that the ground is soil (not water, rock, or another crop).
+fn validate_planting_location(ctx: &ReducerContext, world_x: f32, world_y: f32) -> Result<(), String> {
+    if world_x < 0.0 || world_y < 0.0 || world_x > WORLD_WIDTH_PX || world_y > WORLD_HEIGHT_PX {
+        return Err(GameError::InvalidPlacement("Outside of the world".to_string()).into());
+    }
+
+    let player = ctx.db.player().identity().find(ctx.sender)
+        .ok_or_else(|| GameError::NotFound("Player".to_string()))?;
+    if player.is_dead {
+        return Err("Cannot plant while dead.".to_string());
+    }
+    if get_distance_squared(player.position_x, player.position_y, world_x, world_y) > PLANTING_MAX_DISTANCE_SQUARED {
+        return Err(GameError::TooFarAway.into());
+    }
+
+    let in_water = ctx.db.water_source().iter()
+        .any(|w| get_distance_squared(w.pos_x, w.pos_y, world_x, world_y) < w.radius * w.radius);
+    if in_water {
+        return Err(GameError::InvalidPlacement("Cannot plant in water".to_string()).into());
+    }
+    let on_rock = ctx.db.stone().iter()
+        .any(|s| s.health > 0 && get_distance_squared(s.pos_x, s.pos_y, world_x, world_y) < MIN_CROP_OBSTACLE_DISTANCE_SQUARED);
+    let under_tree = ctx.db.tree().iter()
+        .any(|t| t.health > 0 && get_distance_squared(t.pos_x, t.pos_y, world_x, world_y) < MIN_CROP_OBSTACLE_DISTANCE_SQUARED);
+    if on_rock || under_tree {
+        return Err(GameError::InvalidPlacement("The ground here is too rocky".to_string()).into());
+    }
+    let too_close_to_crop = ctx.db.crop().iter()
+        .any(|c| get_distance_squared(c.pos_x, c.pos_y, world_x, world_y) < MIN_CROP_SPACING_SQUARED);
+    if too_close_to_crop {
+        return Err(GameError::InvalidPlacement("Too close to another crop".to_string()).into());
+    }
+    Ok(())
+}
+
+/// Validates that the player exists and is close enough to the crop.
+fn validate_crop_interaction(ctx: &ReducerContext, crop_id: u64) -> Result<Crop, String> {
+    let player = ctx.db.player().identity().find(ctx.sender)
+        .ok_or_else(|| GameError::NotFound("Player".to_string()))?;
+    if player.is_dead {
+        return Err("Cannot tend crops while dead.".to_string());
+    }
+    let crop = ctx.db.crop().id().find(crop_id)
+        .ok_or_else(|| GameError::NotFound(format!("Crop {}", crop_id)))?;
+    if get_distance_squared(player.position_x, player.position_y, crop.pos_x, crop.pos_y) > CROP_INTERACTION_DISTANCE_SQUARED {
+        return Err(GameError::TooFarAway.into());
+    }
+    Ok(crop)
+}
+
+// --- Init Helper (Called from lib.rs) ---
+pub(crate) fn init_crop_growth_schedule(ctx: &ReducerContext) -> Result<(), String> {
+    let schedule_table = ctx.db.crop_growth_schedule();
+    if schedule_table.iter().count() == 0 {
+        log::info!("Starting crop growth schedule (every {}s).", CROP_GROWTH_TICK_SECS);
+        let interval = Duration::from_secs(CROP_GROWTH_TICK_SECS);
+        schedule_table.insert(CropGrowthSchedule {
+            id: 0, // Auto-incremented
+            scheduled_at: ScheduleAt::Interval(interval.into()),
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn watering_wears_off_and_neglected_crops_wither() {
+        assert_eq!(crop_water_multiplier(false, true, 0), Some(WET_GROWTH_MULTIPLIER));
+        assert_eq!(crop_water_multiplier(false, true, CROP_WATERED_DURATION_SECS), Some(DRY_GROWTH_MULTIPLIER));
+        // Never watered: dry from the moment it was planted
+        assert_eq!(crop_water_multiplier(false, false, 0), Some(DRY_GROWTH_MULTIPLIER));
+        assert_eq!(crop_water_multiplier(false, true, CROP_WITHER_AFTER_DRY_SECS), None);
+        assert_eq!(crop_water_multiplier(false, false, CROP_WITHER_AFTER_DRY_SECS - 1), Some(DRY_GROWTH_MULTIPLIER));
+    }
+
+    #[test]
+    fn crops_next_to_water_never_wither() {
+        assert_eq!(crop_water_multiplier(true, false, CROP_WITHER_AFTER_DRY_SECS * 10), Some(WET_GROWTH_MULTIPLIER));
+    }
+}
@@ -0,0 +1,84 @@
+use spacetimedb::{Identity, ReducerContext, Table};
+use log;
+
+use crate::player as PlayerTableTrait;
+use crate::utils::GameRng;
+use crate::PLAYER_RADIUS;
+
+// --- Falling Tree Constants ---
+const FALLING_TREE_LENGTH: f32 = 120.0; // How far along the fall line the trunk reaches from its base
+const FALLING_TREE_HALF_WIDTH: f32 = PLAYER_RADIUS * 1.5; // Sideways reach of the trunk from the fall line
+const FALLING_TREE_DAMAGE: f32 = 5.0; // Flavor damage, not a weapon
+const FALLING_TREE_MIN_HEALTH: f32 = 1.0; // A falling tree never kills; it can only bring someone down to this
+
+// --- Falling Trees ---
+
+/// Rolls the configured chance for a felled tree to fall along `fall_dir` (the chopper's facing)
+/// and hurts any other living player lying across the fall line. Called where a tree reaches 0 health.
+pub(crate) fn try_fell_tree(ctx: &ReducerContext, chopper_id: Identity, tree_id: u64, trunk_x: f32, trunk_y: f32, fall_dir: (f32, f32)) {
+    let chance = crate::world_config::get_tree_fall_chance(ctx);
+    if chance <= 0.0 || !GameRng::from_ctx(ctx).chance(chance) {
+        return;
+    }
+
+    let players = ctx.db.player();
+    let victims: Vec<_> = players.iter()
+        .filter(|p| p.identity != chopper_id && !p.is_dead)
+        .filter(|p| is_in_fall_path(trunk_x, trunk_y, fall_dir, p.position_x, p.position_y))
+        .collect();
+    log::info!("[TreeFall] Tree {} felled by {:?} fell toward ({:.0}, {:.0}), hitting {} players.",
+             tree_id, chopper_id, fall_dir.0, fall_dir.1, victims.len());
+
+    for mut victim in victims {
+        let old_health = victim.health;
+        let damage = FALLING_TREE_DAMAGE * crate::armor_sets::damage_taken_multiplier(ctx, victim.identity);
+        victim.health = health_after_falling_tree(victim.health, damage);
+        if victim.health < old_health {
+            victim.last_hit_time = Some(ctx.timestamp);
+            log::info!("[TreeFall] Tree {} hit Player {:?}. Health: {:.1} -> {:.1}", tree_id, victim.identity, old_health, victim.health);
+            players.identity().update(victim);
+        }
+    }
+}
+
+/// True if a point lies on the trunk's footprint once it falls: ahead of the base along
+/// `fall_dir` (a unit vector) within the trunk length, and within half a trunk width of the line.
+fn is_in_fall_path(trunk_x: f32, trunk_y: f32, fall_dir: (f32, f32), point_x: f32, point_y: f32) -> bool {
+    let dx = point_x - trunk_x;
+    let dy = point_y - trunk_y;
+    let along = dx * fall_dir.0 + dy * fall_dir.1;
+    let across = (dx * fall_dir.1 - dy * fall_dir.0).abs();
+    along > 0.0 && along <= FALLING_TREE_LENGTH && across <= FALLING_TREE_HALF_WIDTH
+}
+
+/// Non-lethal by design: never takes anyone below the floor, and never hurts someone already at it.
+fn health_after_falling_tree(health: f32, damage: f32) -> f32 {
+    (health - damage).max(FALLING_TREE_MIN_HEALTH.min(health))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn falling_tree_hits_players_beside_the_fall_line() {
+        let fall_east = (1.0, 0.0);
+        // Standing next to the trunk, in line with it, and just off the line within half a trunk width
+        assert!(is_in_fall_path(0.0, 0.0, fall_east, PLAYER_RADIUS * 2.0, 0.0));
+        assert!(is_in_fall_path(0.0, 0.0, fall_east, FALLING_TREE_LENGTH / 2.0, FALLING_TREE_HALF_WIDTH - 1.0));
+        assert!(is_in_fall_path(0.0, 0.0, fall_east, FALLING_TREE_LENGTH, 0.0));
+        // Behind the chopper, past the crown, or too far to the side
+        assert!(!is_in_fall_path(0.0, 0.0, fall_east, -PLAYER_RADIUS, 0.0));
+        assert!(!is_in_fall_path(0.0, 0.0, fall_east, FALLING_TREE_LENGTH + 1.0, 0.0));
+        assert!(!is_in_fall_path(0.0, 0.0, fall_east, FALLING_TREE_LENGTH / 2.0, FALLING_TREE_HALF_WIDTH + 1.0));
+        // The same spot is missed when the tree falls the other way
+        assert!(!is_in_fall_path(0.0, 0.0, (-1.0, 0.0), PLAYER_RADIUS * 2.0, 0.0));
+    }
+
+    #[test]
+    fn falling_tree_damage_is_never_lethal() {
+        assert_eq!(health_after_falling_tree(100.0, FALLING_TREE_DAMAGE), 100.0 - FALLING_TREE_DAMAGE);
+        assert_eq!(health_after_falling_tree(3.0, FALLING_TREE_DAMAGE), FALLING_TREE_MIN_HEALTH);
+        assert_eq!(health_after_falling_tree(0.5, FALLING_TREE_DAMAGE), 0.5);
+    }
+}
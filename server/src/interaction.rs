@@ -0,0 +1,136 @@
+use spacetimedb::{Identity, ReducerContext, SpacetimeType, Table, Timestamp};
+use log;
+
+// Import table traits needed for ctx.db access
+use crate::player as PlayerTableTrait;
+use crate::campfire::campfire as CampfireTableTrait;
+use crate::wooden_storage_box::wooden_storage_box as WoodenStorageBoxTableTrait;
+use crate::mushroom::mushroom as MushroomTableTrait;
+use crate::dropped_item::dropped_item as DroppedItemTableTrait;
+use crate::resource_remnant::resource_remnant as ResourceRemnantTableTrait;
+
+use crate::utils::get_distance_squared;
+use crate::errors::GameError;
+
+// --- Interaction Distances ---
+// How close a player must be to use each kind of entity. Both the generic `interact` reducer
+// and the entity-specific reducers check against these, so they never disagree about "too far".
+// Everything a player opens or picks up shares one reach; only the ranges that place things
+// into the world are longer, since the target spot may be a bit ahead of the player.
+pub(crate) const DEFAULT_INTERACTION_DISTANCE: f32 = 64.0;
+pub(crate) const DEFAULT_INTERACTION_DISTANCE_SQUARED: f32 = DEFAULT_INTERACTION_DISTANCE * DEFAULT_INTERACTION_DISTANCE;
+pub(crate) const CAMPFIRE_INTERACTION_DISTANCE_SQUARED: f32 = DEFAULT_INTERACTION_DISTANCE_SQUARED;
+pub(crate) const BOX_INTERACTION_DISTANCE_SQUARED: f32 = DEFAULT_INTERACTION_DISTANCE_SQUARED;
+pub(crate) const COMPOSTER_INTERACTION_DISTANCE_SQUARED: f32 = DEFAULT_INTERACTION_DISTANCE_SQUARED;
+pub(crate) const MUSHROOM_INTERACTION_DISTANCE_SQUARED: f32 = DEFAULT_INTERACTION_DISTANCE_SQUARED;
+pub(crate) const CROP_INTERACTION_DISTANCE_SQUARED: f32 = DEFAULT_INTERACTION_DISTANCE_SQUARED;
+pub(crate) const DROPPED_ITEM_PICKUP_DISTANCE_SQUARED: f32 = DEFAULT_INTERACTION_DISTANCE_SQUARED;
+pub(crate) const REMNANT_INTERACTION_DISTANCE_SQUARED: f32 = DEFAULT_INTERACTION_DISTANCE_SQUARED;
+pub(crate) const PLACEMENT_MAX_DISTANCE_SQUARED: f32 = 96.0 * 96.0; // Placing campfires, boxes, composters
+pub(crate) const PLANTING_MAX_DISTANCE_SQUARED: f32 = 96.0 * 96.0;
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize, SpacetimeType)]
+pub enum InteractionTargetType {
+    Campfire,
+    WoodenStorageBox,
+    Mushroom,
+    DroppedItem,
+    ResourceRemnant,
+}
+
+// Result of the player's most recent generic interaction, so the client knows which UI to open
+#[spacetimedb::table(name = player_interaction, public)]
+#[derive(Clone, Debug)]
+pub struct PlayerInteraction {
+    #[primary_key]
+    pub player_identity: Identity,
+    pub target_type: InteractionTargetType,
+    pub target_id: u64,
+    pub interacted_at: Timestamp,
+}
+
+/// Squared reach for a generic interaction target.
+pub(crate) fn interaction_distance_squared(target_type: &InteractionTargetType) -> f32 {
+    match target_type {
+        InteractionTargetType::Campfire => CAMPFIRE_INTERACTION_DISTANCE_SQUARED,
+        InteractionTargetType::WoodenStorageBox => BOX_INTERACTION_DISTANCE_SQUARED,
+        InteractionTargetType::Mushroom => MUSHROOM_INTERACTION_DISTANCE_SQUARED,
+        InteractionTargetType::DroppedItem => DROPPED_ITEM_PICKUP_DISTANCE_SQUARED,
+        InteractionTargetType::ResourceRemnant => REMNANT_INTERACTION_DISTANCE_SQUARED,
+    }
+}
+
+/// Generic interact ('E') reducer. Finds the interactable entity closest to (x, y)
+/// that is within its own interaction range of the player, dispatches to the
+/// entity-specific reducer, and records what was interacted with.
+#[spacetimedb::reducer]
+pub fn interact(ctx: &ReducerContext, x: f32, y: f32) -> Result<(), String> {
+    crate::errors::ensure_finite(&[("x", x), ("y", y)])?;
+    let sender_id = ctx.sender;
+
+    // 1. Find Player
+    let player = ctx.db.player().identity().find(sender_id)
+        .ok_or_else(|| GameError::NotFound("Player".to_string()))?;
+    if player.is_dead {
+        return Err("Cannot interact while dead".to_string());
+    }
+
+    // 2. Collect candidates in range of the player: (type, id, distance_sq to the aim point)
+    let mut best_target: Option<(InteractionTargetType, u64, f32)> = None;
+    let mut consider = |target_type: InteractionTargetType, id: u64, pos_x: f32, pos_y: f32| {
+        if get_distance_squared(player.position_x, player.position_y, pos_x, pos_y) > interaction_distance_squared(&target_type) {
+            return;
+        }
+        let aim_dist_sq = get_distance_squared(x, y, pos_x, pos_y);
+        if best_target.as_ref().is_none_or(|(_, _, best)| aim_dist_sq < *best) {
+            best_target = Some((target_type, id, aim_dist_sq));
+        }
+    };
+
+    for campfire in ctx.db.campfire().iter() {
+        consider(InteractionTargetType::Campfire, campfire.id as u64, campfire.pos_x, campfire.pos_y);
+    }
+    for storage_box in ctx.db.wooden_storage_box().iter() {
+        consider(InteractionTargetType::WoodenStorageBox, storage_box.id as u64, storage_box.pos_x, storage_box.pos_y);
+    }
+    for mushroom in ctx.db.mushroom().iter() {
+        if mushroom.respawn_at.is_some() { continue; } // Already harvested
+        consider(InteractionTargetType::Mushroom, mushroom.id, mushroom.pos_x, mushroom.pos_y);
+    }
+    for dropped in ctx.db.dropped_item().iter() {
+        consider(InteractionTargetType::DroppedItem, dropped.id, dropped.pos_x, dropped.pos_y);
+    }
+    for remnant in ctx.db.resource_remnant().iter() {
+        if remnant.expires_at <= ctx.timestamp { continue; } // Decayed, awaiting despawn
+        consider(InteractionTargetType::ResourceRemnant, remnant.id, remnant.pos_x, remnant.pos_y);
+    }
+
+    let (target_type, target_id, _) = best_target
+        .ok_or_else(|| "Nothing to interact with nearby".to_string())?;
+
+    // 3. Dispatch to the entity-specific reducer (which re-validates range)
+    match target_type {
+        InteractionTargetType::Campfire => crate::campfire::interact_with_campfire(ctx, target_id as u32)?,
+        InteractionTargetType::WoodenStorageBox => crate::wooden_storage_box::interact_with_storage_box(ctx, target_id as u32)?,
+        InteractionTargetType::Mushroom => crate::mushroom::interact_with_mushroom(ctx, target_id)?,
+        InteractionTargetType::DroppedItem => crate::dropped_item::pickup_dropped_item(ctx, target_id)?,
+        InteractionTargetType::ResourceRemnant => crate::resource_remnant::harvest_remnant(ctx, target_id)?,
+    }
+
+    // 4. Record the result for the client
+    let record = PlayerInteraction {
+        player_identity: sender_id,
+        target_type: target_type.clone(),
+        target_id,
+        interacted_at: ctx.timestamp,
+    };
+    let interactions = ctx.db.player_interaction();
+    if interactions.player_identity().find(sender_id).is_some() {
+        interactions.player_identity().update(record);
+    } else {
+        interactions.insert(record);
+    }
+
+    log::info!("[Interact] Player {:?} interacted with {:?} {}.", sender_id, target_type, target_id);
+    Ok(())
+}
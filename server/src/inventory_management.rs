@@ -2,10 +2,11 @@ use spacetimedb::{ReducerContext, Identity, Table};
 use log;
 
 // Import necessary types and Table Traits
-use crate::items::{InventoryItem, ItemDefinition, calculate_merge_result, add_item_to_player_inventory};
+use crate::items::{InventoryItem, ItemDefinition, ItemInstanceState, calculate_merge_result};
 use crate::items::{inventory_item as InventoryItemTableTrait, item_definition as ItemDefinitionTableTrait};
 use crate::wooden_storage_box::{WoodenStorageBox, NUM_BOX_SLOTS}; // Import Box struct and constant
 use crate::wooden_storage_box::wooden_storage_box as WoodenStorageBoxTableTrait;
+use crate::errors::GameError;
 
 // --- Generic Item Container Trait --- 
 
@@ -26,6 +27,18 @@ pub(crate) trait ItemContainer {
     /// Implementations should handle invalid indices gracefully (e.g., do nothing).
     fn set_slot(&mut self, slot_index: u8, instance_id: Option<u64>, def_id: Option<u64>);
 
+    /// Whether an item of this definition may be put into the given slot.
+    /// Containers that only take certain items (e.g. a composter) override this.
+    fn accepts(&self, _slot_index: u8, _item_def: &ItemDefinition) -> bool {
+        true
+    }
+
+    /// Most items (summed over all slots) the container may hold, or None if only its slots
+    /// and stack sizes limit it. Small containers (e.g. a pouch) override this.
+    fn max_capacity(&self) -> Option<u32> {
+        None
+    }
+
     // We could add more methods later if needed, e.g., find_first_empty_slot
 }
 
@@ -127,9 +140,30 @@ pub(crate) fn is_container_empty<C: ItemContainer>(container: &C) -> bool {
     true // Went through all slots, all were empty
 }
 
+/// Total quantity of items across all of a container's slots.
+pub(crate) fn container_item_count<C: ItemContainer>(ctx: &ReducerContext, container: &C) -> u32 {
+    let inventory_table = ctx.db.inventory_item();
+    (0..container.num_slots() as u8)
+        .filter_map(|slot_index| container.get_slot_instance_id(slot_index))
+        .filter_map(|instance_id| inventory_table.instance_id().find(instance_id))
+        .map(|item| item.quantity)
+        .sum()
+}
+
+/// Rejects a move that would push the container past its `max_capacity`.
+/// `adding` is the quantity coming in, `removing` what leaves in the same move (e.g. a swap).
+fn ensure_container_capacity<C: ItemContainer>(ctx: &ReducerContext, container: &C, adding: u32, removing: u32) -> Result<(), String> {
+    let Some(capacity) = container.max_capacity() else { return Ok(()); };
+    let resulting = container_item_count(ctx, container).saturating_sub(removing).saturating_add(adding);
+    if resulting > capacity {
+        return Err(GameError::InvalidSlot(format!("container can hold at most {} items ({} after this move)", capacity, resulting)).into());
+    }
+    Ok(())
+}
+
 // --- NEW Helper: Clear item from any container --- 
 
-/// Checks known container types (Boxes, Campfires) and removes the specified item instance if found.
+/// Checks known container types (Boxes, Belts, Campfires) and removes the specified item instance if found.
 pub(crate) fn clear_item_from_any_container(ctx: &ReducerContext, item_instance_id: u64) {
     // Check Wooden Storage Boxes
     let mut boxes = ctx.db.wooden_storage_box();
@@ -156,6 +190,16 @@ pub(crate) fn clear_item_from_any_container(ctx: &ReducerContext, item_instance_
          boxes.id().update(updated_box);
     }
 
+    // If not found in a box, check utility belts
+    if !box_updated && crate::utility_belt::clear_item_from_belts(ctx, item_instance_id) {
+        return;
+    }
+
+    // ...then composters
+    if !box_updated && crate::composter::clear_item_from_composters(ctx, item_instance_id) {
+        return;
+    }
+
     // If not found in a box, check Campfires (using the now pub(crate) helper)
     if !box_updated {
         crate::items::clear_item_from_campfire_fuel_slots(ctx, item_instance_id);
@@ -198,6 +242,9 @@ pub(crate) fn handle_move_to_container_slot<C: ItemContainer>(
     if target_slot_index >= container.num_slots() as u8 {
         return Err(format!("Target slot index {} out of bounds.", target_slot_index));
     }
+    if !container.accepts(target_slot_index, &item_def_to_move) {
+        return Err(GameError::InvalidSlot(format!("'{}' cannot go in slot {}", item_def_to_move.name, target_slot_index)).into());
+    }
     let target_instance_id_opt = container.get_slot_instance_id(target_slot_index);
     
     // --- Merge/Swap/Place Logic --- 
@@ -206,9 +253,10 @@ pub(crate) fn handle_move_to_container_slot<C: ItemContainer>(
         let mut target_item = inventory_table.instance_id().find(target_instance_id)
                                 .ok_or_else(|| format!("Target item instance {} in container slot {} not found!", target_instance_id, target_slot_index))?;
 
-        match calculate_merge_result(&item_to_move, &target_item, &item_def_to_move) {
-            Ok((_, source_new_qty, target_new_qty, delete_source)) => {
+        match calculate_merge_result(ctx, &item_to_move, &target_item, &item_def_to_move) {
+            Ok((qty_transfer, source_new_qty, target_new_qty, delete_source)) => {
                 // Merge successful
+                ensure_container_capacity(ctx, container, qty_transfer, 0)?;
                 log::info!("[InvManager MergeToContainer] Merging item {} onto item {}.", item_instance_id, target_instance_id);
                 target_item.quantity = target_new_qty;
                 inventory_table.instance_id().update(target_item);
@@ -224,6 +272,7 @@ pub(crate) fn handle_move_to_container_slot<C: ItemContainer>(
             },
             Err(_) => {
                 // Merge Failed: Swap
+                ensure_container_capacity(ctx, container, item_to_move.quantity, target_item.quantity)?;
                 log::info!("[InvManager SwapToContainer] Cannot merge. Swapping slot {}.", target_slot_index);
                 let source_inv_slot = item_to_move.inventory_slot;
                 let source_hotbar_slot = item_to_move.hotbar_slot;
@@ -245,6 +294,7 @@ pub(crate) fn handle_move_to_container_slot<C: ItemContainer>(
         }
     } else {
         // Target Empty: Place
+        ensure_container_capacity(ctx, container, item_to_move.quantity, 0)?;
         log::info!("[InvManager PlaceInContainer] Moving item {} to empty slot {}", item_instance_id, target_slot_index);
         item_to_move.inventory_slot = None;
         item_to_move.hotbar_slot = None;
@@ -334,6 +384,17 @@ pub(crate) fn handle_move_within_container<C: ItemContainer>(
     let target_instance_id_opt = container.get_slot_instance_id(target_slot_index);
     let target_def_id_opt = container.get_slot_def_id(target_slot_index);
 
+    // Both directions of a potential swap must be allowed by the container
+    let source_def = item_def_table.id().find(source_def_id).ok_or("Item definition not found")?;
+    if !container.accepts(target_slot_index, &source_def) {
+        return Err(GameError::InvalidSlot(format!("'{}' cannot go in slot {}", source_def.name, target_slot_index)).into());
+    }
+    if let Some(target_def) = target_def_id_opt.and_then(|def_id| item_def_table.id().find(def_id)) {
+        if target_def.id != source_def_id && !container.accepts(source_slot_index, &target_def) {
+            return Err(GameError::InvalidSlot(format!("'{}' cannot go in slot {}", target_def.name, source_slot_index)).into());
+        }
+    }
+
     // --- Merge/Swap/Move Logic --- 
     if let Some(target_instance_id) = target_instance_id_opt {
         // Target occupied: Try Merge then Swap
@@ -341,7 +402,7 @@ pub(crate) fn handle_move_within_container<C: ItemContainer>(
         let mut target_item = inventory_table.instance_id().find(target_instance_id).ok_or("Target item not found")?;
         let item_def = item_def_table.id().find(source_def_id).ok_or("Item definition not found")?;
 
-        match calculate_merge_result(&source_item, &target_item, &item_def) {
+        match calculate_merge_result(ctx, &source_item, &target_item, &item_def) {
             Ok((_, source_new_qty, target_new_qty, delete_source)) => {
                 // Merge Possible
                 log::info!("[InvManager WithinContainer Merge] Merging slot {} onto slot {}", source_slot_index, target_slot_index);
@@ -395,6 +456,13 @@ pub(crate) fn handle_split_into_container<C: ItemContainer>(
     let inventory_table = ctx.db.inventory_item();
     let item_def_table = ctx.db.item_definition();
 
+    let source_def = item_def_table.id().find(source_item.item_def_id)
+                        .ok_or("Failed to find definition for source item")?;
+    if !container.accepts(target_slot_index, &source_def) {
+        return Err(GameError::InvalidSlot(format!("'{}' cannot go in slot {}", source_def.name, target_slot_index)).into());
+    }
+    ensure_container_capacity(ctx, container, quantity_to_split, 0)?;
+
     // 1. Perform split using helper from items.rs
     // This updates source_item quantity and creates a new item instance.
     let new_item_instance_id = crate::items::split_stack_helper(ctx, source_item, quantity_to_split)?;
@@ -412,7 +480,7 @@ pub(crate) fn handle_split_into_container<C: ItemContainer>(
         let mut target_item = inventory_table.instance_id().find(target_instance_id)
                             .ok_or_else(|| format!("Target item {} in container slot {} not found!", target_instance_id, target_slot_index))?;
 
-        match calculate_merge_result(&new_item, &target_item, &new_item_def) {
+        match calculate_merge_result(ctx, &new_item, &target_item, &new_item_def) {
             Ok((_, _source_new_qty, target_new_qty, delete_source)) => {
                 // Merge successful
                 log::info!("[InvManager SplitToContainer Merge] Merging new item {} onto target {}. Target new qty: {}", 
@@ -555,6 +623,9 @@ pub(crate) fn handle_split_within_container<C: ItemContainer>(
     }
     let item_def = item_defs.id().find(source_item.item_def_id).ok_or("Item definition not found")?;
     if !item_def.is_stackable { return Err("Source item is not stackable".to_string()); }
+    if !container.accepts(target_slot_index, &item_def) {
+        return Err(GameError::InvalidSlot(format!("'{}' cannot go in slot {}", item_def.name, target_slot_index)).into());
+    }
 
     // --- Perform Split --- 
     let new_item_instance_id = crate::items::split_stack_helper(ctx, &mut source_item, quantity_to_split)?;
@@ -572,7 +643,7 @@ pub(crate) fn handle_split_within_container<C: ItemContainer>(
         let mut target_item = inventory_table.instance_id().find(target_instance_id)
                             .ok_or_else(|| format!("Target item {} in container slot {} not found!", target_instance_id, target_slot_index))?;
 
-        match calculate_merge_result(&new_item, &target_item, &new_item_def) {
+        match calculate_merge_result(ctx, &new_item, &target_item, &new_item_def) {
             Ok((_, _source_new_qty, target_new_qty, delete_source)) => {
                 // Merge successful
                 log::info!("[InvManager SplitWithinContainer Merge] Merging new item {} onto target {}. Target new qty: {}", 
@@ -625,17 +696,76 @@ pub(crate) fn handle_quick_move_from_container<C: ItemContainer>(
     let source_def_id = container.get_slot_def_id(source_slot_index)
         .ok_or_else(|| format!("Missing definition ID in source slot {}", source_slot_index))?;
     
-    let mut item_to_move = inventory_table.instance_id().find(source_instance_id)
+    let item_to_move = inventory_table.instance_id().find(source_instance_id)
         .ok_or("Item instance in container slot not found in inventory table")?;
 
-    // Clear Slot in container struct using trait method
-    container.set_slot(source_slot_index, None, None);
-    
     log::info!("[InvManager QuickFromContainer] Moving item {} (Def {}) from container slot {} to player {:?} inventory", 
              source_instance_id, source_def_id, source_slot_index, sender_id);
     
-    // Call helper from items.rs to add to player inventory (handles stacking/finding slots)
-    add_item_to_player_inventory(ctx, sender_id, source_def_id, item_to_move.quantity)
+    // Only stack onto player stacks in the same state, so charges, durability, quality and spoilage move with the item
+    let leftover = crate::items::add_as_much_as_fits_with_state(ctx, sender_id, source_def_id, item_to_move.quantity, &ItemInstanceState::of(&item_to_move))?;
+    if leftover > 0 {
+        return Err(GameError::InventoryFull.into()); // Rolls back whatever did fit
+    }
+
+    // The quantity now lives in the player's stacks; drop the container's instance so it isn't orphaned
+    inventory_table.instance_id().delete(source_instance_id);
+    container.set_slot(source_slot_index, None, None);
+    Ok(())
+}
+
+/// Moves every stack in the container into the player's inventory, as far as it fits.
+/// A stack that only partly fits keeps the remainder in its slot. Returns
+/// (stacks fully moved, stacks found) so the caller can report a partial take-all.
+pub(crate) fn handle_take_all_from_container<C: ItemContainer>(
+    ctx: &ReducerContext,
+    container: &mut C,
+) -> Result<(u32, u32), String> {
+    let inventory_table = ctx.db.inventory_item();
+    let sender_id = ctx.sender;
+    let (mut moved, mut found) = (0, 0);
+
+    for slot_index in 0..container.num_slots() as u8 {
+        let Some(instance_id) = container.get_slot_instance_id(slot_index) else { continue; };
+        let Some(mut item) = inventory_table.instance_id().find(instance_id) else { continue; };
+        found += 1;
+
+        // Charges, durability, quality and spoilage move with the stack (see handle_quick_move_from_container)
+        let leftover = crate::items::add_as_much_as_fits_with_state(ctx, sender_id, item.item_def_id, item.quantity, &ItemInstanceState::of(&item))?;
+        match take_outcome(item.quantity, leftover) {
+            TakeOutcome::Moved => {
+                inventory_table.instance_id().delete(instance_id);
+                container.set_slot(slot_index, None, None);
+                moved += 1;
+            }
+            TakeOutcome::Partial { remaining } => {
+                item.quantity = remaining;
+                inventory_table.instance_id().update(item);
+            }
+            TakeOutcome::NoRoom => {}
+        }
+        // Keep going after a stack that didn't fit: later ones may still stack onto existing stacks
+    }
+    log::info!("[InvManager TakeAll] Player {:?} took {} of {} stacks from container.", sender_id, moved, found);
+    Ok((moved, found))
+}
+
+/// What taking one container stack of `quantity` did, given how much of it didn't fit.
+#[derive(Debug, PartialEq)]
+enum TakeOutcome {
+    Moved,                       // All of it: the slot is cleared
+    Partial { remaining: u32 },  // Some of it: the rest stays in the slot
+    NoRoom,                      // None of it
+}
+
+fn take_outcome(quantity: u32, leftover: u32) -> TakeOutcome {
+    if leftover == 0 {
+        TakeOutcome::Moved
+    } else if leftover < quantity {
+        TakeOutcome::Partial { remaining: leftover }
+    } else {
+        TakeOutcome::NoRoom
+    }
 }
 
 /// Handles quickly moving an item FROM the player inventory/hotbar INTO the first
@@ -662,17 +792,24 @@ pub(crate) fn handle_quick_move_to_container<C: ItemContainer>(
         log::debug!("[MoveToContainer] Item {} is potentially coming from an equipment slot.", item_instance_id);
     }
 
+    let accepted_anywhere = (0..container.num_slots() as u8).any(|slot_index| container.accepts(slot_index, &item_def_to_move));
+    if !accepted_anywhere {
+        return Err(GameError::InvalidSlot(format!("'{}' cannot be put in this container", item_def_to_move.name)).into());
+    }
+    ensure_container_capacity(ctx, container, item_to_move.quantity, 0)?;
+
     let mut operation_occured = false; 
 
     // 1. Attempt to merge with existing stacks
     if item_def_to_move.is_stackable {
         for slot_index in 0..container.num_slots() as u8 {
+            if !container.accepts(slot_index, &item_def_to_move) { continue; }
             if let Some(target_instance_id) = container.get_slot_instance_id(slot_index) {
                 if container.get_slot_def_id(slot_index) == Some(item_def_to_move.id) { // Check if same item type
                     let mut target_item = inventory_table.instance_id().find(target_instance_id)
                                             .ok_or_else(|| format!("Target item {} in slot {} missing!", target_instance_id, slot_index))?;
                     
-                    match calculate_merge_result(&item_to_move, &target_item, &item_def_to_move) {
+                    match calculate_merge_result(ctx, &item_to_move, &target_item, &item_def_to_move) {
                         Ok((qty_transfer, source_new_qty, target_new_qty, delete_source)) => {
                             if qty_transfer > 0 { // Only proceed if merge actually happened
                                 log::info!("[InvManager QuickToContainer Merge] Merging {} from item {} onto item {} in slot {}",
@@ -703,7 +840,7 @@ pub(crate) fn handle_quick_move_to_container<C: ItemContainer>(
     if item_to_move.quantity > 0 {
         let mut empty_slot_found: Option<u8> = None;
         for slot_index in 0..container.num_slots() as u8 {
-            if container.get_slot_instance_id(slot_index).is_none() {
+            if container.get_slot_instance_id(slot_index).is_none() && container.accepts(slot_index, &item_def_to_move) {
                 empty_slot_found = Some(slot_index);
                 break;
             }
@@ -742,4 +879,29 @@ pub(crate) fn handle_quick_move_to_container<C: ItemContainer>(
     }
 
     Ok(())
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::items::test_inventory_item;
+
+    #[test]
+    fn take_all_leaves_what_does_not_fit_in_the_container() {
+        // Three stacks of 20 and room for 30 in the inventory
+        let mut room = 30;
+        let outcomes: Vec<TakeOutcome> = [20, 20, 20].into_iter().map(|quantity: u32| {
+            let taken = quantity.min(room);
+            room -= taken;
+            take_outcome(quantity, quantity - taken)
+        }).collect();
+        assert_eq!(outcomes, vec![TakeOutcome::Moved, TakeOutcome::Partial { remaining: 10 }, TakeOutcome::NoRoom]);
+        assert_eq!(outcomes.iter().filter(|outcome| **outcome == TakeOutcome::Moved).count(), 1); // Reported as 1 of 3
+
+        // The state the stack is added with is the stack's own, not a fresh one
+        let worn_axe = InventoryItem { durability: Some(40), quality: Some(3), charges: Some(2), ..test_inventory_item(1, 9, 1) };
+        let state = ItemInstanceState::of(&worn_axe);
+        assert_eq!((state.durability, state.quality, state.charges), (Some(40), Some(3), Some(2)));
+        assert_ne!(state, ItemInstanceState::default());
+    }
+}
@@ -0,0 +1,94 @@
+use spacetimedb::ReducerContext;
+
+use crate::items::{ItemCategory, ItemDefinition};
+use crate::utils::GameRng;
+
+// --- Item Quality Tiers ---
+// Crafted gear rolls a tier stored in `InventoryItem::quality`. The tier scales weapon damage,
+// worn armor protection and, for cooked food, how much it restores. Items without a quality (gathered, looted, pre-existing) count as Standard.
+pub(crate) const QUALITY_CRUDE: u8 = 0;
+pub(crate) const QUALITY_STANDARD: u8 = 1;
+pub(crate) const QUALITY_FINE: u8 = 2;
+pub(crate) const QUALITY_SUPERIOR: u8 = 3;
+pub(crate) const QUALITY_MASTERWORK: u8 = 4;
+
+// Stat multiplier per tier, indexed by the quality value
+const QUALITY_MULTIPLIERS: [f32; 5] = [0.85, 1.0, 1.1, 1.2, 1.35];
+const QUALITY_NAMES: [&str; 5] = ["Crude", "Standard", "Fine", "Superior", "Masterwork"];
+
+// Roll thresholds on a 0-99 score. Skill and station bonuses push the score up.
+const ROLL_THRESHOLDS: [(u32, u8); 4] = [
+    (98, QUALITY_MASTERWORK),
+    (90, QUALITY_SUPERIOR),
+    (70, QUALITY_FINE),
+    (15, QUALITY_STANDARD),
+];
+const SCORE_PER_SKILL_LEVEL: u32 = 2;
+const MAX_SKILL_SCORE_BONUS: u32 = 40;
+
+/// Stat multiplier for an item instance's quality (1.0 for None or unknown tiers).
+pub(crate) fn quality_multiplier(quality: Option<u8>) -> f32 {
+    quality.and_then(|q| QUALITY_MULTIPLIERS.get(q as usize).copied()).unwrap_or(1.0)
+}
+
+/// Display name for a quality tier.
+pub(crate) fn quality_name(quality: Option<u8>) -> &'static str {
+    QUALITY_NAMES.get(quality.unwrap_or(QUALITY_STANDARD) as usize).copied().unwrap_or("Standard")
+}
+
+/// True for gear whose stats quality scales: non-stackable equippable tools and armor.
+pub(crate) fn rolls_quality(item_def: &ItemDefinition) -> bool {
+    item_def.is_equippable && !item_def.is_stackable
+        && matches!(item_def.category, ItemCategory::Tool | ItemCategory::Armor)
+}
+
+/// Rolls the quality of a freshly crafted item. Call once when the item is created
+/// (a craft finishing, or gear granted through `ItemInstanceState::fresh`).
+/// `skill_level` is the crafter's relevant skill (crafting, cooking); `station_bonus` is a flat
+/// score bonus from the workstation used (0 for hand crafting).
+pub(crate) fn roll_crafted_quality(ctx: &ReducerContext, skill_level: u32, station_bonus: u32) -> u8 {
+    let mut rng = GameRng::from_ctx(ctx);
+    quality_for_score(rng.gen_range(0..100), skill_level, station_bonus)
+}
+
+fn quality_for_score(roll: u32, skill_level: u32, station_bonus: u32) -> u8 {
+    let skill_bonus = skill_level.saturating_mul(SCORE_PER_SKILL_LEVEL).min(MAX_SKILL_SCORE_BONUS);
+    let score = roll.saturating_add(skill_bonus).saturating_add(station_bonus);
+    ROLL_THRESHOLDS.iter()
+        .find(|(threshold, _)| score >= *threshold)
+        .map_or(QUALITY_CRUDE, |(_, quality)| *quality)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::items::test_item_def;
+
+    #[test]
+    fn higher_scores_give_better_tiers() {
+        assert_eq!(quality_for_score(0, 0, 0), QUALITY_CRUDE);
+        assert_eq!(quality_for_score(15, 0, 0), QUALITY_STANDARD);
+        assert_eq!(quality_for_score(70, 0, 0), QUALITY_FINE);
+        assert_eq!(quality_for_score(99, 0, 0), QUALITY_MASTERWORK);
+        // Skill adds 2 per level up to +40; the station bonus stacks on top
+        assert_eq!(quality_for_score(50, 10, 0), QUALITY_FINE);
+        assert_eq!(quality_for_score(50, 100, 0), QUALITY_SUPERIOR);
+        assert_eq!(quality_for_score(50, 100, 8), QUALITY_MASTERWORK);
+        assert_eq!(quality_for_score(u32::MAX, u32::MAX, u32::MAX), QUALITY_MASTERWORK);
+    }
+
+    #[test]
+    fn quality_scales_stats_and_defaults_to_standard() {
+        assert_eq!(quality_multiplier(None), 1.0);
+        assert_eq!(quality_multiplier(Some(QUALITY_STANDARD)), 1.0);
+        assert!(quality_multiplier(Some(QUALITY_CRUDE)) < 1.0);
+        let tiers = [QUALITY_CRUDE, QUALITY_STANDARD, QUALITY_FINE, QUALITY_SUPERIOR, QUALITY_MASTERWORK];
+        assert!(tiers.windows(2).all(|pair| quality_multiplier(Some(pair[0])) < quality_multiplier(Some(pair[1]))));
+        assert_eq!(quality_multiplier(Some(200)), 1.0);
+        assert_eq!(quality_name(Some(QUALITY_MASTERWORK)), "Masterwork");
+
+        let sword = ItemDefinition { is_equippable: true, damage: Some(10), ..test_item_def("Sword", ItemCategory::Tool) };
+        let wood = ItemDefinition { is_stackable: true, ..test_item_def("Wood", ItemCategory::Material) };
+        assert!(rolls_quality(&sword) && !rolls_quality(&wood));
+    }
+}
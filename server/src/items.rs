@@ -1,4 +1,4 @@
-use spacetimedb::{ReducerContext, SpacetimeType, Table};
+use spacetimedb::{ReducerContext, SpacetimeType, Table, Timestamp};
 use log;
 // Import ActiveEquipment table definition
 // use crate::active_equipment::{ActiveEquipment};
@@ -9,12 +9,13 @@ use crate::campfire::campfire as CampfireTableTrait;
 // Import Player table trait
 use crate::player as PlayerTableTrait;
 // Import DroppedItem helpers
-use crate::dropped_item::{calculate_drop_position, create_dropped_item_entity};
+use crate::dropped_item::{calculate_drop_position, create_owner_locked_dropped_item_entity, create_stack_sized_dropped_item_entities};
 // REMOVE unused concrete table type imports
 // use crate::items::{InventoryItemTable, ItemDefinitionTable};
 use crate::items_database; // ADD import for new module
 use std::cmp::min;
 use spacetimedb::Identity; // ADDED for add_item_to_player_inventory
+use crate::errors::GameError;
 
 // --- Item Enums and Structs ---
 
@@ -41,21 +42,111 @@ pub enum EquipmentSlot {
     // Maybe add Trinket1, Trinket2 etc. later
 }
 
+// Which world entity a Placeable item spawns when placed
+#[derive(Clone, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize, SpacetimeType)]
+pub enum PlaceableKind {
+    Campfire,
+    WoodenStorageBox,
+    Composter,
+}
+
 #[spacetimedb::table(name = item_definition, public)]
 #[derive(Clone)]
 pub struct ItemDefinition {
     #[primary_key]
     #[auto_inc]
     pub id: u64,
-    pub name: String,          // Unique name used as an identifier too?
+    #[unique]
+    pub name: String,          // Unique; look up with `get_item_def_by_name`
     pub description: String,   // Optional flavor text
     pub category: ItemCategory,
     pub icon_asset_name: String, // e.g., "stone_hatchet.png", used by client
     pub damage: Option<u32>,   // Damage dealt (e.g., by tools)
+    pub damage_falloff: bool,  // If true, damage to players drops off towards the edge of melee range
+    pub attack_range: Option<f32>, // Melee reach in px. None = DEFAULT_ATTACK_RANGE (see active_equipment.rs)
+    pub attack_arc_degrees: Option<f32>, // Width of the swing in front of the player. None = DEFAULT_ATTACK_ARC_DEGREES
     pub is_stackable: bool,    // Can multiple instances exist in one inventory slot?
     pub stack_size: u32,       // Max number per stack (if stackable)
+    pub water_capacity: Option<u32>, // Water containers: sips held when full (see water.rs). None = cannot hold water
     pub is_equippable: bool,   // Can this item be equipped (in hand OR on body)?
     pub equipment_slot: Option<EquipmentSlot>, // If equippable, does it go in a specific body slot?
+    pub armor_set: Option<String>, // Armor set this piece belongs to (see armor_sets.rs). None = not part of a set
+    pub placeable_kind: Option<PlaceableKind>, // If placeable, which entity it spawns
+    pub placement_footprint: Option<f32>, // Placed radius in px used for spacing checks. None = default spacing
+    pub shelf_life_secs: Option<u32>, // Seconds until a perishable item spoils. None = never spoils
+    pub warmth_output: Option<f32>, // Heat sources: warmth/s given right next to the placed entity. None = gives no warmth
+    pub fuel_burn_duration_secs: Option<u64>, // Fuel items: seconds one unit burns in a campfire. None = not fuel
+    pub cookable_into: Option<String>, // Raw food: name of the item it becomes when cooked in a burning campfire. None = not cookable
+    pub cook_time_secs: Option<u64>, // Raw food: seconds of cooking a stack needs before it turns into `cookable_into`
+}
+
+/// Bare item definition for unit tests; set the fields a test cares about with struct update syntax.
+#[cfg(test)]
+pub(crate) fn test_item_def(name: &str, category: ItemCategory) -> ItemDefinition {
+    ItemDefinition {
+        id: 0,
+        name: name.to_string(),
+        description: String::new(),
+        category,
+        icon_asset_name: String::new(),
+        damage: None,
+        damage_falloff: false,
+        attack_range: None,
+        attack_arc_degrees: None,
+        is_stackable: false,
+        stack_size: 1,
+        water_capacity: None,
+        is_equippable: false,
+        equipment_slot: None,
+        armor_set: None,
+        placeable_kind: None,
+        placement_footprint: None,
+        shelf_life_secs: None,
+        warmth_output: None,
+        fuel_burn_duration_secs: None,
+        cookable_into: None,
+        cook_time_secs: None,
+    }
+}
+
+/// Bare inventory item for unit tests, held by the default identity in no slot.
+#[cfg(test)]
+pub(crate) fn test_inventory_item(instance_id: u64, item_def_id: u64, quantity: u32) -> InventoryItem {
+    InventoryItem {
+        instance_id,
+        player_identity: Identity::default(),
+        item_def_id,
+        quantity,
+        hotbar_slot: None,
+        inventory_slot: None,
+        charges: None,
+        durability: None,
+        spoils_at: None,
+        quality: None,
+    }
+}
+
+/// Inserts a new item instance, always letting auto_inc pick `instance_id`.
+/// A non-zero id here would be a caller bug (usually a cloned existing row) and could collide
+/// with a live instance, so it trips a debug assertion and is reset to 0 in release builds.
+pub(crate) fn insert_new_inventory_item(ctx: &ReducerContext, item: InventoryItem) -> InventoryItem {
+    debug_assert_eq!(item.instance_id, 0, "new InventoryItem must leave instance_id at 0 for auto_inc");
+    ctx.db.inventory_item().insert(with_auto_inc_id(item))
+}
+
+/// Clears a stray `instance_id` so the insert gets a fresh id from auto_inc.
+fn with_auto_inc_id(mut item: InventoryItem) -> InventoryItem {
+    if item.instance_id != 0 {
+        log::error!("[InsertItem] New item for def {} came with instance_id {}; resetting to 0 so auto_inc assigns one.",
+                  item.item_def_id, item.instance_id);
+        item.instance_id = 0;
+    }
+    item
+}
+
+/// Finds an item definition by its unique name via the `name` index (no table scan).
+pub(crate) fn get_item_def_by_name(ctx: &ReducerContext, name: &str) -> Option<ItemDefinition> {
+    ctx.db.item_definition().name().find(name.to_string())
 }
 
 // --- Inventory Table ---
@@ -67,12 +158,18 @@ pub struct InventoryItem {
     #[primary_key]
     #[auto_inc]
     pub instance_id: u64,      // Unique ID for this specific item instance
-    pub player_identity: spacetimedb::Identity, // Who owns this item
+    #[index(btree)]
+    pub player_identity: spacetimedb::Identity, // Who owns this item. Indexed: query with `.player_identity().filter(&id)`
     pub item_def_id: u64,      // Links to ItemDefinition table (FK)
     pub quantity: u32,         // How many of this item
     pub hotbar_slot: Option<u8>, // Which hotbar slot (0-5), if any
     pub inventory_slot: Option<u16>, // Which main inventory slot (e.g., 0-23), if any
-    // Add other instance-specific data later (e.g., current_durability)
+    // --- Per-instance state ---
+    // Stacks may only merge when all of these are equal (see `has_same_instance_state`).
+    pub charges: Option<u32>,  // Per-instance charges (e.g., water left in a bottle). None if unused
+    pub durability: Option<u32>, // Remaining durability for tools/weapons. None if the item doesn't wear
+    pub spoils_at: Option<Timestamp>, // When a perishable stack spoils, bucketed so similar freshness still stacks. None if it doesn't spoil
+    pub quality: Option<u8>, // Crafted quality tier (see item_quality.rs). None for non-gear and pre-existing items, treated as Standard
 }
 
 // --- Item Reducers ---
@@ -107,25 +204,24 @@ pub fn seed_items(ctx: &ReducerContext) -> Result<(), String> {
 // Helper to find an item instance owned by the caller
 fn get_player_item(ctx: &ReducerContext, instance_id: u64) -> Result<InventoryItem, String> {
     ctx.db
-        .inventory_item().iter()
-        .filter(|i| i.instance_id == instance_id && i.player_identity == ctx.sender)
-        .next()
+        .inventory_item().instance_id().find(instance_id)
+        .filter(|i| i.player_identity == ctx.sender)
         .ok_or_else(|| format!("Item instance {} not found or not owned by caller.", instance_id))
 }
 
 // Helper to find an item occupying a specific inventory slot for the caller
 fn find_item_in_inventory_slot(ctx: &ReducerContext, slot: u16) -> Option<InventoryItem> {
     ctx.db
-        .inventory_item().iter()
-        .filter(|i| i.player_identity == ctx.sender && i.inventory_slot == Some(slot))
+        .inventory_item().player_identity().filter(&ctx.sender)
+        .filter(|i| i.inventory_slot == Some(slot))
         .next()
 }
 
 // Helper to find an item occupying a specific hotbar slot for the caller
 fn find_item_in_hotbar_slot(ctx: &ReducerContext, slot: u8) -> Option<InventoryItem> {
     ctx.db
-        .inventory_item().iter()
-        .filter(|i| i.player_identity == ctx.sender && i.hotbar_slot == Some(slot))
+        .inventory_item().player_identity().filter(&ctx.sender)
+        .filter(|i| i.hotbar_slot == Some(slot))
         .next()
 }
 
@@ -133,124 +229,245 @@ fn find_item_in_hotbar_slot(ctx: &ReducerContext, slot: u8) -> Option<InventoryI
 // Needs to be pub(crate) to be callable from other modules like campfire.rs
 pub(crate) fn find_first_empty_inventory_slot(ctx: &ReducerContext, player_id: Identity) -> Option<u16> {
     let occupied_slots: std::collections::HashSet<u16> = ctx.db
-        .inventory_item().iter()
-        .filter(|i| i.player_identity == player_id && i.inventory_slot.is_some())
-        .map(|i| i.inventory_slot.unwrap())
+        .inventory_item().player_identity().filter(&player_id)
+        .filter_map(|i| i.inventory_slot)
         .collect();
 
     // Assuming 24 inventory slots (0-23)
     (0..24).find(|slot| !occupied_slots.contains(slot))
 }
 
+// Helper to delete everything a player carries: items in their inventory/hotbar slots plus the
+// items their ActiveEquipment holds or wears (whose refs are cleared). Items they deposited into a
+// box, campfire or composter keep `player_identity` but have no slot and aren't equipped, so they stay.
+// Returns how many items were deleted.
+pub(crate) fn delete_carried_items(ctx: &ReducerContext, player_id: Identity) -> usize {
+    let inventory = ctx.db.inventory_item();
+    let mut items_to_delete: Vec<u64> = inventory.player_identity().filter(&player_id)
+        .filter(is_in_player_slot)
+        .map(|i| i.instance_id)
+        .collect();
+
+    let active_equipments = ctx.db.active_equipment();
+    if let Some(mut equipment) = active_equipments.player_identity().find(player_id) {
+        items_to_delete.extend(take_equipped_instance_ids(&mut equipment));
+        active_equipments.player_identity().update(equipment);
+    }
+
+    // A btree range delete on player_identity would also take deposited items, so delete by id
+    let delete_count = items_to_delete.len();
+    for item_instance_id in items_to_delete {
+        inventory.instance_id().delete(item_instance_id);
+    }
+    delete_count
+}
+
+// Empties the main hand and every armor slot, returning the instance ids that were held or worn.
+fn take_equipped_instance_ids(equipment: &mut crate::active_equipment::ActiveEquipment) -> Vec<u64> {
+    let taken = [
+        equipment.equipped_item_instance_id.take(),
+        equipment.head_item_instance_id.take(),
+        equipment.chest_item_instance_id.take(),
+        equipment.legs_item_instance_id.take(),
+        equipment.feet_item_instance_id.take(),
+        equipment.hands_item_instance_id.take(),
+        equipment.back_item_instance_id.take(),
+    ].into_iter().flatten().collect();
+    equipment.equipped_item_def_id = None;
+    equipment.swing_start_time_ms = 0;
+    equipment.weapon_drawn = false;
+    taken
+}
+
+// True for items sitting in one of the owner's inventory or hotbar slots. Deposited and equipped items have neither.
+fn is_in_player_slot(item: &InventoryItem) -> bool {
+    item.inventory_slot.is_some() || item.hotbar_slot.is_some()
+}
+
+// Helper to find the first empty player slot, checking main inventory first, then hotbar.
+// Returns (hotbar_slot, inventory_slot) with exactly one of them set.
+pub(crate) fn find_first_empty_player_slot(ctx: &ReducerContext, player_id: Identity) -> Option<(Option<u8>, Option<u16>)> {
+    let player_items: Vec<InventoryItem> = ctx.db.inventory_item().player_identity().filter(&player_id).collect();
+    first_empty_player_slot(&player_items)
+}
+
+// Free main inventory slots among `player_items`, lowest first.
+fn empty_inventory_slots(player_items: &[InventoryItem]) -> Vec<u16> {
+    let occupied_slots: std::collections::HashSet<u16> = player_items.iter()
+        .filter_map(|i| i.inventory_slot)
+        .collect();
+    // Assuming 24 inventory slots (0-23)
+    (0..24).filter(|slot| !occupied_slots.contains(slot)).collect()
+}
+
+// Free hotbar slots among `player_items`, lowest first.
+fn empty_hotbar_slots(player_items: &[InventoryItem]) -> Vec<u8> {
+    let occupied_slots: std::collections::HashSet<u8> = player_items.iter()
+        .filter_map(|i| i.hotbar_slot)
+        .collect();
+    // Assuming 6 hotbar slots (0-5)
+    (0..6).filter(|slot| !occupied_slots.contains(slot)).collect()
+}
+
+// Pure part of `find_first_empty_player_slot`, over the player's items.
+fn first_empty_player_slot(player_items: &[InventoryItem]) -> Option<(Option<u8>, Option<u16>)> {
+    empty_inventory_slots(player_items).first().map(|&slot| (None, Some(slot)))
+        .or_else(|| empty_hotbar_slots(player_items).first().map(|&slot| (Some(slot), None)))
+}
+
 // Helper to add an item to inventory, prioritizing hotbar for stacking and new slots.
 // Called when items are gathered/added directly (e.g., picking mushrooms, gathering resources).
+// All-or-error: fails with InventoryFull if any of `quantity` doesn't fit. Use `grant_or_drop`
+// for rewards the player must never lose.
 pub(crate) fn add_item_to_player_inventory(ctx: &ReducerContext, player_id: Identity, item_def_id: u64, quantity: u32) -> Result<(), String> {
-    let inventory = ctx.db.inventory_item();
-    let item_defs = ctx.db.item_definition();
-    let mut remaining_quantity = quantity; // Use remaining_quantity throughout
+    let leftover = add_as_much_as_fits(ctx, player_id, item_def_id, quantity)?;
+    if leftover > 0 {
+        log::error!("[AddItem] No empty hotbar or inventory slots for player {:?} to add {} of item def {}.", player_id, leftover, item_def_id);
+        return Err(GameError::InventoryFull.into());
+    }
+    Ok(())
+}
 
-    let item_def = item_defs.id().find(item_def_id)
-        .ok_or_else(|| format!("Item definition {} not found", item_def_id))?;
+// Helper to grant an item that must not be lost: adds what fits to the inventory and drops the
+// rest at the player's feet, reserved for them for the usual drop-lock window.
+pub(crate) fn grant_or_drop(ctx: &ReducerContext, player_id: Identity, item_def_id: u64, quantity: u32) -> Result<(), String> {
+    let leftover = add_as_much_as_fits(ctx, player_id, item_def_id, quantity)?;
+    if leftover == 0 {
+        return Ok(());
+    }
+    let player = ctx.db.player().identity().find(player_id)
+        .ok_or_else(|| GameError::NotFound("Player".to_string()))?;
+    let (drop_x, drop_y) = calculate_drop_position(&player);
+    let item_def = ctx.db.item_definition().id().find(item_def_id)
+        .ok_or_else(|| GameError::NotFound(format!("Item definition {}", item_def_id)))?;
+    let lock_secs = crate::world_config::get_drop_lock_secs(ctx, false);
+    create_owner_locked_dropped_item_entity(ctx, item_def_id, leftover, &ItemInstanceState::fresh(ctx, &item_def), drop_x, drop_y, Some(player_id), lock_secs)?;
+    log::info!("[GrantOrDrop] Inventory full for player {:?}; dropped {} of item def {} at ({:.1}, {:.1}).",
+             player_id, leftover, item_def_id, drop_x, drop_y);
+    Ok(())
+}
 
-    // 1. Try to stack onto existing items - PRIORITIZE HOTBAR
-    if item_def.is_stackable && remaining_quantity > 0 {
-        let mut items_to_update: Vec<crate::items::InventoryItem> = Vec::new();
-
-        // --- Stack on Hotbar First ---
-        for mut item in inventory.iter().filter(|i| i.player_identity == player_id && i.item_def_id == item_def_id && i.hotbar_slot.is_some()) {
-            let space_available = item_def.stack_size.saturating_sub(item.quantity);
-            if space_available > 0 {
-                let transfer_qty = std::cmp::min(remaining_quantity, space_available);
-                item.quantity += transfer_qty;
-                remaining_quantity -= transfer_qty;
-                items_to_update.push(item); // Add item to update list
-                if remaining_quantity == 0 { break; } // Done stacking
-            }
-        }
+// Drops everything in a dying player's inventory and hotbar around where they fell. Each stack keeps
+// its own state and is reserved for the victim for the death-drop lock window (see WorldConfig).
+pub(crate) fn drop_inventory_on_death(ctx: &ReducerContext, player: &crate::Player) -> Result<(), String> {
+    let stacks: Vec<InventoryItem> = ctx.db.inventory_item().player_identity().filter(&player.identity)
+        .filter(|item| item.inventory_slot.is_some() || item.hotbar_slot.is_some())
+        .collect();
+    if stacks.is_empty() {
+        return Ok(());
+    }
 
-        // --- Then Stack on Inventory ---
-        if remaining_quantity > 0 {
-            for mut item in inventory.iter().filter(|i| i.player_identity == player_id && i.item_def_id == item_def_id && i.inventory_slot.is_some()) {
-                let space_available = item_def.stack_size.saturating_sub(item.quantity);
-                if space_available > 0 {
-                    let transfer_qty = std::cmp::min(remaining_quantity, space_available);
-                    item.quantity += transfer_qty;
-                    remaining_quantity -= transfer_qty;
-                    items_to_update.push(item); // Add item to update list
-                    if remaining_quantity == 0 { break; } // Done stacking
-                }
-            }
+    // The main hand always holds a hotbar item, so it goes with the drop
+    let active_equip_table = ctx.db.active_equipment();
+    if let Some(mut equip) = active_equip_table.player_identity().find(player.identity) {
+        if equip.equipped_item_instance_id.is_some() {
+            equip.equipped_item_instance_id = None;
+            equip.equipped_item_def_id = None;
+            equip.swing_start_time_ms = 0;
+            active_equip_table.player_identity().update(equip);
         }
+    }
 
-        // Apply updates if any stacking occurred
-        for item in items_to_update {
-             inventory.instance_id().update(item);
-        }
+    let lock_secs = crate::world_config::get_drop_lock_secs(ctx, true);
+    for item in &stacks {
+        let Some(item_def) = ctx.db.item_definition().id().find(item.item_def_id) else {
+            log::warn!("[DeathDrop] Item {} has unknown definition {}; discarding it.", item.instance_id, item.item_def_id);
+            ctx.db.inventory_item().instance_id().delete(item.instance_id);
+            continue;
+        };
+        create_stack_sized_dropped_item_entities(ctx, &item_def, item.quantity, &ItemInstanceState::of(item),
+            player.position_x, player.position_y, Some(player.identity), lock_secs)?;
+        ctx.db.inventory_item().instance_id().delete(item.instance_id);
+    }
+    log::info!("[DeathDrop] Player {:?} dropped {} stacks at ({:.1}, {:.1}), locked for {}s.",
+             player.identity, stacks.len(), player.position_x, player.position_y, lock_secs);
+    Ok(())
+}
 
-        // If quantity fully stacked, return early
-        if remaining_quantity == 0 {
-            log::info!("[AddItem] Fully stacked {} of item def {} for player {:?}.", quantity, item_def_id, player_id);
-            return Ok(());
+// Adds as much of `quantity` as fits: stacks onto matching hotbar, then inventory stacks, then fills
+// empty hotbar, then inventory slots. Returns how much didn't fit (0 when everything was added).
+pub(crate) fn add_as_much_as_fits(ctx: &ReducerContext, player_id: Identity, item_def_id: u64, quantity: u32) -> Result<u32, String> {
+    let item_def = ctx.db.item_definition().id().find(item_def_id)
+        .ok_or_else(|| format!("Item definition {} not found", item_def_id))?;
+    // Perishables created now share a spoilage bucket with anything else created around now
+    let state = ItemInstanceState::fresh(ctx, &item_def);
+    add_as_much_as_fits_with_state(ctx, player_id, item_def_id, quantity, &state)
+}
+
+// Like `add_as_much_as_fits`, but the added items carry `state` (e.g. a dropped pile's durability
+// or spoilage) and only stack onto stacks with exactly that state.
+pub(crate) fn add_as_much_as_fits_with_state(ctx: &ReducerContext, player_id: Identity, item_def_id: u64, quantity: u32, state: &ItemInstanceState) -> Result<u32, String> {
+    let inventory = ctx.db.inventory_item();
+    let item_def = ctx.db.item_definition().id().find(item_def_id)
+        .ok_or_else(|| format!("Item definition {} not found", item_def_id))?;
+
+    let player_items: Vec<InventoryItem> = inventory.player_identity().filter(&player_id).collect();
+    let plan = plan_inventory_add(&player_items, &item_def, effective_stack_size(ctx, &item_def), quantity, state);
+
+    for (instance_id, new_quantity) in plan.topped_up {
+        if let Some(mut item) = inventory.instance_id().find(instance_id) {
+            item.quantity = new_quantity;
+            inventory.instance_id().update(item);
         }
-    } // End of stacking logic
+    }
+    for (hotbar_slot, inventory_slot, stack_quantity) in plan.new_stacks {
+        insert_new_inventory_item(ctx, crate::items::InventoryItem {
+            instance_id: 0, // Auto-inc
+            player_identity: player_id,
+            item_def_id,
+            quantity: stack_quantity,
+            hotbar_slot,
+            inventory_slot,
+            charges: state.charges,
+            durability: state.durability,
+            spoils_at: state.spoils_at,
+            quality: state.quality,
+        });
+        log::info!("[AddItem] Added {} of item def {} to slot (H: {:?}, I: {:?}) for player {:?}.",
+                 stack_quantity, item_def_id, hotbar_slot, inventory_slot, player_id);
+    }
+    Ok(plan.leftover)
+}
 
-    // If quantity still remains (or item not stackable), find an empty slot
-    if remaining_quantity > 0 {
-        let final_quantity_to_add = if item_def.is_stackable { remaining_quantity } else { 1 }; // Non-stackable always adds 1
+// Where adding `quantity` of `item_def` to `player_items` puts it, without touching the table.
+struct InventoryAddPlan {
+    topped_up: Vec<(u64, u32)>, // (instance_id, new quantity) for existing stacks it tops up
+    new_stacks: Vec<(Option<u8>, Option<u16>, u32)>, // (hotbar_slot, inventory_slot, quantity) per new stack
+    leftover: u32, // What didn't fit
+}
 
-        // 2. Find first empty HOTBAR slot
-        let occupied_hotbar_slots: std::collections::HashSet<u8> = inventory.iter()
-            .filter(|i| i.player_identity == player_id && i.hotbar_slot.is_some())
-            .map(|i| i.hotbar_slot.unwrap())
-            .collect();
+// Tops up matching stacks (hotbar first, then inventory), then fills empty slots (hotbar first).
+// Stackables go in one new stack, non-stackables take one slot each.
+fn plan_inventory_add(player_items: &[InventoryItem], item_def: &ItemDefinition, max_stack: u32, quantity: u32, state: &ItemInstanceState) -> InventoryAddPlan {
+    let mut remaining_quantity = quantity;
 
-        if let Some(empty_hotbar_slot) = (0..6).find(|slot| !occupied_hotbar_slots.contains(slot)) {
-            // Found empty hotbar slot
-            let new_item = crate::items::InventoryItem {
-                instance_id: 0, // Auto-inc
-                player_identity: player_id,
-                item_def_id,
-                quantity: final_quantity_to_add,
-                hotbar_slot: Some(empty_hotbar_slot),
-                inventory_slot: None,
-            };
-            inventory.insert(new_item);
-            log::info!("[AddItem] Added {} of item def {} to hotbar slot {} for player {:?}.",
-                     final_quantity_to_add, item_def_id, empty_hotbar_slot, player_id);
-            return Ok(()); // Item added successfully
-        } else {
-             // 3. Hotbar full, find first empty INVENTORY slot
-            let occupied_inventory_slots: std::collections::HashSet<u16> = inventory.iter()
-                .filter(|i| i.player_identity == player_id && i.inventory_slot.is_some())
-                .map(|i| i.inventory_slot.unwrap())
-                .collect();
-
-            if let Some(empty_inventory_slot) = (0..24).find(|slot| !occupied_inventory_slots.contains(slot)) {
-                // Found empty inventory slot
-                let new_item = crate::items::InventoryItem {
-                    instance_id: 0, // Auto-inc
-                    player_identity: player_id,
-                    item_def_id,
-                    quantity: final_quantity_to_add,
-                    hotbar_slot: None,
-                    inventory_slot: Some(empty_inventory_slot),
-                };
-                inventory.insert(new_item);
-                log::info!("[AddItem] Added {} of item def {} to inventory slot {} for player {:?}. (Hotbar was full)",
-                         final_quantity_to_add, item_def_id, empty_inventory_slot, player_id);
-                return Ok(()); // Item added successfully
-            } else {
-                // 4. Both hotbar and inventory are full
-                log::error!("[AddItem] No empty hotbar or inventory slots for player {:?} to add item def {}.", player_id, item_def_id);
-                return Err("Inventory is full".to_string());
+    // 1. Try to stack onto existing items - PRIORITIZE HOTBAR
+    let mut topped_up = Vec::new();
+    if item_def.is_stackable {
+        let hotbar_stacks = player_items.iter().filter(|i| i.hotbar_slot.is_some());
+        let inventory_stacks = player_items.iter().filter(|i| i.inventory_slot.is_some());
+        for item in hotbar_stacks.chain(inventory_stacks).filter(|i| i.item_def_id == item_def.id && state.matches(i)) {
+            if remaining_quantity == 0 { break; } // Done stacking
+            let transfer_qty = std::cmp::min(remaining_quantity, max_stack.saturating_sub(item.quantity));
+            if transfer_qty > 0 {
+                topped_up.push((item.instance_id, item.quantity + transfer_qty));
+                remaining_quantity -= transfer_qty;
             }
         }
-    } else {
-         // This case should only be reached if stacking happened perfectly and remaining_quantity became 0
-         // No further action needed, the stacking return above handles this.
-         log::debug!("[AddItem] Stacking completed successfully for item def {} for player {:?}. No new slot needed.", item_def_id, player_id);
-         Ok(())
     }
+
+    // 2. Put what remains into empty slots, hotbar first, then the inventory
+    let mut empty_slots = empty_hotbar_slots(player_items).into_iter().map(|slot| (Some(slot), None))
+        .chain(empty_inventory_slots(player_items).into_iter().map(|slot| (None, Some(slot))));
+    let mut new_stacks = Vec::new();
+    while remaining_quantity > 0 {
+        let Some((hotbar_slot, inventory_slot)) = empty_slots.next() else { break; }; // Both hotbar and inventory are full
+        let quantity_to_add = if item_def.is_stackable { remaining_quantity } else { 1 };
+        new_stacks.push((hotbar_slot, inventory_slot, quantity_to_add));
+        remaining_quantity -= quantity_to_add;
+    }
+
+    InventoryAddPlan { topped_up, new_stacks, leftover: remaining_quantity }
 }
 
 // Helper to clear a specific item instance from any equipment slot it might occupy
@@ -349,13 +566,7 @@ pub(crate) fn clear_item_from_campfire_fuel_slots(ctx: &ReducerContext, item_ins
             if updated {
                 log::debug!("[ClearCampfireSlot] Cleared item {} from a fuel slot in campfire {}", item_instance_id_to_clear, campfire_id);
                 // Check if fire should extinguish after clearing slot
-                // Pass ctx instead of table handles
-                let still_has_fuel = crate::campfire::check_if_campfire_has_fuel(ctx, &campfire);
-                 if !still_has_fuel && campfire.is_burning {
-                    campfire.is_burning = false;
-                    campfire.next_fuel_consume_at = None;
-                    log::info!("Campfire {} extinguished as last valid fuel was removed.", campfire_id);
-                }
+                crate::campfire::refresh_campfire_burning_state(ctx, &mut campfire);
                 campfires.id().update(campfire);
             }
         }
@@ -391,7 +602,10 @@ fn clear_item_from_source_location(ctx: &ReducerContext, item_instance_id: u64)
         // This is safe even if it wasn't fuel, the inner function handles lookup.
         clear_item_from_campfire_fuel_slots(ctx, item_instance_id);
 
-        log::debug!("[ClearSource] Attempted clearing item {} from equipment/campfire slots for player {:?}", item_instance_id, sender_id);
+        // And from the utility belt, whose items are unslotted as well
+        crate::utility_belt::clear_item_from_belts(ctx, item_instance_id);
+
+        log::debug!("[ClearSource] Attempted clearing item {} from equipment/campfire/belt slots for player {:?}", item_instance_id, sender_id);
     } else {
         log::debug!("[ClearSource] Item {} was in inventory/hotbar. No equipment/campfire clearing needed.", item_instance_id);
     }
@@ -407,7 +621,7 @@ pub fn move_item_to_inventory(ctx: &ReducerContext, item_instance_id: u64, targe
 
     // --- 1. Find Item to Move --- 
     let mut item_to_move = inventory_items.instance_id().find(item_instance_id)
-        .ok_or_else(|| format!("Item instance {} not found", item_instance_id))?;
+        .ok_or_else(|| GameError::NotFound(format!("Item instance {}", item_instance_id)))?;
     // REMOVED player identity check here - item might be coming from a container
     // if item_to_move.player_identity != sender_id { 
     //     return Err("Item does not belong to the caller".to_string()); 
@@ -421,11 +635,15 @@ pub fn move_item_to_inventory(ctx: &ReducerContext, item_instance_id: u64, targe
     // A move from a container (box/campfire) would use a different reducer.
     if original_location_was_equipment {
         log::debug!("[MoveInv] Item {} is potentially coming from an equipment slot.", item_instance_id);
+        // Belt items are unslotted too, but only their owner may take them out
+        if item_to_move.player_identity != sender_id && crate::utility_belt::is_in_belt(ctx, &item_to_move) {
+            return Err(GameError::NotOwner.into());
+        }
     }
     
     // --- 3. Check Target Slot --- 
     if target_inventory_slot >= 24 { // Assuming 0-23 are valid slots
-        return Err("Invalid target inventory slot index".to_string());
+        return Err(GameError::InvalidSlot(format!("inventory slot {}", target_inventory_slot)).into());
     }
     
     let target_item_opt = find_item_in_inventory_slot(ctx, target_inventory_slot);
@@ -445,7 +663,7 @@ pub fn move_item_to_inventory(ctx: &ReducerContext, item_instance_id: u64, targe
         log::debug!("[MoveInv] Target slot {} occupied by {}. Trying merge/swap for item {}.", 
                  target_inventory_slot, target_item.instance_id, item_instance_id);
 
-        match calculate_merge_result(&item_to_move, &target_item, &item_def_to_move) {
+        match calculate_merge_result(ctx, &item_to_move, &target_item, &item_def_to_move) {
             Ok((qty_transfer, source_new_qty, target_new_qty, delete_source)) => {
                  // Merge successful
                 log::info!("[MoveInv Merge] Merging {} from item {} onto {} in inv slot {}. Target new qty: {}", 
@@ -515,6 +733,7 @@ pub fn move_item_to_inventory(ctx: &ReducerContext, item_instance_id: u64, targe
     if original_location_was_equipment {
         log::info!("[MoveInv] Clearing original equipment slot for item {}.", item_instance_id);
         clear_specific_item_from_equipment_slots(ctx, sender_id, item_instance_id);
+        crate::utility_belt::clear_item_from_belts(ctx, item_instance_id);
     }
 
     Ok(())
@@ -528,7 +747,7 @@ pub fn move_item_to_hotbar(ctx: &ReducerContext, item_instance_id: u64, target_h
 
     // --- 1. Find Item to Move --- 
     let mut item_to_move = inventory_items.instance_id().find(item_instance_id)
-        .ok_or_else(|| format!("Item instance {} not found", item_instance_id))?;
+        .ok_or_else(|| GameError::NotFound(format!("Item instance {}", item_instance_id)))?;
     // REMOVED player identity check here
     // if item_to_move.player_identity != sender_id { 
     //     return Err("Item does not belong to the caller".to_string()); 
@@ -542,11 +761,15 @@ pub fn move_item_to_hotbar(ctx: &ReducerContext, item_instance_id: u64, target_h
     // A move from a container (box/campfire) would use a different reducer.
     if original_location_was_equipment {
         log::debug!("[MoveHotbar] Item {} is potentially coming from an equipment slot.", item_instance_id);
+        // Belt items are unslotted too, but only their owner may take them out
+        if item_to_move.player_identity != sender_id && crate::utility_belt::is_in_belt(ctx, &item_to_move) {
+            return Err(GameError::NotOwner.into());
+        }
     }
     
     // --- 3. Check Target Slot --- 
     if target_hotbar_slot >= 6 { // Assuming 0-5 are valid slots
-        return Err("Invalid target hotbar slot index".to_string());
+        return Err(GameError::InvalidSlot(format!("hotbar slot {}", target_hotbar_slot)).into());
     }
 
     let target_item_opt = find_item_in_hotbar_slot(ctx, target_hotbar_slot);
@@ -566,7 +789,7 @@ pub fn move_item_to_hotbar(ctx: &ReducerContext, item_instance_id: u64, target_h
         log::debug!("[MoveHotbar] Target slot {} occupied by {}. Trying merge/swap for item {}.", 
                  target_hotbar_slot, target_item.instance_id, item_instance_id);
         
-        match calculate_merge_result(&item_to_move, &target_item, &item_def_to_move) {
+        match calculate_merge_result(ctx, &item_to_move, &target_item, &item_def_to_move) {
              Ok((qty_transfer, source_new_qty, target_new_qty, delete_source)) => {
                  // Merge successful
                  log::info!("[MoveHotbar Merge] Merging {} from item {} onto {} in hotbar slot {}. Target new qty: {}", 
@@ -627,6 +850,7 @@ pub fn move_item_to_hotbar(ctx: &ReducerContext, item_instance_id: u64, target_h
     if original_location_was_equipment {
         log::info!("[MoveHotbar] Clearing original equipment slot for item {}.", item_instance_id);
         clear_specific_item_from_equipment_slots(ctx, sender_id, item_instance_id);
+        crate::utility_belt::clear_item_from_belts(ctx, item_instance_id);
     }
 
     Ok(())
@@ -751,18 +975,82 @@ pub fn equip_armor_from_drag(ctx: &ReducerContext, item_instance_id: u64, target
     Ok(())
 }
 
+// Invariant: two stacks of a stackable item may only merge if their per-instance state
+// (charges, durability, spoilage bucket, quality) is identical, otherwise merging would silently discard one side's state.
+pub(crate) fn has_same_instance_state(a: &InventoryItem, b: &InventoryItem) -> bool {
+    a.charges == b.charges && a.durability == b.durability && a.spoils_at == b.spoils_at
+        && a.quality == b.quality
+}
+
+/// The per-instance columns of an `InventoryItem`, for carrying a stack's state through places
+/// that don't hold an inventory row, such as a pile on the ground.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct ItemInstanceState {
+    pub charges: Option<u32>,
+    pub durability: Option<u32>,
+    pub spoils_at: Option<Timestamp>,
+    pub quality: Option<u8>,
+}
+
+impl ItemInstanceState {
+    pub(crate) fn of(item: &InventoryItem) -> Self {
+        Self { charges: item.charges, durability: item.durability, spoils_at: item.spoils_at, quality: item.quality }
+    }
+
+    /// State of a freshly created (gathered, crafted, granted) stack of `item_def`: the spoilage
+    /// bucket it was created in, and a rolled quality tier if it is gear.
+    pub(crate) fn fresh(ctx: &ReducerContext, item_def: &ItemDefinition) -> Self {
+        Self {
+            spoils_at: crate::spoilage::initial_spoils_at(ctx, item_def),
+            quality: crate::item_quality::rolls_quality(item_def).then(|| crate::item_quality::roll_crafted_quality(ctx, 0, 0)),
+            ..Self::default()
+        }
+    }
+
+    pub(crate) fn matches(&self, item: &InventoryItem) -> bool {
+        *self == Self::of(item)
+    }
+}
+
+/// Largest stack of `item_def` allowed on this server: its `stack_size` scaled by the
+/// WorldConfig stack multiplier. Every path that fills a stack must use this, not `stack_size`.
+pub(crate) fn effective_stack_size(ctx: &ReducerContext, item_def: &ItemDefinition) -> u32 {
+    scaled_stack_size(item_def, crate::world_config::get_stack_size_multiplier(ctx))
+}
+
+fn scaled_stack_size(item_def: &ItemDefinition, multiplier: u32) -> u32 {
+    if !item_def.is_stackable {
+        return item_def.stack_size;
+    }
+    item_def.stack_size.saturating_mul(multiplier)
+}
+
 // Calculates the result of merging source onto target
 // Returns: (qty_to_transfer, source_new_qty, target_new_qty, delete_source)
 pub(crate) fn calculate_merge_result(
+    ctx: &ReducerContext,
     source_item: &InventoryItem,
     target_item: &InventoryItem, 
     item_def: &ItemDefinition
+) -> Result<(u32, u32, u32, bool), String> {
+    merge_result_with_cap(source_item, target_item, item_def, effective_stack_size(ctx, item_def))
+}
+
+// `calculate_merge_result` against an explicit stack cap, without touching the tables.
+fn merge_result_with_cap(
+    source_item: &InventoryItem,
+    target_item: &InventoryItem,
+    item_def: &ItemDefinition,
+    max_stack: u32,
 ) -> Result<(u32, u32, u32, bool), String> {
     if !item_def.is_stackable || source_item.item_def_id != target_item.item_def_id {
         return Err("Items cannot be merged".to_string());
     }
+    if !has_same_instance_state(source_item, target_item) {
+        return Err("Items have different state and cannot be merged".to_string());
+    }
 
-    let space_available = item_def.stack_size.saturating_sub(target_item.quantity);
+    let space_available = max_stack.saturating_sub(target_item.quantity);
     if space_available == 0 {
         return Err("Target stack is full".to_string()); // Or handle as a swap later
     }
@@ -799,8 +1087,12 @@ pub(crate) fn split_stack_helper(
         quantity: quantity_to_split,
         hotbar_slot: None, // New item has no location yet
         inventory_slot: None,
+        charges: source_item.charges,
+        durability: source_item.durability,
+        spoils_at: source_item.spoils_at,
+        quality: source_item.quality,
     };
-    let inserted_item = ctx.db.inventory_item().insert(new_item);
+    let inserted_item = insert_new_inventory_item(ctx, new_item);
     let new_instance_id = inserted_item.instance_id;
 
     log::info!(
@@ -867,10 +1159,9 @@ pub fn split_stack(
     let target_inventory_slot_check = if target_is_inventory { Some(target_slot_index as u16) } else { None };
     let target_hotbar_slot_check = if !target_is_inventory { Some(target_slot_index as u8) } else { None };
 
-    let target_occupied = ctx.db.inventory_item().iter().any(|i| {
-        i.player_identity == sender_id &&
-        ((target_is_inventory && i.inventory_slot == target_inventory_slot_check) ||
-         (!target_is_inventory && i.hotbar_slot == target_hotbar_slot_check))
+    let target_occupied = ctx.db.inventory_item().player_identity().filter(&sender_id).any(|i| {
+        (target_is_inventory && i.inventory_slot == target_inventory_slot_check) ||
+        (!target_is_inventory && i.hotbar_slot == target_hotbar_slot_check)
     });
 
     if target_occupied {
@@ -891,8 +1182,12 @@ pub fn split_stack(
         quantity: quantity_to_split,
         hotbar_slot: if !target_is_inventory { Some(target_slot_index as u8) } else { None },
         inventory_slot: if target_is_inventory { Some(target_slot_index as u16) } else { None },
+        charges: source_item.charges,
+        durability: source_item.durability,
+        spoils_at: source_item.spoils_at,
+        quality: source_item.quality,
     };
-    ctx.db.inventory_item().insert(new_item);
+    insert_new_inventory_item(ctx, new_item);
 
     log::info!(
         "[SplitStack] Successfully split {} of item {} (Def: {}) to {} slot {}. Original stack quantity now {}.",
@@ -903,6 +1198,236 @@ pub fn split_stack(
     Ok(())
 }
 
+/// Tidies the caller's main inventory: merges partial stacks of the same item (and same
+/// per-instance state) up to the stack size, then packs everything into the first slots sorted
+/// by category, then name. The hotbar is left alone. Sorting only merges and moves existing
+/// stacks, so they always fit back into the slots they came from.
+#[spacetimedb::reducer]
+pub fn sort_inventory(ctx: &ReducerContext) -> Result<(), String> {
+    let sender_id = ctx.sender;
+    let inventory = ctx.db.inventory_item();
+    let item_defs = ctx.db.item_definition();
+
+    // 1. Gather main inventory stacks (a stack whose definition is missing sorts last and never merges)
+    let mut stacks: Vec<(InventoryItem, Option<ItemDefinition>)> = inventory.player_identity().filter(&sender_id)
+        .filter(|item| item.inventory_slot.is_some())
+        .map(|item| { let def = item_defs.id().find(item.item_def_id); (item, def) })
+        .collect();
+    let original_count = stacks.len();
+
+    // 2. Category, then name; within one item the fullest stacks first so merging pours into them
+    stacks.sort_by(|(a, a_def), (b, b_def)| {
+        let key = |def: &Option<ItemDefinition>| def.as_ref()
+            .map_or((u8::MAX, String::new()), |d| (category_sort_key(&d.category), d.name.clone()));
+        key(a_def).cmp(&key(b_def))
+            .then(a.item_def_id.cmp(&b.item_def_id))
+            .then(b.quantity.cmp(&a.quantity))
+    });
+
+    // 3. Pour each stack into earlier compatible ones (as consolidate_inventory does), deleting stacks that empty out
+    let max_stacks: std::collections::HashMap<u64, u32> = stacks.iter()
+        .filter_map(|(_, def)| def.as_ref().filter(|d| d.is_stackable).map(|d| (d.id, effective_stack_size(ctx, d))))
+        .collect();
+    let instance_ids: Vec<u64> = stacks.iter().map(|(item, _)| item.instance_id).collect();
+    let mut working: Vec<Option<InventoryItem>> = stacks.into_iter().map(|(item, _)| Some(item)).collect();
+    pour_into_earlier_stacks(&mut working, |item_def_id| max_stacks.get(&item_def_id).copied());
+    let mut sorted: Vec<InventoryItem> = Vec::with_capacity(working.len());
+    for (instance_id, item) in instance_ids.into_iter().zip(working) {
+        match item {
+            Some(item) => sorted.push(item),
+            None => { inventory.instance_id().delete(instance_id); }
+        }
+    }
+
+    // 4. Pack into slots 0.. in sorted order
+    let merged_away = original_count - sorted.len();
+    let packed_count = sorted.len();
+    for (slot_index, mut item) in sorted.into_iter().enumerate() {
+        item.inventory_slot = Some(slot_index as u16);
+        inventory.instance_id().update(item);
+    }
+
+    log::info!("[SortInventory] Player {:?} sorted {} stacks ({} merged away).", sender_id, packed_count, merged_away);
+    Ok(())
+}
+
+/// Order of item categories when sorting an inventory.
+fn category_sort_key(category: &ItemCategory) -> u8 {
+    match category {
+        ItemCategory::Tool => 0,
+        ItemCategory::Armor => 1,
+        ItemCategory::Consumable => 2,
+        ItemCategory::Placeable => 3,
+        ItemCategory::Material => 4,
+    }
+}
+
+/// Splits `quantity_to_split` off a stack and merges it straight onto another of the caller's
+/// stacks of the same item (dropping a partial stack onto a matching pile). Only what fits in the
+/// target moves; the rest stays in the source. A full or incompatible target leaves both untouched.
+#[spacetimedb::reducer]
+pub fn split_stack_onto(
+    ctx: &ReducerContext,
+    source_item_instance_id: u64,
+    quantity_to_split: u32,
+    target_item_instance_id: u64,
+) -> Result<(), String> {
+    let sender_id = ctx.sender;
+
+    // 1. Both stacks must be the caller's and sit in their inventory or hotbar
+    if source_item_instance_id == target_item_instance_id {
+        return Err(GameError::InvalidInput("Cannot split a stack onto itself.".to_string()).into());
+    }
+    let mut source_item = get_player_item(ctx, source_item_instance_id)?;
+    let mut target_item = get_player_item(ctx, target_item_instance_id)?;
+    for item in [&source_item, &target_item] {
+        if item.inventory_slot.is_none() && item.hotbar_slot.is_none() {
+            return Err(format!("Item instance {} is not in the inventory or hotbar.", item.instance_id));
+        }
+    }
+    let item_def = ctx.db.item_definition().id().find(source_item.item_def_id)
+        .ok_or_else(|| format!("Definition not found for item ID {}", source_item.item_def_id))?;
+
+    // 2. Same quantity rules as split_stack: at least 1 moves, at least 1 stays
+    if quantity_to_split == 0 || quantity_to_split >= source_item.quantity {
+        return Err(GameError::InvalidInput(format!(
+            "Split quantity must be between 1 and {} (stack has {}).",
+            source_item.quantity.saturating_sub(1), source_item.quantity
+        )).into());
+    }
+
+    // 3. Merge as if the split-off part were its own stack (errors on a full or different target)
+    let split_part = InventoryItem { quantity: quantity_to_split, ..source_item.clone() };
+    let (qty_to_transfer, _, target_new_qty, _) = calculate_merge_result(ctx, &split_part, &target_item, &item_def)?;
+
+    // 4. Apply: the source always keeps at least what wasn't split off
+    source_item.quantity -= qty_to_transfer;
+    target_item.quantity = target_new_qty;
+    ctx.db.inventory_item().instance_id().update(source_item.clone());
+    ctx.db.inventory_item().instance_id().update(target_item);
+
+    log::info!("[SplitStackOnto] Player {:?} moved {} of {} requested '{}' from stack {} onto stack {} (source now {}, target now {}).",
+             sender_id, qty_to_transfer, quantity_to_split, item_def.name, source_item_instance_id, target_item_instance_id, source_item.quantity, target_new_qty);
+    Ok(())
+}
+
+// Splits a stack in half (rounding down) into the first empty player slot.
+#[spacetimedb::reducer]
+pub fn split_stack_half(ctx: &ReducerContext, item_instance_id: u64) -> Result<(), String> {
+    let sender_id = ctx.sender;
+
+    // 1. Get the original item stack and its definition
+    let mut source_item = get_player_item(ctx, item_instance_id)?;
+    let item_def = ctx.db.item_definition().id().find(source_item.item_def_id)
+        .ok_or_else(|| format!("Definition not found for item ID {}", source_item.item_def_id))?;
+
+    // 2. Validate
+    if !item_def.is_stackable {
+        return Err(format!("Item '{}' is not stackable.", item_def.name));
+    }
+    if source_item.inventory_slot.is_none() && source_item.hotbar_slot.is_none() {
+        return Err("Item must be in inventory or hotbar to split.".to_string());
+    }
+    let quantity_to_split = half_split_quantity(source_item.quantity)?;
+
+    // 3. Find a destination BEFORE splitting so a full inventory doesn't orphan the new stack
+    let (hotbar_slot, inventory_slot) = find_first_empty_player_slot(ctx, sender_id)
+        .ok_or(GameError::InventoryFull)?;
+
+    // 4. Split and place the new stack
+    let new_item_instance_id = split_stack_helper(ctx, &mut source_item, quantity_to_split)?;
+    let mut new_item = ctx.db.inventory_item().instance_id().find(new_item_instance_id)
+        .ok_or("Newly split item stack not found!")?;
+    new_item.hotbar_slot = hotbar_slot;
+    new_item.inventory_slot = inventory_slot;
+    ctx.db.inventory_item().instance_id().update(new_item);
+
+    log::info!("[SplitHalf] Player {:?} split {} from item {} into new item {} (H: {:?}, I: {:?}). Original now {}.",
+             sender_id, quantity_to_split, item_instance_id, new_item_instance_id, hotbar_slot, inventory_slot, source_item.quantity);
+    Ok(())
+}
+
+// Pours later stacks into earlier stacks of the same definition and instance state, in order.
+// `max_stack_of` gives the effective stack size of a stackable definition (None = doesn't stack).
+// Entries merged away become None. Returns which entries changed.
+fn pour_into_earlier_stacks(working: &mut [Option<InventoryItem>], max_stack_of: impl Fn(u64) -> Option<u32>) -> Vec<bool> {
+    let mut changed = vec![false; working.len()];
+    for target_idx in 0..working.len() {
+        let Some(target_def_id) = working[target_idx].as_ref().map(|item| item.item_def_id) else { continue; };
+        let Some(max_stack) = max_stack_of(target_def_id) else { continue; };
+
+        for source_idx in (target_idx + 1)..working.len() {
+            let qty_to_transfer = match (&working[source_idx], &working[target_idx]) {
+                (Some(source), Some(target)) if source.item_def_id == target_def_id && has_same_instance_state(source, target) =>
+                    source.quantity.min(max_stack.saturating_sub(target.quantity)),
+                _ => continue,
+            };
+            if qty_to_transfer == 0 {
+                break; // Target stack is full, move on to the next target
+            }
+            if let Some(target_item) = working[target_idx].as_mut() { target_item.quantity += qty_to_transfer; }
+            changed[target_idx] = true;
+            let source_emptied = working[source_idx].as_mut().is_some_and(|source_item| {
+                source_item.quantity -= qty_to_transfer;
+                source_item.quantity == 0
+            });
+            if source_emptied {
+                working[source_idx] = None;
+            }
+            changed[source_idx] = true;
+        }
+    }
+    changed
+}
+
+// How much `split_stack_half` moves out: half, rounded down, so the original keeps the extra.
+fn half_split_quantity(quantity: u32) -> Result<u32, String> {
+    match quantity / 2 {
+        0 => Err("Cannot split a stack of 1.".to_string()),
+        half => Ok(half),
+    }
+}
+
+// Merges all partial stacks of the same item across the caller's hotbar and inventory,
+// filling earlier slots first (hotbar 0-5, then inventory 0-23). Items are not moved between slots.
+#[spacetimedb::reducer]
+pub fn consolidate_inventory(ctx: &ReducerContext) -> Result<(), String> {
+    let sender_id = ctx.sender;
+    let inventory = ctx.db.inventory_item();
+    let item_defs = ctx.db.item_definition();
+
+    // 1. Collect the caller's slotted items in slot order
+    let mut player_items: Vec<InventoryItem> = inventory.player_identity().filter(&sender_id)
+        .filter(|i| i.hotbar_slot.is_some() || i.inventory_slot.is_some())
+        .collect();
+    player_items.sort_by_key(|i| (i.hotbar_slot.is_none(), i.hotbar_slot.unwrap_or(0), i.inventory_slot.unwrap_or(0)));
+
+    // Working copies: None = merged away and should be deleted
+    let mut working: Vec<Option<InventoryItem>> = player_items.iter().cloned().map(Some).collect();
+
+    // 2. Pour later stacks into earlier stacks of the same definition
+    let max_stack_of = |item_def_id: u64| item_defs.id().find(item_def_id)
+        .filter(|def| def.is_stackable)
+        .map(|def| effective_stack_size(ctx, &def));
+    let changed = pour_into_earlier_stacks(&mut working, max_stack_of);
+
+    // 3. Persist changes
+    let mut merged_count = 0;
+    for (idx, original) in player_items.iter().enumerate() {
+        if !changed[idx] { continue; }
+        match &working[idx] {
+            Some(item) => { inventory.instance_id().update(item.clone()); }
+            None => {
+                inventory.instance_id().delete(original.instance_id);
+                merged_count += 1;
+            }
+        }
+    }
+
+    log::info!("[Consolidate] Player {:?} consolidated inventory. {} stacks merged away.", sender_id, merged_count);
+    Ok(())
+}
+
 // --- NEW: Split Stack From Campfire Reducer ---
 
 #[spacetimedb::reducer]
@@ -961,10 +1486,9 @@ pub fn split_stack_from_campfire(
     // --- Check Target Occupancy (Simplified - No Merge/Swap for split target yet) ---
     let target_inv_slot_check = if target_is_inventory { Some(target_slot_index as u16) } else { None };
     let target_hotbar_slot_check = if !target_is_inventory { Some(target_slot_index as u8) } else { None };
-    let target_occupied = inventory_items.iter().any(|i| {
-        i.player_identity == sender_id &&
-        ((target_is_inventory && i.inventory_slot == target_inv_slot_check) ||
-         (!target_is_inventory && i.hotbar_slot == target_hotbar_slot_check))
+    let target_occupied = inventory_items.player_identity().filter(&sender_id).any(|i| {
+        (target_is_inventory && i.inventory_slot == target_inv_slot_check) ||
+        (!target_is_inventory && i.hotbar_slot == target_hotbar_slot_check)
     });
     if target_occupied {
         return Err(format!("Target {} slot {} is already occupied (merging split not implemented yet).", target_slot_type, target_slot_index));
@@ -1017,9 +1541,8 @@ pub fn move_to_first_available_hotbar_slot(ctx: &ReducerContext, item_instance_i
 
 
     // 2. Find the first empty hotbar slot (0-5)
-    let occupied_slots: std::collections::HashSet<u8> = ctx.db.inventory_item().iter()
-        .filter(|i| i.player_identity == sender_id && i.hotbar_slot.is_some())
-        .map(|i| i.hotbar_slot.unwrap())
+    let occupied_slots: std::collections::HashSet<u8> = ctx.db.inventory_item().player_identity().filter(&sender_id)
+        .filter_map(|i| i.hotbar_slot)
         .collect();
 
     match (0..6).find(|slot| !occupied_slots.contains(slot)) {
@@ -1041,6 +1564,24 @@ pub fn drop_item(
     ctx: &ReducerContext,
     item_instance_id: u64,
     quantity_to_drop: u32, // How many to drop (can be less than total stack)
+) -> Result<(), String> {
+    drop_item_toward(ctx, item_instance_id, quantity_to_drop, None)
+}
+
+/// Like `drop_item`, but throws the item up to `MAX_TOSS_DISTANCE` along (aim_x, aim_y),
+/// e.g. to hand loot to another player. A zero aim drops it in front as usual.
+#[spacetimedb::reducer]
+pub fn toss_item(ctx: &ReducerContext, item_instance_id: u64, quantity_to_drop: u32, aim_x: f32, aim_y: f32) -> Result<(), String> {
+    crate::errors::ensure_finite(&[("aim_x", aim_x), ("aim_y", aim_y)])?;
+    drop_item_toward(ctx, item_instance_id, quantity_to_drop, Some((aim_x, aim_y)))
+}
+
+// Shared by `drop_item` and `toss_item`. `aim` of None drops just in front of the player.
+fn drop_item_toward(
+    ctx: &ReducerContext,
+    item_instance_id: u64,
+    quantity_to_drop: u32,
+    aim: Option<(f32, f32)>,
 ) -> Result<(), String> {
     let sender_id = ctx.sender;
     log::info!("[DropItem] Player {:?} attempting to drop {} of item instance {}", sender_id, quantity_to_drop, item_instance_id);
@@ -1059,6 +1600,10 @@ pub fn drop_item(
     if !was_originally_equipped_or_fuel && item_to_drop.player_identity != sender_id {
         return Err(format!("Item instance {} not owned by caller.", item_instance_id));
     }
+    // Belt items are unslotted too, but only their owner may drop them
+    if was_originally_equipped_or_fuel && item_to_drop.player_identity != sender_id && crate::utility_belt::is_in_belt(ctx, &item_to_drop) {
+        return Err(GameError::NotOwner.into());
+    }
     // Validate quantity
     if quantity_to_drop == 0 {
         return Err("Cannot drop a quantity of 0.".to_string());
@@ -1090,11 +1635,11 @@ pub fn drop_item(
     // Armor unequipping happens via equip_armor_from_drag or potentially a context menu action.
 
     // --- 5. Calculate Drop Position ---
-    let (drop_x, drop_y) = calculate_drop_position(&player);
-    log::debug!("[DropItem] Calculated drop position: ({:.1}, {:.1}) for player {:?}", drop_x, drop_y, sender_id);
-    // TODO: Add collision check for drop position? Ensure it's not inside a wall/tree? For now, just place it.
+    let (drop_x, drop_y) = crate::dropped_item::calculate_aimed_drop_position(ctx, &player, aim);
+    log::debug!("[DropItem] Calculated drop position: ({:.1}, {:.1}) for player {:?} (aim {:?})", drop_x, drop_y, sender_id, aim);
 
     // --- 6. Handle Item Quantity (Split or Delete Original) ---
+    let dropped_state = ItemInstanceState::of(&item_to_drop);
     if quantity_to_drop == item_to_drop.quantity {
         // Dropping the entire stack
         log::info!("[DropItem] Dropping entire stack (ID: {}, Qty: {}). Deleting original InventoryItem.", item_instance_id, quantity_to_drop);
@@ -1115,8 +1660,9 @@ pub fn drop_item(
         ctx.db.inventory_item().instance_id().update(item_to_drop);
     }
 
-    // --- 7. Create Dropped Item Entity in World ---
-    create_dropped_item_entity(ctx, item_def.id, quantity_to_drop, drop_x, drop_y)?;
+    // --- 7. Create Dropped Item Entities in World (one per full stack) ---
+    let lock_secs = crate::world_config::get_drop_lock_secs(ctx, false);
+    create_stack_sized_dropped_item_entities(ctx, &item_def, quantity_to_drop, &dropped_state, drop_x, drop_y, Some(sender_id), lock_secs)?;
 
     log::info!("[DropItem] Successfully dropped {} of item def {} (Original ID: {}) at ({:.1}, {:.1}) for player {:?}.",
              quantity_to_drop, item_def.id, item_instance_id, drop_x, drop_y, sender_id);
@@ -1124,7 +1670,119 @@ pub fn drop_item(
     Ok(())
 }
 
-// --- NEW Reducer: Split and Move/Merge --- 
+/// Splits `quantity` off a stack in the caller's inventory or hotbar and drops it straight at
+/// their feet, so the client doesn't have to park the split stack in a free slot first.
+#[spacetimedb::reducer]
+pub fn split_and_drop(ctx: &ReducerContext, item_instance_id: u64, quantity: u32) -> Result<(), String> {
+    let sender_id = ctx.sender;
+
+    // 1. Find Player
+    let player = ctx.db.player().identity().find(sender_id)
+        .ok_or_else(|| GameError::NotFound("Player".to_string()))?;
+    if player.is_dead {
+        return Err("Cannot drop items while dead.".to_string());
+    }
+
+    // 2. Find Item & Validate (only stacks sitting in the caller's own slots)
+    let mut source_item = get_player_item(ctx, item_instance_id)?;
+    if source_item.inventory_slot.is_none() && source_item.hotbar_slot.is_none() {
+        return Err(format!("Item instance {} is not in the inventory or hotbar.", item_instance_id));
+    }
+    let item_def = ctx.db.item_definition().id().find(source_item.item_def_id)
+        .ok_or_else(|| format!("Definition missing for item {}", source_item.item_def_id))?;
+    if !item_def.is_stackable {
+        return Err(format!("Cannot split non-stackable item '{}'.", item_def.name));
+    }
+    if quantity == 0 || quantity >= source_item.quantity {
+        return Err(GameError::InvalidInput(format!(
+            "Split quantity must be between 1 and {} (stack has {}).",
+            source_item.quantity.saturating_sub(1), source_item.quantity
+        )).into());
+    }
+
+    // 3. Take the quantity off the source stack and drop it as a world item
+    source_item.quantity -= quantity;
+    ctx.db.inventory_item().instance_id().update(source_item.clone());
+
+    let (drop_x, drop_y) = calculate_drop_position(&player);
+    let lock_secs = crate::world_config::get_drop_lock_secs(ctx, false);
+    create_owner_locked_dropped_item_entity(ctx, item_def.id, quantity, &ItemInstanceState::of(&source_item), drop_x, drop_y, Some(sender_id), lock_secs)?;
+
+    log::info!("[SplitAndDrop] Player {:?} dropped {} of '{}' from stack {} ({} left) at ({:.1}, {:.1}).",
+             sender_id, quantity, item_def.name, item_instance_id, source_item.quantity, drop_x, drop_y);
+    Ok(())
+}
+
+/// Drops every stack of one item type from the caller's inventory and hotbar.
+/// Stackable items are merged and re-split into full-stack piles; non-stackable items each get their own drop.
+#[spacetimedb::reducer]
+pub fn drop_all_of_type(ctx: &ReducerContext, item_def_id: u64) -> Result<(), String> {
+    let sender_id = ctx.sender;
+
+    // 1. Find Player
+    let player = ctx.db.player().identity().find(sender_id)
+        .ok_or_else(|| GameError::NotFound("Player".to_string()))?;
+    if player.is_dead {
+        return Err("Cannot drop items while dead.".to_string());
+    }
+    let item_def = ctx.db.item_definition().id().find(item_def_id)
+        .ok_or_else(|| GameError::NotFound(format!("Item definition {}", item_def_id)))?;
+
+    // 2. Collect the caller's own slotted stacks of this type (equipped armor/fuel are left alone)
+    let stacks = slotted_stacks_of_type(ctx.db.inventory_item().player_identity().filter(&sender_id), item_def_id);
+    if stacks.is_empty() {
+        return Err(format!("No '{}' in inventory or hotbar to drop.", item_def.name));
+    }
+
+    // 3. Remove the stacks, unequipping the main hand if it held one of them
+    let active_equip_table = ctx.db.active_equipment();
+    if let Some(mut equip) = active_equip_table.player_identity().find(sender_id) {
+        if stacks.iter().any(|item| equip.equipped_item_instance_id == Some(item.instance_id)) {
+            equip.equipped_item_instance_id = None;
+            equip.equipped_item_def_id = None;
+            equip.swing_start_time_ms = 0;
+            active_equip_table.player_identity().update(equip);
+        }
+    }
+    let total_quantity: u32 = stacks.iter().map(|item| item.quantity).sum();
+    for item in &stacks {
+        ctx.db.inventory_item().instance_id().delete(item.instance_id);
+    }
+
+    // 4. Drop at the player's feet, reserved for the caller like a normal drop. Only stacks with
+    //    the same per-instance state are merged, so durability, spoilage etc. survive the drop.
+    let (drop_x, drop_y) = calculate_drop_position(&player);
+    let lock_secs = crate::world_config::get_drop_lock_secs(ctx, false);
+    for (state, quantity) in group_quantities_by_state(&stacks) {
+        create_stack_sized_dropped_item_entities(ctx, &item_def, quantity, &state, drop_x, drop_y, Some(sender_id), lock_secs)?;
+    }
+
+    log::info!("[DropAll] Player {:?} dropped {} '{}' from {} stacks at ({:.1}, {:.1}).",
+             sender_id, total_quantity, item_def.name, stacks.len(), drop_x, drop_y);
+    Ok(())
+}
+
+// The hotbar and inventory stacks of one item type among a player's items.
+fn slotted_stacks_of_type(player_items: impl IntoIterator<Item = InventoryItem>, item_def_id: u64) -> Vec<InventoryItem> {
+    player_items.into_iter()
+        .filter(|item| item.item_def_id == item_def_id && is_in_player_slot(item))
+        .collect()
+}
+
+// Sums the quantities of `stacks` per distinct instance state, in first-seen order.
+fn group_quantities_by_state(stacks: &[InventoryItem]) -> Vec<(ItemInstanceState, u32)> {
+    let mut groups: Vec<(ItemInstanceState, u32)> = Vec::new();
+    for item in stacks {
+        let state = ItemInstanceState::of(item);
+        match groups.iter_mut().find(|(group_state, _)| *group_state == state) {
+            Some((_, quantity)) => *quantity += item.quantity,
+            None => groups.push((state, item.quantity)),
+        }
+    }
+    groups
+}
+
+// --- NEW Reducer: Split and Move/Merge ---
 
 /// Splits a specified quantity from a source stack and attempts to move/merge 
 /// the new stack onto a target slot.
@@ -1357,7 +2015,7 @@ pub fn auto_add_wood_to_campfire(
                 // Check if the target item is also Wood
                 if target_item.item_def_id == wood_def_id {
                     // Attempt merge
-                    match crate::items::calculate_merge_result(&item_to_add, &target_item, &definition) {
+                    match crate::items::calculate_merge_result(ctx, &item_to_add, &target_item, &definition) {
                         Ok((qty_transfer, source_new_qty, target_new_qty, delete_source)) => {
                             if qty_transfer > 0 {
                                 log::info!(
@@ -1560,4 +2218,310 @@ pub fn equip_armor_from_inventory(ctx: &ReducerContext, item_instance_id: u64) -
     ctx.db.inventory_item().instance_id().update(item_to_equip);
 
     Ok(())
-} 
\ No newline at end of file
+} 
+
+// Reducer to move every worn armor piece back into the inventory.
+// Stops at the first piece that doesn't fit; that piece and any after it stay equipped.
+#[spacetimedb::reducer]
+pub fn unequip_all_armor(ctx: &ReducerContext) -> Result<(), String> {
+    let sender_id = ctx.sender;
+    let active_equip_table = ctx.db.active_equipment();
+    let mut equip = active_equip_table.player_identity().find(sender_id)
+                     .ok_or_else(|| "ActiveEquipment entry not found for player.".to_string())?;
+
+    let armor_slots: [(EquipmentSlot, &mut Option<u64>); 6] = [
+        (EquipmentSlot::Head, &mut equip.head_item_instance_id),
+        (EquipmentSlot::Chest, &mut equip.chest_item_instance_id),
+        (EquipmentSlot::Legs, &mut equip.legs_item_instance_id),
+        (EquipmentSlot::Feet, &mut equip.feet_item_instance_id),
+        (EquipmentSlot::Hands, &mut equip.hands_item_instance_id),
+        (EquipmentSlot::Back, &mut equip.back_item_instance_id),
+    ];
+
+    let player_items: Vec<InventoryItem> = ctx.db.inventory_item().player_identity().filter(&sender_id).collect();
+    let mut free_slots = empty_inventory_slots(&player_items).into_iter().peekable();
+    let mut moved_count = 0;
+    let mut left_equipped = 0;
+    for (slot, equipped_id) in armor_slots {
+        let Some(item_instance_id) = *equipped_id else { continue; };
+        let Some(&empty_slot) = free_slots.peek() else {
+            log::warn!("[UnequipAllArmor] Inventory full! Leaving item {} equipped in slot {:?}.", item_instance_id, slot);
+            left_equipped += 1;
+            continue;
+        };
+        match get_player_item(ctx, item_instance_id) {
+            Ok(mut armor_item) => {
+                free_slots.next();
+                armor_item.inventory_slot = Some(empty_slot);
+                armor_item.hotbar_slot = None;
+                ctx.db.inventory_item().instance_id().update(armor_item);
+                log::info!("[UnequipAllArmor] Moved item {} from slot {:?} to inventory slot {}.", item_instance_id, slot, empty_slot);
+                moved_count += 1;
+            }
+            Err(_) => {
+                log::error!("[UnequipAllArmor] Item {} equipped in slot {:?} not found. Clearing the slot.", item_instance_id, slot);
+            }
+        }
+        // Clear the slot in ActiveEquipment only once its item has left it
+        *equipped_id = None;
+    }
+
+    if moved_count == 0 && left_equipped > 0 {
+        return Err("Inventory full, cannot unequip armor.".to_string());
+    }
+    active_equip_table.player_identity().update(equip);
+    log::info!("[UnequipAllArmor] Player {:?} unequipped {} armor pieces ({} left equipped).", sender_id, moved_count, left_equipped);
+    Ok(())
+}
+
+// Actions offered by the client's item context menu (right-click)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, SpacetimeType)]
+pub enum ItemAction {
+    Use,          // Consume it
+    Equip,        // Armor to its body slot, tools/weapons to the main hand
+    Drop,         // Drop the whole stack at the player's feet
+    Split,        // Split the stack in half into the first empty slot
+    MoveToHotbar, // Move from the inventory to the first empty hotbar slot
+}
+
+// Single entry point for the context menu. Checks ownership once, then forwards to the
+// reducer that already implements the action, or explains why the action doesn't apply.
+#[spacetimedb::reducer]
+pub fn item_action(ctx: &ReducerContext, item_instance_id: u64, action: ItemAction) -> Result<(), String> {
+    log::info!("[ItemAction] Player {:?} chose {:?} on item {}.", ctx.sender, action, item_instance_id);
+
+    let item = get_player_item(ctx, item_instance_id)?;
+    let item_def = ctx.db.item_definition().id().find(item.item_def_id)
+        .ok_or_else(|| GameError::NotFound(format!("Item definition {}", item.item_def_id)))?;
+
+    match action {
+        ItemAction::Use => {
+            if item_def.category != ItemCategory::Consumable {
+                return Err(format!("'{}' can't be used.", item_def.name));
+            }
+            crate::consumables::consume_item(ctx, item_instance_id)
+        }
+        ItemAction::Equip => {
+            if item_def.category == ItemCategory::Armor {
+                equip_armor_from_inventory(ctx, item_instance_id)
+            } else if item_def.is_equippable {
+                crate::active_equipment::equip_item(ctx, item_instance_id)
+            } else {
+                Err(format!("'{}' can't be equipped.", item_def.name))
+            }
+        }
+        ItemAction::Drop => drop_item(ctx, item_instance_id, item.quantity),
+        ItemAction::Split => {
+            if !item_def.is_stackable || item.quantity < 2 {
+                return Err(format!("'{}' can't be split.", item_def.name));
+            }
+            split_stack_half(ctx, item_instance_id)
+        }
+        ItemAction::MoveToHotbar => move_to_first_available_hotbar_slot(ctx, item_instance_id),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn in_inventory_slot(instance_id: u64, slot: u16) -> InventoryItem {
+        InventoryItem { inventory_slot: Some(slot), ..test_inventory_item(instance_id, 1, 1) }
+    }
+
+    #[test]
+    fn new_items_always_leave_the_id_to_auto_inc() {
+        // A cloned row keeps its live id; inserting it as-is would collide with the original
+        let cloned = InventoryItem { hotbar_slot: Some(2), ..test_inventory_item(42, 7, 3) };
+        let prepared = with_auto_inc_id(cloned.clone());
+        assert_eq!(prepared.instance_id, 0);
+        assert_eq!((prepared.item_def_id, prepared.quantity, prepared.hotbar_slot), (7, 3, Some(2)));
+        assert_eq!(with_auto_inc_id(test_inventory_item(0, 7, 3)).instance_id, 0);
+    }
+
+    #[test]
+    fn stack_multiplier_sets_the_cap_for_adds_and_merges() {
+        let wood = ItemDefinition { id: 1, is_stackable: true, stack_size: 50, ..test_item_def("Wood", ItemCategory::Material) };
+        let axe = test_item_def("Axe", ItemCategory::Tool);
+        let max_stack = scaled_stack_size(&wood, 2); // An "x2 stacks" server
+        assert_eq!((max_stack, scaled_stack_size(&wood, 1), scaled_stack_size(&axe, 2)), (100, 50, 1));
+
+        // Add path: tops the existing stack up to the scaled cap, then opens a new stack
+        let player_items = vec![InventoryItem { quantity: 60, ..in_inventory_slot(1, 0) }];
+        let plan = plan_inventory_add(&player_items, &wood, max_stack, 70, &ItemInstanceState::default());
+        assert_eq!(plan.topped_up, vec![(1, 100)]);
+        assert_eq!(plan.new_stacks, vec![(Some(0), None, 30)]);
+
+        // Merge path: stops at the same cap
+        let source = test_inventory_item(2, 1, 70);
+        let target = test_inventory_item(1, 1, 60);
+        assert_eq!(merge_result_with_cap(&source, &target, &wood, max_stack), Ok((40, 30, 100, false)));
+        let full = test_inventory_item(1, 1, 100);
+        assert!(merge_result_with_cap(&source, &full, &wood, max_stack).is_err());
+    }
+
+    #[test]
+    fn grant_into_a_full_inventory_tops_up_and_spills_the_rest() {
+        let wood = ItemDefinition { id: 1, is_stackable: true, stack_size: 50, ..test_item_def("Wood", ItemCategory::Material) };
+        let state = ItemInstanceState::default();
+        // Every hotbar and inventory slot is taken; only the wood stack in slot 0 has room
+        let mut player_items: Vec<InventoryItem> = (0..24).map(|slot| in_inventory_slot(slot as u64 + 1, slot)).collect();
+        player_items[0].item_def_id = 1;
+        player_items[0].quantity = 45;
+        for item in player_items.iter_mut().skip(1) {
+            item.item_def_id = 2;
+        }
+        player_items.extend((0..6).map(|slot| InventoryItem { hotbar_slot: Some(slot), ..test_inventory_item(100 + slot as u64, 2, 1) }));
+
+        let plan = plan_inventory_add(&player_items, &wood, 50, 30, &state);
+        assert_eq!(plan.topped_up, vec![(1, 50)]);
+        assert!(plan.new_stacks.is_empty());
+        assert_eq!(plan.leftover, 25); // grant_or_drop drops this at the player's feet
+    }
+
+    #[test]
+    fn non_stackables_take_one_free_slot_each() {
+        let axe = test_item_def("Axe", ItemCategory::Tool);
+        let player_items: Vec<InventoryItem> = (0..6).map(|slot| InventoryItem { hotbar_slot: Some(slot), ..test_inventory_item(slot as u64 + 1, 2, 1) })
+            .chain((0..22).map(|slot| in_inventory_slot(10 + slot as u64, slot)))
+            .collect();
+        let plan = plan_inventory_add(&player_items, &axe, 1, 3, &ItemInstanceState::default());
+        assert_eq!(plan.new_stacks, vec![(None, Some(22), 1), (None, Some(23), 1)]);
+        assert_eq!(plan.leftover, 1);
+    }
+
+    #[test]
+    fn unequip_all_armor_only_finds_the_slots_that_are_left() {
+        let nearly_full: Vec<InventoryItem> = (0..23).map(|slot| in_inventory_slot(slot as u64 + 1, slot)).collect();
+        assert_eq!(empty_inventory_slots(&nearly_full), vec![23]);
+
+        let full: Vec<InventoryItem> = (0..24).map(|slot| in_inventory_slot(slot as u64 + 1, slot)).collect();
+        assert!(empty_inventory_slots(&full).is_empty());
+
+        // Hotbar items don't take inventory slots
+        let hotbar_only = vec![InventoryItem { hotbar_slot: Some(0), ..test_inventory_item(1, 1, 1) }];
+        assert_eq!(empty_inventory_slots(&hotbar_only).len(), 24);
+    }
+
+    #[test]
+    fn disconnect_takes_everything_a_fully_equipped_player_holds_and_wears() {
+        let mut equipment = crate::active_equipment::ActiveEquipment {
+            player_identity: Identity::default(),
+            equipped_item_def_id: Some(9),
+            equipped_item_instance_id: Some(1),
+            swing_start_time_ms: 1234,
+            weapon_drawn: true,
+            draw_ready_at_ms: 0,
+            head_item_instance_id: Some(2),
+            chest_item_instance_id: Some(3),
+            legs_item_instance_id: Some(4),
+            feet_item_instance_id: Some(5),
+            hands_item_instance_id: Some(6),
+            back_item_instance_id: Some(7),
+        };
+        assert_eq!(take_equipped_instance_ids(&mut equipment), vec![1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(equipment.equipped_item_def_id, None);
+        assert!(!equipment.weapon_drawn);
+        // Nothing is left pointing at the deleted items
+        assert_eq!(take_equipped_instance_ids(&mut equipment), Vec::<u64>::new());
+    }
+
+    #[test]
+    fn deposited_items_are_not_carried_slot_items() {
+        assert!(is_in_player_slot(&in_inventory_slot(1, 3)));
+        assert!(is_in_player_slot(&InventoryItem { hotbar_slot: Some(0), ..test_inventory_item(2, 1, 1) }));
+        // A stack in a box keeps its depositor's player_identity but has no slot, so disconnect leaves it alone
+        assert!(!is_in_player_slot(&test_inventory_item(3, 1, 1)));
+    }
+
+    #[test]
+    fn seeded_names_are_unique_and_cover_every_name_lookup() {
+        let defs = crate::items_database::get_initial_item_definitions();
+        let names: std::collections::HashSet<&str> = defs.iter().map(|def| def.name.as_str()).collect();
+        assert_eq!(names.len(), defs.len(), "duplicate item names would fail the unique name index");
+        for looked_up in ["Wood", "Stone", "Rock", "Mushroom", "Mushroom Spores", "Wooden Storage Box",
+                          crate::composter::FERTILIZER_NAME, crate::spoilage::ROTTEN_FOOD_NAME, crate::campfire::BURNT_FOOD_NAME] {
+            assert!(names.contains(looked_up), "get_item_def_by_name(\"{}\") has no seeded item", looked_up);
+        }
+    }
+
+    #[test]
+    fn dropping_all_of_a_type_keeps_each_stack_state() {
+        let worn_axe = InventoryItem { durability: Some(40), ..test_inventory_item(1, 9, 1) };
+        let new_axe = InventoryItem { durability: Some(100), ..test_inventory_item(2, 9, 1) };
+        let other_worn_axe = InventoryItem { durability: Some(40), ..test_inventory_item(3, 9, 1) };
+        let groups = group_quantities_by_state(&[worn_axe.clone(), new_axe.clone(), other_worn_axe]);
+        assert_eq!(groups, vec![(ItemInstanceState::of(&worn_axe), 2), (ItemInstanceState::of(&new_axe), 1)]);
+        assert!(groups[0].0.matches(&worn_axe));
+        assert!(!groups[0].0.matches(&new_axe));
+    }
+
+    #[test]
+    fn instance_state_matches_only_identical_stacks() {
+        let worn_axe = InventoryItem { durability: Some(40), ..test_inventory_item(1, 9, 1) };
+        let new_axe = InventoryItem { durability: Some(100), ..test_inventory_item(2, 9, 1) };
+        let state = ItemInstanceState::of(&worn_axe);
+        assert!(state.matches(&worn_axe));
+        assert!(!state.matches(&new_axe));
+        assert!(ItemInstanceState::default().matches(&test_inventory_item(3, 9, 1)));
+    }
+
+    #[test]
+    fn dropping_all_of_a_type_takes_every_slotted_stack() {
+        let wood = |instance_id, quantity| test_inventory_item(instance_id, 1, quantity);
+        let player_items = vec![
+            InventoryItem { inventory_slot: Some(0), ..wood(1, 50) },
+            InventoryItem { hotbar_slot: Some(2), ..wood(2, 50) },
+            InventoryItem { inventory_slot: Some(5), ..wood(3, 20) },
+            InventoryItem { inventory_slot: Some(6), ..test_inventory_item(4, 2, 10) }, // Stone stays
+            wood(5, 30), // Deposited in a campfire, not carried
+        ];
+        let stacks = slotted_stacks_of_type(player_items, 1);
+        assert_eq!(stacks.iter().map(|item| item.instance_id).collect::<Vec<_>>(), vec![1, 2, 3]);
+
+        // All three share a state, so they land as one 120-count drop split into stack-sized piles
+        let groups = group_quantities_by_state(&stacks);
+        assert_eq!(groups, vec![(ItemInstanceState::default(), 120)]);
+        assert_eq!(crate::dropped_item::pile_quantities(120, 50), vec![50, 50, 20]);
+    }
+
+    #[test]
+    fn consolidate_fills_a_stack_and_keeps_the_remainder() {
+        // Three 10-count stacks of an item that stacks to 20
+        let mut working: Vec<Option<InventoryItem>> = (1..=3).map(|id| Some(test_inventory_item(id, 7, 10))).collect();
+        let changed = pour_into_earlier_stacks(&mut working, |_| Some(20));
+        let quantities: Vec<Option<u32>> = working.iter().map(|item| item.as_ref().map(|i| i.quantity)).collect();
+        assert_eq!(quantities, vec![Some(20), None, Some(10)]);
+        assert_eq!(changed, vec![true, true, false]);
+    }
+
+    #[test]
+    fn consolidate_skips_differing_state_and_non_stackables() {
+        let full_bottle = InventoryItem { charges: Some(5), ..test_inventory_item(1, 7, 1) };
+        let empty_bottle = InventoryItem { charges: Some(0), ..test_inventory_item(2, 7, 1) };
+        let mut working = vec![Some(full_bottle), Some(empty_bottle)];
+        assert_eq!(pour_into_earlier_stacks(&mut working, |_| Some(10)), vec![false, false]);
+
+        let mut tools = vec![Some(test_inventory_item(3, 8, 1)), Some(test_inventory_item(4, 8, 1))];
+        assert_eq!(pour_into_earlier_stacks(&mut tools, |_| None), vec![false, false]);
+    }
+
+    #[test]
+    fn split_half_leaves_the_extra_on_the_original() {
+        assert_eq!(half_split_quantity(10), Ok(5));
+        assert_eq!(half_split_quantity(5), Ok(2));
+        assert!(half_split_quantity(1).is_err());
+    }
+
+    #[test]
+    fn split_destination_prefers_inventory_then_hotbar() {
+        assert_eq!(first_empty_player_slot(&[]), Some((None, Some(0))));
+
+        let mut items: Vec<InventoryItem> = (0..24).map(|slot| in_inventory_slot(slot as u64 + 1, slot)).collect();
+        items.push(InventoryItem { hotbar_slot: Some(0), ..test_inventory_item(100, 1, 1) });
+        assert_eq!(first_empty_player_slot(&items), Some((Some(1), None)));
+
+        items.extend((1..6).map(|slot| InventoryItem { hotbar_slot: Some(slot), ..test_inventory_item(100 + slot as u64, 1, 1) }));
+        assert_eq!(first_empty_player_slot(&items), None);
+    }
+}
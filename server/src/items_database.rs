@@ -1,4 +1,4 @@
-use crate::items::{ItemDefinition, ItemCategory, EquipmentSlot};
+use crate::items::{ItemDefinition, ItemCategory, EquipmentSlot, PlaceableKind};
 
 pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
     let initial_items = vec![
@@ -9,10 +9,22 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             category: ItemCategory::Material,
             icon_asset_name: "wood.png".to_string(),
             damage: None,
+            damage_falloff: false,
+            attack_range: None,
+            attack_arc_degrees: None,
             is_stackable: true,
             stack_size: 1000,
+            water_capacity: None,
             is_equippable: false,
             equipment_slot: None,
+            armor_set: None,
+            placeable_kind: None,
+            placement_footprint: None,
+            shelf_life_secs: None,
+            warmth_output: None,
+            fuel_burn_duration_secs: Some(crate::campfire::FUEL_CONSUME_INTERVAL_SECS),
+            cookable_into: None,
+            cook_time_secs: None,
         },
         ItemDefinition {
             id: 0,
@@ -21,10 +33,22 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             category: ItemCategory::Material,
             icon_asset_name: "stone.png".to_string(),
             damage: None,
+            damage_falloff: false,
+            attack_range: None,
+            attack_arc_degrees: None,
             is_stackable: true,
             stack_size: 1000,
+            water_capacity: None,
             is_equippable: false,
             equipment_slot: None,
+            armor_set: None,
+            placeable_kind: None,
+            placement_footprint: None,
+            shelf_life_secs: None,
+            warmth_output: None,
+            fuel_burn_duration_secs: None,
+            cookable_into: None,
+            cook_time_secs: None,
         },
         ItemDefinition {
             id: 0,
@@ -33,10 +57,22 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             category: ItemCategory::Tool,
             icon_asset_name: "wood_hatchet.png".to_string(),
             damage: Some(5),
+            damage_falloff: false,
+            attack_range: None,
+            attack_arc_degrees: None,
             is_stackable: false,
             stack_size: 1,
+            water_capacity: None,
             is_equippable: true,
             equipment_slot: None,
+            armor_set: None,
+            placeable_kind: None,
+            placement_footprint: None,
+            shelf_life_secs: None,
+            warmth_output: None,
+            fuel_burn_duration_secs: None,
+            cookable_into: None,
+            cook_time_secs: None,
         },
         ItemDefinition {
             id: 0,
@@ -45,10 +81,22 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             category: ItemCategory::Tool,
             icon_asset_name: "pick_axe.png".to_string(),
             damage: Some(5),
+            damage_falloff: false,
+            attack_range: None,
+            attack_arc_degrees: None,
             is_stackable: false,
             stack_size: 1,
+            water_capacity: None,
             is_equippable: true,
             equipment_slot: None,
+            armor_set: None,
+            placeable_kind: None,
+            placement_footprint: None,
+            shelf_life_secs: None,
+            warmth_output: None,
+            fuel_burn_duration_secs: None,
+            cookable_into: None,
+            cook_time_secs: None,
         },
         ItemDefinition {
             id: 0,
@@ -57,10 +105,22 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             category: ItemCategory::Tool,
             icon_asset_name: "rock_item.png".to_string(),
             damage: Some(1),
+            damage_falloff: false,
+            attack_range: None,
+            attack_arc_degrees: None,
             is_stackable: false,
             stack_size: 1,
+            water_capacity: None,
             is_equippable: true,
             equipment_slot: None,
+            armor_set: None,
+            placeable_kind: None,
+            placement_footprint: None,
+            shelf_life_secs: None,
+            warmth_output: None,
+            fuel_burn_duration_secs: None,
+            cookable_into: None,
+            cook_time_secs: None,
         },
         ItemDefinition {
             id: 0,
@@ -69,10 +129,22 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             category: ItemCategory::Placeable,
             icon_asset_name: "campfire.png".to_string(),
             damage: None,
+            damage_falloff: false,
+            attack_range: None,
+            attack_arc_degrees: None,
             is_stackable: false,
             stack_size: 1,
+            water_capacity: None,
             is_equippable: false,
             equipment_slot: None,
+            armor_set: None,
+            placeable_kind: Some(PlaceableKind::Campfire),
+            placement_footprint: None,
+            shelf_life_secs: None,
+            warmth_output: Some(crate::campfire::WARMTH_PER_SECOND),
+            fuel_burn_duration_secs: None,
+            cookable_into: None,
+            cook_time_secs: None,
         },
         ItemDefinition {
             id: 0,
@@ -81,10 +153,22 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             category: ItemCategory::Armor,
             icon_asset_name: "cloth_shirt.png".to_string(),
             damage: None,
+            damage_falloff: false,
+            attack_range: None,
+            attack_arc_degrees: None,
             is_stackable: false,
             stack_size: 1,
+            water_capacity: None,
             is_equippable: true,
             equipment_slot: Some(EquipmentSlot::Chest),
+            armor_set: Some("Cloth".to_string()),
+            placeable_kind: None,
+            placement_footprint: None,
+            shelf_life_secs: None,
+            warmth_output: None,
+            fuel_burn_duration_secs: None,
+            cookable_into: None,
+            cook_time_secs: None,
         },
         ItemDefinition {
             id: 0,
@@ -93,10 +177,22 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             category: ItemCategory::Armor,
             icon_asset_name: "cloth_pants.png".to_string(),
             damage: None,
+            damage_falloff: false,
+            attack_range: None,
+            attack_arc_degrees: None,
             is_stackable: false,
             stack_size: 1,
+            water_capacity: None,
             is_equippable: true,
             equipment_slot: Some(EquipmentSlot::Legs),
+            armor_set: Some("Cloth".to_string()),
+            placeable_kind: None,
+            placement_footprint: None,
+            shelf_life_secs: None,
+            warmth_output: None,
+            fuel_burn_duration_secs: None,
+            cookable_into: None,
+            cook_time_secs: None,
         },
         ItemDefinition {
             id: 0,
@@ -105,10 +201,22 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             category: ItemCategory::Armor,
             icon_asset_name: "cloth_hood.png".to_string(),
             damage: None,
+            damage_falloff: false,
+            attack_range: None,
+            attack_arc_degrees: None,
             is_stackable: false,
             stack_size: 1,
+            water_capacity: None,
             is_equippable: true,
             equipment_slot: Some(EquipmentSlot::Head),
+            armor_set: Some("Cloth".to_string()),
+            placeable_kind: None,
+            placement_footprint: None,
+            shelf_life_secs: None,
+            warmth_output: None,
+            fuel_burn_duration_secs: None,
+            cookable_into: None,
+            cook_time_secs: None,
         },
         ItemDefinition {
             id: 0,
@@ -117,10 +225,22 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             category: ItemCategory::Armor,
             icon_asset_name: "cloth_boots.png".to_string(),
             damage: None,
+            damage_falloff: false,
+            attack_range: None,
+            attack_arc_degrees: None,
             is_stackable: false,
             stack_size: 1,
+            water_capacity: None,
             is_equippable: true,
             equipment_slot: Some(EquipmentSlot::Feet),
+            armor_set: Some("Cloth".to_string()),
+            placeable_kind: None,
+            placement_footprint: None,
+            shelf_life_secs: None,
+            warmth_output: None,
+            fuel_burn_duration_secs: None,
+            cookable_into: None,
+            cook_time_secs: None,
         },
         ItemDefinition {
             id: 0,
@@ -129,10 +249,22 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             category: ItemCategory::Armor,
             icon_asset_name: "cloth_gloves.png".to_string(),
             damage: None,
+            damage_falloff: false,
+            attack_range: None,
+            attack_arc_degrees: None,
             is_stackable: false,
             stack_size: 1,
+            water_capacity: None,
             is_equippable: true,
             equipment_slot: Some(EquipmentSlot::Hands),
+            armor_set: Some("Cloth".to_string()),
+            placeable_kind: None,
+            placement_footprint: None,
+            shelf_life_secs: None,
+            warmth_output: None,
+            fuel_burn_duration_secs: None,
+            cookable_into: None,
+            cook_time_secs: None,
         },
         ItemDefinition {
             id: 0,
@@ -141,10 +273,22 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             category: ItemCategory::Armor,
             icon_asset_name: "burlap_backpack.png".to_string(),
             damage: None,
+            damage_falloff: false,
+            attack_range: None,
+            attack_arc_degrees: None,
             is_stackable: false,
             stack_size: 1,
+            water_capacity: None,
             is_equippable: true,
             equipment_slot: Some(EquipmentSlot::Back),
+            armor_set: None,
+            placeable_kind: None,
+            placement_footprint: None,
+            shelf_life_secs: None,
+            warmth_output: None,
+            fuel_burn_duration_secs: None,
+            cookable_into: None,
+            cook_time_secs: None,
         },
         ItemDefinition {
             id: 0,
@@ -153,10 +297,142 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             category: ItemCategory::Consumable,
             icon_asset_name: "mushroom.png".to_string(),
             damage: None,
+            damage_falloff: false,
+            attack_range: None,
+            attack_arc_degrees: None,
             is_stackable: true,
             stack_size: 50,
+            water_capacity: None,
             is_equippable: false,
             equipment_slot: None,
+            armor_set: None,
+            placeable_kind: None,
+            placement_footprint: None,
+            shelf_life_secs: Some(1800), // Spoils 30 minutes after being picked
+            warmth_output: None,
+            fuel_burn_duration_secs: None,
+            cookable_into: Some("Cooked Mushroom".to_string()),
+            cook_time_secs: Some(10), // Seconds over a burning fire
+        },
+        ItemDefinition {
+            id: 0,
+            name: "Cooked Mushroom".to_string(),
+            description: "A mushroom roasted over a fire. More filling than a raw one.".to_string(),
+            category: ItemCategory::Consumable,
+            icon_asset_name: "cooked_mushroom.png".to_string(),
+            damage: None,
+            damage_falloff: false,
+            attack_range: None,
+            attack_arc_degrees: None,
+            is_stackable: true,
+            stack_size: 50,
+            water_capacity: None,
+            is_equippable: false,
+            equipment_slot: None,
+            armor_set: None,
+            placeable_kind: None,
+            placement_footprint: None,
+            shelf_life_secs: Some(3600), // Cooked food keeps twice as long
+            warmth_output: None,
+            fuel_burn_duration_secs: None,
+            cookable_into: None,
+            cook_time_secs: None,
+        },
+        ItemDefinition {
+            id: 0,
+            name: "Mushroom Spores".to_string(),
+            description: "Plant these in soil to grow your own mushrooms.".to_string(),
+            category: ItemCategory::Material,
+            icon_asset_name: "mushroom_spores.png".to_string(),
+            damage: None,
+            damage_falloff: false,
+            attack_range: None,
+            attack_arc_degrees: None,
+            is_stackable: true,
+            stack_size: 50,
+            water_capacity: None,
+            is_equippable: false,
+            equipment_slot: None,
+            armor_set: None,
+            placeable_kind: None,
+            placement_footprint: None,
+            shelf_life_secs: None,
+            warmth_output: None,
+            fuel_burn_duration_secs: None,
+            cookable_into: None,
+            cook_time_secs: None,
+        },
+        ItemDefinition {
+            id: 0,
+            name: "Rotten Food".to_string(),
+            description: "Food that has gone bad. Edible, if you're desperate.".to_string(),
+            category: ItemCategory::Consumable,
+            icon_asset_name: "rotten_food.png".to_string(),
+            damage: None,
+            damage_falloff: false,
+            attack_range: None,
+            attack_arc_degrees: None,
+            is_stackable: true,
+            stack_size: 50,
+            water_capacity: None,
+            is_equippable: false,
+            equipment_slot: None,
+            armor_set: None,
+            placeable_kind: None,
+            placement_footprint: None,
+            shelf_life_secs: None,
+            warmth_output: None,
+            fuel_burn_duration_secs: None,
+            cookable_into: None,
+            cook_time_secs: None,
+        },
+        ItemDefinition {
+            id: 0,
+            name: "Burnt Food".to_string(),
+            description: "Left on the fire too long. Barely worth eating.".to_string(),
+            category: ItemCategory::Consumable,
+            icon_asset_name: "burnt_food.png".to_string(),
+            damage: None,
+            damage_falloff: false,
+            attack_range: None,
+            attack_arc_degrees: None,
+            is_stackable: true,
+            stack_size: 50,
+            water_capacity: None,
+            is_equippable: false,
+            equipment_slot: None,
+            armor_set: None,
+            placeable_kind: None,
+            placement_footprint: None,
+            shelf_life_secs: None,
+            warmth_output: None,
+            fuel_burn_duration_secs: None,
+            cookable_into: None,
+            cook_time_secs: None,
+        },
+        ItemDefinition {
+            id: 0,
+            name: "Water Bottle".to_string(),
+            description: "Holds a few sips of water. Fill it at a water source.".to_string(),
+            category: ItemCategory::Tool,
+            icon_asset_name: "water_bottle.png".to_string(),
+            damage: None,
+            damage_falloff: false,
+            attack_range: None,
+            attack_arc_degrees: None,
+            is_stackable: false, // Each bottle tracks its own water (charges)
+            stack_size: 1,
+            water_capacity: Some(crate::water::WATER_BOTTLE_CAPACITY),
+            is_equippable: false,
+            equipment_slot: None,
+            armor_set: None,
+            placeable_kind: None,
+            placement_footprint: None,
+            shelf_life_secs: None,
+            warmth_output: None,
+            fuel_burn_duration_secs: None,
+            cookable_into: None,
+            cook_time_secs: None,
         },
         // --- NEW Item: Wooden Storage Box ---
         ItemDefinition {
@@ -166,10 +442,70 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             category: ItemCategory::Placeable,
             icon_asset_name: "wooden_storage_box.png".to_string(), // Assume this asset exists client-side
             damage: None,
+            damage_falloff: false,
+            attack_range: None,
+            attack_arc_degrees: None,
             is_stackable: false, // Placeables are usually not stackable in inventory
             stack_size: 1,
+            water_capacity: None,
+            is_equippable: false,
+            equipment_slot: None,
+            armor_set: None,
+            placeable_kind: Some(PlaceableKind::WoodenStorageBox),
+            placement_footprint: Some(18.0), // Boxes sit side by side with collision circles just touching
+            shelf_life_secs: None,
+            warmth_output: None,
+            fuel_burn_duration_secs: None,
+            cookable_into: None,
+            cook_time_secs: None,
+        },
+        ItemDefinition {
+            id: 0,
+            name: "Composter".to_string(),
+            description: "Slowly turns rotten food and other scraps into fertilizer.".to_string(),
+            category: ItemCategory::Placeable,
+            icon_asset_name: "composter.png".to_string(),
+            damage: None,
+            damage_falloff: false,
+            attack_range: None,
+            attack_arc_degrees: None,
+            is_stackable: false,
+            stack_size: 1,
+            water_capacity: None,
+            is_equippable: false,
+            equipment_slot: None,
+            armor_set: None,
+            placeable_kind: Some(PlaceableKind::Composter),
+            placement_footprint: None,
+            shelf_life_secs: None,
+            warmth_output: None,
+            fuel_burn_duration_secs: None,
+            cookable_into: None,
+            cook_time_secs: None,
+        },
+        ItemDefinition {
+            id: 0,
+            name: "Fertilizer".to_string(),
+            description: "Rich compost. Helps crops grow.".to_string(),
+            category: ItemCategory::Material,
+            icon_asset_name: "fertilizer.png".to_string(),
+            damage: None,
+            damage_falloff: false,
+            attack_range: None,
+            attack_arc_degrees: None,
+            is_stackable: true,
+            stack_size: 50,
+            water_capacity: None,
             is_equippable: false,
             equipment_slot: None,
+            armor_set: None,
+            placeable_kind: None,
+            placement_footprint: None,
+            shelf_life_secs: None,
+            warmth_output: None,
+            fuel_burn_duration_secs: None,
+            cookable_into: None,
+            cook_time_secs: None,
         },
         ItemDefinition {
             id: 0,
@@ -178,10 +514,22 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             category: ItemCategory::Tool,
             icon_asset_name: "hammer.png".to_string(),
             damage: Some(15),
+            damage_falloff: true, // Heavy swings lose force at the tip
+            attack_range: Some(84.0),
+            attack_arc_degrees: Some(120.0), // Short, wide swings
             is_stackable: false,
             stack_size: 1,
+            water_capacity: None,
             is_equippable: true,
             equipment_slot: None,
+            armor_set: None,
+            placeable_kind: None,
+            placement_footprint: None,
+            shelf_life_secs: None,
+            warmth_output: None,
+            fuel_burn_duration_secs: None,
+            cookable_into: None,
+            cook_time_secs: None,
         },
         ItemDefinition {
             id: 0,
@@ -190,10 +538,22 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             category: ItemCategory::Tool,
             icon_asset_name: "dagger.png".to_string(),
             damage: Some(5),
+            damage_falloff: false,
+            attack_range: Some(72.0),
+            attack_arc_degrees: Some(60.0), // Short, narrow stabs
             is_stackable: false,
             stack_size: 1,
+            water_capacity: None,
             is_equippable: true,
             equipment_slot: None,
+            armor_set: None,
+            placeable_kind: None,
+            placement_footprint: None,
+            shelf_life_secs: None,
+            warmth_output: None,
+            fuel_burn_duration_secs: None,
+            cookable_into: None,
+            cook_time_secs: None,
         },
         ItemDefinition {
             id: 0,
@@ -202,10 +562,22 @@ pub fn get_initial_item_definitions() -> Vec<ItemDefinition> {
             category: ItemCategory::Tool,
             icon_asset_name: "sword.png".to_string(),
             damage: Some(10),
+            damage_falloff: true,
+            attack_range: None,
+            attack_arc_degrees: None,
             is_stackable: false,
             stack_size: 1,
+            water_capacity: None,
             is_equippable: true,
             equipment_slot: None,
+            armor_set: None,
+            placeable_kind: None,
+            placement_footprint: None,
+            shelf_life_secs: None,
+            warmth_output: None,
+            fuel_burn_duration_secs: None,
+            cookable_into: None,
+            cook_time_secs: None,
         },
     ];
     initial_items
@@ -223,6 +595,7 @@ pub const HAMMER_DEF: ItemDefinition = ItemDefinition {
     swing_cooldown_ms: Some(1000),
     durability: Some(100),
     stack_size: 1,
+    water_capacity: None,
     icon_path: "items/hammer.png",
 };
 
@@ -237,6 +610,7 @@ pub const DAGGER_DEF: ItemDefinition = ItemDefinition {
     swing_cooldown_ms: Some(300),
     durability: Some(100),
     stack_size: 1,
+    water_capacity: None,
     icon_path: "items/dagger.png",
 };
 
@@ -251,6 +625,7 @@ pub const SWORD_DEF: ItemDefinition = ItemDefinition {
     swing_cooldown_ms: Some(500),
     durability: Some(100),
     stack_size: 1,
+    water_capacity: None,
     icon_path: "items/sword.png",
 };
 
@@ -24,6 +24,35 @@ mod wooden_storage_box; // Add the new module
 mod items_database; // <<< ADDED module declaration
 mod starting_items; // <<< ADDED module declaration
 mod inventory_management; // <<< ADDED new module
+mod world_config; // Global admin-tunable settings
+mod errors; // Shared error codes for reducers
+mod interaction; // Generic 'press E' interaction dispatch
+mod placement; // Generic item-to-world placement
+mod water; // Water sources and water containers
+mod skills; // Per-player skill XP (cooking)
+mod combat_log; // PvP combat tagging and delayed logout
+mod player_stats; // Levels, experience and level-up buffs
+mod utility_belt; // Small per-player consumables container
+mod viewport; // Per-client visible world rectangle
+mod presence; // Lightweight online/offline projection of players
+mod moderation; // Player reports for admin review
+mod spoilage; // Perishable food freshness and rotting
+mod composter; // Placeable container turning compostables into fertilizer
+mod farming; // Planting seeds and growing crops
+mod achievements; // Per-player achievement unlocks
+mod nearby_players; // Per-viewer snapshot of players in range
+mod containers; // Generic reducers for any slot container
+mod nearby_storage; // Per-viewer snapshot of storage boxes in range
+mod armor_sets; // Full-set armor bonuses
+mod player_snapshot; // Per-player consolidated state for client bootstrap
+mod examine; // Admin inspection of entity state for support
+mod item_quality; // Crafted quality tiers scaling item stats
+mod resource_remnant; // Stumps and rubble left by destroyed resource nodes
+mod hazards; // Environmental dangers like falling trees
+mod offline_summary; // What happened to a player's things while they were away
+mod pickup_preferences; // Per-player item types that bulk pickup skips
+mod batch_results; // Outcome reporting for batch reducers that can partially succeed
+mod chat; // Global chat messages
 
 // Import Table Traits needed in this module
 use crate::tree::tree as TreeTableTrait; 
@@ -31,7 +60,6 @@ use crate::stone::stone as StoneTableTrait;
 use crate::campfire::campfire as CampfireTableTrait; // Already present, but good to keep together
 use crate::world_state::world_state as WorldStateTableTrait; // Already present
 use crate::items::inventory_item as InventoryItemTableTrait; // Already present
-use crate::items::item_definition as ItemDefinitionTableTrait; // Already present
 use crate::player as PlayerTableTrait; // Needed for ctx.db.player()
 use crate::active_equipment::active_equipment as ActiveEquipmentTableTrait;
 // Import the schedule table trait
@@ -39,11 +67,14 @@ use crate::dropped_item::dropped_item_despawn_schedule as DroppedItemDespawnSche
 // NEW: Import the campfire fuel check schedule table trait
 use crate::campfire::campfire_fuel_check_schedule as CampfireFuelCheckScheduleTableTrait;
 use crate::wooden_storage_box::wooden_storage_box as WoodenStorageBoxTableTrait;
+use crate::interaction::player_interaction as PlayerInteractionTableTrait;
+use crate::viewport::client_viewport as ClientViewportTableTrait;
 
 // Use specific items needed globally (or use qualified paths)
 // use crate::items::{inventory_item as InventoryItemTableTrait, item_definition as ItemDefinitionTableTrait}; 
 use crate::world_state::{TimeOfDay, BASE_WARMTH_DRAIN_PER_SECOND, WARMTH_DRAIN_MULTIPLIER_DAWN_DUSK, WARMTH_DRAIN_MULTIPLIER_NIGHT, WARMTH_DRAIN_MULTIPLIER_MIDNIGHT};
-use crate::campfire::{Campfire, WARMTH_RADIUS_SQUARED, WARMTH_PER_SECOND, CAMPFIRE_COLLISION_RADIUS, CAMPFIRE_CAMPFIRE_COLLISION_DISTANCE_SQUARED, CAMPFIRE_COLLISION_Y_OFFSET, PLAYER_CAMPFIRE_COLLISION_DISTANCE_SQUARED, PLAYER_CAMPFIRE_INTERACTION_DISTANCE_SQUARED };
+use crate::campfire::{Campfire, WARMTH_RADIUS_SQUARED, CAMPFIRE_COLLISION_RADIUS, CAMPFIRE_COLLISION_Y_OFFSET, PLAYER_CAMPFIRE_COLLISION_DISTANCE_SQUARED };
+use crate::utils::get_distance_squared;
 
 // --- World/Player Constants --- 
 pub(crate) const WORLD_WIDTH_TILES: u32 = 100;
@@ -57,6 +88,8 @@ const PLAYER_DIAMETER_SQUARED: f32 = (PLAYER_RADIUS * 2.0) * (PLAYER_RADIUS * 2.
 // Passive Stat Drain Rates
 const HUNGER_DRAIN_PER_SECOND: f32 = 100.0 / (30.0 * 60.0); 
 const THIRST_DRAIN_PER_SECOND: f32 = 100.0 / (20.0 * 60.0); 
+const SPRINT_HUNGER_DRAIN_MULTIPLIER: f32 = 2.0; // Hunger drains this much faster while sprinting
+const SPRINT_THIRST_DRAIN_MULTIPLIER: f32 = 2.5; // Thirst drains this much faster while sprinting
 const STAMINA_DRAIN_PER_SECOND: f32 = 20.0; 
 const STAMINA_RECOVERY_PER_SECOND: f32 = 5.0;  
 const SPRINT_SPEED_MULTIPLIER: f32 = 1.5;     
@@ -75,10 +108,6 @@ const HEALTH_RECOVERY_PER_SEC: f32 = 1.0;
 const HEALTH_LOSS_PER_SEC_LOW_WARMTH: f32 = 0.6; // Slightly higher than thirst/hunger
 const LOW_WARMTH_SPEED_PENALTY: f32 = 0.8; // 20% speed reduction when cold
 
-// NEW: Campfire placement range constant
-const CAMPFIRE_PLACEMENT_MAX_DISTANCE: f32 = 96.0;
-const CAMPFIRE_PLACEMENT_MAX_DISTANCE_SQUARED: f32 = CAMPFIRE_PLACEMENT_MAX_DISTANCE * CAMPFIRE_PLACEMENT_MAX_DISTANCE;
-
 // Player table to store position and color
 #[spacetimedb::table(name = player, public)]
 #[derive(Clone)]
@@ -101,6 +130,10 @@ pub struct Player {
     pub is_dead: bool,
     pub respawn_at: Timestamp,
     pub last_hit_time: Option<Timestamp>,
+    pub combat_until: Option<Timestamp>, // PvP combat tag; while set and in the future, disconnecting leaves the body behind
+    pub days_survived: u32, // Full in-game days survived in the current life. Kept while dead, reset on respawn
+    pub selected_hotbar_slot: Option<u8>, // Hotbar slot (0-5) the player has selected; its item is in the main hand. None = nothing selected
+    pub last_drank_at: Option<Timestamp>, // Last drink straight from a water source, for the drink cooldown (see water.rs)
 }
 
 // --- Lifecycle Reducers ---
@@ -114,6 +147,17 @@ pub fn init_module(ctx: &ReducerContext) -> Result<(), String> {
     crate::dropped_item::init_dropped_item_schedule(ctx)?;
     // NEW: Initialize the campfire fuel check schedule
     crate::campfire::init_campfire_fuel_schedule(ctx)?;
+    crate::campfire::init_campfire_state_check_schedule(ctx)?;
+    crate::resource_remnant::init_remnant_schedule(ctx)?;
+    crate::spoilage::init_spoilage_schedule(ctx)?;
+    crate::composter::init_composter_schedule(ctx)?;
+    crate::farming::init_crop_growth_schedule(ctx)?;
+    crate::combat_log::init_combat_log_cleanup_schedule(ctx)?;
+    crate::viewport::init_viewport_cleanup_schedule(ctx)?;
+    crate::chat::init_chat_cleanup_schedule(ctx)?;
+    // Seed global config (records the publishing identity as admin)
+    crate::world_config::init_world_config(ctx)?;
+    crate::achievements::seed_achievements(ctx)?;
 
     log::info!("Module initialization complete.");
     Ok(())
@@ -123,10 +167,14 @@ pub fn init_module(ctx: &ReducerContext) -> Result<(), String> {
 #[spacetimedb::reducer(client_connected)]
 pub fn identity_connected(ctx: &ReducerContext) -> Result<(), String> {
     // Call seeders using qualified paths
+    crate::world_state::seed_world_state(ctx)?; // Call the world state seeder (first: it holds the world seed)
     crate::environment::seed_environment(ctx)?; // Call the updated seeder
     crate::items::seed_items(ctx)?; // Call the item seeder
-    crate::world_state::seed_world_state(ctx)?; // Call the world state seeder
     // No seeder needed for Campfire yet, table will be empty initially
+    // Reclaim a body left behind by a combat-log disconnect, if any
+    crate::combat_log::cancel_pending_combat_logout(ctx, ctx.sender);
+    crate::offline_summary::record_offline_summary(ctx, ctx.sender); // Before set_online overwrites last_seen
+    crate::presence::set_online(ctx, ctx.sender, true);
     Ok(())
 }
 
@@ -135,39 +183,130 @@ pub fn identity_connected(ctx: &ReducerContext) -> Result<(), String> {
 pub fn identity_disconnected(ctx: &ReducerContext) {
     log::info!("identity_disconnected triggered for identity: {:?}", ctx.sender);
     let sender_id = ctx.sender;
+    crate::presence::set_online(ctx, sender_id, false);
+    crate::nearby_players::clear_nearby_players(ctx, sender_id);
+    crate::nearby_storage::clear_nearby_storage(ctx, sender_id);
+    crate::player_snapshot::clear_player_snapshot(ctx, sender_id);
+    crate::active_equipment::clear_item_comparison(ctx, sender_id);
+    crate::moderation::clear_report_review(ctx, sender_id);
+
+    match ctx.db.player().identity().find(sender_id) {
+        // Combat-tagged players can't vanish instantly; their body lingers until the tag expires
+        Some(player) if crate::combat_log::is_in_combat(&player, ctx.timestamp) => {
+            crate::combat_log::schedule_combat_logout(ctx, &player);
+        }
+        Some(_) => cleanup_disconnected_player(ctx, sender_id),
+        None => {
+            log::warn!("Disconnected identity {:?} did not have a registered player entity. No cleanup needed.", sender_id);
+        }
+    }
+}
+
+/// Removes a player and everything tied to their session (called on disconnect, or when a combat-log window ends).
+/// Item policy: there is no character persistence, so everything the player carries is deleted —
+/// inventory, hotbar, held and worn equipment, and the utility belt. Items they deposited into world
+/// containers are not theirs to carry anymore and stay where they are.
+pub(crate) fn cleanup_disconnected_player(ctx: &ReducerContext, sender_id: Identity) {
     let players = ctx.db.player();
-    
+
     if let Some(player) = players.identity().find(sender_id) {
         let username = player.username.clone();
         // 1. Delete the Player entity
         players.identity().delete(sender_id);
         log::info!("Deleted Player entity for disconnected player: {} ({:?})", username, sender_id);
 
-        // 2. Delete player's inventory items (ONLY those in main inventory or hotbar)
-        let inventory = ctx.db.inventory_item();
-        let mut items_to_delete = Vec::new();
-        for item in inventory.iter().filter(|i| i.player_identity == sender_id) {
-            // Only delete if actually in inventory/hotbar
-            if item.inventory_slot.is_some() || item.hotbar_slot.is_some() {
-                items_to_delete.push(item.instance_id);
-            }
-        }
-        let delete_count = items_to_delete.len();
-        for item_instance_id in items_to_delete {
-            inventory.instance_id().delete(item_instance_id);
-        }
-        log::info!("Deleted {} inventory items for player {:?}", delete_count, sender_id);
+        // 2. Delete the items they carry (inventory, hotbar, equipped). Items stored in containers stay.
+        let delete_count = crate::items::delete_carried_items(ctx, sender_id);
+        log::info!("Deleted {} carried items for player {:?}", delete_count, sender_id);
 
-        // 3. Delete player's active equipment entry
+        // 3. Delete player's active equipment entry (its items were deleted above, so nothing is orphaned)
         let equipment_table = ctx.db.active_equipment();
         if equipment_table.player_identity().find(&sender_id).is_some() {
             equipment_table.player_identity().delete(sender_id);
             log::info!("Deleted active equipment for player {:?}", sender_id);
         }
 
-    } else {
-        log::warn!("Disconnected identity {:?} did not have a registered player entity. No cleanup needed.", sender_id);
+        // 4. Delete player's last interaction record
+        ctx.db.player_interaction().player_identity().delete(sender_id);
+
+        // 5. Delete the player's utility belt and its contents
+        crate::utility_belt::delete_player_belt(ctx, sender_id);
+
+        // 6. Delete the client's viewport
+        ctx.db.client_viewport().client_identity().delete(sender_id);
+
+    }
+}
+
+// --- Spawn Position Helpers ---
+const SPAWN_SEARCH_ATTEMPTS: u32 = 20;
+
+/// Picks a random, collision-free point inside the configured spawn region (see WorldConfig),
+/// so new and respawning players spread out instead of stacking on one coordinate.
+/// Points are uniform over the disc. If every attempt collides, falls back to the region's center.
+fn find_spawn_position(ctx: &ReducerContext, spawning_player: Identity) -> (f32, f32) {
+    let (center_x, center_y, radius) = crate::world_config::get_spawn_region(ctx);
+    let mut rng = crate::utils::GameRng::from_ctx(ctx);
+    for _ in 0..SPAWN_SEARCH_ATTEMPTS {
+        let (spawn_x, spawn_y) = random_spawn_point(&mut rng, center_x, center_y, radius);
+        if is_spawn_position_clear(ctx, spawn_x, spawn_y, spawning_player) {
+            return (spawn_x, spawn_y);
+        }
+    }
+    log::warn!("Could not find clear spawn point for {:?} after {} attempts, spawning at region center (may collide).",
+             spawning_player, SPAWN_SEARCH_ATTEMPTS);
+    (center_x, center_y)
+}
+
+/// A uniformly random point on the spawn disc (sqrt of the radius draw keeps the center from
+/// clustering), clamped so a player there stays inside the world.
+fn random_spawn_point(rng: &mut crate::utils::GameRng, center_x: f32, center_y: f32, radius: f32) -> (f32, f32) {
+    let angle = rng.gen_range(0.0..std::f32::consts::TAU);
+    let distance = radius * rng.gen_range(0.0f32..=1.0).sqrt();
+    let spawn_x = (center_x + distance * angle.cos()).clamp(PLAYER_RADIUS, WORLD_WIDTH_PX - PLAYER_RADIUS);
+    let spawn_y = (center_y + distance * angle.sin()).clamp(PLAYER_RADIUS, WORLD_HEIGHT_PX - PLAYER_RADIUS);
+    (spawn_x, spawn_y)
+}
+
+/// True if a player standing at (x, y) wouldn't overlap another player, tree, stone, campfire or box.
+fn is_spawn_position_clear(ctx: &ReducerContext, spawn_x: f32, spawn_y: f32, spawning_player: Identity) -> bool {
+    // 1. Check Player-Player Collision (ignoring the spawning player's own row on respawn)
+    let player_collision = ctx.db.player().iter().any(|other_player| {
+        other_player.identity != spawning_player
+            && get_distance_squared(spawn_x, spawn_y, other_player.position_x, other_player.position_y) < PLAYER_DIAMETER_SQUARED
+    });
+    if player_collision {
+        return false;
     }
+    // 2. Check Player-Tree Collision
+    let tree_collision = ctx.db.tree().iter().any(|tree| {
+        get_distance_squared(spawn_x, spawn_y, tree.pos_x, tree.pos_y - crate::tree::TREE_COLLISION_Y_OFFSET)
+            < crate::tree::PLAYER_TREE_COLLISION_DISTANCE_SQUARED
+    });
+    if tree_collision {
+        return false;
+    }
+    // 3. Check Player-Stone Collision
+    let stone_collision = ctx.db.stone().iter().any(|stone| {
+        get_distance_squared(spawn_x, spawn_y, stone.pos_x, stone.pos_y - crate::stone::STONE_COLLISION_Y_OFFSET)
+            < crate::stone::PLAYER_STONE_COLLISION_DISTANCE_SQUARED
+    });
+    if stone_collision {
+        return false;
+    }
+    // 4. Check Player-Campfire Collision
+    let campfire_collision = ctx.db.campfire().iter().any(|fire| {
+        get_distance_squared(spawn_x, spawn_y, fire.pos_x, fire.pos_y - CAMPFIRE_COLLISION_Y_OFFSET)
+            < PLAYER_CAMPFIRE_COLLISION_DISTANCE_SQUARED
+    });
+    if campfire_collision {
+        return false;
+    }
+    // 5. Check Player-WoodenStorageBox Collision
+    !ctx.db.wooden_storage_box().iter().any(|box_instance| {
+        get_distance_squared(spawn_x, spawn_y, box_instance.pos_x, box_instance.pos_y - crate::wooden_storage_box::BOX_COLLISION_Y_OFFSET)
+            < crate::wooden_storage_box::PLAYER_BOX_COLLISION_DISTANCE_SQUARED
+    })
 }
 
 // Register a new player
@@ -176,11 +315,7 @@ pub fn register_player(ctx: &ReducerContext, username: String) -> Result<(), Str
     log::info!("register_player called by {:?} with username: {}", ctx.sender, username);
     let sender_id = ctx.sender;
     let players = ctx.db.player();
-    let trees = ctx.db.tree();
-    let stones = ctx.db.stone();
-    let campfires = ctx.db.campfire();
-    let wooden_storage_boxes = ctx.db.wooden_storage_box();
-    
+
     // Check if username is already taken by *any* player
     let username_taken = players.iter().any(|p| p.username == username);
     if username_taken {
@@ -194,111 +329,8 @@ pub fn register_player(ctx: &ReducerContext, username: String) -> Result<(), Str
         return Err("Player identity already registered".to_string());
     }
     
-    // --- Find a valid spawn position --- 
-    let initial_x = 640.0; 
-    let initial_y = 480.0;
-    let mut spawn_x = initial_x;
-    let mut spawn_y = initial_y;
-    let max_attempts = 10;
-    let offset_step = PLAYER_RADIUS * 2.5;
-    let mut attempt = 0;
-
-    loop {
-        let mut collision = false;
-
-        // 1. Check Player-Player Collision
-        for other_player in players.iter() {
-            let dx = spawn_x - other_player.position_x;
-            let dy = spawn_y - other_player.position_y;
-            if (dx * dx + dy * dy) < PLAYER_DIAMETER_SQUARED {
-                collision = true;
-                break;
-            }
-        }
-
-        // 2. Check Player-Tree Collision (if no player collision)
-        if !collision {
-            for tree in trees.iter() {
-                let dx = spawn_x - tree.pos_x;
-                let dy = spawn_y - (tree.pos_y - crate::tree::TREE_COLLISION_Y_OFFSET); // Already qualified
-                let dist_sq = dx * dx + dy * dy;
-                if dist_sq < crate::tree::PLAYER_TREE_COLLISION_DISTANCE_SQUARED { // Already qualified
-                    collision = true;
-                    break;
-                }
-            }
-        }
-
-        // 2.5 Check Player-Stone Collision (if no player/tree collision)
-        if !collision {
-            for stone in stones.iter() {
-                let dx = spawn_x - stone.pos_x;
-                let dy = spawn_y - (stone.pos_y - crate::stone::STONE_COLLISION_Y_OFFSET); // Already qualified
-                let dist_sq = dx * dx + dy * dy;
-                if dist_sq < crate::stone::PLAYER_STONE_COLLISION_DISTANCE_SQUARED { // Already qualified
-                    collision = true;
-                    break;
-                }
-            }
-        }
-
-        // 2.7 Check Player-Campfire Collision
-        if !collision {
-            for fire in campfires.iter() {
-                let dx = spawn_x - fire.pos_x;
-                let dy = spawn_y - (fire.pos_y - CAMPFIRE_COLLISION_Y_OFFSET);
-                let dist_sq = dx * dx + dy * dy;
-                // Use specific player-campfire collision check distance
-                if dist_sq < PLAYER_CAMPFIRE_COLLISION_DISTANCE_SQUARED {
-                    collision = true;
-                    break;
-                }
-            }
-        }
-
-        // 2.8 Check Player-WoodenStorageBox Collision <<< ADDED Check
-        if !collision {
-            for box_instance in wooden_storage_boxes.iter() {
-                // Use constants from wooden_storage_box module
-                let dx = spawn_x - box_instance.pos_x;
-                let dy = spawn_y - (box_instance.pos_y - crate::wooden_storage_box::BOX_COLLISION_Y_OFFSET); 
-                let dist_sq = dx * dx + dy * dy;
-                // Use specific player-box collision check distance
-                if dist_sq < crate::wooden_storage_box::PLAYER_BOX_COLLISION_DISTANCE_SQUARED {
-                    collision = true;
-                    break;
-                }
-            }
-        }
-
-        // 3. Decide if position is valid or max attempts reached
-        if !collision || attempt >= max_attempts {
-            if attempt >= max_attempts && collision { 
-                 log::warn!("Could not find clear spawn point for {}, spawning at default (may collide).", username);
-                 spawn_x = initial_x;
-                 spawn_y = initial_y;
-            }
-            break;
-        }
-
-        // Simple offset pattern: move right, down, left, up, then spiral out slightly?
-        // This is basic, could be improved (random, spiral search)
-        match attempt % 4 {
-            0 => spawn_x += offset_step, 
-            1 => spawn_y += offset_step, 
-            2 => spawn_x -= offset_step * 2.0, 
-            3 => spawn_y -= offset_step * 2.0, 
-            _ => {}, 
-        }
-        // Reset to center if offset gets too wild after a few attempts (basic safeguard)
-        if attempt == 5 { 
-             spawn_x = initial_x;
-             spawn_y = initial_y;
-             spawn_x += offset_step * 1.5; 
-             spawn_y += offset_step * 1.5;
-        }
-        attempt += 1;
-    }
+    // --- Find a valid spawn position ---
+    let (spawn_x, spawn_y) = find_spawn_position(ctx, sender_id);
     // --- End spawn position logic ---
 
     let color = random_color(&username);
@@ -321,12 +353,17 @@ pub fn register_player(ctx: &ReducerContext, username: String) -> Result<(), Str
         is_dead: false,
         respawn_at: ctx.timestamp,
         last_hit_time: None,
+        combat_until: None,
+        days_survived: 0,
+        selected_hotbar_slot: None,
+        last_drank_at: None,
     };
     
     // Insert the new player
     match players.try_insert(player) {
         Ok(_) => {
             log::info!("Player registered: {}. Initializing systems...", username);
+            crate::presence::mark_registered(ctx, sender_id, &username);
 
             // Initialize player stats
             match crate::player_stats::initialize_player_stats(ctx, sender_id) {
@@ -354,116 +391,11 @@ pub fn register_player(ctx: &ReducerContext, username: String) -> Result<(), Str
     }
 }
 
-// Reducer to place a campfire
+// Reducer to place a campfire (thin wrapper around the generic placement flow)
 #[spacetimedb::reducer]
 pub fn place_campfire(ctx: &ReducerContext, item_instance_id: u64, world_x: f32, world_y: f32) -> Result<(), String> {
-    let sender_id = ctx.sender;
-    let inventory_items = ctx.db.inventory_item();
-    let item_defs = ctx.db.item_definition();
-    let players = ctx.db.player();
-    let campfires = ctx.db.campfire();
-
-    log::info!(
-        "[PlaceCampfire] Player {:?} attempting placement of item {} at ({:.1}, {:.1})",
-        sender_id, item_instance_id, world_x, world_y
-    );
-
-    // --- 1. Check Collision / Placement Rules --- 
-
-    // --- 2. Find the "Camp Fire" item definition ---
-    let campfire_def_id = item_defs.iter()
-        .find(|def| def.name == "Camp Fire")
-        .map(|def| def.id)
-        .ok_or_else(|| "Item definition 'Camp Fire' not found.".to_string())?;
-
-    // --- 3. Find the specific item instance and validate --- 
-    let item_to_consume = inventory_items.instance_id().find(item_instance_id)
-        .ok_or_else(|| format!("Item instance {} not found.", item_instance_id))?;
-    
-    // Validate ownership
-    if item_to_consume.player_identity != sender_id {
-        return Err(format!("Item instance {} not owned by player {:?}.", item_instance_id, sender_id));
-    }
-    // Validate item type
-    if item_to_consume.item_def_id != campfire_def_id {
-        return Err(format!("Item instance {} is not a Camp Fire (expected def {}, got {}).", 
-                        item_instance_id, campfire_def_id, item_to_consume.item_def_id));
-    }
-    // Validate location (must be in inv or hotbar)
-    if item_to_consume.inventory_slot.is_none() && item_to_consume.hotbar_slot.is_none() {
-        return Err(format!("Item instance {} must be in inventory or hotbar to be placed.", item_instance_id));
-    }
-    
-    // Use the validated item_instance_id directly
-    let item_instance_id_to_delete = item_instance_id;
-
-    // --- 4. Validate Placement Distance --- 
-    if let Some(player) = players.identity().find(sender_id) {
-        // ... existing code ...
-    } else {
-        return Err("Player not found".to_string());
-    }
-
-    // --- 5. Consume the Item --- 
-    log::info!(
-        "[PlaceCampfire] Consuming item instance {} (Def ID: {}) from player {:?}",
-        item_instance_id_to_delete, campfire_def_id, sender_id
-    );
-    inventory_items.instance_id().delete(item_instance_id_to_delete);
-
-    // --- 6. Create Campfire Entity ---
-    // --- 6a. Create Initial Fuel Item (Wood) --- 
-    let wood_def = item_defs.iter()
-        .find(|def| def.name == "Wood")
-        .ok_or_else(|| "Wood item definition not found for initial fuel".to_string())?;
-        
-    let initial_fuel_item = crate::items::InventoryItem {
-        instance_id: 0, // Auto-inc
-        player_identity: sender_id, // Belongs to the placer initially (needed? maybe not)
-        item_def_id: wood_def.id,
-        quantity: 50, // Start with 50 wood
-        hotbar_slot: None, // Not in hotbar
-        inventory_slot: None, // Not in inventory (it's "in" the campfire slot 0)
-    };
-    // Insert the fuel item and get its generated instance ID
-    let inserted_fuel_item = inventory_items.insert(initial_fuel_item);
-    let fuel_instance_id = inserted_fuel_item.instance_id;
-    log::info!("[PlaceCampfire] Created initial fuel item (Wood, instance {}) for campfire.", fuel_instance_id);
-
-    // --- 6b. Initialize Campfire with Fuel and Burning --- 
-    let current_time = ctx.timestamp;
-    let first_consumption_time = current_time + Duration::from_secs(crate::campfire::FUEL_CONSUME_INTERVAL_SECS);
-    
-    // Initialize all fields explicitly
-    let new_campfire = crate::campfire::Campfire {
-        id: 0, // Auto-incremented
-        pos_x: world_x,
-        pos_y: world_y,
-        placed_by: sender_id,
-        placed_at: ctx.timestamp,
-        is_burning: true, // Start burning
-        // Initialize all fuel slots to None
-        fuel_instance_id_0: Some(fuel_instance_id), // Add the wood
-        fuel_def_id_0: Some(wood_def.id),
-        fuel_instance_id_1: None,
-        fuel_def_id_1: None,
-        fuel_instance_id_2: None,
-        fuel_def_id_2: None,
-        fuel_instance_id_3: None,
-        fuel_def_id_3: None,
-        fuel_instance_id_4: None,
-        fuel_def_id_4: None,
-        next_fuel_consume_at: Some(first_consumption_time), // Schedule consumption
-    };
-
-    campfires.try_insert(new_campfire)?;
-    // Re-fetch player for username in log message
-    let player_for_log = players.identity().find(sender_id)
-        .ok_or_else(|| "Player disappeared during placement?".to_string())?;
-    log::info!("Player {} placed a campfire at ({:.1}, {:.1}) with initial fuel (Item {} in slot 0).", 
-             player_for_log.username, world_x, world_y, fuel_instance_id);
-
-    Ok(())
+    crate::errors::ensure_finite(&[("world_x", world_x), ("world_y", world_y)])?;
+    crate::placement::place_item_of_kind(ctx, item_instance_id, world_x, world_y, 0.0, Some(crate::items::PlaceableKind::Campfire))
 }
 
 // Called by the client to set the sprinting state
@@ -494,6 +426,7 @@ pub fn update_player_position(
     move_dy: f32,  
     intended_direction: Option<String>
 ) -> Result<(), String> {
+    crate::errors::ensure_finite(&[("move_dx", move_dx), ("move_dy", move_dy)])?;
     let sender_id = ctx.sender;
     let players = ctx.db.player();
     let trees = ctx.db.tree();
@@ -547,8 +480,16 @@ pub fn update_player_position(
     let last_update_time = current_player.last_update;
     let elapsed_micros = now.to_micros_since_unix_epoch().saturating_sub(last_update_time.to_micros_since_unix_epoch());
     let elapsed_seconds = (elapsed_micros as f64 / 1_000_000.0) as f32;
-    let new_hunger = (current_player.hunger - (elapsed_seconds * HUNGER_DRAIN_PER_SECOND)).max(0.0);
-    let new_thirst = (current_player.thirst - (elapsed_seconds * THIRST_DRAIN_PER_SECOND)).max(0.0);
+    // Sprinting (actually moving, with stamina left) burns food and water faster. This reducer is the
+    // only place hunger/thirst drain, measured since `last_update`, so no interval is counted twice.
+    let is_sprint_moving = current_player.is_sprinting && current_player.stamina > 0.0 && (move_dx != 0.0 || move_dy != 0.0);
+    let (hunger_drain_multiplier, thirst_drain_multiplier) = if is_sprint_moving {
+        (SPRINT_HUNGER_DRAIN_MULTIPLIER, SPRINT_THIRST_DRAIN_MULTIPLIER)
+    } else {
+        (1.0, 1.0)
+    };
+    let new_hunger = (current_player.hunger - (elapsed_seconds * HUNGER_DRAIN_PER_SECOND * hunger_drain_multiplier)).max(0.0);
+    let new_thirst = (current_player.thirst - (elapsed_seconds * THIRST_DRAIN_PER_SECOND * thirst_drain_multiplier)).max(0.0);
 
     // --- Calculate new Warmth (Moved earlier) ---
     let mut warmth_change_per_sec: f32 = 0.0;
@@ -560,15 +501,25 @@ pub fn update_player_position(
         TimeOfDay::Midnight => WARMTH_DRAIN_MULTIPLIER_MIDNIGHT * 1.33, // Increased midnight drain
     };
     warmth_change_per_sec -= BASE_WARMTH_DRAIN_PER_SECOND * drain_multiplier;
-    // 2. Warmth Gain from nearby Campfires
+    // 2. Warmth Gain from nearby heat sources. Each source type's output comes from its item definition;
+    //    sources of one type combine with diminishing returns (see combine_fire_warmth), types add up.
+    let campfire_warmth_output = crate::campfire::campfire_warmth_output(ctx);
+    let mut fire_warmth_contributions: Vec<f32> = Vec::new();
     for fire in campfires.iter() {
-        let dx = current_player.position_x - fire.pos_x;
-        let dy = current_player.position_y - fire.pos_y;
-        if (dx * dx + dy * dy) < WARMTH_RADIUS_SQUARED {
-            warmth_change_per_sec += WARMTH_PER_SECOND;
-            log::trace!("Player {:?} gaining warmth from campfire {}", sender_id, fire.id);
+        // Lit fires give full warmth; recently extinguished ones fade out (unlit, cold fires give none)
+        let heat_fraction = crate::campfire::campfire_heat_fraction(&fire, now);
+        if heat_fraction <= 0.0 {
+            continue;
+        }
+        let distance_sq = get_distance_squared(current_player.position_x, current_player.position_y, fire.pos_x, fire.pos_y);
+        if distance_sq < WARMTH_RADIUS_SQUARED {
+            // Closer to the fire = more warmth (tapers to zero at the edge)
+            let fire_warmth = crate::campfire::warmth_per_second_at_distance_sq(distance_sq, campfire_warmth_output) * heat_fraction;
+            fire_warmth_contributions.push(fire_warmth);
+            log::trace!("Player {:?} gaining {:.2} warmth/s from campfire {}", sender_id, fire_warmth, fire.id);
         }
     }
+    warmth_change_per_sec += crate::campfire::combine_fire_warmth(fire_warmth_contributions, campfire_warmth_output);
     let new_warmth = (current_player.warmth + (warmth_change_per_sec * elapsed_seconds))
                      .max(0.0) // Clamp between 0 and 100
                      .min(100.0);
@@ -584,7 +535,8 @@ pub fn update_player_position(
     let is_moving = move_dx != 0.0 || move_dy != 0.0;
     let mut current_sprinting_state = current_player.is_sprinting;
     if current_sprinting_state && is_moving && new_stamina > 0.0 {
-        new_stamina = (new_stamina - (elapsed_seconds * STAMINA_DRAIN_PER_SECOND)).max(0.0);
+        let drain_per_second = STAMINA_DRAIN_PER_SECOND * crate::armor_sets::stamina_drain_multiplier(ctx, sender_id);
+        new_stamina = (new_stamina - (elapsed_seconds * drain_per_second)).max(0.0);
         if new_stamina > 0.0 { 
             base_speed_multiplier = SPRINT_SPEED_MULTIPLIER;
         } else { 
@@ -1056,7 +1008,6 @@ pub fn jump(ctx: &ReducerContext) -> Result<(), String> {
 pub fn request_respawn(ctx: &ReducerContext) -> Result<(), String> {
     let sender_id = ctx.sender;
     let players = ctx.db.player();
-    let item_defs = ctx.db.item_definition(); // Keep for potential future use (e.g., dropping items)
     let inventory = ctx.db.inventory_item();
 
     // Find the player requesting respawn
@@ -1080,20 +1031,14 @@ pub fn request_respawn(ctx: &ReducerContext) -> Result<(), String> {
     log::info!("Respawning player {} ({:?}). Clearing inventory...", player.username, sender_id);
 
     // --- Clear Player Inventory ---
-    let mut items_to_delete = Vec::new();
-    for item in inventory.iter().filter(|item| item.player_identity == sender_id) {
-        items_to_delete.push(item.instance_id);
-    }
-    let delete_count = items_to_delete.len();
-    for item_instance_id in items_to_delete {
-        inventory.instance_id().delete(item_instance_id);
-    }
+    // Only what they carry; anything they stored in boxes or campfires is still theirs
+    let delete_count = crate::items::delete_carried_items(ctx, sender_id);
     log::info!("Cleared {} items from inventory for player {:?}.", delete_count, sender_id);
     // --- End Clear Inventory ---
 
     // --- Grant Starting Rock ---
     log::info!("Granting starting Rock to respawned player: {}", player.username);
-    if let Some(rock_def) = item_defs.iter().find(|def| def.name == "Rock") {
+    if let Some(rock_def) = crate::items::get_item_def_by_name(ctx, "Rock") {
         match inventory.try_insert(crate::items::InventoryItem { // Qualify struct path
             instance_id: 0, // Auto-incremented
             player_identity: sender_id,
@@ -1101,6 +1046,10 @@ pub fn request_respawn(ctx: &ReducerContext) -> Result<(), String> {
             quantity: 1,
             hotbar_slot: Some(0), // Put rock in first slot
             inventory_slot: None,
+            charges: None,
+            durability: None,
+            spoils_at: None,
+            quality: None,
         }) {
             Ok(_) => log::info!("Granted 1 Rock (slot 0) to player {}", player.username),
             Err(e) => log::error!("Failed to grant starting Rock to player {}: {}", player.username, e),
@@ -1120,10 +1069,11 @@ pub fn request_respawn(ctx: &ReducerContext) -> Result<(), String> {
     player.is_sprinting = false;
     player.is_dead = false; // Mark as alive again
     player.last_hit_time = None; 
+    player.days_survived = 0; // New life, new count (the old one stayed visible on the death screen)
+    player.selected_hotbar_slot = None; // Main hand is cleared below
 
     // --- Reset Position ---
-    let spawn_x = 640.0; // Simple initial spawn point
-    let spawn_y = 480.0;
+    let (spawn_x, spawn_y) = find_spawn_position(ctx, sender_id);
     player.position_x = spawn_x;
     player.position_y = spawn_y;
     player.direction = "down".to_string();
@@ -1142,4 +1092,33 @@ pub fn request_respawn(ctx: &ReducerContext) -> Result<(), String> {
     }
 
     Ok(())
-} 
\ No newline at end of file
+} 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::GameRng;
+
+    #[test]
+    fn spawns_spread_across_the_region() {
+        let (center_x, center_y, radius) = (640.0, 480.0, 200.0);
+        let mut rng = GameRng::from_seed(3);
+        let spawns: Vec<(f32, f32)> = (0..10).map(|_| random_spawn_point(&mut rng, center_x, center_y, radius)).collect();
+
+        for &(x, y) in &spawns {
+            assert!(get_distance_squared(x, y, center_x, center_y) <= radius * radius + 0.01);
+        }
+        // Not piled on one coordinate: no two spawns share a player footprint and they span the disc
+        for (i, a) in spawns.iter().enumerate() {
+            for b in &spawns[i + 1..] {
+                assert!(get_distance_squared(a.0, a.1, b.0, b.1) > PLAYER_RADIUS * PLAYER_RADIUS / 4.0);
+            }
+        }
+        let span_x = spawns.iter().map(|p| p.0).fold(f32::MIN, f32::max) - spawns.iter().map(|p| p.0).fold(f32::MAX, f32::min);
+        assert!(span_x > radius);
+
+        // A region at the world's corner is clamped back inside
+        let (x, y) = random_spawn_point(&mut rng, 0.0, 0.0, radius);
+        assert!(x >= PLAYER_RADIUS && y >= PLAYER_RADIUS);
+    }
+}
@@ -0,0 +1,174 @@
+use spacetimedb::{Identity, ReducerContext, Table, Timestamp};
+use log;
+
+use crate::player as PlayerTableTrait;
+use crate::errors::GameError;
+use crate::world_config::ensure_admin;
+
+// --- Constants ---
+const MAX_REPORT_REASON_LEN: usize = 200; // Characters, after sanitizing
+const REPORT_COOLDOWN_SECS: i64 = 60; // Minimum time between two reports from the same reporter
+
+// --- Player Report Table ---
+// Private: only the module owner (admin tooling) can read it.
+#[spacetimedb::table(name = player_report)]
+#[derive(Clone)]
+pub struct PlayerReport {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    #[index(btree)]
+    pub reporter: Identity, // Indexed for the per-reporter rate limit
+    pub target: Identity,
+    pub reason: String,
+    pub created_at: Timestamp,
+    pub resolved: bool,
+    pub resolved_by: Option<Identity>,
+}
+
+// --- Report Review Table ---
+// Snapshot of the open reports for each admin who asked for one (`list_player_reports`).
+// Admin clients subscribe to `SELECT * FROM player_report_review WHERE viewer = <self>`.
+// Rows only exist while a review is open; they are dropped on resolve and on disconnect.
+#[spacetimedb::table(name = player_report_review, public)]
+#[derive(Clone)]
+pub struct PlayerReportReview {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    #[index(btree)]
+    pub viewer: Identity, // Admin the snapshot was taken for
+    pub report_id: u64,
+    pub reporter: Identity,
+    pub target: Identity,
+    pub reason: String,
+    pub created_at: Timestamp,
+}
+
+// --- Helpers ---
+
+/// Turns control characters into spaces, collapses whitespace, and truncates to the max length.
+/// (Dropping them outright would glue together words separated by a newline or tab.)
+fn sanitize_reason(reason: &str) -> String {
+    let cleaned: String = reason
+        .chars()
+        .map(|c| if c.is_control() { ' ' } else { c })
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    cleaned.chars().take(MAX_REPORT_REASON_LEN).collect()
+}
+
+// --- Reducers ---
+
+/// Files a moderation report against another player.
+#[spacetimedb::reducer]
+pub fn report_player(ctx: &ReducerContext, target_identity: Identity, reason: String) -> Result<(), String> {
+    let reporter = ctx.sender;
+    let reports = ctx.db.player_report();
+
+    // 1. Validate target
+    if target_identity == reporter {
+        return Err("You cannot report yourself.".to_string());
+    }
+    if ctx.db.player().identity().find(target_identity).is_none() {
+        return Err(GameError::NotFound("Reported player".to_string()).into());
+    }
+
+    // 2. Validate reason
+    let reason = sanitize_reason(&reason);
+    if reason.is_empty() {
+        return Err(GameError::InvalidInput("reason must not be empty".to_string()).into());
+    }
+
+    // 3. Rate limit per reporter
+    let now_micros = ctx.timestamp.to_micros_since_unix_epoch();
+    let recently_reported = reports.reporter().filter(&reporter)
+        .any(|r| now_micros.saturating_sub(r.created_at.to_micros_since_unix_epoch()) < REPORT_COOLDOWN_SECS * 1_000_000);
+    if recently_reported {
+        return Err("You are reporting too quickly. Please wait a moment.".to_string());
+    }
+
+    // 4. Record it
+    let report = reports.insert(PlayerReport {
+        id: 0, // Auto-incremented
+        reporter,
+        target: target_identity,
+        reason,
+        created_at: ctx.timestamp,
+        resolved: false,
+        resolved_by: None,
+    });
+    log::info!("[Report] Player {:?} reported {:?} (report {}): {}", reporter, target_identity, report.id, report.reason);
+    Ok(())
+}
+
+/// Admin: replaces the caller's `player_report_review` rows with a snapshot of all unresolved reports.
+#[spacetimedb::reducer]
+pub fn list_player_reports(ctx: &ReducerContext) -> Result<(), String> {
+    ensure_admin(ctx)?;
+    let viewer = ctx.sender;
+    let reviews = ctx.db.player_report_review();
+    reviews.viewer().delete(&viewer);
+
+    let mut open_count = 0;
+    for report in ctx.db.player_report().iter().filter(|r| !r.resolved) {
+        reviews.insert(PlayerReportReview {
+            id: 0, // Auto-incremented
+            viewer,
+            report_id: report.id,
+            reporter: report.reporter,
+            target: report.target,
+            reason: report.reason,
+            created_at: report.created_at,
+        });
+        open_count += 1;
+    }
+    log::info!("[Report] Admin {:?} opened a review of {} unresolved report(s).", viewer, open_count);
+    Ok(())
+}
+
+/// Admin: marks a report as handled.
+#[spacetimedb::reducer]
+pub fn resolve_player_report(ctx: &ReducerContext, report_id: u64) -> Result<(), String> {
+    ensure_admin(ctx)?;
+    let reports = ctx.db.player_report();
+    let mut report = reports.id().find(report_id)
+        .ok_or_else(|| GameError::NotFound(format!("Report {}", report_id)))?;
+    if report.resolved {
+        return Err(format!("Report {} is already resolved.", report_id));
+    }
+    report.resolved = true;
+    report.resolved_by = Some(ctx.sender);
+    reports.id().update(report);
+
+    // Drop it from every open review
+    let reviews = ctx.db.player_report_review();
+    let stale_ids: Vec<u64> = reviews.iter()
+        .filter(|review| review.report_id == report_id)
+        .map(|review| review.id)
+        .collect();
+    for review_id in stale_ids {
+        reviews.id().delete(review_id);
+    }
+    log::info!("[Report] Admin {:?} resolved report {}.", ctx.sender, report_id);
+    Ok(())
+}
+
+/// Drops the viewer's report review snapshot (on disconnect).
+pub(crate) fn clear_report_review(ctx: &ReducerContext, viewer_id: Identity) {
+    ctx.db.player_report_review().viewer().delete(&viewer_id);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report_reasons_are_cleaned_and_truncated() {
+        assert_eq!(sanitize_reason("  spam\n\tin   chat\u{7}  "), "spam in chat");
+        assert_eq!(sanitize_reason(&"x".repeat(MAX_REPORT_REASON_LEN + 50)).chars().count(), MAX_REPORT_REASON_LEN);
+        assert_eq!(sanitize_reason("\n\r"), "");
+    }
+}
@@ -1,17 +1,18 @@
 use spacetimedb::{Table, ReducerContext, Identity, Timestamp};
 // Add imports for required table traits
-use crate::items::{inventory_item as InventoryItemTableTrait, item_definition as ItemDefinitionTableTrait};
+use crate::items::inventory_item as InventoryItemTableTrait;
 use crate::player as PlayerTableTrait; // Assuming player table is defined in lib.rs
 use log;
 
 // Import the respawn duration constant
 use crate::active_equipment::RESOURCE_RESPAWN_DURATION_SECS;
 use std::time::Duration;
+use crate::utils::within_range;
+use crate::interaction::MUSHROOM_INTERACTION_DISTANCE_SQUARED;
 
 // --- Mushroom Constants ---
 const MUSHROOM_RADIUS: f32 = 16.0; // Visual/interaction radius
-const PLAYER_MUSHROOM_INTERACTION_DISTANCE: f32 = 64.0; // Max distance player can be to interact
-const PLAYER_MUSHROOM_INTERACTION_DISTANCE_SQUARED: f32 = PLAYER_MUSHROOM_INTERACTION_DISTANCE * PLAYER_MUSHROOM_INTERACTION_DISTANCE;
+const MUSHROOM_SPORE_DROP_CHANCE: f32 = 0.25; // Chance to also get spores for farming
 
 // Constants for spawning (will be used in environment.rs)
 pub(crate) const MUSHROOM_DENSITY_PERCENT: f32 = 0.005; // Target 0.5% of map tiles
@@ -36,12 +37,13 @@ pub struct Mushroom {
 
 // --- Interaction Reducer ---
 
+/// Harvests a mushroom by hand (also reached via the generic `interact` reducer).
+/// Grants the item directly and starts the regrow cooldown.
 #[spacetimedb::reducer]
 pub fn interact_with_mushroom(ctx: &ReducerContext, mushroom_id: u64) -> Result<(), String> {
     let sender_id = ctx.sender;
     let players = ctx.db.player();
     let mushrooms = ctx.db.mushroom();
-    let item_defs = ctx.db.item_definition();
 
     // 1. Find Player
     let player = players.identity().find(sender_id)
@@ -51,24 +53,35 @@ pub fn interact_with_mushroom(ctx: &ReducerContext, mushroom_id: u64) -> Result<
     let mushroom = mushrooms.id().find(mushroom_id)
         .ok_or_else(|| format!("Mushroom {} not found", mushroom_id))?;
 
-    // 3. Check Distance
-    let dx = player.position_x - mushroom.pos_x;
-    let dy = player.position_y - mushroom.pos_y;
-    let dist_sq = dx * dx + dy * dy;
+    // 3. Check Harvestable (still regrowing if respawn_at is set)
+    if let Some(respawn_at) = mushroom.respawn_at {
+        let remaining_secs = respawn_at.to_micros_since_unix_epoch()
+            .saturating_sub(ctx.timestamp.to_micros_since_unix_epoch()) / 1_000_000;
+        log::debug!("Player {:?} tried to harvest regrowing mushroom {} ({}s left).", sender_id, mushroom_id, remaining_secs);
+        return Err(format!("Mushroom is not ready to harvest yet ({}s remaining)", remaining_secs.max(0)));
+    }
 
-    if dist_sq > PLAYER_MUSHROOM_INTERACTION_DISTANCE_SQUARED {
+    // 4. Check Distance
+    if !within_range(player.position_x, player.position_y, mushroom.pos_x, mushroom.pos_y, MUSHROOM_INTERACTION_DISTANCE_SQUARED) {
         return Err("Too far away to interact with the mushroom".to_string());
     }
 
-    // 4. Find Mushroom Item Definition
-    let mushroom_def = item_defs.iter()
-        .find(|def| def.name == "Mushroom")
+    // 5. Find Mushroom Item Definition
+    let mushroom_def = crate::items::get_item_def_by_name(ctx, "Mushroom")
         .ok_or_else(|| "Mushroom item definition not found".to_string())?;
 
-    // 5. Add Mushroom to Inventory (using helper from items module)
+    // 6. Add Mushroom to Inventory (no tool or swing required)
     crate::items::add_item_to_player_inventory(ctx, sender_id, mushroom_def.id, 1)?;
+    if crate::utils::GameRng::from_ctx(ctx).chance(MUSHROOM_SPORE_DROP_CHANCE) {
+        if let Some(spores_def) = crate::items::get_item_def_by_name(ctx, "Mushroom Spores") {
+            // Spores are a bonus; a full inventory drops them instead of blocking the harvest
+            if let Err(e) = crate::items::grant_or_drop(ctx, sender_id, spores_def.id, 1) {
+                log::debug!("Player {:?} found spores but they could not be granted: {}", sender_id, e);
+            }
+        }
+    }
 
-    // 6. Schedule Respawn instead of Deleting
+    // 7. Start regrow cooldown instead of Deleting
     let respawn_time = ctx.timestamp + Duration::from_secs(RESOURCE_RESPAWN_DURATION_SECS);
     let mut mushroom_to_update = mushroom; // Clone the found mushroom to modify
     mushroom_to_update.respawn_at = Some(respawn_time);
@@ -0,0 +1,87 @@
+use spacetimedb::{Identity, ReducerContext, Table, Timestamp};
+use log;
+
+use crate::player as PlayerTableTrait;
+use crate::viewport::client_viewport as ClientViewportTableTrait;
+use crate::utils::within_range;
+
+// --- Constants ---
+// Fallback range for clients that haven't reported a viewport yet.
+const NEARBY_PLAYER_RADIUS_PX: f32 = 1200.0;
+const NEARBY_PLAYER_RADIUS_SQUARED: f32 = NEARBY_PLAYER_RADIUS_PX * NEARBY_PLAYER_RADIUS_PX;
+const PLAYER_MAX_HEALTH: f32 = 100.0;
+
+// --- Nearby Player Table ---
+// Per-viewer snapshot of the players around them, for the "players nearby" panel and nameplates.
+// Clients subscribe to `SELECT * FROM nearby_player WHERE viewer = <self>` instead of the full
+// Player table. Rows are rebuilt on every `refresh_nearby_players` call.
+#[spacetimedb::table(name = nearby_player, public)]
+#[derive(Clone)]
+pub struct NearbyPlayer {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    #[index(btree)]
+    pub viewer: Identity, // Indexed: a refresh only touches the caller's rows
+    pub identity: Identity,
+    pub username: String,
+    pub color: String,
+    pub position_x: f32,
+    pub position_y: f32,
+    pub health_ratio: f32, // 0.0 - 1.0
+    pub updated_at: Timestamp,
+}
+
+/// Rebuilds the caller's nearby-player snapshot. Uses the caller's viewport when known,
+/// otherwise a fixed radius around them. Dead players are left out; the caller never lists themselves.
+#[spacetimedb::reducer]
+pub fn refresh_nearby_players(ctx: &ReducerContext) -> Result<(), String> {
+    let viewer_id = ctx.sender;
+    let players = ctx.db.player();
+    let viewer = players.identity().find(viewer_id)
+        .ok_or_else(|| "Player not found".to_string())?;
+    let viewport = ctx.db.client_viewport().client_identity().find(viewer_id);
+
+    clear_nearby_players(ctx, viewer_id);
+
+    let nearby_players = ctx.db.nearby_player();
+    let mut listed = 0;
+    for other in players.iter() {
+        if other.identity == viewer_id || other.is_dead {
+            continue;
+        }
+        let in_range = match &viewport {
+            Some(view) => other.position_x >= view.min_x && other.position_x <= view.max_x
+                && other.position_y >= view.min_y && other.position_y <= view.max_y,
+            None => within_range(other.position_x, other.position_y, viewer.position_x, viewer.position_y, NEARBY_PLAYER_RADIUS_SQUARED),
+        };
+        if !in_range {
+            continue;
+        }
+        nearby_players.insert(NearbyPlayer {
+            id: 0, // Auto-incremented
+            viewer: viewer_id,
+            identity: other.identity,
+            username: other.username.clone(),
+            color: other.color.clone(),
+            position_x: other.position_x,
+            position_y: other.position_y,
+            health_ratio: (other.health / PLAYER_MAX_HEALTH).clamp(0.0, 1.0),
+            updated_at: ctx.timestamp,
+        });
+        listed += 1;
+    }
+    log::trace!("[NearbyPlayers] {:?} sees {} nearby players.", viewer_id, listed);
+    Ok(())
+}
+
+/// Drops every snapshot row owned by `viewer_id` (on refresh and on disconnect).
+pub(crate) fn clear_nearby_players(ctx: &ReducerContext, viewer_id: Identity) {
+    let nearby_players = ctx.db.nearby_player();
+    let stale_ids: Vec<u64> = nearby_players.viewer().filter(&viewer_id)
+        .map(|row| row.id)
+        .collect();
+    for row_id in stale_ids {
+        nearby_players.id().delete(row_id);
+    }
+}
@@ -0,0 +1,101 @@
+use spacetimedb::{Identity, ReducerContext, Table, Timestamp};
+use log;
+
+use crate::player as PlayerTableTrait;
+use crate::items::inventory_item as InventoryItemTableTrait;
+use crate::wooden_storage_box::wooden_storage_box as WoodenStorageBoxTableTrait;
+use crate::inventory_management::ItemContainer;
+use crate::errors::{GameError, ensure_finite};
+use crate::utils::within_range;
+
+// --- Constants ---
+// Radius used when the client doesn't ask for one; requests are clamped to the max.
+pub(crate) const DEFAULT_NEARBY_STORAGE_RADIUS_PX: f32 = 400.0;
+pub(crate) const MAX_NEARBY_STORAGE_RADIUS_PX: f32 = 1000.0;
+
+// --- Nearby Storage Table ---
+// Per-viewer, read-only snapshot of every slot in the storage boxes around them, for the
+// "central access" storage view. Clients subscribe to `SELECT * FROM nearby_storage_item WHERE viewer = <self>`
+// and group rows by `item_def_id` to show combined totals. Moving items still goes through the
+// regular container reducers using `box_id` / `slot_index`.
+#[spacetimedb::table(name = nearby_storage_item, public)]
+#[derive(Clone)]
+pub struct NearbyStorageItem {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    #[index(btree)]
+    pub viewer: Identity, // Indexed: a refresh only touches the caller's rows
+    pub box_id: u32,
+    pub slot_index: u8,
+    pub item_instance_id: u64,
+    pub item_def_id: u64,
+    pub quantity: u32,
+    pub updated_at: Timestamp,
+}
+
+/// Rebuilds the caller's nearby-storage snapshot from the boxes they can access within `radius`
+/// (`None` = `DEFAULT_NEARBY_STORAGE_RADIUS_PX`, capped at `MAX_NEARBY_STORAGE_RADIUS_PX`).
+/// Boxes have no locks or sharing yet, so "accessible" means placed by the caller.
+#[spacetimedb::reducer]
+pub fn refresh_nearby_storage(ctx: &ReducerContext, radius: Option<f32>) -> Result<(), String> {
+    let viewer_id = ctx.sender;
+    let radius = radius.unwrap_or(DEFAULT_NEARBY_STORAGE_RADIUS_PX);
+    ensure_finite(&[("radius", radius)])?;
+    let radius = radius.clamp(0.0, MAX_NEARBY_STORAGE_RADIUS_PX);
+
+    let player = ctx.db.player().identity().find(viewer_id)
+        .ok_or_else(|| GameError::NotFound("Player".to_string()))?;
+    if player.is_dead {
+        return Err("Cannot browse storage while dead".to_string());
+    }
+
+    clear_nearby_storage(ctx, viewer_id);
+
+    let mut box_count = 0;
+    let mut slot_count = 0;
+    for storage_box in ctx.db.wooden_storage_box().iter() {
+        if storage_box.placed_by != viewer_id
+            || !within_range(player.position_x, player.position_y, storage_box.pos_x, storage_box.pos_y, radius * radius) {
+            continue;
+        }
+        slot_count += snapshot_container(ctx, viewer_id, storage_box.id, &storage_box);
+        box_count += 1;
+    }
+    log::debug!("[NearbyStorage] {:?} sees {} slots across {} boxes (radius {:.0}).", viewer_id, slot_count, box_count, radius);
+    Ok(())
+}
+
+/// Copies each occupied slot of `container` into the viewer's snapshot. Returns the rows written.
+fn snapshot_container<C: ItemContainer>(ctx: &ReducerContext, viewer_id: Identity, box_id: u32, container: &C) -> u32 {
+    let inventory_items = ctx.db.inventory_item();
+    let nearby_storage = ctx.db.nearby_storage_item();
+    let mut written = 0;
+    for slot_index in 0..container.num_slots() as u8 {
+        let Some(instance_id) = container.get_slot_instance_id(slot_index) else { continue; };
+        let Some(item) = inventory_items.instance_id().find(instance_id) else { continue; };
+        nearby_storage.insert(NearbyStorageItem {
+            id: 0, // Auto-incremented
+            viewer: viewer_id,
+            box_id,
+            slot_index,
+            item_instance_id: instance_id,
+            item_def_id: item.item_def_id,
+            quantity: item.quantity,
+            updated_at: ctx.timestamp,
+        });
+        written += 1;
+    }
+    written
+}
+
+/// Drops every snapshot row owned by `viewer_id` (on refresh and on disconnect).
+pub(crate) fn clear_nearby_storage(ctx: &ReducerContext, viewer_id: Identity) {
+    let nearby_storage = ctx.db.nearby_storage_item();
+    let stale_ids: Vec<u64> = nearby_storage.viewer().filter(&viewer_id)
+        .map(|row| row.id)
+        .collect();
+    for row_id in stale_ids {
+        nearby_storage.id().delete(row_id);
+    }
+}
@@ -0,0 +1,91 @@
+use spacetimedb::{Identity, ReducerContext, Table, Timestamp};
+use log;
+
+use crate::presence::online_player as OnlinePlayerTableTrait;
+use crate::campfire::campfire as CampfireTableTrait;
+use crate::farming::{crop as CropTableTrait, CropStage};
+use crate::items::item_definition as ItemDefinitionTableTrait;
+
+// --- Constants ---
+const MAX_SUMMARY_EVENTS: usize = 10; // Only the most recent events are listed; the counts cover everything
+
+// --- Offline Summary Table ---
+// One row per returning player describing what happened to their things while they were away.
+// Derived on reconnect from the timestamps the other systems keep (`Campfire.extinguished_at`,
+// `Crop.stage_changed_at`), replaced on every reconnect and removed once dismissed.
+#[spacetimedb::table(name = offline_summary, public)]
+#[derive(Clone)]
+pub struct OfflineSummary {
+    #[primary_key]
+    pub player_identity: Identity,
+    pub away_since: Timestamp,
+    pub returned_at: Timestamp,
+    pub campfires_went_out: u32,
+    pub crops_matured: u32,
+    pub crops_withered: u32,
+    pub events: Vec<String>, // Newest first, at most MAX_SUMMARY_EVENTS
+}
+
+/// Builds the player's offline summary. Call from the connect flow before presence is flipped
+/// back online, while `last_seen` still holds the time they left. First-time players get nothing.
+pub(crate) fn record_offline_summary(ctx: &ReducerContext, player_id: Identity) {
+    let Some(presence) = ctx.db.online_player().identity().find(player_id) else { return; };
+    if presence.is_online {
+        return; // Second connection for the same identity; they never left
+    }
+    let away_since = presence.last_seen;
+    let while_away = |at: Option<Timestamp>| at.filter(|t| *t > away_since);
+
+    // 1. Collect timestamped events for things the player owns
+    let mut events: Vec<(Timestamp, String)> = Vec::new();
+    let mut campfires_went_out = 0;
+    for campfire in ctx.db.campfire().iter().filter(|c| c.placed_by == player_id) {
+        if campfire.is_burning {
+            continue;
+        }
+        if let Some(went_out_at) = while_away(campfire.extinguished_at) {
+            campfires_went_out += 1;
+            events.push((went_out_at, format!("Your campfire at ({:.0}, {:.0}) went out.", campfire.pos_x, campfire.pos_y)));
+        }
+    }
+    let (mut crops_matured, mut crops_withered) = (0, 0);
+    for crop in ctx.db.crop().iter().filter(|c| c.planted_by == player_id) {
+        let Some(changed_at) = while_away(crop.stage_changed_at) else { continue; };
+        let produce_name = ctx.db.item_definition().id().find(crop.produce_def_id)
+            .map_or_else(|| "crop".to_string(), |def| def.name);
+        let line = match crop.stage {
+            CropStage::Mature => { crops_matured += 1; format!("Your {} is ready to harvest.", produce_name) }
+            CropStage::Withered => { crops_withered += 1; format!("Your {} withered without water.", produce_name) }
+            _ => continue,
+        };
+        events.push((changed_at, line));
+    }
+
+    // 2. Replace any previous summary; nothing to report means no row
+    let summaries = ctx.db.offline_summary();
+    summaries.player_identity().delete(player_id);
+    if events.is_empty() {
+        return;
+    }
+    events.sort_by_key(|(at, _)| std::cmp::Reverse(*at));
+    events.truncate(MAX_SUMMARY_EVENTS);
+
+    log::info!("[OfflineSummary] {:?} returned: {} campfires out, {} crops matured, {} withered.",
+             player_id, campfires_went_out, crops_matured, crops_withered);
+    summaries.insert(OfflineSummary {
+        player_identity: player_id,
+        away_since,
+        returned_at: ctx.timestamp,
+        campfires_went_out,
+        crops_matured,
+        crops_withered,
+        events: events.into_iter().map(|(_, line)| line).collect(),
+    });
+}
+
+/// Clears the caller's offline summary once the client has shown it.
+#[spacetimedb::reducer]
+pub fn dismiss_offline_summary(ctx: &ReducerContext) -> Result<(), String> {
+    ctx.db.offline_summary().player_identity().delete(ctx.sender);
+    Ok(())
+}
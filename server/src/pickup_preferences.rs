@@ -0,0 +1,52 @@
+use spacetimedb::{Identity, ReducerContext, Table};
+use log;
+
+use crate::items::item_definition as ItemDefinitionTableTrait;
+use crate::errors::GameError;
+
+// --- Pickup Preferences Table ---
+// One row per player that has ignored anything. Bulk pickup (`pickup_nearby_items`) leaves
+// ignored item types on the ground; picking a specific item up by hand always works.
+#[spacetimedb::table(name = pickup_preferences, public)]
+#[derive(Clone)]
+pub struct PickupPreferences {
+    #[primary_key]
+    pub player_identity: Identity,
+    pub ignored_item_def_ids: Vec<u64>,
+}
+
+/// Adds or removes an item type from the caller's pickup ignore list.
+#[spacetimedb::reducer]
+pub fn set_item_ignored(ctx: &ReducerContext, item_def_id: u64, ignored: bool) -> Result<(), String> {
+    let sender_id = ctx.sender;
+    let item_def = ctx.db.item_definition().id().find(item_def_id)
+        .ok_or_else(|| GameError::NotFound(format!("Item definition {}", item_def_id)))?;
+
+    let preferences_table = ctx.db.pickup_preferences();
+    let mut preferences = preferences_table.player_identity().find(sender_id)
+        .unwrap_or_else(|| PickupPreferences { player_identity: sender_id, ignored_item_def_ids: Vec::new() });
+    let already_ignored = preferences.ignored_item_def_ids.contains(&item_def_id);
+    if ignored == already_ignored {
+        return Ok(()); // Nothing to change
+    }
+    if ignored {
+        preferences.ignored_item_def_ids.push(item_def_id);
+    } else {
+        preferences.ignored_item_def_ids.retain(|id| *id != item_def_id);
+    }
+
+    // Drop the row once the list is empty rather than keeping an empty preference around
+    preferences_table.player_identity().delete(sender_id);
+    if !preferences.ignored_item_def_ids.is_empty() {
+        preferences_table.insert(preferences);
+    }
+    log::info!("[PickupPrefs] Player {:?} {} '{}' for bulk pickup.", sender_id, if ignored { "ignored" } else { "un-ignored" }, item_def.name);
+    Ok(())
+}
+
+/// Item types the player has asked bulk pickup to skip (empty if they never set any).
+pub(crate) fn ignored_item_def_ids(ctx: &ReducerContext, player_id: Identity) -> Vec<u64> {
+    ctx.db.pickup_preferences().player_identity().find(player_id)
+        .map(|preferences| preferences.ignored_item_def_ids)
+        .unwrap_or_default()
+}
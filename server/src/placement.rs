@@ -0,0 +1,276 @@
+use spacetimedb::{Identity, ReducerContext, Table};
+use log;
+
+// Import table traits needed for ctx.db access
+use crate::player as PlayerTableTrait;
+use crate::items::{inventory_item as InventoryItemTableTrait, item_definition as ItemDefinitionTableTrait};
+use crate::campfire::campfire as CampfireTableTrait;
+use crate::wooden_storage_box::wooden_storage_box as WoodenStorageBoxTableTrait;
+use crate::composter::composter as ComposterTableTrait;
+
+use crate::items::{ItemCategory, ItemDefinition, PlaceableKind};
+use crate::campfire::CAMPFIRE_COLLISION_RADIUS;
+use crate::wooden_storage_box::BOX_COLLISION_RADIUS;
+use crate::errors::{GameError, ensure_finite};
+use crate::utils::get_distance_squared;
+use crate::interaction::PLACEMENT_MAX_DISTANCE_SQUARED;
+use crate::{WORLD_WIDTH_PX, WORLD_HEIGHT_PX};
+
+// --- Constants ---
+// Two placed entities may not be closer than the sum of their footprints.
+// The default keeps the old campfire-campfire spacing for items without a `placement_footprint`.
+const DEFAULT_PLACEMENT_FOOTPRINT: f32 = CAMPFIRE_COLLISION_RADIUS;
+// Floor for configured footprints, so nothing can be placed on top of something else
+const MIN_PLACEMENT_FOOTPRINT: f32 = 8.0;
+
+// --- Placeable Spawn Registry ---
+// Maps each PlaceableKind to the function that creates its world entity.
+// Spawn functions run after validation and item consumption, so they only build the entity.
+//
+// To add a new placeable (e.g. a Workbench):
+//   1. Add a `Workbench` variant to `PlaceableKind` in items.rs.
+//   2. Write `pub(crate) fn spawn_workbench(ctx, placer_id, world_x, world_y) -> Result<(), String>` in its module.
+//   3. Register it below: `(PlaceableKind::Workbench, crate::workbench::spawn_workbench)`.
+//   4. Give its ItemDefinition `category: ItemCategory::Placeable` and `placeable_kind: Some(PlaceableKind::Workbench)`.
+//   5. (Optional) Add its table to the spacing check in `validate_placement_location`,
+//      and set `placement_footprint` if it should pack tighter than the default.
+type PlaceableSpawnFn = fn(&ReducerContext, Identity, f32, f32) -> Result<(), String>;
+
+const PLACEABLE_SPAWNERS: &[(PlaceableKind, PlaceableSpawnFn)] = &[
+    (PlaceableKind::Campfire, crate::campfire::spawn_campfire),
+    (PlaceableKind::WoodenStorageBox, crate::wooden_storage_box::spawn_wooden_storage_box),
+    (PlaceableKind::Composter, crate::composter::spawn_composter),
+];
+
+/// Looks up the spawn function registered for a placeable kind.
+fn get_spawn_fn(kind: &PlaceableKind) -> Option<PlaceableSpawnFn> {
+    PLACEABLE_SPAWNERS.iter()
+        .find(|(registered_kind, _)| registered_kind == kind)
+        .map(|(_, spawn_fn)| *spawn_fn)
+}
+
+/// Generic placement reducer. Reads the item's `placeable_kind` to decide which entity to spawn.
+/// `rotation` (radians) is accepted for client drag previews; current placeables are rotation-agnostic.
+#[spacetimedb::reducer]
+pub fn place_item(ctx: &ReducerContext, item_instance_id: u64, world_x: f32, world_y: f32, rotation: f32) -> Result<(), String> {
+    place_item_of_kind(ctx, item_instance_id, world_x, world_y, rotation, None)
+}
+
+/// Shared placement flow. If `expected_kind` is set, the item must spawn that kind
+/// (used by the per-type wrapper reducers like `place_campfire`).
+pub(crate) fn place_item_of_kind(
+    ctx: &ReducerContext,
+    item_instance_id: u64,
+    world_x: f32,
+    world_y: f32,
+    rotation: f32,
+    expected_kind: Option<PlaceableKind>,
+) -> Result<(), String> {
+    ensure_finite(&[("world_x", world_x), ("world_y", world_y), ("rotation", rotation)])?;
+    let sender_id = ctx.sender;
+    let inventory_items = ctx.db.inventory_item();
+    let item_defs = ctx.db.item_definition();
+
+    log::info!(
+        "[PlaceItem] Player {:?} attempting placement of item {} at ({:.1}, {:.1}) rot {:.2}",
+        sender_id, item_instance_id, world_x, world_y, rotation
+    );
+
+    // 1. Find the item instance and validate ownership/location
+    let mut item_to_consume = inventory_items.instance_id().find(item_instance_id)
+        .ok_or_else(|| GameError::NotFound(format!("Item instance {}", item_instance_id)))?;
+    if item_to_consume.player_identity != sender_id {
+        return Err(GameError::NotOwner.into());
+    }
+    if item_to_consume.inventory_slot.is_none() && item_to_consume.hotbar_slot.is_none() {
+        return Err(GameError::InvalidPlacement(format!("Item instance {} must be in inventory or hotbar to be placed.", item_instance_id)).into());
+    }
+
+    // 2. Determine what this item spawns
+    let item_def = item_defs.id().find(item_to_consume.item_def_id)
+        .ok_or_else(|| GameError::NotFound(format!("Item definition {}", item_to_consume.item_def_id)))?;
+    let kind = resolve_placeable_kind(&item_def, expected_kind.as_ref())?;
+    let spawn_fn = get_spawn_fn(&kind)
+        .ok_or_else(|| format!("No spawn function registered for {:?}", kind))?;
+
+    // 3. Shared placement validation
+    check_build_limit(ctx, sender_id, &kind)?;
+    validate_placement_location(ctx, world_x, world_y, placement_footprint(&item_def))?;
+
+    // 4. Consume the Item (one from the stack)
+    if item_to_consume.quantity > 1 {
+        item_to_consume.quantity -= 1;
+        inventory_items.instance_id().update(item_to_consume);
+    } else {
+        inventory_items.instance_id().delete(item_instance_id);
+    }
+    log::info!("[PlaceItem] Consumed 1x '{}' (instance {}) from player {:?}", item_def.name, item_instance_id, sender_id);
+
+    // 5. Spawn the entity via the registry
+    spawn_fn(ctx, sender_id, world_x, world_y)
+}
+
+/// Works out which entity an item spawns. Rejects items that aren't placeable and, for the
+/// per-type wrapper reducers, items of a different kind than `expected_kind`.
+fn resolve_placeable_kind(item_def: &ItemDefinition, expected_kind: Option<&PlaceableKind>) -> Result<PlaceableKind, String> {
+    let kind = match (&item_def.category, &item_def.placeable_kind) {
+        (ItemCategory::Placeable, Some(kind)) => kind.clone(),
+        _ => return Err(GameError::InvalidPlacement(format!("'{}' cannot be placed.", item_def.name)).into()),
+    };
+    if let Some(expected) = expected_kind {
+        if *expected != kind {
+            return Err(GameError::InvalidPlacement(format!("'{}' is not a {:?}.", item_def.name, expected)).into());
+        }
+    }
+    Ok(kind)
+}
+
+/// Rejects the placement if the player already has the maximum number of this kind standing.
+/// Only existing rows count, so picking a structure up (or losing it) frees its slot.
+/// Kinds without a cap (composters) always pass.
+fn check_build_limit(ctx: &ReducerContext, placer_id: Identity, kind: &PlaceableKind) -> Result<(), String> {
+    let (placed, limit) = match kind {
+        PlaceableKind::Campfire => (
+            ctx.db.campfire().iter().filter(|c| c.placed_by == placer_id).count(),
+            crate::campfire::MAX_CAMPFIRES_PER_PLAYER,
+        ),
+        PlaceableKind::WoodenStorageBox => (
+            ctx.db.wooden_storage_box().iter().filter(|b| b.placed_by == placer_id).count(),
+            crate::wooden_storage_box::MAX_BOXES_PER_PLAYER,
+        ),
+        _ => return Ok(()),
+    };
+    if placed >= limit {
+        return Err(GameError::InvalidPlacement(format!("Build limit reached: you already have {} of {} {:?}s placed.", placed, limit, kind)).into());
+    }
+    Ok(())
+}
+
+/// Placed radius of an item, falling back to the default. Never below the minimum or the
+/// collision radius of what it spawns, so packed entities can touch but never overlap.
+fn placement_footprint(item_def: &ItemDefinition) -> f32 {
+    let footprint = item_def.placement_footprint
+        .filter(|footprint| footprint.is_finite())
+        .unwrap_or(DEFAULT_PLACEMENT_FOOTPRINT)
+        .max(MIN_PLACEMENT_FOOTPRINT);
+    match &item_def.placeable_kind {
+        Some(kind) => footprint.max(collision_radius(kind)),
+        None => footprint,
+    }
+}
+
+/// Radius players collide with around a placed entity of `kind` (0 for entities players walk through).
+fn collision_radius(kind: &PlaceableKind) -> f32 {
+    match kind {
+        PlaceableKind::Campfire => CAMPFIRE_COLLISION_RADIUS,
+        PlaceableKind::WoodenStorageBox => BOX_COLLISION_RADIUS,
+        PlaceableKind::Composter => 0.0,
+    }
+}
+
+/// Placed radius of entities of `kind`, taken from the item definition that spawns them.
+fn placed_kind_footprint(ctx: &ReducerContext, kind: PlaceableKind) -> f32 {
+    ctx.db.item_definition().iter()
+        .find(|def| def.placeable_kind.as_ref() == Some(&kind))
+        .map(|def| placement_footprint(&def))
+        .unwrap_or(DEFAULT_PLACEMENT_FOOTPRINT)
+}
+
+/// Checks range from the player, world bounds, and spacing from other placed entities.
+/// `footprint` is the new entity's placed radius; it overlaps another entity if their
+/// centers are closer than the two footprints combined.
+fn validate_placement_location(ctx: &ReducerContext, world_x: f32, world_y: f32, footprint: f32) -> Result<(), String> {
+    if world_x < 0.0 || world_y < 0.0 || world_x > WORLD_WIDTH_PX || world_y > WORLD_HEIGHT_PX {
+        return Err(GameError::InvalidPlacement("Outside of the world".to_string()).into());
+    }
+
+    let player = ctx.db.player().identity().find(ctx.sender)
+        .ok_or_else(|| GameError::NotFound("Player".to_string()))?;
+    if get_distance_squared(player.position_x, player.position_y, world_x, world_y) > PLACEMENT_MAX_DISTANCE_SQUARED {
+        return Err(GameError::TooFarAway.into());
+    }
+
+    let min_spacing_squared = |kind: PlaceableKind| {
+        let spacing = footprint + placed_kind_footprint(ctx, kind);
+        spacing * spacing
+    };
+    let campfire_spacing_sq = min_spacing_squared(PlaceableKind::Campfire);
+    let box_spacing_sq = min_spacing_squared(PlaceableKind::WoodenStorageBox);
+    let composter_spacing_sq = min_spacing_squared(PlaceableKind::Composter);
+
+    let too_close_to_campfire = ctx.db.campfire().iter()
+        .any(|c| get_distance_squared(c.pos_x, c.pos_y, world_x, world_y) < campfire_spacing_sq);
+    let too_close_to_box = ctx.db.wooden_storage_box().iter()
+        .any(|b| get_distance_squared(b.pos_x, b.pos_y, world_x, world_y) < box_spacing_sq);
+    let too_close_to_composter = ctx.db.composter().iter()
+        .any(|c| get_distance_squared(c.pos_x, c.pos_y, world_x, world_y) < composter_spacing_sq);
+    if too_close_to_campfire || too_close_to_box || too_close_to_composter {
+        return Err(GameError::InvalidPlacement("Too close to another placed object".to_string()).into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::items::test_item_def;
+
+    #[test]
+    fn every_placeable_item_resolves_to_a_registered_spawner() {
+        let placeables: Vec<ItemDefinition> = crate::items_database::get_initial_item_definitions().into_iter()
+            .filter(|def| def.category == ItemCategory::Placeable)
+            .collect();
+        assert!(!placeables.is_empty());
+        for def in &placeables {
+            let kind = resolve_placeable_kind(def, None)
+                .unwrap_or_else(|e| panic!("'{}' should be placeable: {}", def.name, e));
+            assert!(get_spawn_fn(&kind).is_some(), "no spawner registered for {:?}", kind);
+            // The per-type wrappers accept their own kind
+            assert_eq!(resolve_placeable_kind(def, Some(&kind)), Ok(kind));
+        }
+    }
+
+    #[test]
+    fn every_kind_is_registered() {
+        for kind in [PlaceableKind::Campfire, PlaceableKind::WoodenStorageBox, PlaceableKind::Composter] {
+            assert!(get_spawn_fn(&kind).is_some(), "no spawner registered for {:?}", kind);
+        }
+    }
+
+    #[test]
+    fn rejects_non_placeables_and_the_wrong_kind() {
+        let wood = test_item_def("Wood", ItemCategory::Material);
+        assert!(resolve_placeable_kind(&wood, None).is_err());
+
+        let campfire = ItemDefinition {
+            placeable_kind: Some(PlaceableKind::Campfire),
+            ..test_item_def("Camp Fire", ItemCategory::Placeable)
+        };
+        assert!(resolve_placeable_kind(&campfire, Some(&PlaceableKind::WoodenStorageBox)).is_err());
+    }
+
+    #[test]
+    fn footprint_never_drops_below_the_collision_radius() {
+        let storage_box = |footprint: Option<f32>| ItemDefinition {
+            placeable_kind: Some(PlaceableKind::WoodenStorageBox),
+            placement_footprint: footprint,
+            ..test_item_def("Wooden Storage Box", ItemCategory::Placeable)
+        };
+        assert_eq!(placement_footprint(&storage_box(Some(12.0))), BOX_COLLISION_RADIUS);
+        assert_eq!(placement_footprint(&storage_box(Some(30.0))), 30.0);
+        assert_eq!(placement_footprint(&storage_box(Some(f32::NAN))), DEFAULT_PLACEMENT_FOOTPRINT);
+
+        let composter = ItemDefinition {
+            placeable_kind: Some(PlaceableKind::Composter),
+            placement_footprint: Some(1.0),
+            ..test_item_def("Composter", ItemCategory::Placeable)
+        };
+        assert_eq!(placement_footprint(&composter), MIN_PLACEMENT_FOOTPRINT);
+
+        for def in crate::items_database::get_initial_item_definitions() {
+            if let Some(kind) = &def.placeable_kind {
+                assert!(placement_footprint(&def) >= collision_radius(kind), "'{}' footprint overlaps its collision", def.name);
+            }
+        }
+    }
+}
@@ -0,0 +1,153 @@
+use spacetimedb::{Identity, ReducerContext, Table, Timestamp};
+use log;
+
+use crate::player as PlayerTableTrait;
+use crate::player_stats::{player_stats as PlayerStatsTableTrait, buff as BuffTableTrait};
+use crate::active_equipment::active_equipment as ActiveEquipmentTableTrait;
+use crate::items::inventory_item as InventoryItemTableTrait;
+use crate::items::InventoryItem;
+use crate::achievements::player_achievement as PlayerAchievementTableTrait;
+use crate::errors::GameError;
+
+// --- Player Snapshot Table ---
+// One row per player with everything the client needs to draw its HUD on join, so a
+// reconnecting client can subscribe to `SELECT * FROM player_snapshot WHERE player_identity = <self>`
+// instead of querying each per-player table first. Purely derived data: rebuilt on every
+// `refresh_player_snapshot` call and dropped on disconnect. Live updates still come from the source tables.
+#[spacetimedb::table(name = player_snapshot, public)]
+#[derive(Clone)]
+pub struct PlayerSnapshot {
+    #[primary_key]
+    pub player_identity: Identity,
+    // Vitals
+    pub health: f32,
+    pub stamina: f32,
+    pub thirst: f32,
+    pub hunger: f32,
+    pub warmth: f32,
+    pub is_dead: bool,
+    pub in_combat: bool,
+    pub days_survived: u32,
+    // Progression (None until the player has a player_stats row)
+    pub level: Option<u32>,
+    pub experience: Option<f32>,
+    // Equipment
+    pub selected_hotbar_slot: Option<u8>,
+    pub main_hand_item_def_id: Option<u64>,
+    pub worn_item_instance_ids: Vec<u64>, // Head, chest, legs, feet, hands, back; only occupied slots
+    pub armor_set_bonus: Option<String>, // Name of the active full-set bonus, if any
+    // Inventory summary
+    pub hotbar_slots_used: u32,
+    pub inventory_slots_used: u32,
+    pub total_item_count: u32, // Sum of quantities in the hotbar and inventory
+    // Effects and unlocks
+    pub active_buff_ids: Vec<u64>,
+    pub achievements_unlocked: u32,
+    pub updated_at: Timestamp,
+}
+
+/// Rebuilds the caller's snapshot row from the per-player tables.
+#[spacetimedb::reducer]
+pub fn refresh_player_snapshot(ctx: &ReducerContext) -> Result<(), String> {
+    let player_id = ctx.sender;
+    let player = ctx.db.player().identity().find(player_id)
+        .ok_or_else(|| GameError::NotFound("Player".to_string()))?;
+
+    let stats = ctx.db.player_stats().player_id().find(player_id);
+    let equipment = ctx.db.active_equipment().player_identity().find(player_id);
+    let worn_item_instance_ids: Vec<u64> = equipment.as_ref()
+        .map(|equip| [
+            equip.head_item_instance_id,
+            equip.chest_item_instance_id,
+            equip.legs_item_instance_id,
+            equip.feet_item_instance_id,
+            equip.hands_item_instance_id,
+            equip.back_item_instance_id,
+        ].into_iter().flatten().collect())
+        .unwrap_or_default();
+
+    // Inventory summary via the player_identity index
+    let (hotbar_slots_used, inventory_slots_used, total_item_count) =
+        summarize_carried_items(ctx.db.inventory_item().player_identity().filter(&player_id));
+
+    let active_buff_ids: Vec<u64> = ctx.db.buff().iter()
+        .filter(|buff| buff.player_id == player_id)
+        .map(|buff| buff.id)
+        .collect();
+    let achievements_unlocked = ctx.db.player_achievement().iter()
+        .filter(|unlock| unlock.player_identity == player_id)
+        .count() as u32;
+
+    let snapshot = PlayerSnapshot {
+        player_identity: player_id,
+        health: player.health,
+        stamina: player.stamina,
+        thirst: player.thirst,
+        hunger: player.hunger,
+        warmth: player.warmth,
+        is_dead: player.is_dead,
+        in_combat: crate::combat_log::is_in_combat(&player, ctx.timestamp),
+        days_survived: player.days_survived,
+        level: stats.as_ref().map(|s| s.level),
+        experience: stats.as_ref().map(|s| s.experience),
+        selected_hotbar_slot: player.selected_hotbar_slot,
+        main_hand_item_def_id: equipment.as_ref().and_then(|equip| equip.equipped_item_def_id),
+        worn_item_instance_ids,
+        armor_set_bonus: crate::armor_sets::get_active_set_bonus(ctx, player_id).map(|bonus| bonus.set_name.to_string()),
+        hotbar_slots_used,
+        inventory_slots_used,
+        total_item_count,
+        active_buff_ids,
+        achievements_unlocked,
+        updated_at: ctx.timestamp,
+    };
+
+    let snapshots = ctx.db.player_snapshot();
+    if snapshots.player_identity().find(player_id).is_some() {
+        snapshots.player_identity().update(snapshot);
+    } else {
+        snapshots.insert(snapshot);
+    }
+    log::debug!("[PlayerSnapshot] Refreshed snapshot for {:?}.", player_id);
+    Ok(())
+}
+
+/// (hotbar slots used, inventory slots used, total quantity) over the hotbar and inventory.
+/// Equipped items and items held in containers have no slot and are skipped.
+fn summarize_carried_items(items: impl IntoIterator<Item = InventoryItem>) -> (u32, u32, u32) {
+    let (mut hotbar_slots_used, mut inventory_slots_used, mut total_item_count) = (0, 0, 0u32);
+    for item in items {
+        if item.hotbar_slot.is_some() {
+            hotbar_slots_used += 1;
+        } else if item.inventory_slot.is_some() {
+            inventory_slots_used += 1;
+        } else {
+            continue;
+        }
+        total_item_count = total_item_count.saturating_add(item.quantity);
+    }
+    (hotbar_slots_used, inventory_slots_used, total_item_count)
+}
+
+/// Drops the snapshot row for `player_id` (on disconnect).
+pub(crate) fn clear_player_snapshot(ctx: &ReducerContext, player_id: Identity) {
+    ctx.db.player_snapshot().player_identity().delete(player_id);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::items::test_inventory_item;
+
+    #[test]
+    fn summary_counts_only_hotbar_and_inventory_items() {
+        let items = vec![
+            InventoryItem { hotbar_slot: Some(0), ..test_inventory_item(1, 1, 5) },
+            InventoryItem { inventory_slot: Some(3), ..test_inventory_item(2, 1, 10) },
+            InventoryItem { inventory_slot: Some(4), ..test_inventory_item(3, 2, u32::MAX) },
+            test_inventory_item(4, 3, 1), // Worn or deposited
+        ];
+        assert_eq!(summarize_carried_items(items), (1, 2, u32::MAX));
+        assert_eq!(summarize_carried_items(Vec::new()), (0, 0, 0));
+    }
+}
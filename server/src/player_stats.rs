@@ -1,6 +1,6 @@
-use spacetimedb::{Identity, ReducerContext, Table, Timestamp};
+use spacetimedb::{Identity, ReducerContext, SpacetimeType, Table};
 use log;
-use rand::Rng;
+use crate::utils::GameRng;
 use serde::{Serialize, Deserialize};
 
 // --- Experience and Level Constants ---
@@ -8,9 +8,13 @@ const BASE_EXP_PER_KILL: f32 = 10.0;
 const EXP_MULTIPLIER_PER_LEVEL: f32 = 1.2;
 const BASE_EXP_TO_LEVEL: f32 = 100.0;
 const EXP_TO_LEVEL_MULTIPLIER: f32 = 1.5;
+// PvP kill rewards scale with the level gap, clamped so farming low levels is near-worthless
+// and a single lucky kill can't skip several levels.
+const MIN_PVP_EXP_SCALE: f32 = 0.1;
+const MAX_PVP_EXP_SCALE: f32 = 3.0;
 
 // --- Buff Rarity Constants ---
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(SpacetimeType, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum BuffRarity {
     Common,
     Uncommon,
@@ -20,7 +24,7 @@ pub enum BuffRarity {
 }
 
 // --- Buff Types ---
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(SpacetimeType, Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum BuffType {
     Health(f32),           // Percentage increase
     Attack(f32),          // Percentage increase
@@ -64,8 +68,18 @@ fn calculate_exp_to_next_level(level: u32) -> f32 {
     BASE_EXP_TO_LEVEL * (EXP_TO_LEVEL_MULTIPLIER.powi(level as i32 - 1))
 }
 
-fn get_random_buff(rarity: BuffRarity) -> BuffType {
-    let mut rng = rand::thread_rng();
+/// Experience for killing a player, scaled by `EXP_MULTIPLIER_PER_LEVEL` per level of difference.
+/// Killing someone higher-level pays more, someone lower pays less. Never negative, capped both ways.
+pub(crate) fn calculate_pvp_kill_exp(attacker_level: u32, victim_level: u32) -> f32 {
+    // Gaps this wide are far past either clamp; bounding them keeps the cast and powi in range
+    let level_gap = (victim_level as i64 - attacker_level as i64).clamp(-64, 64) as i32;
+    let scale = EXP_MULTIPLIER_PER_LEVEL
+        .powi(level_gap)
+        .clamp(MIN_PVP_EXP_SCALE, MAX_PVP_EXP_SCALE);
+    BASE_EXP_PER_KILL * scale
+}
+
+fn get_random_buff(rng: &mut GameRng, rarity: BuffRarity) -> BuffType {
     let buff_type = rng.gen_range(0..6);
     
     match (buff_type, rarity) {
@@ -109,8 +123,7 @@ fn get_random_buff(rarity: BuffRarity) -> BuffType {
     }
 }
 
-fn get_random_rarity() -> BuffRarity {
-    let mut rng = rand::thread_rng();
+fn get_random_rarity(rng: &mut GameRng) -> BuffRarity {
     let roll = rng.gen_range(0..100);
     
     match roll {
@@ -123,10 +136,8 @@ fn get_random_rarity() -> BuffRarity {
     }
 }
 
-// --- Reducers ---
-#[spacetimedb::reducer]
-pub fn add_experience(ctx: &ReducerContext, amount: f32) -> Result<(), String> {
-    let sender_id = ctx.sender;
+/// Adds experience to a player, handling level-ups and buff offers.
+pub(crate) fn grant_experience(ctx: &ReducerContext, sender_id: Identity, amount: f32) -> Result<(), String> {
     let player_stats = ctx.db.player_stats();
     let buffs = ctx.db.buff();
     
@@ -144,10 +155,11 @@ pub fn add_experience(ctx: &ReducerContext, amount: f32) -> Result<(), String> {
         // Generate random buffs for level up
         let buff_count = 3; // Number of buffs to choose from
         let mut available_buffs = Vec::new();
+        let mut rng = GameRng::from_ctx(ctx);
         
         for _ in 0..buff_count {
-            let rarity = get_random_rarity();
-            let buff_type = get_random_buff(rarity.clone());
+            let rarity = get_random_rarity(&mut rng);
+            let buff_type = get_random_buff(&mut rng, rarity.clone());
             
             let buff = Buff {
                 id: 0, // Auto-incremented
@@ -171,6 +183,31 @@ pub fn add_experience(ctx: &ReducerContext, amount: f32) -> Result<(), String> {
     Ok(())
 }
 
+/// Rewards the killer of a player with level-scaled experience.
+pub(crate) fn award_pvp_kill(ctx: &ReducerContext, killer_id: Identity, victim_id: Identity) {
+    let player_stats = ctx.db.player_stats();
+    let (killer_level, victim_level) = match (player_stats.player_id().find(killer_id), player_stats.player_id().find(victim_id)) {
+        (Some(killer), Some(victim)) => (killer.level, victim.level),
+        _ => {
+            log::warn!("[PvPKill] Missing stats for killer {:?} or victim {:?}. No experience awarded.", killer_id, victim_id);
+            return;
+        }
+    };
+    let exp = calculate_pvp_kill_exp(killer_level, victim_level);
+    match grant_experience(ctx, killer_id, exp) {
+        Ok(_) => log::info!("[PvPKill] Player {:?} (lvl {}) killed {:?} (lvl {}) for {:.1} exp.",
+                          killer_id, killer_level, victim_id, victim_level, exp),
+        Err(e) => log::error!("[PvPKill] Failed to grant kill experience to {:?}: {}", killer_id, e),
+    }
+}
+
+// --- Reducers ---
+#[spacetimedb::reducer]
+pub fn add_experience(ctx: &ReducerContext, amount: f32) -> Result<(), String> {
+    crate::errors::ensure_finite(&[("amount", amount)])?;
+    grant_experience(ctx, ctx.sender, amount)
+}
+
 #[spacetimedb::reducer]
 pub fn select_buff(ctx: &ReducerContext, buff_id: u64) -> Result<(), String> {
     let sender_id = ctx.sender;
@@ -192,10 +229,10 @@ pub fn select_buff(ctx: &ReducerContext, buff_id: u64) -> Result<(), String> {
     
     // Apply buff effect
     match selected_buff.buff_type {
-        BuffType::Health(amount) => stats.base_health *= (1.0 + amount),
-        BuffType::Attack(amount) => stats.base_attack *= (1.0 + amount),
-        BuffType::AttackSpeed(amount) => stats.base_attack_speed *= (1.0 + amount),
-        BuffType::MoveSpeed(amount) => stats.base_move_speed *= (1.0 + amount),
+        BuffType::Health(amount) => stats.base_health *= 1.0 + amount,
+        BuffType::Attack(amount) => stats.base_attack *= 1.0 + amount,
+        BuffType::AttackSpeed(amount) => stats.base_attack_speed *= 1.0 + amount,
+        BuffType::MoveSpeed(amount) => stats.base_move_speed *= 1.0 + amount,
         BuffType::HpRegen(amount) => stats.base_hp_regen += amount,
         BuffType::Armor(amount) => stats.base_armor += amount,
     }
@@ -233,4 +270,25 @@ pub fn initialize_player_stats(ctx: &ReducerContext, player_id: Identity) -> Res
     
     player_stats.insert(stats);
     Ok(())
-} 
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn killing_higher_levels_pays_more_than_farming_lower_ones() {
+        let even_fight = calculate_pvp_kill_exp(5, 5);
+        assert_eq!(even_fight, BASE_EXP_PER_KILL);
+        assert!(calculate_pvp_kill_exp(5, 8) > even_fight);
+        assert!(calculate_pvp_kill_exp(8, 5) < even_fight);
+    }
+
+    #[test]
+    fn pvp_kill_exp_is_positive_and_capped() {
+        assert_eq!(calculate_pvp_kill_exp(50, 1), BASE_EXP_PER_KILL * MIN_PVP_EXP_SCALE);
+        assert_eq!(calculate_pvp_kill_exp(1, 50), BASE_EXP_PER_KILL * MAX_PVP_EXP_SCALE);
+        assert_eq!(calculate_pvp_kill_exp(u32::MAX, 0), BASE_EXP_PER_KILL * MIN_PVP_EXP_SCALE);
+        assert_eq!(calculate_pvp_kill_exp(0, u32::MAX), BASE_EXP_PER_KILL * MAX_PVP_EXP_SCALE);
+    }
+}
@@ -0,0 +1,43 @@
+use spacetimedb::{Identity, ReducerContext, Table, Timestamp};
+use log;
+
+// --- Online Player Table ---
+// Tiny, always-synced projection of who is online. Lets clients show friend lists and
+// population counts without subscribing to every Player row (and its position updates).
+// Rows persist across sessions; disconnecting only flips `is_online`.
+#[spacetimedb::table(name = online_player, public)]
+#[derive(Clone)]
+pub struct OnlinePlayer {
+    #[primary_key]
+    pub identity: Identity,
+    pub username: String,
+    pub is_online: bool,
+    pub last_seen: Timestamp,
+}
+
+/// Records a (newly registered) player as online, creating or refreshing their row.
+pub(crate) fn mark_registered(ctx: &ReducerContext, player_id: Identity, username: &str) {
+    let presence = OnlinePlayer {
+        identity: player_id,
+        username: username.to_string(),
+        is_online: true,
+        last_seen: ctx.timestamp,
+    };
+    let online_players = ctx.db.online_player();
+    if online_players.identity().find(player_id).is_some() {
+        online_players.identity().update(presence);
+    } else {
+        online_players.insert(presence);
+    }
+}
+
+/// Flips an existing player's online flag. Unknown identities (not yet registered) are ignored.
+pub(crate) fn set_online(ctx: &ReducerContext, player_id: Identity, is_online: bool) {
+    let online_players = ctx.db.online_player();
+    if let Some(mut presence) = online_players.identity().find(player_id) {
+        presence.is_online = is_online;
+        presence.last_seen = ctx.timestamp;
+        log::debug!("[Presence] {} ({:?}) is now {}.", presence.username, player_id, if is_online { "online" } else { "offline" });
+        online_players.identity().update(presence);
+    }
+}
@@ -0,0 +1,140 @@
+use spacetimedb::{ReducerContext, SpacetimeType, Table, Timestamp};
+use spacetimedb::spacetimedb_lib::ScheduleAt;
+use std::time::Duration;
+use log;
+
+use crate::player as PlayerTableTrait;
+use crate::utils::get_distance_squared;
+use crate::errors::GameError;
+
+// --- Remnant Constants ---
+const REMNANT_LIFETIME_SECS: i64 = 120; // How long a stump/rubble pile stays before despawning
+const REMNANT_DESPAWN_CHECK_INTERVAL_SECS: u64 = 10;
+const STUMP_BONUS_WOOD: u32 = 8; // One-time bonus for finishing off a tree
+const RUBBLE_BONUS_STONE: u32 = 8; // One-time bonus for finishing off a stone
+
+// What a destroyed resource node leaves behind
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize, SpacetimeType)]
+pub enum RemnantKind {
+    Stump,  // Left by a felled tree, yields Wood
+    Rubble, // Left by a depleted stone, yields Stone
+}
+
+// --- Resource Remnant Table ---
+// A short-lived leftover at the spot where a tree or stone was destroyed. It can be
+// harvested exactly once (the row is deleted on harvest) and despawns after its lifetime.
+#[spacetimedb::table(name = resource_remnant, public)]
+#[derive(Clone)]
+pub struct ResourceRemnant {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub kind: RemnantKind,
+    pub pos_x: f32,
+    pub pos_y: f32,
+    pub source_id: u64, // Tree or stone id it was left by
+    pub expires_at: Timestamp,
+}
+
+// --- Schedule Table ---
+#[spacetimedb::table(name = resource_remnant_despawn_schedule, scheduled(despawn_expired_remnants))]
+#[derive(Clone)]
+pub struct ResourceRemnantDespawnSchedule {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub scheduled_at: ScheduleAt,
+}
+
+// --- Spawn (called from the destruction paths in active_equipment.rs) ---
+
+/// Leaves a remnant where a tree or stone was just destroyed.
+pub(crate) fn spawn_remnant(ctx: &ReducerContext, kind: RemnantKind, source_id: u64, pos_x: f32, pos_y: f32) {
+    let expires_micros = ctx.timestamp.to_micros_since_unix_epoch().saturating_add(REMNANT_LIFETIME_SECS * 1_000_000);
+    match ctx.db.resource_remnant().try_insert(ResourceRemnant {
+        id: 0, // Auto-incremented
+        kind: kind.clone(),
+        pos_x,
+        pos_y,
+        source_id,
+        expires_at: Timestamp::from_micros_since_unix_epoch(expires_micros),
+    }) {
+        Ok(remnant) => log::info!("[Remnant] {:?} {} left by node {} at ({:.1}, {:.1}).", kind, remnant.id, source_id, pos_x, pos_y),
+        Err(e) => log::error!("[Remnant] Failed to insert {:?} for node {}: {}", kind, source_id, e),
+    }
+}
+
+// --- Harvest ---
+
+/// Harvests a remnant for its one-time bonus. Reached through the generic `interact` reducer.
+#[spacetimedb::reducer]
+pub fn harvest_remnant(ctx: &ReducerContext, remnant_id: u64) -> Result<(), String> {
+    let sender_id = ctx.sender;
+    let player = ctx.db.player().identity().find(sender_id)
+        .ok_or_else(|| GameError::NotFound("Player".to_string()))?;
+    if player.is_dead {
+        return Err("Cannot harvest while dead".to_string());
+    }
+
+    // 1. The row only exists until its single harvest, so a missing row is the one-time guard
+    let remnant = ctx.db.resource_remnant().id().find(remnant_id)
+        .ok_or_else(|| GameError::NotFound(format!("Remnant {} (already harvested or despawned)", remnant_id)))?;
+    if remnant.expires_at <= ctx.timestamp {
+        return Err("Remnant has already decayed".to_string());
+    }
+
+    // 2. Range check
+    let dist_sq = get_distance_squared(player.position_x, player.position_y, remnant.pos_x, remnant.pos_y);
+    if dist_sq > crate::interaction::REMNANT_INTERACTION_DISTANCE_SQUARED {
+        return Err(GameError::TooFarAway.into());
+    }
+
+    // 3. Remove first, then grant; anything that doesn't fit is dropped at the player's feet
+    ctx.db.resource_remnant().id().delete(remnant_id);
+    let (item_name, bonus) = match remnant.kind {
+        RemnantKind::Stump => ("Wood", STUMP_BONUS_WOOD),
+        RemnantKind::Rubble => ("Stone", RUBBLE_BONUS_STONE),
+    };
+    let item_def = crate::items::get_item_def_by_name(ctx, item_name)
+        .ok_or_else(|| GameError::NotFound(format!("Item definition '{}'", item_name)))?;
+    crate::items::grant_or_drop(ctx, sender_id, item_def.id, bonus)?;
+
+    log::info!("[Remnant] Player {:?} harvested {:?} {} for {} {}.", sender_id, remnant.kind, remnant_id, bonus, item_name);
+    Ok(())
+}
+
+// --- Scheduled Despawn Reducer ---
+
+/// Scheduled reducer that removes remnants past their lifetime.
+#[spacetimedb::reducer]
+pub fn despawn_expired_remnants(ctx: &ReducerContext, _schedule: ResourceRemnantDespawnSchedule) -> Result<(), String> {
+    let remnants = ctx.db.resource_remnant();
+    let expired_ids: Vec<u64> = remnants.iter()
+        .filter(|remnant| remnant.expires_at <= ctx.timestamp)
+        .map(|remnant| remnant.id)
+        .collect();
+
+    for remnant_id in &expired_ids {
+        remnants.id().delete(*remnant_id);
+    }
+    if !expired_ids.is_empty() {
+        log::info!("[RemnantDespawn] Despawned {} expired remnants.", expired_ids.len());
+    }
+    Ok(())
+}
+
+// --- Init Helper (Called from lib.rs) ---
+pub(crate) fn init_remnant_schedule(ctx: &ReducerContext) -> Result<(), String> {
+    let schedule_table = ctx.db.resource_remnant_despawn_schedule();
+    if schedule_table.iter().count() == 0 {
+        log::info!("Starting resource remnant despawn schedule (every {}s).", REMNANT_DESPAWN_CHECK_INTERVAL_SECS);
+        let interval = Duration::from_secs(REMNANT_DESPAWN_CHECK_INTERVAL_SECS);
+        schedule_table.insert(ResourceRemnantDespawnSchedule {
+            id: 0, // Auto-incremented
+            scheduled_at: ScheduleAt::Interval(interval.into()),
+        });
+    } else {
+        log::debug!("Resource remnant despawn schedule already exists.");
+    }
+    Ok(())
+}
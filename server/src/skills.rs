@@ -0,0 +1,81 @@
+use spacetimedb::{Identity, ReducerContext, Table};
+use log;
+
+// --- Cooking Skill Constants ---
+const COOKING_XP_PER_ITEM: u32 = 5; // Per item in a stack that comes off the fire cooked (not burnt)
+const XP_PER_COOKING_LEVEL: u32 = 100;
+const MAX_COOKING_LEVEL: u32 = 20;
+const BASE_BURN_CHANCE: f32 = 0.25; // Chance an unskilled cook burns a finished stack
+const BURN_CHANCE_REDUCTION_PER_LEVEL: f32 = 0.0125; // Reaches 0 at MAX_COOKING_LEVEL
+
+// --- Table ---
+
+// One row per player that has trained a skill, created on the first XP gain.
+#[spacetimedb::table(name = player_skill, public)]
+#[derive(Clone)]
+pub struct PlayerSkill {
+    #[primary_key]
+    pub player_identity: Identity,
+    pub cooking_xp: u32,
+}
+
+// --- Skill Effects ---
+
+/// Cooking level for a total amount of cooking XP, capped at `MAX_COOKING_LEVEL`.
+pub(crate) fn cooking_level_for_xp(cooking_xp: u32) -> u32 {
+    (cooking_xp / XP_PER_COOKING_LEVEL).min(MAX_COOKING_LEVEL)
+}
+
+/// Chance (0-1) that a finished stack burns instead of cooking. Falls linearly with cooking level.
+pub(crate) fn cooking_burn_chance(cooking_level: u32) -> f32 {
+    (BASE_BURN_CHANCE - cooking_level.min(MAX_COOKING_LEVEL) as f32 * BURN_CHANCE_REDUCTION_PER_LEVEL).max(0.0)
+}
+
+// --- Lookups and XP ---
+
+/// The player's cooking level (0 if they have never cooked).
+pub(crate) fn get_cooking_level(ctx: &ReducerContext, player_id: Identity) -> u32 {
+    ctx.db.player_skill().player_identity().find(player_id)
+        .map_or(0, |skill| cooking_level_for_xp(skill.cooking_xp))
+}
+
+/// Adds XP for `cooked_quantity` successfully cooked items.
+pub(crate) fn grant_cooking_xp(ctx: &ReducerContext, player_id: Identity, cooked_quantity: u32) {
+    let skills = ctx.db.player_skill();
+    let xp_gain = cooked_quantity.saturating_mul(COOKING_XP_PER_ITEM);
+    match skills.player_identity().find(player_id) {
+        Some(mut skill) => {
+            let old_level = cooking_level_for_xp(skill.cooking_xp);
+            skill.cooking_xp = skill.cooking_xp.saturating_add(xp_gain);
+            let new_level = cooking_level_for_xp(skill.cooking_xp);
+            if new_level > old_level {
+                log::info!("[Skills] Player {:?} reached cooking level {}.", player_id, new_level);
+            }
+            skills.player_identity().update(skill);
+        }
+        None => {
+            skills.insert(PlayerSkill { player_identity: player_id, cooking_xp: xp_gain });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn burn_chance_falls_with_cooking_level() {
+        assert_eq!(cooking_burn_chance(0), BASE_BURN_CHANCE);
+        assert!(cooking_burn_chance(5) < cooking_burn_chance(0));
+        assert!(cooking_burn_chance(10) < cooking_burn_chance(5));
+        assert_eq!(cooking_burn_chance(MAX_COOKING_LEVEL), 0.0);
+        assert_eq!(cooking_burn_chance(MAX_COOKING_LEVEL + 10), 0.0);
+    }
+
+    #[test]
+    fn cooking_level_is_capped() {
+        assert_eq!(cooking_level_for_xp(0), 0);
+        assert_eq!(cooking_level_for_xp(XP_PER_COOKING_LEVEL * 3 + 1), 3);
+        assert_eq!(cooking_level_for_xp(u32::MAX), MAX_COOKING_LEVEL);
+    }
+}
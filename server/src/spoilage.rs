@@ -0,0 +1,139 @@
+use spacetimedb::{ReducerContext, Table, Timestamp};
+use spacetimedb::spacetimedb_lib::ScheduleAt;
+use std::time::Duration;
+use log;
+
+use crate::items::{InventoryItem, ItemDefinition};
+use crate::items::inventory_item as InventoryItemTableTrait;
+use crate::inventory_management::ItemContainer;
+use crate::wooden_storage_box::wooden_storage_box as WoodenStorageBoxTableTrait;
+use crate::utility_belt::utility_belt as UtilityBeltTableTrait;
+use crate::composter::composter as ComposterTableTrait;
+use crate::campfire::campfire as CampfireTableTrait;
+
+// --- Spoilage Constants ---
+// Spoil times are rounded up to this granularity so food gathered a few minutes apart
+// still stacks (stacks only merge on identical `spoils_at`, see `has_same_instance_state`).
+const SPOILAGE_BUCKET_SECS: i64 = 300;
+const SPOILAGE_CHECK_INTERVAL_SECS: u64 = 30;
+const MIN_FRESHNESS_EFFECT_MULTIPLIER: f32 = 0.25; // Nearly spoiled food still restores a quarter of its value
+pub(crate) const ROTTEN_FOOD_NAME: &str = "Rotten Food";
+
+// --- Schedule Table ---
+#[spacetimedb::table(name = spoilage_schedule, scheduled(spoil_expired_food))]
+#[derive(Clone)]
+pub struct SpoilageSchedule {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub scheduled_at: ScheduleAt,
+}
+
+// --- Freshness Helpers ---
+
+/// Spoil time for a freshly created stack of `item_def`, or None if the item never spoils.
+/// Longer-lasting foods (e.g. cooked ones) just need a larger `shelf_life_secs`.
+pub(crate) fn initial_spoils_at(ctx: &ReducerContext, item_def: &ItemDefinition) -> Option<Timestamp> {
+    let shelf_life_secs = item_def.shelf_life_secs? as i64;
+    let bucket_micros = SPOILAGE_BUCKET_SECS * 1_000_000;
+    let raw_micros = ctx.timestamp.to_micros_since_unix_epoch().saturating_add(shelf_life_secs * 1_000_000);
+    // Round up so the bucket never shortens an item's shelf life
+    let bucketed_micros = (raw_micros + bucket_micros - 1) / bucket_micros * bucket_micros;
+    Some(Timestamp::from_micros_since_unix_epoch(bucketed_micros))
+}
+
+/// Remaining freshness in [0.0, 1.0]. Items that don't spoil are always fully fresh.
+pub(crate) fn freshness(ctx: &ReducerContext, item: &InventoryItem, item_def: &ItemDefinition) -> f32 {
+    let (Some(spoils_at), Some(shelf_life_secs)) = (item.spoils_at, item_def.shelf_life_secs) else {
+        return 1.0;
+    };
+    if shelf_life_secs == 0 {
+        return 0.0;
+    }
+    let remaining_micros = spoils_at.to_micros_since_unix_epoch()
+        .saturating_sub(ctx.timestamp.to_micros_since_unix_epoch());
+    (remaining_micros as f32 / (shelf_life_secs as f32 * 1_000_000.0)).clamp(0.0, 1.0)
+}
+
+/// Scale applied to a consumable's effects: full value when fresh, down to
+/// `MIN_FRESHNESS_EFFECT_MULTIPLIER` just before it turns into Rotten Food.
+pub(crate) fn freshness_effect_multiplier(freshness: f32) -> f32 {
+    MIN_FRESHNESS_EFFECT_MULTIPLIER + (1.0 - MIN_FRESHNESS_EFFECT_MULTIPLIER) * freshness.clamp(0.0, 1.0)
+}
+
+// --- Scheduled Reducer ---
+
+/// Periodically turns spoiled stacks into Rotten Food, wherever they are stored.
+#[spacetimedb::reducer]
+pub fn spoil_expired_food(ctx: &ReducerContext, _schedule: SpoilageSchedule) -> Result<(), String> {
+    let inventory = ctx.db.inventory_item();
+    let rotten_def_id = match crate::items::get_item_def_by_name(ctx, ROTTEN_FOOD_NAME) {
+        Some(def) => def.id,
+        None => {
+            log::warn!("[Spoilage] '{}' item definition missing; skipping spoilage check.", ROTTEN_FOOD_NAME);
+            return Ok(());
+        }
+    };
+
+    let spoiled: Vec<InventoryItem> = inventory.iter()
+        .filter(|item| item.spoils_at.is_some_and(|spoils_at| spoils_at <= ctx.timestamp))
+        .collect();
+
+    for mut item in spoiled {
+        let instance_id = item.instance_id;
+        item.item_def_id = rotten_def_id;
+        item.spoils_at = None;
+        inventory.instance_id().update(item);
+        // Containers cache the definition id per slot, keep them in sync
+        update_container_slot_def_id(ctx, instance_id, rotten_def_id);
+        log::info!("[Spoilage] Item instance {} spoiled into {}.", instance_id, ROTTEN_FOOD_NAME);
+    }
+
+    Ok(())
+}
+
+// Rewrites the cached definition id of whichever box, belt, composter or campfire slot holds `instance_id`.
+fn update_container_slot_def_id(ctx: &ReducerContext, instance_id: u64, new_def_id: u64) {
+    for mut storage_box in ctx.db.wooden_storage_box().iter() {
+        if let Some(slot) = (0..storage_box.num_slots() as u8).find(|&i| storage_box.get_slot_instance_id(i) == Some(instance_id)) {
+            storage_box.set_slot(slot, Some(instance_id), Some(new_def_id));
+            ctx.db.wooden_storage_box().id().update(storage_box);
+            return;
+        }
+    }
+    for mut belt in ctx.db.utility_belt().iter() {
+        if let Some(slot) = (0..belt.num_slots() as u8).find(|&i| belt.get_slot_instance_id(i) == Some(instance_id)) {
+            belt.set_slot(slot, Some(instance_id), Some(new_def_id));
+            ctx.db.utility_belt().player_identity().update(belt);
+            return;
+        }
+    }
+    for mut composter in ctx.db.composter().iter() {
+        if let Some(slot) = (0..composter.num_slots() as u8).find(|&i| composter.get_slot_instance_id(i) == Some(instance_id)) {
+            composter.set_slot(slot, Some(instance_id), Some(new_def_id));
+            ctx.db.composter().id().update(composter);
+            return;
+        }
+    }
+    for mut campfire in ctx.db.campfire().iter() { // Raw food waiting to be cooked
+        if let Some(slot) = (0..campfire.num_slots() as u8).find(|&i| campfire.get_slot_instance_id(i) == Some(instance_id)) {
+            campfire.set_slot(slot, Some(instance_id), Some(new_def_id));
+            ctx.db.campfire().id().update(campfire);
+            return;
+        }
+    }
+}
+
+// --- Init Helper (Called from lib.rs) ---
+pub(crate) fn init_spoilage_schedule(ctx: &ReducerContext) -> Result<(), String> {
+    let schedule_table = ctx.db.spoilage_schedule();
+    if schedule_table.iter().count() == 0 {
+        log::info!("Starting food spoilage schedule (every {}s).", SPOILAGE_CHECK_INTERVAL_SECS);
+        let interval = Duration::from_secs(SPOILAGE_CHECK_INTERVAL_SECS);
+        schedule_table.insert(SpoilageSchedule {
+            id: 0, // Auto-incremented
+            scheduled_at: ScheduleAt::Interval(interval.into()),
+        });
+    }
+    Ok(())
+}
@@ -5,7 +5,7 @@ use crate::items_database::{ITEM_DEFINITIONS, HAMMER_DEF, DAGGER_DEF, SWORD_DEF,
 use crate::character::CharacterType;
 
 // Import needed Item types and Table Traits
-use crate::items::{ItemDefinition, InventoryItem, EquipmentSlot, item_definition as ItemDefinitionTableTrait, inventory_item as InventoryItemTableTrait};
+use crate::items::{ItemDefinition, InventoryItem, EquipmentSlot, inventory_item as InventoryItemTableTrait};
 // Import ActiveEquipment types and Table Trait
 use crate::active_equipment::{ActiveEquipment, active_equipment as ActiveEquipmentTableTrait};
 
@@ -13,7 +13,6 @@ use crate::active_equipment::{ActiveEquipment, active_equipment as ActiveEquipme
 pub(crate) fn grant_starting_items(ctx: &ReducerContext, player_id: Identity, username: &str) -> Result<(), String> {
     log::info!("[GrantItems] Granting starting items & equipment to player {} ({:?})...", username, player_id);
 
-    let item_defs = ctx.db.item_definition();
     let inventory = ctx.db.inventory_item();
     let characters = ctx.db.character();
     
@@ -34,6 +33,10 @@ pub(crate) fn grant_starting_items(ctx: &ReducerContext, player_id: Identity, us
         quantity: 1,
         hotbar_slot: Some(0), // Put weapon in first slot
         inventory_slot: None,
+        charges: None,
+        durability: None,
+        spoils_at: None,
+        quality: None,
     };
     
     // Insert the weapon
@@ -92,7 +95,7 @@ let starting_weapon = match character_type {
 };
 
 // Add starting weapon to hotbar slot 0
-if let Some(weapon_def) = item_defs.iter().find(|def| def.name == starting_weapon) {
+if let Some(weapon_def) = crate::items::get_item_def_by_name(ctx, starting_weapon) {
     let weapon_item = InventoryItem { 
         instance_id: 0,
         player_identity: player_id, 
@@ -100,6 +103,10 @@ if let Some(weapon_def) = item_defs.iter().find(|def| def.name == starting_weapo
         quantity: 1,
         hotbar_slot: Some(0u8),
         inventory_slot: None,
+        charges: None,
+        durability: None,
+        spoils_at: None,
+        quality: None,
     };
     match inventory.try_insert(weapon_item) {
         Ok(_) => {
@@ -121,7 +128,7 @@ log::info!("[GrantItems] Defined {} starting inventory/hotbar item entries.", st
 
 for (item_name, quantity, hotbar_slot_opt, inventory_slot_opt) in starting_inv_items.iter() {
      log::debug!("[GrantItems] Processing inv/hotbar entry: {}", item_name);
-    if let Some(item_def) = item_defs.iter().find(|def| def.name == *item_name) {
+    if let Some(item_def) = crate::items::get_item_def_by_name(ctx, item_name) {
         let item_to_insert = InventoryItem { 
             instance_id: 0,
             player_identity: player_id, 
@@ -129,6 +136,10 @@ for (item_name, quantity, hotbar_slot_opt, inventory_slot_opt) in starting_inv_i
             quantity: *quantity,
             hotbar_slot: *hotbar_slot_opt,
             inventory_slot: *inventory_slot_opt,
+            charges: None,
+            durability: None,
+            spoils_at: None,
+            quality: None,
         };
         match inventory.try_insert(item_to_insert) {
             Ok(_) => {
@@ -161,6 +172,8 @@ let mut equip_entry = match active_equip_table.player_identity().find(player_id)
             equipped_item_instance_id: None,
             equipped_item_def_id: None,
             swing_start_time_ms: 0,
+            weapon_drawn: false,
+            draw_ready_at_ms: 0,
             head_item_instance_id: None,
             chest_item_instance_id: None,
             legs_item_instance_id: None,
@@ -184,7 +197,7 @@ let starting_equipment = [
 
 for (item_name, target_slot) in starting_equipment.iter() {
     log::debug!("[GrantItems] Processing equipment entry: {}", item_name);
-    if let Some(item_def) = item_defs.iter().find(|def| def.name == *item_name) {
+    if let Some(item_def) = crate::items::get_item_def_by_name(ctx, item_name) {
         // Create the InventoryItem instance (unslotted)
         let item_to_equip = InventoryItem {
             instance_id: 0, // Auto-inc
@@ -193,6 +206,10 @@ for (item_name, target_slot) in starting_equipment.iter() {
             quantity: 1, // Equipment is typically quantity 1
             hotbar_slot: None, // Not in hotbar
             inventory_slot: None, // Not in inventory
+            charges: None,
+            durability: None,
+            spoils_at: None,
+            quality: None,
         };
         match inventory.try_insert(item_to_equip) {
             Ok(inserted_item) => {
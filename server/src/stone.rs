@@ -2,6 +2,7 @@ use spacetimedb::{Timestamp};
 
 // Import necessary constants
 use crate::{PLAYER_RADIUS}; // Removed unused TILE_SIZE_PX
+use crate::utils::ResourceStage;
 
 // Import tree constants needed for density calculation
 use crate::tree::TREE_DENSITY_PERCENT;
@@ -16,6 +17,7 @@ pub(crate) const MIN_STONE_DISTANCE_SQ: f32 = MIN_STONE_DISTANCE_PX * MIN_STONE_
 pub(crate) const MIN_STONE_TREE_DISTANCE_PX: f32 = 100.0;
 pub(crate) const MIN_STONE_TREE_DISTANCE_SQ: f32 = MIN_STONE_TREE_DISTANCE_PX * MIN_STONE_TREE_DISTANCE_PX;
 pub(crate) const STONE_INITIAL_HEALTH: u32 = 100;
+pub(crate) const STONE_TOTAL_YIELD: u32 = 100; // Stone given over the node's life, independent of tool damage
 
 // --- Stone Struct and Table ---
 #[spacetimedb::table(name = stone, public)]
@@ -27,6 +29,9 @@ pub struct Stone {
     pub pos_x: f32,
     pub pos_y: f32,
     pub health: u32, // Stones just disappear when health is 0
+    pub stage: ResourceStage, // Derived from health; updated on every hit and on respawn
+    pub remaining_yield: u32, // Stone left to give. Decoupled from health (see utils::take_resource_yield)
     pub last_hit_time: Option<Timestamp>, // Added for shake effect
     pub respawn_at: Option<Timestamp>, // Added for respawn timer
+    pub last_regrow_at: Option<Timestamp>, // Renewable mode: last time health regrew (see environment.rs)
 }
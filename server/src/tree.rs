@@ -2,6 +2,7 @@ use spacetimedb::{SpacetimeType, Timestamp};
 
 // Import necessary constants from the main crate or environment
 use crate::{PLAYER_RADIUS}; // Removed unused TILE_SIZE_PX
+use crate::utils::ResourceStage;
 
 // --- Tree-Specific Constants ---
 
@@ -19,6 +20,7 @@ pub(crate) const MAX_TREE_SEEDING_ATTEMPTS_FACTOR: u32 = 5;
 pub(crate) const MIN_TREE_DISTANCE_PX: f32 = 200.0;
 pub(crate) const MIN_TREE_DISTANCE_SQ: f32 = MIN_TREE_DISTANCE_PX * MIN_TREE_DISTANCE_PX;
 pub(crate) const TREE_INITIAL_HEALTH: u32 = 100;
+pub(crate) const TREE_TOTAL_YIELD: u32 = 100; // Wood given over the tree's life, independent of tool damage
 
 // --- Tree Enums and Structs ---
 
@@ -37,7 +39,10 @@ pub struct Tree {
     pub pos_x: f32,
     pub pos_y: f32,
     pub health: u32,
+    pub stage: ResourceStage, // Derived from health; updated on every hit and on respawn
+    pub remaining_yield: u32, // Wood left to give. Decoupled from health (see utils::take_resource_yield)
     pub tree_type: TreeType,
     pub last_hit_time: Option<Timestamp>,
     pub respawn_at: Option<Timestamp>,
+    pub last_regrow_at: Option<Timestamp>, // Renewable mode: last time health regrew (see environment.rs)
 }
\ No newline at end of file
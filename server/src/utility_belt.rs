@@ -0,0 +1,190 @@
+use spacetimedb::{Identity, ReducerContext, Table};
+use log;
+
+// Import table traits needed for ctx.db access
+use crate::items::{inventory_item as InventoryItemTableTrait, item_definition as ItemDefinitionTableTrait};
+use crate::items::{InventoryItem, ItemCategory};
+use crate::inventory_management::{self, ItemContainer};
+use crate::errors::GameError;
+
+// --- Constants ---
+pub const NUM_BELT_SLOTS: usize = 2;
+
+// --- Utility Belt Table ---
+// A tiny per-player container reserved for consumables, separate from the hotbar.
+// Items in the belt have both hotbar_slot and inventory_slot set to None (like box contents).
+#[spacetimedb::table(name = utility_belt, public)]
+#[derive(Clone)]
+pub struct UtilityBelt {
+    #[primary_key]
+    pub player_identity: Identity,
+    pub slot_instance_id_0: Option<u64>,
+    pub slot_def_id_0: Option<u64>,
+    pub slot_instance_id_1: Option<u64>,
+    pub slot_def_id_1: Option<u64>,
+}
+
+// --- Trait Implementation ---
+
+impl ItemContainer for UtilityBelt {
+    fn num_slots(&self) -> usize {
+        NUM_BELT_SLOTS
+    }
+
+    fn get_slot_instance_id(&self, slot_index: u8) -> Option<u64> {
+        match slot_index {
+            0 => self.slot_instance_id_0,
+            1 => self.slot_instance_id_1,
+            _ => None,
+        }
+    }
+
+    fn get_slot_def_id(&self, slot_index: u8) -> Option<u64> {
+        match slot_index {
+            0 => self.slot_def_id_0,
+            1 => self.slot_def_id_1,
+            _ => None,
+        }
+    }
+
+    fn set_slot(&mut self, slot_index: u8, instance_id: Option<u64>, def_id: Option<u64>) {
+        match slot_index {
+            0 => { self.slot_instance_id_0 = instance_id; self.slot_def_id_0 = def_id; }
+            1 => { self.slot_instance_id_1 = instance_id; self.slot_def_id_1 = def_id; }
+            _ => {} // Ignore invalid index
+        }
+    }
+}
+
+// --- Helpers ---
+
+/// Returns the player's belt row, creating an empty one if needed (not yet inserted).
+fn get_or_default_belt(ctx: &ReducerContext, player_id: Identity) -> (UtilityBelt, bool) {
+    match ctx.db.utility_belt().player_identity().find(player_id) {
+        Some(belt) => (belt, true),
+        None => (UtilityBelt {
+            player_identity: player_id,
+            slot_instance_id_0: None,
+            slot_def_id_0: None,
+            slot_instance_id_1: None,
+            slot_def_id_1: None,
+        }, false),
+    }
+}
+
+/// Inserts or updates the belt row.
+fn commit_belt(ctx: &ReducerContext, belt: UtilityBelt, exists: bool) {
+    if exists {
+        ctx.db.utility_belt().player_identity().update(belt);
+    } else {
+        ctx.db.utility_belt().insert(belt);
+    }
+}
+
+/// Removes the given item from whichever belt slot holds it, if any.
+pub(crate) fn clear_item_from_belts(ctx: &ReducerContext, item_instance_id: u64) -> bool {
+    let belts = ctx.db.utility_belt();
+    for mut belt in belts.iter() {
+        for slot_index in 0..NUM_BELT_SLOTS as u8 {
+            if belt.get_slot_instance_id(slot_index) == Some(item_instance_id) {
+                log::debug!("[UtilityBelt] Clearing item {} from belt slot {} of {:?}.", item_instance_id, slot_index, belt.player_identity);
+                belt.set_slot(slot_index, None, None);
+                belts.player_identity().update(belt);
+                return true;
+            }
+        }
+    }
+    false
+}
+
+/// True if the item sits in its owner's belt. Belt items have no inventory or hotbar slot, so
+/// reducers that let unslotted (equipped, fuel) items through without an owner check use this.
+pub(crate) fn is_in_belt(ctx: &ReducerContext, item: &InventoryItem) -> bool {
+    ctx.db.utility_belt().player_identity().find(item.player_identity)
+        .is_some_and(|belt| belt_holds(&belt, item.instance_id))
+}
+
+fn belt_holds(belt: &UtilityBelt, item_instance_id: u64) -> bool {
+    (0..NUM_BELT_SLOTS as u8).any(|slot_index| belt.get_slot_instance_id(slot_index) == Some(item_instance_id))
+}
+
+/// Deletes a player's belt and everything in it (used on disconnect cleanup).
+pub(crate) fn delete_player_belt(ctx: &ReducerContext, player_id: Identity) {
+    if let Some(belt) = ctx.db.utility_belt().player_identity().find(player_id) {
+        for slot_index in 0..NUM_BELT_SLOTS as u8 {
+            if let Some(instance_id) = belt.get_slot_instance_id(slot_index) {
+                ctx.db.inventory_item().instance_id().delete(instance_id);
+            }
+        }
+        ctx.db.utility_belt().player_identity().delete(player_id);
+        log::info!("Deleted utility belt for player {:?}", player_id);
+    }
+}
+
+// --- Reducers ---
+
+/// Moves a consumable from the player's inventory/hotbar into a belt slot (merging or swapping if occupied).
+#[spacetimedb::reducer]
+pub fn move_item_to_belt(ctx: &ReducerContext, belt_slot: u8, item_instance_id: u64) -> Result<(), String> {
+    let item = ctx.db.inventory_item().instance_id().find(item_instance_id)
+        .ok_or_else(|| GameError::NotFound(format!("Item instance {}", item_instance_id)))?;
+    let item_def = ctx.db.item_definition().id().find(item.item_def_id)
+        .ok_or_else(|| GameError::NotFound(format!("Item definition {}", item.item_def_id)))?;
+    if item_def.category != ItemCategory::Consumable {
+        return Err(GameError::InvalidSlot(format!("Only consumables fit in the utility belt ('{}' is not).", item_def.name)).into());
+    }
+
+    let (mut belt, exists) = get_or_default_belt(ctx, ctx.sender);
+    inventory_management::handle_move_to_container_slot(ctx, &mut belt, belt_slot, item_instance_id)?;
+    commit_belt(ctx, belt, exists);
+    Ok(())
+}
+
+/// Moves an item from a belt slot back into the player's inventory or hotbar.
+#[spacetimedb::reducer]
+pub fn move_item_from_belt(ctx: &ReducerContext, belt_slot: u8, target_slot_type: String, target_slot_index: u32) -> Result<(), String> {
+    let (mut belt, exists) = get_or_default_belt(ctx, ctx.sender);
+    inventory_management::handle_move_from_container_slot(ctx, &mut belt, belt_slot, target_slot_type, target_slot_index)?;
+    commit_belt(ctx, belt, exists);
+    Ok(())
+}
+
+/// Consumes one item from the given belt slot.
+#[spacetimedb::reducer]
+pub fn use_belt_slot(ctx: &ReducerContext, belt_slot: u8) -> Result<(), String> {
+    let sender_id = ctx.sender;
+    let (mut belt, exists) = get_or_default_belt(ctx, sender_id);
+    if belt_slot as usize >= NUM_BELT_SLOTS {
+        return Err(GameError::InvalidSlot(format!("Belt slot {} (must be 0-{})", belt_slot, NUM_BELT_SLOTS - 1)).into());
+    }
+    let instance_id = belt.get_slot_instance_id(belt_slot)
+        .ok_or_else(|| format!("Belt slot {} is empty.", belt_slot))?;
+
+    crate::consumables::consume_item(ctx, instance_id)?;
+
+    // Clear the slot if that was the last one in the stack
+    if ctx.db.inventory_item().instance_id().find(instance_id).is_none() {
+        belt.set_slot(belt_slot, None, None);
+        commit_belt(ctx, belt, exists);
+    }
+    log::info!("[UtilityBelt] Player {:?} used belt slot {} (item {}).", sender_id, belt_slot, instance_id);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn belt_holds_only_its_slotted_items() {
+        let belt = UtilityBelt {
+            player_identity: Identity::default(),
+            slot_instance_id_0: None,
+            slot_def_id_0: None,
+            slot_instance_id_1: Some(42),
+            slot_def_id_1: Some(7),
+        };
+        assert!(belt_holds(&belt, 42));
+        assert!(!belt_holds(&belt, 41));
+    }
+}
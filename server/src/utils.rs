@@ -9,19 +9,103 @@
  *   - Enhances code readability in the modules that use these helpers.
  *
  * Examples:
+ *   - `GameRng`: Shared randomness source for gameplay rolls, seedable for deterministic tests.
  *   - `attempt_single_spawn`: Generic function for spawning resources during environment seeding.
  *   - `check_and_respawn_resource`: Macro for handling the logic of checking and respawning resources.
  */
 
 use spacetimedb::{ReducerContext, Table, SpacetimeType};
 use noise::NoiseFn;
-use rand::{Rng, rngs::StdRng};
+use rand::{Rng, RngCore, SeedableRng, rngs::StdRng};
+use rand::distributions::uniform::{SampleRange, SampleUniform};
 use std::collections::HashSet;
 use log;
 
 // Assuming these are accessible from the crate root
 use crate::{WORLD_WIDTH_PX, WORLD_HEIGHT_PX, TILE_SIZE_PX};
 
+/// Randomness source for gameplay rolls (loot, bonus drops, spawn positions).
+/// Reducers build it with `GameRng::from_ctx`, which draws from SpacetimeDB's per-reducer RNG;
+/// `GameRng::from_seed` yields a reproducible sequence for tests and debugging.
+/// Implements `RngCore`, so it can be passed to helpers generic over `R: Rng`.
+pub struct GameRng {
+    inner: StdRng,
+}
+
+impl GameRng {
+    pub fn from_ctx(ctx: &ReducerContext) -> Self {
+        Self::from_seed(ctx.rng().gen())
+    }
+
+    pub fn from_seed(seed: u64) -> Self {
+        Self { inner: StdRng::seed_from_u64(seed) }
+    }
+
+    pub fn gen_range<T: SampleUniform, R: SampleRange<T>>(&mut self, range: R) -> T {
+        self.inner.gen_range(range)
+    }
+
+    /// True with the given probability (0.0 = never, 1.0 = always).
+    pub fn chance(&mut self, probability: f32) -> bool {
+        self.inner.gen::<f32>() < probability
+    }
+}
+
+impl RngCore for GameRng {
+    fn next_u32(&mut self) -> u32 {
+        self.inner.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.inner.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.inner.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.inner.try_fill_bytes(dest)
+    }
+}
+
+/// Discrete depletion stage of a harvestable resource, derived from its health.
+/// Lets clients pick a sprite (full, damaged, stump) without knowing health thresholds.
+#[derive(Clone, Debug, PartialEq, Eq, SpacetimeType)]
+pub enum ResourceStage {
+    Full,     // Untouched, or above half health
+    Damaged,  // At or below half health
+    Depleted, // Health 0, waiting to respawn (tree stump / rubble)
+}
+
+const RESOURCE_DAMAGED_HEALTH_RATIO: f32 = 0.5;
+
+/// Maps a resource's current health to its visual stage.
+pub fn resource_stage_for_health(health: u32, max_health: u32) -> ResourceStage {
+    if health == 0 {
+        ResourceStage::Depleted
+    } else if max_health > 0 && (health as f32 / max_health as f32) <= RESOURCE_DAMAGED_HEALTH_RATIO {
+        ResourceStage::Damaged
+    } else {
+        ResourceStage::Full
+    }
+}
+
+/// Takes the share of a resource's yield that a hit down to `health` has earned.
+/// Yield is paid out proportionally to health lost, so a resource always gives exactly
+/// `total_yield` over its life no matter how hard (or how often) it was hit.
+/// Decrements `remaining_yield` and returns the amount to grant.
+pub fn take_resource_yield(remaining_yield: &mut u32, health: u32, max_health: u32, total_yield: u32) -> u32 {
+    let yield_still_owed = if max_health == 0 {
+        0
+    } else {
+        (total_yield as u64 * health.min(max_health) as u64 / max_health as u64) as u32
+    };
+    let to_grant = remaining_yield.saturating_sub(yield_still_owed);
+    *remaining_yield -= to_grant;
+    to_grant
+}
+
 /// Calculates the valid min/max tile coordinates based on world dimensions and a margin.
 pub fn calculate_tile_bounds(world_width_tiles: u32, world_height_tiles: u32, margin: u32) -> (u32, u32, u32, u32) {
     let min_tile_x = margin;
@@ -58,6 +142,25 @@ pub fn get_distance_squared(x1: f32, y1: f32, x2: f32, y2: f32) -> f32 {
     dx * dx + dy * dy
 }
 
+/// True if (x1, y1) is within the squared distance `range_sq` of (x2, y2). The boundary counts as in range.
+/// Callers pass the target's collision point (e.g. `pos_y - TREE_COLLISION_Y_OFFSET`) where one applies.
+#[inline]
+pub fn within_range(x1: f32, y1: f32, x2: f32, y2: f32, range_sq: f32) -> bool {
+    get_distance_squared(x1, y1, x2, y2) <= range_sq
+}
+
+/// True if a target at offset (dx, dy) from the attacker lies in the attack cone around the
+/// unit vector (forward_x, forward_y), at most `half_angle_rad` off-axis. A zero offset is never in the arc.
+pub fn is_within_arc(dx: f32, dy: f32, forward_x: f32, forward_y: f32, half_angle_rad: f32) -> bool {
+    let distance = (dx * dx + dy * dy).sqrt();
+    if distance <= 0.0 {
+        return false;
+    }
+    // Clamp guards acos against rounding just past +-1
+    let dot_product = ((forward_x * dx + forward_y * dy) / distance).clamp(-1.0, 1.0);
+    dot_product.acos() <= half_angle_rad
+}
+
 /// Attempts one resource spawn at a random valid tile.
 /// Handles noise check, distance checks, and insertion.
 /// Returns Ok(true) if successful, Ok(false) if conditions not met (e.g., tile occupied, too close), Err on DB error.
@@ -193,3 +296,64 @@ macro_rules! check_and_respawn_resource {
         }
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Hits `health` down to 0 in steps of `damage`, summing what each hit grants
+    fn total_yield_for_damage(damage: u32) -> u32 {
+        let (max_health, total_yield): (u32, u32) = (100, 30);
+        let (mut health, mut remaining_yield, mut granted) = (max_health, total_yield, 0);
+        while health > 0 {
+            health = health.saturating_sub(damage);
+            granted += take_resource_yield(&mut remaining_yield, health, max_health, total_yield);
+        }
+        assert_eq!(remaining_yield, 0);
+        granted
+    }
+
+    #[test]
+    fn total_yield_is_tool_independent() {
+        for damage in [1, 3, 7, 15, 34, 100, 250] {
+            assert_eq!(total_yield_for_damage(damage), 30, "damage per hit {}", damage);
+        }
+    }
+
+    #[test]
+    fn same_seed_gives_the_same_rolls() {
+        let rolls = |seed: u64| {
+            let mut rng = GameRng::from_seed(seed);
+            (0..8).map(|_| rng.gen_range(0..1000u32)).collect::<Vec<_>>()
+        };
+        assert_eq!(rolls(42), rolls(42));
+        assert_ne!(rolls(42), rolls(43));
+    }
+
+    #[test]
+    fn chance_respects_its_extremes() {
+        let mut rng = GameRng::from_seed(7);
+        assert!((0..100).all(|_| !rng.chance(0.0)));
+        assert!((0..100).all(|_| rng.chance(1.0)));
+    }
+
+    #[test]
+    fn range_boundary_counts_as_in_range() {
+        assert!(within_range(0.0, 0.0, 30.0, 40.0, 50.0 * 50.0));
+        assert!(!within_range(0.0, 0.0, 30.0, 40.1, 50.0 * 50.0));
+        assert!(within_range(5.0, 5.0, 5.0, 5.0, 0.0));
+    }
+
+    #[test]
+    fn arc_includes_its_edges_but_not_the_attacker() {
+        let quarter = std::f32::consts::FRAC_PI_4;
+        // Facing +x with a 90 degree swing (45 either side)
+        assert!(is_within_arc(10.0, 0.0, 1.0, 0.0, quarter));
+        assert!(is_within_arc(10.0, 10.0, 1.0, 0.0, quarter));
+        assert!(!is_within_arc(10.0, 10.5, 1.0, 0.0, quarter));
+        assert!(!is_within_arc(-10.0, 0.0, 1.0, 0.0, quarter));
+        assert!(!is_within_arc(0.0, 0.0, 1.0, 0.0, quarter));
+        // A full 360 degree swing hits behind too
+        assert!(is_within_arc(-10.0, 0.0, 1.0, 0.0, std::f32::consts::PI));
+    }
+}
@@ -0,0 +1,155 @@
+use spacetimedb::{Identity, ReducerContext, Table, Timestamp};
+use spacetimedb::spacetimedb_lib::ScheduleAt;
+use std::time::Duration;
+use log;
+
+use crate::errors::{GameError, ensure_finite};
+use crate::{WORLD_WIDTH_PX, WORLD_HEIGHT_PX};
+
+// --- Constants ---
+// Largest area a client may report. Generous enough for big monitors at low zoom,
+// small enough that a client can't "see" (and later subscribe to) the whole world.
+const MAX_VIEWPORT_WIDTH_PX: f32 = 4096.0;
+const MAX_VIEWPORT_HEIGHT_PX: f32 = 4096.0;
+// Viewports not refreshed for this long are treated as left behind by a crashed client.
+// Connected clients report on every camera move, so this only needs to outlast idle players.
+const STALE_VIEWPORT_TIMEOUT_SECS: i64 = 300;
+const STALE_VIEWPORT_CHECK_INTERVAL_SECS: u64 = 60;
+
+// --- Client Viewport Table ---
+// The world-space rectangle each client is currently rendering. Used for visibility filtering.
+#[spacetimedb::table(name = client_viewport)]
+#[derive(Clone)]
+pub struct ClientViewport {
+    #[primary_key]
+    pub client_identity: Identity,
+    pub min_x: f32,
+    pub min_y: f32,
+    pub max_x: f32,
+    pub max_y: f32,
+    pub last_update: Timestamp,
+}
+
+/// Stores the caller's viewport after validating it (see `validate_viewport`).
+#[spacetimedb::reducer]
+pub fn update_viewport(ctx: &ReducerContext, min_x: f32, min_y: f32, max_x: f32, max_y: f32) -> Result<(), String> {
+    let client_id = ctx.sender;
+    let (min_x, min_y, max_x, max_y) = validate_viewport(min_x, min_y, max_x, max_y)?;
+    let viewport = ClientViewport {
+        client_identity: client_id,
+        min_x,
+        min_y,
+        max_x,
+        max_y,
+        last_update: ctx.timestamp,
+    };
+
+    let viewports = ctx.db.client_viewport();
+    if viewports.client_identity().find(client_id).is_some() {
+        viewports.client_identity().update(viewport);
+    } else {
+        viewports.insert(viewport);
+    }
+    log::trace!("[Viewport] Updated viewport for {:?}: ({:.1}, {:.1}) - ({:.1}, {:.1})",
+              client_id, min_x, min_y, max_x, max_y);
+    Ok(())
+}
+
+/// Rejects non-finite, inverted, or oversized rectangles and returns the rest clamped to world bounds.
+fn validate_viewport(min_x: f32, min_y: f32, max_x: f32, max_y: f32) -> Result<(f32, f32, f32, f32), String> {
+    ensure_finite(&[("min_x", min_x), ("min_y", min_y), ("max_x", max_x), ("max_y", max_y)])?;
+
+    if min_x > max_x || min_y > max_y {
+        return Err(GameError::InvalidInput(format!(
+            "viewport is inverted (x: {:.1}..{:.1}, y: {:.1}..{:.1})", min_x, max_x, min_y, max_y
+        )).into());
+    }
+    if max_x - min_x > MAX_VIEWPORT_WIDTH_PX || max_y - min_y > MAX_VIEWPORT_HEIGHT_PX {
+        return Err(GameError::InvalidInput(format!(
+            "viewport too large ({:.0}x{:.0}, max {:.0}x{:.0})",
+            max_x - min_x, max_y - min_y, MAX_VIEWPORT_WIDTH_PX, MAX_VIEWPORT_HEIGHT_PX
+        )).into());
+    }
+
+    // Clamp to the world; a viewport hanging off the edge is normal near borders
+    Ok((
+        min_x.clamp(0.0, WORLD_WIDTH_PX),
+        min_y.clamp(0.0, WORLD_HEIGHT_PX),
+        max_x.clamp(0.0, WORLD_WIDTH_PX),
+        max_y.clamp(0.0, WORLD_HEIGHT_PX),
+    ))
+}
+
+// --- Stale Viewport Cleanup ---
+
+#[spacetimedb::table(name = viewport_cleanup_schedule, scheduled(prune_stale_viewports))]
+#[derive(Clone)]
+pub struct ViewportCleanupSchedule {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u64,
+    pub scheduled_at: ScheduleAt,
+}
+
+/// Deletes viewports whose client stopped reporting (normally they are removed on disconnect).
+#[spacetimedb::reducer]
+pub fn prune_stale_viewports(ctx: &ReducerContext, _schedule: ViewportCleanupSchedule) -> Result<(), String> {
+    let cutoff_micros = ctx.timestamp.to_micros_since_unix_epoch().saturating_sub(STALE_VIEWPORT_TIMEOUT_SECS * 1_000_000);
+    let viewports = ctx.db.client_viewport();
+    let stale: Vec<Identity> = viewports.iter()
+        .filter(|viewport| viewport.last_update.to_micros_since_unix_epoch() < cutoff_micros)
+        .map(|viewport| viewport.client_identity)
+        .collect();
+    for client_id in &stale {
+        viewports.client_identity().delete(*client_id);
+    }
+    if !stale.is_empty() {
+        log::info!("[Viewport] Pruned {} stale viewports (no update for {}s).", stale.len(), STALE_VIEWPORT_TIMEOUT_SECS);
+    }
+    Ok(())
+}
+
+// --- Init Helper (Called from lib.rs) ---
+pub(crate) fn init_viewport_cleanup_schedule(ctx: &ReducerContext) -> Result<(), String> {
+    let schedule_table = ctx.db.viewport_cleanup_schedule();
+    if schedule_table.iter().count() == 0 {
+        log::info!("Starting stale viewport cleanup schedule (every {}s).", STALE_VIEWPORT_CHECK_INTERVAL_SECS);
+        let interval = Duration::from_secs(STALE_VIEWPORT_CHECK_INTERVAL_SECS);
+        schedule_table.insert(ViewportCleanupSchedule {
+            id: 0, // Auto-incremented
+            scheduled_at: ScheduleAt::Interval(interval.into()),
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inverted_and_oversized_viewports_are_rejected() {
+        assert!(validate_viewport(100.0, 0.0, 50.0, 100.0).is_err());
+        assert!(validate_viewport(0.0, 100.0, 100.0, 50.0).is_err());
+        assert!(validate_viewport(0.0, 0.0, MAX_VIEWPORT_WIDTH_PX + 1.0, 100.0).is_err());
+        assert!(validate_viewport(0.0, 0.0, 100.0, MAX_VIEWPORT_HEIGHT_PX + 1.0).is_err());
+        assert!(validate_viewport(0.0, 0.0, MAX_VIEWPORT_WIDTH_PX, MAX_VIEWPORT_HEIGHT_PX).is_ok());
+    }
+
+    #[test]
+    fn viewports_are_clamped_to_the_world() {
+        assert_eq!(validate_viewport(-200.0, -50.0, 300.0, 400.0), Ok((0.0, 0.0, 300.0, 400.0)));
+        assert_eq!(
+            validate_viewport(WORLD_WIDTH_PX - 100.0, 10.0, WORLD_WIDTH_PX + 100.0, 20.0),
+            Ok((WORLD_WIDTH_PX - 100.0, 10.0, WORLD_WIDTH_PX, 20.0))
+        );
+    }
+
+    #[test]
+    fn non_finite_viewports_are_rejected() {
+        assert!(validate_viewport(f32::NAN, 0.0, 100.0, 100.0).is_err());
+        assert!(validate_viewport(0.0, 0.0, f32::INFINITY, 100.0).is_err());
+        assert!(validate_viewport(0.0, f32::NEG_INFINITY, 100.0, 100.0).is_err());
+        assert!(validate_viewport(0.0, 0.0, 100.0, 100.0).is_ok());
+    }
+}
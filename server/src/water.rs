@@ -0,0 +1,260 @@
+use spacetimedb::{Identity, ReducerContext, Table};
+use log;
+use rand::Rng;
+
+// Import table traits needed for ctx.db access
+use crate::player as PlayerTableTrait;
+use crate::items::{InventoryItem, ItemDefinition, inventory_item as InventoryItemTableTrait, item_definition as ItemDefinitionTableTrait};
+
+use crate::errors::GameError;
+use crate::utils::get_distance_squared;
+
+// --- Water Constants ---
+pub(crate) const WATER_BOTTLE_CAPACITY: u32 = 5; // Sips held by a full bottle
+const WATER_BOTTLE_THIRST_PER_SIP: f32 = 20.0; // Thirst restored per sip
+const WATER_FILL_MARGIN: f32 = 32.0; // How far outside the water's edge the player can still fill
+const WATER_SOURCE_THIRST_PER_DRINK: f32 = 10.0; // Thirst restored per drink straight from a source
+const WATER_SOURCE_DRINK_COOLDOWN_MS: i64 = 1000; // Minimum time between two drinks from a source
+const MAX_STAT_VALUE: f32 = 100.0;
+
+// Constants for seeding (used in environment.rs)
+pub(crate) const WATER_SOURCE_COUNT: u32 = 6;
+const WATER_SOURCE_MIN_RADIUS: f32 = 48.0;
+const WATER_SOURCE_MAX_RADIUS: f32 = 120.0;
+const WATER_SOURCE_WORLD_MARGIN_PX: f32 = 200.0;
+
+// --- Water Source Table ---
+// Ponds/wells the player can drink from or fill containers at. Circular for simple proximity checks.
+#[spacetimedb::table(name = water_source, public)]
+#[derive(Clone)]
+pub struct WaterSource {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u32,
+    pub pos_x: f32,
+    pub pos_y: f32,
+    pub radius: f32,
+}
+
+// --- Seeding Helper (called from environment.rs) ---
+pub(crate) fn seed_water_sources<R: Rng + ?Sized>(ctx: &ReducerContext, rng: &mut R) -> u32 {
+    let water_sources = ctx.db.water_source();
+    let mut spawned_count = 0;
+    for _ in 0..WATER_SOURCE_COUNT {
+        let source = WaterSource {
+            id: 0, // Auto-incremented
+            pos_x: rng.gen_range(WATER_SOURCE_WORLD_MARGIN_PX..(crate::WORLD_WIDTH_PX - WATER_SOURCE_WORLD_MARGIN_PX)),
+            pos_y: rng.gen_range(WATER_SOURCE_WORLD_MARGIN_PX..(crate::WORLD_HEIGHT_PX - WATER_SOURCE_WORLD_MARGIN_PX)),
+            radius: rng.gen_range(WATER_SOURCE_MIN_RADIUS..WATER_SOURCE_MAX_RADIUS),
+        };
+        match water_sources.try_insert(source) {
+            Ok(_) => spawned_count += 1,
+            Err(e) => log::error!("Failed to insert water source during seeding: {}", e),
+        }
+    }
+    spawned_count
+}
+
+// --- Helpers ---
+
+/// Returns true if the position is at (or just outside) the edge of any water source.
+pub(crate) fn is_near_water(ctx: &ReducerContext, pos_x: f32, pos_y: f32) -> bool {
+    ctx.db.water_source().iter().any(|source| {
+        let reach = source.radius + WATER_FILL_MARGIN;
+        get_distance_squared(pos_x, pos_y, source.pos_x, source.pos_y) <= reach * reach
+    })
+}
+
+/// Checks the sender can use this item as a water container right now and returns its capacity.
+/// The container must be theirs, sit in their inventory or hotbar, and be able to hold water.
+pub(crate) fn carried_water_capacity(sender_id: Identity, container: &InventoryItem, item_def: &ItemDefinition) -> Result<u32, String> {
+    if container.player_identity != sender_id {
+        return Err(GameError::NotOwner.into());
+    }
+    if container.inventory_slot.is_none() && container.hotbar_slot.is_none() {
+        return Err(GameError::InvalidSlot("water containers must be in your inventory or hotbar".to_string()).into());
+    }
+    item_def.water_capacity
+        .ok_or_else(|| format!("'{}' cannot hold water.", item_def.name))
+}
+
+/// Charges after filling a container to capacity. Fails if it is already full.
+fn filled_charges(item_name: &str, charges: Option<u32>, capacity: u32) -> Result<u32, String> {
+    if charges.unwrap_or(0) >= capacity {
+        return Err(format!("{} is already full.", item_name));
+    }
+    Ok(capacity)
+}
+
+/// Thirst and charges left after one sip. Fails if the container is empty or the player isn't thirsty.
+fn sip(item_name: &str, thirst: f32, charges: Option<u32>) -> Result<(f32, u32), String> {
+    let charges = charges.unwrap_or(0);
+    if charges == 0 {
+        return Err(format!("{} is empty.", item_name));
+    }
+    if thirst >= MAX_STAT_VALUE {
+        return Err("You are not thirsty.".to_string());
+    }
+    Ok(((thirst + WATER_BOTTLE_THIRST_PER_SIP).min(MAX_STAT_VALUE), charges - 1))
+}
+
+// --- Reducers ---
+
+/// Fills a carried water container to capacity. The player must be standing at a water source.
+#[spacetimedb::reducer]
+pub fn fill_container(ctx: &ReducerContext, item_instance_id: u64) -> Result<(), String> {
+    let sender_id = ctx.sender;
+    let inventory = ctx.db.inventory_item();
+
+    // 1. Find Player
+    let player = ctx.db.player().identity().find(sender_id)
+        .ok_or_else(|| GameError::NotFound("Player".to_string()))?;
+    if player.is_dead {
+        return Err("Cannot fill containers while dead".to_string());
+    }
+
+    // 2. Find the container and validate it
+    let mut container = inventory.instance_id().find(item_instance_id)
+        .ok_or_else(|| GameError::NotFound(format!("Item instance {}", item_instance_id)))?;
+    let item_def = ctx.db.item_definition().id().find(container.item_def_id)
+        .ok_or_else(|| GameError::NotFound(format!("Item definition {}", container.item_def_id)))?;
+    let capacity = carried_water_capacity(sender_id, &container, &item_def)?;
+
+    // 3. Check proximity to water
+    if !is_near_water(ctx, player.position_x, player.position_y) {
+        return Err("You need to be next to water to fill this.".to_string());
+    }
+
+    // 4. Fill
+    let old_charges = container.charges.unwrap_or(0);
+    container.charges = Some(filled_charges(&item_def.name, container.charges, capacity)?);
+    inventory.instance_id().update(container);
+
+    log::info!("[FillContainer] Player {:?} filled {} {} ({} -> {}).",
+             sender_id, item_def.name, item_instance_id, old_charges, capacity);
+    Ok(())
+}
+
+/// Drinks straight from a water source, restoring some thirst. The player must be standing at
+/// that source; filling up takes repeated drinks, each gated by a short cooldown.
+#[spacetimedb::reducer]
+pub fn drink_from_water(ctx: &ReducerContext, source_id: u32) -> Result<(), String> {
+    let sender_id = ctx.sender;
+    let players = ctx.db.player();
+
+    // 1. Find Player
+    let mut player = players.identity().find(sender_id)
+        .ok_or_else(|| GameError::NotFound("Player".to_string()))?;
+    if player.is_dead {
+        return Err("Cannot drink while dead".to_string());
+    }
+
+    // 2. Find the source and check proximity (same reach as filling a bottle)
+    let source = ctx.db.water_source().id().find(source_id)
+        .ok_or_else(|| GameError::NotFound(format!("Water source {}", source_id)))?;
+    let reach = source.radius + WATER_FILL_MARGIN;
+    if get_distance_squared(player.position_x, player.position_y, source.pos_x, source.pos_y) > reach * reach {
+        return Err(GameError::TooFarAway.into());
+    }
+    if player.thirst >= MAX_STAT_VALUE {
+        return Err("You are not thirsty.".to_string());
+    }
+
+    // 3. Drink cooldown
+    if let Some(last_drank_at) = player.last_drank_at {
+        let elapsed_ms = (ctx.timestamp.to_micros_since_unix_epoch() - last_drank_at.to_micros_since_unix_epoch()) / 1000;
+        if elapsed_ms < WATER_SOURCE_DRINK_COOLDOWN_MS {
+            return Err("You are still drinking.".to_string());
+        }
+    }
+
+    // 4. Apply the drink
+    let old_thirst = player.thirst;
+    player.thirst = (player.thirst + WATER_SOURCE_THIRST_PER_DRINK).min(MAX_STAT_VALUE);
+    player.last_drank_at = Some(ctx.timestamp);
+    players.identity().update(player.clone());
+
+    log::info!("[DrinkWater] Player {:?} drank from water source {}. Thirst {:.1} -> {:.1}.",
+             sender_id, source_id, old_thirst, player.thirst);
+    Ok(())
+}
+
+/// Takes one sip from a carried water container, restoring some thirst. Works anywhere.
+#[spacetimedb::reducer]
+pub fn drink_from_container(ctx: &ReducerContext, item_instance_id: u64) -> Result<(), String> {
+    let sender_id = ctx.sender;
+    let players = ctx.db.player();
+    let inventory = ctx.db.inventory_item();
+
+    // 1. Find Player
+    let mut player = players.identity().find(sender_id)
+        .ok_or_else(|| GameError::NotFound("Player".to_string()))?;
+    if player.is_dead {
+        return Err("Cannot drink while dead".to_string());
+    }
+
+    // 2. Find the container and validate it
+    let mut container = inventory.instance_id().find(item_instance_id)
+        .ok_or_else(|| GameError::NotFound(format!("Item instance {}", item_instance_id)))?;
+    let item_def = ctx.db.item_definition().id().find(container.item_def_id)
+        .ok_or_else(|| GameError::NotFound(format!("Item definition {}", container.item_def_id)))?;
+    carried_water_capacity(sender_id, &container, &item_def)?;
+
+    // 3. Apply the sip
+    let old_thirst = player.thirst;
+    let (new_thirst, charges_left) = sip(&item_def.name, player.thirst, container.charges)?;
+    player.thirst = new_thirst;
+    container.charges = Some(charges_left);
+    players.identity().update(player);
+    inventory.instance_id().update(container);
+
+    log::info!("[DrinkContainer] Player {:?} drank from {} {}. Thirst {:.1} -> {:.1}, sips left: {}.",
+             sender_id, item_def.name, item_instance_id, old_thirst, new_thirst, charges_left);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::items::{test_inventory_item, test_item_def, ItemCategory};
+
+    fn water_bottle() -> ItemDefinition {
+        ItemDefinition { water_capacity: Some(WATER_BOTTLE_CAPACITY), ..test_item_def("Water Bottle", ItemCategory::Tool) }
+    }
+
+    fn carried_bottle(charges: Option<u32>) -> InventoryItem {
+        InventoryItem { hotbar_slot: Some(0), charges, ..test_inventory_item(1, 1, 1) }
+    }
+
+    #[test]
+    fn filling_tops_up_to_capacity() {
+        assert_eq!(filled_charges("Water Bottle", None, WATER_BOTTLE_CAPACITY), Ok(WATER_BOTTLE_CAPACITY));
+        assert_eq!(filled_charges("Water Bottle", Some(2), WATER_BOTTLE_CAPACITY), Ok(WATER_BOTTLE_CAPACITY));
+        assert!(filled_charges("Water Bottle", Some(WATER_BOTTLE_CAPACITY), WATER_BOTTLE_CAPACITY).is_err());
+    }
+
+    #[test]
+    fn drinking_takes_one_sip_and_caps_thirst() {
+        assert_eq!(sip("Water Bottle", 50.0, Some(3)), Ok((50.0 + WATER_BOTTLE_THIRST_PER_SIP, 2)));
+        assert_eq!(sip("Water Bottle", MAX_STAT_VALUE - 1.0, Some(1)), Ok((MAX_STAT_VALUE, 0)));
+        assert!(sip("Water Bottle", 50.0, Some(0)).is_err());
+        assert!(sip("Water Bottle", 50.0, None).is_err());
+        assert!(sip("Water Bottle", MAX_STAT_VALUE, Some(3)).is_err());
+    }
+
+    #[test]
+    fn containers_must_be_owned_carried_and_hold_water() {
+        let owner = Identity::default();
+        assert_eq!(carried_water_capacity(owner, &carried_bottle(None), &water_bottle()), Ok(WATER_BOTTLE_CAPACITY));
+
+        let other_player = Identity::from_byte_array([1; 32]);
+        assert_eq!(carried_water_capacity(other_player, &carried_bottle(None), &water_bottle()),
+                   Err(GameError::NotOwner.into()));
+
+        let not_carried = InventoryItem { hotbar_slot: None, ..carried_bottle(None) };
+        assert!(carried_water_capacity(owner, &not_carried, &water_bottle()).is_err());
+
+        let stone = test_item_def("Stone", ItemCategory::Material);
+        assert!(carried_water_capacity(owner, &carried_bottle(None), &stone).is_err());
+    }
+}
@@ -5,12 +5,14 @@ use log;
 pub(crate) const BOX_COLLISION_RADIUS: f32 = 18.0; // Similar to campfire
 pub(crate) const BOX_COLLISION_Y_OFFSET: f32 = 10.0; // Similar to campfire
 pub(crate) const PLAYER_BOX_COLLISION_DISTANCE_SQUARED: f32 = (super::PLAYER_RADIUS + BOX_COLLISION_RADIUS) * (super::PLAYER_RADIUS + BOX_COLLISION_RADIUS);
-const BOX_INTERACTION_DISTANCE_SQUARED: f32 = 64.0 * 64.0; // Similar to campfire interaction
 pub const NUM_BOX_SLOTS: usize = 18;
+const BOX_DUMP_SPREAD_RADIUS: f32 = 40.0; // How far from the box dumped items land
+const MAX_BOX_LABEL_CHARS: usize = 32;
+pub(crate) const MAX_BOXES_PER_PLAYER: usize = 20; // Placed boxes one player may have standing at once
 // TODO: Consider box-box collision? For now, just player-box.
 
 // Import InventoryItem and ItemDefinition tables/traits AND STRUCTS for item finding/checking
-use crate::items::{InventoryItem, inventory_item as InventoryItemTableTrait, ItemDefinition, item_definition as ItemDefinitionTableTrait};
+use crate::items::{InventoryItem, inventory_item as InventoryItemTableTrait, ItemDefinition};
 // Import Table Traits needed within the reducer
 use crate::player as PlayerTableTrait;
 // ADDED: Import the WoodenStorageBox table trait itself - REMOVED as it's defined here and accessed via ctx.db
@@ -23,6 +25,11 @@ use crate::items::add_item_to_player_inventory;
 use crate::Player;
 // Import the ItemContainer trait
 use crate::inventory_management::ItemContainer;
+use crate::errors::GameError;
+use crate::containers::ContainerType;
+use crate::utils::within_range;
+use crate::interaction::BOX_INTERACTION_DISTANCE_SQUARED;
+use crate::items::PlaceableKind;
 
 #[spacetimedb::table(name = wooden_storage_box, public)]
 #[derive(Clone)]
@@ -35,6 +42,7 @@ pub struct WoodenStorageBox {
     pub pos_y: f32,
 
     pub placed_by: Identity, // Who placed this storage box
+    pub label: Option<String>, // Owner-given name shown in the box UI. None until renamed
 
     // --- Inventory Slots (0-17) --- 
     pub slot_instance_id_0: Option<u64>,
@@ -93,6 +101,10 @@ impl ItemContainer for WoodenStorageBox {
     fn set_slot(&mut self, slot_index: u8, instance_id: Option<u64>, def_id: Option<u64>) {
         crate::inventory_management::set_box_slot(self, slot_index, instance_id, def_id)
     }
+
+    fn max_capacity(&self) -> Option<u32> {
+        None // Limited only by its slots and stack sizes
+    }
 }
 
 // --- Helper Function (Validation) --- 
@@ -100,7 +112,7 @@ impl ItemContainer for WoodenStorageBox {
 /// Validates if a player can interact with a specific box (checks existence and distance).
 /// Returns Ok((Player struct instance, WoodenStorageBox struct instance)) on success, or Err(String) on failure.
 /// Does NOT check ownership.
-fn validate_box_interaction(
+pub(crate) fn validate_box_interaction(
     ctx: &ReducerContext,
     box_id: u32,
 ) -> Result<(Player, WoodenStorageBox), String> { // Use corrected Player type
@@ -108,90 +120,32 @@ fn validate_box_interaction(
     let players = ctx.db.player();
     let boxes = ctx.db.wooden_storage_box();
 
-    let player = players.identity().find(sender_id).ok_or_else(|| "Player not found".to_string())?;
-    let storage_box = boxes.id().find(box_id).ok_or_else(|| format!("Storage Box {} not found", box_id))?;
+    let player = players.identity().find(sender_id).ok_or_else(|| GameError::NotFound("Player".to_string()))?;
+    let storage_box = boxes.id().find(box_id).ok_or_else(|| GameError::NotFound(format!("Storage Box {}", box_id)))?;
 
     // Check distance between the interacting player and the box
-    let dx = player.position_x - storage_box.pos_x;
-    let dy = player.position_y - storage_box.pos_y;
-    if (dx * dx + dy * dy) > BOX_INTERACTION_DISTANCE_SQUARED {
-        return Err("Too far away".to_string());
+    if !within_range(player.position_x, player.position_y, storage_box.pos_x, storage_box.pos_y, BOX_INTERACTION_DISTANCE_SQUARED) {
+        return Err(GameError::TooFarAway.into());
     }
     Ok((player, storage_box))
 }
 
-// Reducer is now uncommented
+/// Places a Wooden Storage Box item from the player's inventory/hotbar.
+/// Thin wrapper around the generic placement flow in `placement.rs`.
 #[spacetimedb::reducer]
 pub fn place_wooden_storage_box(ctx: &ReducerContext, item_instance_id: u64, world_x: f32, world_y: f32) -> Result<(), String> {
-    let sender_id = ctx.sender;
-    // Use table traits via ctx.db
-    let inventory_items = ctx.db.inventory_item();
-    let item_defs = ctx.db.item_definition();
-    let players = ctx.db.player();
-    let wooden_storage_boxes = ctx.db.wooden_storage_box(); // Use trait alias
-
-    log::info!(
-        "[PlaceStorageBox] Player {:?} attempting placement of item {} at ({:.1}, {:.1})",
-        sender_id, item_instance_id, world_x, world_y
-    );
-
-    // --- 1. Find the 'Wooden Storage Box' Item Definition ID ---
-    let box_def_id = item_defs.iter()
-        .find(|def| def.name == "Wooden Storage Box")
-        .map(|def| def.id)
-        .ok_or_else(|| "Item definition 'Wooden Storage Box' not found.".to_string())?;
-
-    // --- 2. Find the specific item instance and validate --- 
-    let item_to_consume = inventory_items.instance_id().find(item_instance_id)
-        .ok_or_else(|| format!("Item instance {} not found.", item_instance_id))?;
-    
-    // Validate ownership
-    if item_to_consume.player_identity != sender_id {
-        return Err(format!("Item instance {} not owned by player {:?}.", item_instance_id, sender_id));
-    }
-    // Validate item type
-    if item_to_consume.item_def_id != box_def_id {
-        return Err(format!("Item instance {} is not a Wooden Storage Box (expected def {}, got {}).", 
-                        item_instance_id, box_def_id, item_to_consume.item_def_id));
-    }
-    // Validate location (must be in inv or hotbar)
-    if item_to_consume.inventory_slot.is_none() && item_to_consume.hotbar_slot.is_none() {
-        return Err(format!("Item instance {} must be in inventory or hotbar to be placed.", item_instance_id));
-    }
-    
-    // Use the validated item_instance_id directly
-    let item_instance_id_to_delete = item_instance_id; 
-
-    // --- 3. Validate Placement (Simplified - basic distance check) ---
-    if let Some(player) = players.identity().find(sender_id) {
-        let dx = player.position_x - world_x;
-        let dy = player.position_y - world_y;
-        let dist_sq = dx * dx + dy * dy;
-        // Use a reasonable placement distance squared (e.g., 96 pixels radius)
-        let placement_range_sq = 96.0 * 96.0;
-        if dist_sq > placement_range_sq {
-            return Err("Placement location is too far away.".to_string());
-        }
-    } else {
-        return Err("Could not find player data to validate placement distance.".to_string());
-    }
-
-    // TODO: Add collision checks? Ensure not placing inside another object?
-
-    // --- 4. Consume the Item ---
-    // Since storage boxes aren't stackable, we assume quantity is 1 and delete the item.
-    log::info!(
-        "[PlaceStorageBox] Consuming item instance {} (Def ID: {}) from player {:?}",
-        item_instance_id_to_delete, box_def_id, sender_id
-    );
-    inventory_items.instance_id().delete(item_instance_id_to_delete);
+    crate::placement::place_item_of_kind(ctx, item_instance_id, world_x, world_y, 0.0, Some(PlaceableKind::WoodenStorageBox))
+}
 
-    // --- 5. Create the WoodenStorageBox Entity ---
+/// Creates an empty WoodenStorageBox entity.
+/// Called by the generic placement flow after validation and item consumption.
+pub(crate) fn spawn_wooden_storage_box(ctx: &ReducerContext, placer_id: Identity, world_x: f32, world_y: f32) -> Result<(), String> {
     let new_box = WoodenStorageBox {
         id: 0, // Auto-incremented
         pos_x: world_x,
         pos_y: world_y,
-        placed_by: sender_id,
+        placed_by: placer_id,
+        label: None,
         slot_instance_id_0: None,
         slot_def_id_0: None,
         slot_instance_id_1: None,
@@ -229,13 +183,12 @@ pub fn place_wooden_storage_box(ctx: &ReducerContext, item_instance_id: u64, wor
         slot_instance_id_17: None,
         slot_def_id_17: None,
     };
-    wooden_storage_boxes.insert(new_box);
+    ctx.db.wooden_storage_box().insert(new_box);
 
     log::info!(
-        "[PlaceStorageBox] Successfully placed Wooden Storage Box at ({:.1}, {:.1}) by {:?}",
-        world_x, world_y, sender_id
+        "[SpawnStorageBox] Successfully placed Wooden Storage Box at ({:.1}, {:.1}) by {:?}",
+        world_x, world_y, placer_id
     );
-
     Ok(())
 }
 
@@ -249,6 +202,7 @@ pub fn interact_with_storage_box(ctx: &ReducerContext, box_id: u32) -> Result<()
 }
 
 /// Moves an item from the player's inventory/hotbar INTO a specified slot in the storage box.
+/// Like the other box slot reducers, kept for existing clients and forwarded to `containers`.
 #[spacetimedb::reducer]
 pub fn move_item_to_box(
     ctx: &ReducerContext, 
@@ -256,27 +210,7 @@ pub fn move_item_to_box(
     target_slot_index: u8, 
     item_instance_id: u64 // Pass ID directly
 ) -> Result<(), String> {
-    // Get mutable box table handle
-    let mut boxes = ctx.db.wooden_storage_box();
-    // NOTE: Other tables (inventory, item_defs) are accessed within the handler via ctx
-
-    // --- Basic Validations --- 
-    let (_player, mut storage_box) = validate_box_interaction(ctx, box_id)?;
-    // REMOVED: Item fetching/validation moved to handler
-    // REMOVED: Target slot index validation moved to handler (using container.num_slots())
-
-    // --- Call GENERIC Handler --- 
-    inventory_management::handle_move_to_container_slot(
-        ctx, 
-        &mut storage_box, 
-        target_slot_index, 
-        item_instance_id // Pass the ID
-        // REMOVED item references
-    )?;
-
-    // --- Commit Box Update --- 
-    boxes.id().update(storage_box);
-    Ok(())
+    crate::containers::move_inventory_to_container(ctx, ContainerType::WoodenStorageBox, box_id, target_slot_index, item_instance_id)
 }
 
 /// Moves an item FROM a storage box slot INTO the player's inventory.
@@ -288,28 +222,7 @@ pub fn move_item_from_box(
     target_slot_type: String, // NEW: "inventory" or "hotbar"
     target_slot_index: u32    // NEW: Index within inventory or hotbar
 ) -> Result<(), String> {
-    // Get mutable box table handle
-    let mut boxes = ctx.db.wooden_storage_box();
-
-    // --- Validations --- 
-    let (_player, mut storage_box) = validate_box_interaction(ctx, box_id)?;
-    // NOTE: Basic distance/existence checked by validate_box_interaction
-    // NOTE: Item details, slot checks, target validation now handled by inventory_management handler
-
-    // --- Call Handler to attempt move to player inventory FIRST --- 
-    inventory_management::handle_move_from_container_slot(
-        ctx, 
-        &mut storage_box, // Pass mutably, handler will clear slot on success
-        source_slot_index,
-        target_slot_type, // Pass through
-        target_slot_index // Pass through
-    )?;
-    // ^ If this returns Ok, it means the move/merge/swap into the player slot succeeded.
-
-    // --- Commit Box Update --- 
-    // The handler modified storage_box (cleared the slot) if the move was successful.
-    boxes.id().update(storage_box);
-    Ok(())
+    crate::containers::move_container_to_inventory(ctx, ContainerType::WoodenStorageBox, box_id, source_slot_index, target_slot_type, target_slot_index)
 }
 
 /// Moves an item BETWEEN two slots within the same storage box.
@@ -320,27 +233,7 @@ pub fn move_item_within_box(
     source_slot_index: u8,
     target_slot_index: u8,
 ) -> Result<(), String> {
-    // Get mutable box table handle
-    let mut boxes = ctx.db.wooden_storage_box();
-    // NOTE: Other tables accessed in handler via ctx
-
-    // --- Basic Validations --- 
-    let (_player, mut storage_box) = validate_box_interaction(ctx, box_id)?;
-    // REMOVED: Item fetching/validation moved to handler
-    // NOTE: Slot index validation moved to handler
-
-    // --- Call GENERIC Handler --- 
-    inventory_management::handle_move_within_container(
-        ctx, 
-        &mut storage_box, 
-        source_slot_index, 
-        target_slot_index
-        // Removed table args
-    )?;
-
-    // --- Commit Box Update --- 
-    boxes.id().update(storage_box);
-    Ok(())
+    crate::containers::move_within_container(ctx, ContainerType::WoodenStorageBox, box_id, source_slot_index, target_slot_index)
 }
 
 /// Splits a stack from player inventory/hotbar into an empty box slot.
@@ -352,30 +245,7 @@ pub fn split_stack_into_box(
     source_item_instance_id: u64,
     quantity_to_split: u32,
 ) -> Result<(), String> {
-    // Get tables
-    let mut boxes = ctx.db.wooden_storage_box();
-    let inventory_items = ctx.db.inventory_item(); // Need this to find source_item
-    let item_defs = ctx.db.item_definition(); // Need this for validation
-
-    // --- Validations --- 
-    let (_player, mut storage_box) = validate_box_interaction(ctx, box_id)?;
-    // Fetch item here because handler needs mutable ref
-    let mut source_item = inventory_items.instance_id().find(source_item_instance_id).ok_or("Source item not found")?;
-    // REMOVED: Further validations moved to handler
-    
-    // --- Call GENERIC Handler --- 
-    inventory_management::handle_split_into_container(
-        ctx, 
-        &mut storage_box, 
-        target_slot_index, 
-        &mut source_item, // Pass mutable source item ref
-        quantity_to_split
-        // Removed inventory_table arg
-    )?;
-
-    // --- Commit Box Update --- 
-    boxes.id().update(storage_box);
-    Ok(())
+    crate::containers::split_inventory_into_container(ctx, ContainerType::WoodenStorageBox, box_id, target_slot_index, source_item_instance_id, quantity_to_split)
 }
 
 /// Splits a stack from a box slot into the player's inventory/hotbar.
@@ -388,27 +258,7 @@ pub fn split_stack_from_box(
     target_slot_type: String, 
     target_slot_index: u32,   
 ) -> Result<(), String> {
-    // Get tables
-    let mut boxes = ctx.db.wooden_storage_box();
-    // NOTE: Other tables accessed in handler via ctx
-
-    // --- Validations --- 
-    let (_player, mut storage_box) = validate_box_interaction(ctx, box_id)?;
-    // REMOVED: Item fetching/validation moved to handler
-
-    // --- Call GENERIC Handler --- 
-    inventory_management::handle_split_from_container(
-        ctx, 
-        &mut storage_box, 
-        source_slot_index, 
-        quantity_to_split,
-        target_slot_type, 
-        target_slot_index
-    )?;
-
-    // --- Commit Box Update --- 
-    boxes.id().update(storage_box);
-    Ok(())
+    crate::containers::split_container_to_inventory(ctx, ContainerType::WoodenStorageBox, box_id, source_slot_index, quantity_to_split, target_slot_type, target_slot_index)
 }
 
 /// Splits a stack from one box slot into another empty box slot.
@@ -420,27 +270,7 @@ pub fn split_stack_within_box(
     target_slot_index: u8,
     quantity_to_split: u32,
 ) -> Result<(), String> {
-    // Get tables
-    let mut boxes = ctx.db.wooden_storage_box();
-    // NOTE: Other tables accessed in handler
-
-    // --- Validations --- 
-    let (_player, mut storage_box) = validate_box_interaction(ctx, box_id)?;
-    // REMOVED: Item fetching/validation moved to handler
-    // NOTE: Slot index/target empty validation moved to handler
-
-    // --- Call GENERIC Handler ---
-    inventory_management::handle_split_within_container(
-        ctx,
-        &mut storage_box,
-        source_slot_index,
-        target_slot_index,
-        quantity_to_split
-    )?;
-
-    // --- Commit Box Update --- 
-    boxes.id().update(storage_box);
-    Ok(())
+    crate::containers::split_within_container(ctx, ContainerType::WoodenStorageBox, box_id, source_slot_index, target_slot_index, quantity_to_split)
 }
 
 /// Quickly moves an item from a box slot to the player inventory.
@@ -450,23 +280,7 @@ pub fn quick_move_from_box(
     box_id: u32, 
     source_slot_index: u8
 ) -> Result<(), String> {
-    // Get mutable box table handle
-    let mut boxes = ctx.db.wooden_storage_box();
-
-    // --- Basic Validations --- 
-    let (_player, mut storage_box) = validate_box_interaction(ctx, box_id)?;
-    // REMOVED: Item fetching/slot empty validation moved to handler
-
-    // --- Call Handler --- 
-    inventory_management::handle_quick_move_from_container(
-        ctx, 
-        &mut storage_box, 
-        source_slot_index
-    )?;
-
-    // --- Commit Box Update --- 
-    boxes.id().update(storage_box);
-    Ok(())
+    crate::containers::quick_move_from_container(ctx, ContainerType::WoodenStorageBox, box_id, source_slot_index)
 }
 
 /// Quickly moves an item from player inventory/hotbar to the first available/mergeable slot in the box.
@@ -476,25 +290,7 @@ pub fn quick_move_to_box(
     box_id: u32, 
     item_instance_id: u64 // Pass ID directly
 ) -> Result<(), String> {
-    // Get tables
-    let mut boxes = ctx.db.wooden_storage_box();
-    // NOTE: Other tables accessed in handler via ctx
-
-    // --- Validations --- 
-    let (_player, mut storage_box) = validate_box_interaction(ctx, box_id)?;
-    // REMOVED: Item fetching/validation moved to handler
-
-    // --- Call Handler --- 
-    inventory_management::handle_quick_move_to_container(
-        ctx, 
-        &mut storage_box, 
-        item_instance_id // Pass the ID
-        // REMOVED item references
-    )?;
-
-    // --- Commit Box Update --- 
-    boxes.id().update(storage_box);
-    Ok(())
+    crate::containers::quick_move_to_container(ctx, ContainerType::WoodenStorageBox, box_id, item_instance_id)
 }
 
 // NEW: Reducer to pick up an empty storage box
@@ -502,12 +298,14 @@ pub fn quick_move_to_box(
 pub fn pickup_storage_box(ctx: &ReducerContext, box_id: u32) -> Result<(), String> {
     let sender_id = ctx.sender;
     let mut boxes = ctx.db.wooden_storage_box();
-    let item_defs = ctx.db.item_definition();
 
     log::info!("[PickupBox] Player {:?} attempting pickup of box {}", sender_id, box_id);
 
-    // 1. Validate Interaction & Get Entities
+    // 1. Validate Interaction, Ownership & Get Entities
     let (_player, storage_box) = validate_box_interaction(ctx, box_id)?;
+    if storage_box.placed_by != sender_id {
+        return Err(GameError::NotOwner.into());
+    }
 
     // 2. Check if Box is Empty
     let is_empty = inventory_management::is_container_empty(&storage_box);
@@ -517,8 +315,7 @@ pub fn pickup_storage_box(ctx: &ReducerContext, box_id: u32) -> Result<(), Strin
     }
 
     // 3. Find the "Wooden Storage Box" Item Definition
-    let box_item_def = item_defs.iter()
-        .find(|def| def.name == "Wooden Storage Box")
+    let box_item_def = crate::items::get_item_def_by_name(ctx, "Wooden Storage Box")
         .ok_or_else(|| "Item definition 'Wooden Storage Box' not found.".to_string())?;
 
     // 4. Add the item to the player's inventory
@@ -535,4 +332,114 @@ pub fn pickup_storage_box(ctx: &ReducerContext, box_id: u32) -> Result<(), Strin
             Err(format!("Failed to pick up box: {}", e))
         }
     }
-}
\ No newline at end of file
+}
+
+/// Dumps every item in a storage box onto the ground around it (owner only).
+/// Each slot becomes a `dropped_item` spread in a ring around the box; the box itself stays.
+#[spacetimedb::reducer]
+pub fn dump_container(ctx: &ReducerContext, box_id: u32) -> Result<(), String> {
+    let sender_id = ctx.sender;
+    let mut boxes = ctx.db.wooden_storage_box();
+    let inventory_items = ctx.db.inventory_item();
+
+    log::info!("[DumpBox] Player {:?} attempting to dump box {}", sender_id, box_id);
+
+    // 1. Validate Interaction & Ownership
+    let (_player, mut storage_box) = validate_box_interaction(ctx, box_id)?;
+    if storage_box.placed_by != sender_id {
+        return Err(GameError::NotOwner.into());
+    }
+
+    // 2. Spawn each slot's contents as a dropped item around the box
+    let mut dumped_count = 0;
+    for slot_index in 0..storage_box.num_slots() as u8 {
+        let instance_id = match storage_box.get_slot_instance_id(slot_index) {
+            Some(id) => id,
+            None => continue,
+        };
+        match inventory_items.instance_id().find(instance_id) {
+            Some(item) => {
+                let angle = (slot_index as f32 / NUM_BOX_SLOTS as f32) * std::f32::consts::TAU;
+                let drop_x = storage_box.pos_x + angle.cos() * BOX_DUMP_SPREAD_RADIUS;
+                let drop_y = storage_box.pos_y + angle.sin() * BOX_DUMP_SPREAD_RADIUS;
+                crate::dropped_item::create_dropped_item_entity(ctx, item.item_def_id, item.quantity, &crate::items::ItemInstanceState::of(&item), drop_x, drop_y)?;
+                inventory_items.instance_id().delete(instance_id);
+                dumped_count += 1;
+            }
+            None => {
+                log::warn!("[DumpBox] Box {} slot {} referenced missing item {}. Clearing slot.", box_id, slot_index, instance_id);
+            }
+        }
+        storage_box.set_slot(slot_index, None, None);
+    }
+
+    // 3. Persist the now-empty box
+    boxes.id().update(storage_box);
+    log::info!("[DumpBox] Player {:?} dumped {} item stacks from box {}.", sender_id, dumped_count, box_id);
+    Ok(())
+}
+
+/// Hands a storage box over to another player (current owner only).
+/// The new owner must be alive and standing near the box, so ownership can't be pushed onto
+/// someone who isn't there to take it.
+#[spacetimedb::reducer]
+pub fn transfer_box_ownership(ctx: &ReducerContext, box_id: u32, new_owner: Identity) -> Result<(), String> {
+    let sender_id = ctx.sender;
+    let mut boxes = ctx.db.wooden_storage_box();
+
+    log::info!("[TransferBox] Player {:?} attempting to transfer box {} to {:?}", sender_id, box_id, new_owner);
+
+    // 1. Validate Interaction & Ownership
+    let (owner, mut storage_box) = validate_box_interaction(ctx, box_id)?;
+    if storage_box.placed_by != sender_id {
+        return Err(GameError::NotOwner.into());
+    }
+    if new_owner == sender_id {
+        return Err(GameError::InvalidInput("You already own this box".to_string()).into());
+    }
+
+    // 2. Validate the new owner is a real, living player near the box
+    let target = ctx.db.player().identity().find(new_owner)
+        .ok_or_else(|| GameError::NotFound("Target player".to_string()))?;
+    if target.is_dead {
+        return Err("Cannot transfer a box to a dead player".to_string());
+    }
+    if !within_range(target.position_x, target.position_y, storage_box.pos_x, storage_box.pos_y, BOX_INTERACTION_DISTANCE_SQUARED) {
+        return Err(GameError::TooFarAway.into());
+    }
+
+    // 3. Transfer
+    storage_box.placed_by = new_owner;
+    boxes.id().update(storage_box);
+    log::info!("[TransferBox] Box {} transferred from {:?} ({}) to {:?} ({}).",
+             box_id, sender_id, owner.username, new_owner, target.username);
+    Ok(())
+}
+
+/// Names a storage box so players can tell their boxes apart (owner only).
+/// Labels are trimmed, must not be blank, and may be at most 32 characters.
+#[spacetimedb::reducer]
+pub fn rename_storage_box(ctx: &ReducerContext, box_id: u32, new_label: String) -> Result<(), String> {
+    let sender_id = ctx.sender;
+
+    // 1. Validate Interaction & Ownership
+    let (_player, mut storage_box) = validate_box_interaction(ctx, box_id)?;
+    if storage_box.placed_by != sender_id {
+        return Err(GameError::NotOwner.into());
+    }
+
+    // 2. Validate the label
+    let label = new_label.trim();
+    if label.is_empty() {
+        return Err(GameError::InvalidInput("Box label cannot be empty".to_string()).into());
+    }
+    if label.chars().count() > MAX_BOX_LABEL_CHARS {
+        return Err(GameError::InvalidInput(format!("Box label cannot be longer than {} characters", MAX_BOX_LABEL_CHARS)).into());
+    }
+
+    // 3. Rename
+    log::info!("[RenameBox] Player {:?} renamed box {} from {:?} to '{}'.", sender_id, box_id, storage_box.label, label);
+    storage_box.label = Some(label.to_string());
+    ctx.db.wooden_storage_box().id().update(storage_box);
+    Ok(())
+}
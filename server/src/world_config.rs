@@ -0,0 +1,399 @@
+use spacetimedb::{Identity, ReducerContext, Table};
+use log;
+
+// --- Constants ---
+pub(crate) const DEFAULT_TIME_SCALE: f32 = 1.0;
+const MIN_TIME_SCALE: f32 = 0.1;
+const MAX_TIME_SCALE: f32 = 100.0;
+pub(crate) const DEFAULT_VOLUNTARY_DROP_LOCK_SECS: u32 = 0; // Off: anyone can grab what you throw away
+pub(crate) const DEFAULT_DEATH_DROP_LOCK_SECS: u32 = 60; // Gives the victim time to run back
+const MAX_DROP_LOCK_SECS: u32 = 600;
+pub(crate) const DEFAULT_PVP_DAMAGE_MULTIPLIER: f32 = 6.0;
+const MAX_PVP_DAMAGE_MULTIPLIER: f32 = 20.0;
+pub(crate) const DEFAULT_MAX_DROPPED_ITEMS: u32 = 500;
+const MIN_MAX_DROPPED_ITEMS: u32 = 50; // Keep the cap high enough that fresh drops aren't evicted immediately
+pub(crate) const DEFAULT_RENEWABLE_RESOURCES: bool = false; // Destructive: depleted nodes wait out a respawn timer
+pub(crate) const DEFAULT_SPAWN_CENTER_X: f32 = 640.0;
+pub(crate) const DEFAULT_SPAWN_CENTER_Y: f32 = 480.0;
+pub(crate) const DEFAULT_SPAWN_RADIUS: f32 = 200.0;
+const MAX_SPAWN_RADIUS: f32 = 2000.0;
+// Resource densities are the fraction of map tiles that get a node at seeding time
+pub(crate) const DEFAULT_TREE_DENSITY: f32 = crate::tree::TREE_DENSITY_PERCENT;
+pub(crate) const DEFAULT_STONE_DENSITY: f32 = crate::stone::STONE_DENSITY_PERCENT;
+pub(crate) const DEFAULT_MUSHROOM_DENSITY: f32 = crate::mushroom::MUSHROOM_DENSITY_PERCENT;
+const MAX_RESOURCE_DENSITY: f32 = 0.05; // 5x the default tree density; beyond this seeding mostly burns attempts
+pub(crate) const DEFAULT_STACK_SIZE_MULTIPLIER: u32 = 1;
+const MAX_STACK_SIZE_MULTIPLIER: u32 = 10;
+pub(crate) const DEFAULT_TREE_FALL_CHANCE: f32 = 0.25;
+
+// Global, admin-tunable settings. Only a single row is expected (like WorldState).
+#[spacetimedb::table(name = world_config, public)]
+#[derive(Clone)]
+pub struct WorldConfig {
+    #[primary_key]
+    #[auto_inc]
+    pub id: u32,
+    pub admin_identity: Identity, // Identity allowed to call admin reducers (the module owner)
+    pub time_scale: f32, // Multiplier applied to world clock advancement
+    pub voluntary_drop_lock_secs: u32, // Owner-only pickup window for items a player drops (0 = off)
+    pub death_drop_lock_secs: u32, // Owner-only pickup window for items dropped on death (0 = off)
+    pub pvp_damage_multiplier: f32, // Scales weapon damage dealt to players (0 = no PvP damage)
+    pub max_dropped_items: u32, // Cap on dropped_item entities; past it, the least valuable old drops are evicted
+    pub renewable_resources: bool, // Trees/stones heal back gradually instead of depleting and respawning (see environment.rs)
+    pub spawn_center_x: f32, // Center of the region new and respawning players are placed in
+    pub spawn_center_y: f32,
+    pub spawn_radius: f32, // Players spawn at a random clear point within this distance of the center
+    pub tree_density: f32, // Fraction of map tiles seeded with a tree (read by seed_environment)
+    pub stone_density: f32,
+    pub mushroom_density: f32,
+    pub stack_size_multiplier: u32, // Scales every stackable item's `stack_size` (see items::effective_stack_size)
+    pub tree_fall_chance: f32, // Chance (0-1) a felled tree falls on players beside it (see hazards.rs)
+}
+
+// --- Init Helper ---
+// Called from init_module. ctx.sender there is the identity that published the module.
+pub(crate) fn init_world_config(ctx: &ReducerContext) -> Result<(), String> {
+    let configs = ctx.db.world_config();
+    if configs.iter().count() == 0 {
+        log::info!("Seeding WorldConfig with admin {:?}.", ctx.sender);
+        configs.try_insert(default_world_config(ctx.sender))?;
+    } else {
+        log::debug!("WorldConfig already seeded.");
+    }
+    Ok(())
+}
+
+/// The config a fresh world starts with; `admin_identity` is the only identity allowed to change it.
+fn default_world_config(admin_identity: Identity) -> WorldConfig {
+    WorldConfig {
+        id: 0, // Auto-incremented
+        admin_identity,
+        time_scale: DEFAULT_TIME_SCALE,
+        voluntary_drop_lock_secs: DEFAULT_VOLUNTARY_DROP_LOCK_SECS,
+        death_drop_lock_secs: DEFAULT_DEATH_DROP_LOCK_SECS,
+        pvp_damage_multiplier: DEFAULT_PVP_DAMAGE_MULTIPLIER,
+        max_dropped_items: DEFAULT_MAX_DROPPED_ITEMS,
+        renewable_resources: DEFAULT_RENEWABLE_RESOURCES,
+        spawn_center_x: DEFAULT_SPAWN_CENTER_X,
+        spawn_center_y: DEFAULT_SPAWN_CENTER_Y,
+        spawn_radius: DEFAULT_SPAWN_RADIUS,
+        tree_density: DEFAULT_TREE_DENSITY,
+        stone_density: DEFAULT_STONE_DENSITY,
+        mushroom_density: DEFAULT_MUSHROOM_DENSITY,
+        stack_size_multiplier: DEFAULT_STACK_SIZE_MULTIPLIER,
+        tree_fall_chance: DEFAULT_TREE_FALL_CHANCE,
+    }
+}
+
+// --- Helpers ---
+
+/// Returns the current time scale, falling back to the default if the config row is missing.
+pub(crate) fn get_time_scale(ctx: &ReducerContext) -> f32 {
+    ctx.db.world_config().iter().next()
+        .map(|config| config.time_scale)
+        .unwrap_or(DEFAULT_TIME_SCALE)
+}
+
+/// Returns the owner-lock duration for dropped items, depending on why they were dropped.
+pub(crate) fn get_drop_lock_secs(ctx: &ReducerContext, dropped_on_death: bool) -> u32 {
+    drop_lock_secs(ctx.db.world_config().iter().next().as_ref(), dropped_on_death)
+}
+
+fn drop_lock_secs(config: Option<&WorldConfig>, dropped_on_death: bool) -> u32 {
+    match config {
+        Some(config) if dropped_on_death => config.death_drop_lock_secs,
+        Some(config) => config.voluntary_drop_lock_secs,
+        None if dropped_on_death => DEFAULT_DEATH_DROP_LOCK_SECS,
+        None => DEFAULT_VOLUNTARY_DROP_LOCK_SECS,
+    }
+}
+
+/// Returns the PvP damage multiplier, falling back to the default if the config row is missing.
+pub(crate) fn get_pvp_damage_multiplier(ctx: &ReducerContext) -> f32 {
+    ctx.db.world_config().iter().next()
+        .map(|config| config.pvp_damage_multiplier)
+        .unwrap_or(DEFAULT_PVP_DAMAGE_MULTIPLIER)
+}
+
+/// Validates an admin-requested PvP damage multiplier, clamping it into the allowed range.
+fn clamp_pvp_damage_multiplier(multiplier: f32) -> Result<f32, String> {
+    if !multiplier.is_finite() {
+        return Err("PvP damage multiplier must be a finite number".to_string());
+    }
+    Ok(multiplier.clamp(0.0, MAX_PVP_DAMAGE_MULTIPLIER))
+}
+
+/// Returns the dropped item cap, falling back to the default if the config row is missing.
+pub(crate) fn get_max_dropped_items(ctx: &ReducerContext) -> u32 {
+    ctx.db.world_config().iter().next()
+        .map(|config| config.max_dropped_items)
+        .unwrap_or(DEFAULT_MAX_DROPPED_ITEMS)
+}
+
+/// Returns true if trees and stones regrow gradually instead of depleting (default: false).
+pub(crate) fn resources_are_renewable(ctx: &ReducerContext) -> bool {
+    ctx.db.world_config().iter().next()
+        .map(|config| config.renewable_resources)
+        .unwrap_or(DEFAULT_RENEWABLE_RESOURCES)
+}
+
+/// Returns the spawn region as (center_x, center_y, radius), falling back to the defaults if the config row is missing.
+pub(crate) fn get_spawn_region(ctx: &ReducerContext) -> (f32, f32, f32) {
+    ctx.db.world_config().iter().next()
+        .map(|config| (config.spawn_center_x, config.spawn_center_y, config.spawn_radius))
+        .unwrap_or((DEFAULT_SPAWN_CENTER_X, DEFAULT_SPAWN_CENTER_Y, DEFAULT_SPAWN_RADIUS))
+}
+
+/// Returns the seeding densities as (trees, stones, mushrooms), falling back to the defaults if the config row is missing.
+pub(crate) fn get_resource_densities(ctx: &ReducerContext) -> (f32, f32, f32) {
+    ctx.db.world_config().iter().next()
+        .map(|config| (config.tree_density, config.stone_density, config.mushroom_density))
+        .unwrap_or((DEFAULT_TREE_DENSITY, DEFAULT_STONE_DENSITY, DEFAULT_MUSHROOM_DENSITY))
+}
+
+/// Returns the stack size multiplier, falling back to the default if the config row is missing.
+pub(crate) fn get_stack_size_multiplier(ctx: &ReducerContext) -> u32 {
+    ctx.db.world_config().iter().next()
+        .map(|config| config.stack_size_multiplier.max(1))
+        .unwrap_or(DEFAULT_STACK_SIZE_MULTIPLIER)
+}
+
+/// Returns the falling tree chance, falling back to the default if the config row is missing.
+pub(crate) fn get_tree_fall_chance(ctx: &ReducerContext) -> f32 {
+    ctx.db.world_config().iter().next()
+        .map(|config| config.tree_fall_chance)
+        .unwrap_or(DEFAULT_TREE_FALL_CHANCE)
+}
+
+/// Returns true if the caller is the configured admin.
+pub(crate) fn is_admin(ctx: &ReducerContext) -> bool {
+    ctx.db.world_config().iter().next()
+        .map(|config| config.admin_identity == ctx.sender)
+        .unwrap_or(false)
+}
+
+/// Returns an error if the caller is not the configured admin.
+pub(crate) fn ensure_admin(ctx: &ReducerContext) -> Result<(), String> {
+    if !is_admin(ctx) {
+        log::warn!("[Admin] Player {:?} attempted an admin-only action.", ctx.sender);
+        return Err("Only admins can do that".to_string());
+    }
+    Ok(())
+}
+
+// --- Admin Reducers ---
+
+/// Speeds up (or slows down) how fast the day/night cycle advances.
+/// Only the world clock is scaled; player stat drains stay on real time.
+#[spacetimedb::reducer]
+pub fn set_time_scale(ctx: &ReducerContext, multiplier: f32) -> Result<(), String> {
+    ensure_admin(ctx)?;
+
+    if !multiplier.is_finite() {
+        return Err("Time scale must be a finite number".to_string());
+    }
+    let clamped = multiplier.clamp(MIN_TIME_SCALE, MAX_TIME_SCALE);
+
+    // Advance the clock at the old rate up to now, so the new scale only applies going forward
+    crate::world_state::tick_world_state(ctx, ctx.timestamp)?;
+
+    let mut config = ctx.db.world_config().iter().next()
+        .ok_or_else(|| "WorldConfig not found".to_string())?;
+    let old_scale = config.time_scale;
+    config.time_scale = clamped;
+    ctx.db.world_config().id().update(config);
+
+    log::info!("[TimeScale] Admin {:?} changed time scale {:.2} -> {:.2} (requested {:.2}).",
+             ctx.sender, old_scale, clamped, multiplier);
+    Ok(())
+}
+
+/// Sets how long dropped items stay reserved for the player who dropped them.
+/// Pass 0 to disable the lock for that kind of drop.
+#[spacetimedb::reducer]
+pub fn set_drop_lock_durations(ctx: &ReducerContext, voluntary_secs: u32, death_secs: u32) -> Result<(), String> {
+    ensure_admin(ctx)?;
+
+    let mut config = ctx.db.world_config().iter().next()
+        .ok_or_else(|| "WorldConfig not found".to_string())?;
+    config.voluntary_drop_lock_secs = voluntary_secs.min(MAX_DROP_LOCK_SECS);
+    config.death_drop_lock_secs = death_secs.min(MAX_DROP_LOCK_SECS);
+    log::info!("[DropLock] Admin {:?} set drop lock durations: voluntary {}s, death {}s.",
+             ctx.sender, config.voluntary_drop_lock_secs, config.death_drop_lock_secs);
+    ctx.db.world_config().id().update(config);
+    Ok(())
+}
+
+/// Sets how much weapon damage players deal to each other (softcore vs hardcore servers).
+#[spacetimedb::reducer]
+pub fn set_pvp_damage_multiplier(ctx: &ReducerContext, multiplier: f32) -> Result<(), String> {
+    ensure_admin(ctx)?;
+
+    let clamped = clamp_pvp_damage_multiplier(multiplier)?;
+
+    let mut config = ctx.db.world_config().iter().next()
+        .ok_or_else(|| "WorldConfig not found".to_string())?;
+    let old_multiplier = config.pvp_damage_multiplier;
+    config.pvp_damage_multiplier = clamped;
+    ctx.db.world_config().id().update(config);
+
+    log::info!("[PvPDamage] Admin {:?} changed PvP damage multiplier {:.2} -> {:.2} (requested {:.2}).",
+             ctx.sender, old_multiplier, clamped, multiplier);
+    Ok(())
+}
+
+/// Sets how many dropped items may exist in the world before the oldest common ones are evicted.
+/// Lowering the cap evicts the excess right away.
+#[spacetimedb::reducer]
+pub fn set_max_dropped_items(ctx: &ReducerContext, max_items: u32) -> Result<(), String> {
+    ensure_admin(ctx)?;
+
+    let mut config = ctx.db.world_config().iter().next()
+        .ok_or_else(|| "WorldConfig not found".to_string())?;
+    let old_max = config.max_dropped_items;
+    config.max_dropped_items = max_items.max(MIN_MAX_DROPPED_ITEMS);
+    log::info!("[DropCap] Admin {:?} changed max dropped items {} -> {} (requested {}).",
+             ctx.sender, old_max, config.max_dropped_items, max_items);
+    ctx.db.world_config().id().update(config);
+
+    crate::dropped_item::enforce_dropped_item_cap(ctx);
+    Ok(())
+}
+
+/// Switches trees and stones between destructive (deplete, then respawn on a timer) and
+/// renewable (slowly heal back after being hit) gathering.
+#[spacetimedb::reducer]
+pub fn set_renewable_resources(ctx: &ReducerContext, enabled: bool) -> Result<(), String> {
+    ensure_admin(ctx)?;
+
+    let mut config = ctx.db.world_config().iter().next()
+        .ok_or_else(|| "WorldConfig not found".to_string())?;
+    config.renewable_resources = enabled;
+    ctx.db.world_config().id().update(config);
+
+    log::info!("[Resources] Admin {:?} set renewable resources to {}.", ctx.sender, enabled);
+    Ok(())
+}
+
+/// Moves the spawn region. Players (re)spawn at a random clear point within `radius` of the center.
+/// Pass a radius of 0 to spawn everyone on the center point (collisions permitting).
+#[spacetimedb::reducer]
+pub fn set_spawn_region(ctx: &ReducerContext, center_x: f32, center_y: f32, radius: f32) -> Result<(), String> {
+    ensure_admin(ctx)?;
+    crate::errors::ensure_finite(&[("center_x", center_x), ("center_y", center_y), ("radius", radius)])?;
+    if center_x < 0.0 || center_y < 0.0 || center_x > crate::WORLD_WIDTH_PX || center_y > crate::WORLD_HEIGHT_PX {
+        return Err(crate::errors::GameError::InvalidInput("spawn center must be inside the world".to_string()).into());
+    }
+
+    let mut config = ctx.db.world_config().iter().next()
+        .ok_or_else(|| "WorldConfig not found".to_string())?;
+    config.spawn_center_x = center_x;
+    config.spawn_center_y = center_y;
+    config.spawn_radius = radius.clamp(0.0, MAX_SPAWN_RADIUS);
+    log::info!("[Spawn] Admin {:?} set spawn region to ({:.1}, {:.1}) radius {:.1}.",
+             ctx.sender, config.spawn_center_x, config.spawn_center_y, config.spawn_radius);
+    ctx.db.world_config().id().update(config);
+    Ok(())
+}
+
+/// Validates an admin-requested resource density: negative values are rejected, high ones capped at `MAX_RESOURCE_DENSITY`.
+fn clamp_resource_density(density: f32) -> Result<f32, String> {
+    if density < 0.0 {
+        return Err(crate::errors::GameError::InvalidInput("densities cannot be negative".to_string()).into());
+    }
+    Ok(density.min(MAX_RESOURCE_DENSITY))
+}
+
+/// Sets how densely trees, stones and mushrooms are seeded, as the fraction of map tiles that get one.
+/// Only affects future seeding; call `reseed_resources` to apply it to the current world.
+#[spacetimedb::reducer]
+pub fn set_resource_density(ctx: &ReducerContext, tree_density: f32, stone_density: f32, mushroom_density: f32) -> Result<(), String> {
+    ensure_admin(ctx)?;
+    crate::errors::ensure_finite(&[("tree_density", tree_density), ("stone_density", stone_density), ("mushroom_density", mushroom_density)])?;
+    let (tree_density, stone_density, mushroom_density) =
+        (clamp_resource_density(tree_density)?, clamp_resource_density(stone_density)?, clamp_resource_density(mushroom_density)?);
+
+    let mut config = ctx.db.world_config().iter().next()
+        .ok_or_else(|| "WorldConfig not found".to_string())?;
+    config.tree_density = tree_density;
+    config.stone_density = stone_density;
+    config.mushroom_density = mushroom_density;
+    log::info!("[Density] Admin {:?} set resource densities: trees {:.4}, stones {:.4}, mushrooms {:.4}.",
+             ctx.sender, config.tree_density, config.stone_density, config.mushroom_density);
+    ctx.db.world_config().id().update(config);
+    Ok(())
+}
+
+/// Scales how many of each stackable item fit in one stack (2 = "x2 stacks" server).
+/// Lowering it leaves existing oversized stacks alone; they just can't grow until they're below the new cap.
+#[spacetimedb::reducer]
+pub fn set_stack_size_multiplier(ctx: &ReducerContext, multiplier: u32) -> Result<(), String> {
+    ensure_admin(ctx)?;
+
+    let mut config = ctx.db.world_config().iter().next()
+        .ok_or_else(|| "WorldConfig not found".to_string())?;
+    let old_multiplier = config.stack_size_multiplier;
+    config.stack_size_multiplier = multiplier.clamp(1, MAX_STACK_SIZE_MULTIPLIER);
+    log::info!("[StackSize] Admin {:?} changed stack size multiplier {} -> {} (requested {}).",
+             ctx.sender, old_multiplier, config.stack_size_multiplier, multiplier);
+    ctx.db.world_config().id().update(config);
+    Ok(())
+}
+
+/// Sets the chance that a felled tree falls over and hurts players beside it (0 disables the hazard).
+#[spacetimedb::reducer]
+pub fn set_tree_fall_chance(ctx: &ReducerContext, chance: f32) -> Result<(), String> {
+    ensure_admin(ctx)?;
+
+    if !chance.is_finite() {
+        return Err("Tree fall chance must be a finite number".to_string());
+    }
+    let clamped = chance.clamp(0.0, 1.0);
+
+    let mut config = ctx.db.world_config().iter().next()
+        .ok_or_else(|| "WorldConfig not found".to_string())?;
+    let old_chance = config.tree_fall_chance;
+    config.tree_fall_chance = clamped;
+    ctx.db.world_config().id().update(config);
+    log::info!("[TreeFall] Admin {:?} changed tree fall chance {:.2} -> {:.2} (requested {:.2}).",
+             ctx.sender, old_chance, clamped, chance);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pvp_damage_multiplier_is_clamped_and_defaults_to_six() {
+        assert_eq!(default_world_config(Identity::default()).pvp_damage_multiplier, 6.0);
+        assert_eq!(clamp_pvp_damage_multiplier(0.0), Ok(0.0)); // PvE-only server
+        assert_eq!(clamp_pvp_damage_multiplier(-3.0), Ok(0.0));
+        assert_eq!(clamp_pvp_damage_multiplier(1000.0), Ok(MAX_PVP_DAMAGE_MULTIPLIER));
+        assert!(clamp_pvp_damage_multiplier(f32::NAN).is_err());
+        assert!(clamp_pvp_damage_multiplier(f32::INFINITY).is_err());
+    }
+
+    #[test]
+    fn death_drops_are_locked_and_voluntary_drops_are_not_by_default() {
+        let config = default_world_config(Identity::default());
+        assert_eq!(drop_lock_secs(Some(&config), true), DEFAULT_DEATH_DROP_LOCK_SECS);
+        assert_eq!(drop_lock_secs(Some(&config), false), 0);
+        assert_eq!(drop_lock_secs(None, true), DEFAULT_DEATH_DROP_LOCK_SECS);
+        assert_eq!(drop_lock_secs(None, false), 0);
+
+        let config = WorldConfig { voluntary_drop_lock_secs: 5, death_drop_lock_secs: 90, ..config };
+        assert_eq!(drop_lock_secs(Some(&config), true), 90);
+        assert_eq!(drop_lock_secs(Some(&config), false), 5);
+    }
+
+    #[test]
+    fn resource_densities_are_validated() {
+        let config = default_world_config(Identity::default());
+        assert_eq!(config.tree_density, DEFAULT_TREE_DENSITY);
+        assert_eq!(clamp_resource_density(0.0), Ok(0.0)); // Barren world
+        assert_eq!(clamp_resource_density(DEFAULT_TREE_DENSITY * 2.0), Ok(DEFAULT_TREE_DENSITY * 2.0));
+        assert_eq!(clamp_resource_density(1.0), Ok(MAX_RESOURCE_DENSITY));
+        assert!(clamp_resource_density(-0.01).is_err());
+    }
+}
@@ -1,10 +1,12 @@
 use spacetimedb::{ReducerContext, Table, Timestamp};
 use log;
+use rand::Rng;
 use std::f32::consts::PI;
 use crate::campfire::Campfire;
 use crate::campfire::campfire as CampfireTableTrait;
 use crate::items::inventory_item as InventoryItemTableTrait;
 use crate::items::InventoryItem;
+use crate::player as PlayerTableTrait;
 
 // Define fuel consumption rate (items per second)
 const FUEL_ITEM_CONSUME_PER_SECOND: f32 = 0.2; // e.g., 1 wood every 5 seconds
@@ -47,8 +49,10 @@ pub struct WorldState {
     pub cycle_progress: f32, // 0.0 to 1.0 representing position in the full day/night cycle
     pub time_of_day: TimeOfDay,
     pub cycle_count: u32, // How many full cycles have passed
+    pub day_number: u32, // Current in-game day, starting at 1. Advances with every completed cycle
     pub is_full_moon: bool, // Flag for special night lighting
     pub last_tick: Timestamp,
+    pub world_seed: u64, // Seeds environment generation: the same seed always produces the same map (see environment.rs)
 }
 
 // Reducer to initialize the world state if it doesn't exist
@@ -62,8 +66,10 @@ pub fn seed_world_state(ctx: &ReducerContext) -> Result<(), String> {
             cycle_progress: 0.25, // Start at morning
             time_of_day: TimeOfDay::Morning,
             cycle_count: 0,
+            day_number: 1,
             is_full_moon: false,
             last_tick: ctx.timestamp,
+            world_seed: ctx.rng().gen(), // Random per world, but recorded so the layout can be reproduced
         })?;
     } else {
         log::debug!("WorldState already seeded.");
@@ -86,7 +92,9 @@ pub fn tick_world_state(ctx: &ReducerContext, _timestamp: Timestamp) -> Result<(
 
     // Update the world state only if time actually passed
     if elapsed_seconds > 0.0 {
-        let progress_delta = elapsed_seconds / FULL_CYCLE_DURATION_SECONDS;
+        // Apply admin time scale (only the world clock is accelerated)
+        let time_scale = crate::world_config::get_time_scale(ctx);
+        let progress_delta = (elapsed_seconds * time_scale) / FULL_CYCLE_DURATION_SECONDS;
         
         // Calculate potential progress before wrapping
         let potential_next_progress = world_state.cycle_progress + progress_delta;
@@ -94,12 +102,13 @@ pub fn tick_world_state(ctx: &ReducerContext, _timestamp: Timestamp) -> Result<(
         // Determine actual new progress (after wrapping)
         let new_progress = potential_next_progress % 1.0;
         
-        // Determine if the cycle wrapped during this tick
-        let did_wrap = potential_next_progress >= 1.0;
+        // Determine if the cycle wrapped during this tick (may wrap more than once when accelerated)
+        let cycles_passed = potential_next_progress.floor() as u32;
+        let did_wrap = cycles_passed > 0;
         
         // Determine the correct cycle count for the new_progress point
         let new_cycle_count = if did_wrap { 
-            let next_count = world_state.cycle_count.wrapping_add(1); // Use wrapping_add for safety
+            let next_count = world_state.cycle_count.wrapping_add(cycles_passed); // Use wrapping_add for safety
             log::info!("New cycle started ({} -> {}).", world_state.cycle_count, next_count);
             next_count
         } else { 
@@ -113,16 +122,7 @@ pub fn tick_world_state(ctx: &ReducerContext, _timestamp: Timestamp) -> Result<(
         }
 
         // Determine the new TimeOfDay based on new_progress
-        let new_time_of_day = match new_progress {
-            p if p < 0.05 => TimeOfDay::Midnight, 
-            p if p < 0.20 => TimeOfDay::Night,
-            p if p < 0.35 => TimeOfDay::Dawn,    // Adjusted timing
-            p if p < 0.50 => TimeOfDay::Morning, // Adjusted timing
-            p if p < 0.65 => TimeOfDay::Noon,    // Adjusted timing
-            p if p < 0.80 => TimeOfDay::Afternoon, // Adjusted timing
-            p if p < 0.95 => TimeOfDay::Dusk,    // Adjusted timing
-            _             => TimeOfDay::Night,   // Default to Night for late dusk/early night
-        };
+        let new_time_of_day = time_of_day_for_progress(new_progress);
 
         // Assign the calculated new values to the world_state object
         world_state.cycle_progress = new_progress;
@@ -130,6 +130,9 @@ pub fn tick_world_state(ctx: &ReducerContext, _timestamp: Timestamp) -> Result<(
         world_state.cycle_count = new_cycle_count;
         world_state.is_full_moon = new_is_full_moon; // Use the correctly determined flag
         world_state.last_tick = now;
+        if did_wrap {
+            advance_days(ctx, &mut world_state, cycles_passed);
+        }
 
         // Pass a clone to update
         ctx.db.world_state().id().update(world_state.clone());
@@ -161,6 +164,77 @@ pub fn tick_world_state(ctx: &ReducerContext, _timestamp: Timestamp) -> Result<(
     Ok(())
 }
 
+// Advances the day counter and credits every living player with the survived days.
+fn advance_days(ctx: &ReducerContext, world_state: &mut WorldState, days: u32) {
+    world_state.day_number = world_state.day_number.saturating_add(days);
+    log::info!("Day {} has begun.", world_state.day_number);
+
+    let players = ctx.db.player();
+    let survivors: Vec<crate::Player> = players.iter().filter(|p| !p.is_dead).collect();
+    for mut player in survivors {
+        player.days_survived = player.days_survived.saturating_add(days);
+        let player_id = player.identity;
+        let days_survived = player.days_survived;
+        players.identity().update(player);
+        if days_survived >= crate::achievements::SURVIVE_DAYS_ACHIEVEMENT_THRESHOLD {
+            crate::achievements::grant_achievement(ctx, player_id, crate::achievements::ACH_SURVIVE_10_DAYS);
+        }
+    }
+}
+
+// Maps cycle progress (0.0 - 1.0) to its TimeOfDay phase
+pub(crate) fn time_of_day_for_progress(progress: f32) -> TimeOfDay {
+    match progress {
+        p if p < 0.05 => TimeOfDay::Midnight, 
+        p if p < 0.20 => TimeOfDay::Night,
+        p if p < 0.35 => TimeOfDay::Dawn,    // Adjusted timing
+        p if p < 0.50 => TimeOfDay::Morning, // Adjusted timing
+        p if p < 0.65 => TimeOfDay::Noon,    // Adjusted timing
+        p if p < 0.80 => TimeOfDay::Afternoon, // Adjusted timing
+        p if p < 0.95 => TimeOfDay::Dusk,    // Adjusted timing
+        _             => TimeOfDay::Night,   // Default to Night for late dusk/early night
+    }
+}
+
+// Cycle progress at the middle of each phase (inverse of time_of_day_for_progress)
+fn progress_for_time_of_day(phase: &TimeOfDay) -> f32 {
+    match phase {
+        TimeOfDay::Midnight => 0.025,
+        TimeOfDay::Night => 0.125,
+        TimeOfDay::Dawn => 0.275,
+        TimeOfDay::Morning => 0.425,
+        TimeOfDay::Noon => 0.575,
+        TimeOfDay::Afternoon => 0.725,
+        TimeOfDay::Dusk => 0.875,
+    }
+}
+
+/// Admin/testing reducer: jumps the world clock straight to the given phase.
+/// Warmth drain and client lighting read `time_of_day`/`cycle_progress` directly,
+/// so they pick up the new phase on the next update without any gradual transition.
+#[spacetimedb::reducer]
+pub fn set_time_of_day(ctx: &ReducerContext, phase: TimeOfDay) -> Result<(), String> {
+    crate::world_config::ensure_admin(ctx)?;
+
+    let mut world_state = ctx.db.world_state().iter().next()
+        .ok_or_else(|| "WorldState singleton not found".to_string())?;
+    let old_time_of_day = world_state.time_of_day.clone();
+
+    // Set progress to the middle of the phase and reset last_tick so the next
+    // tick only advances from this point (no catch-up for time before the jump)
+    // Jumping to an earlier phase stays on the current day: only the regular tick
+    // advances days, so a jump never credits days survived or their achievements
+    world_state.cycle_progress = progress_for_time_of_day(&phase);
+    world_state.time_of_day = time_of_day_for_progress(world_state.cycle_progress);
+    world_state.is_full_moon = world_state.cycle_count % FULL_MOON_CYCLE_INTERVAL == 0;
+    world_state.last_tick = ctx.timestamp;
+    ctx.db.world_state().id().update(world_state.clone());
+
+    log::info!("[SetTimeOfDay] Admin {:?} jumped time of day {:?} -> {:?} (progress {:.3}).",
+             ctx.sender, old_time_of_day, world_state.time_of_day, world_state.cycle_progress);
+    Ok(())
+}
+
 // Helper function potentially needed later for client-side interpolation/lighting
 pub fn get_light_intensity(progress: f32) -> f32 {
     // Simple sinusoidal model: peaks at noon (0.5 progress), troughs at midnight (0.0/1.0 progress)